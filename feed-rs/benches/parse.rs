@@ -0,0 +1,78 @@
+//! Benchmarks for the parser, run with `cargo bench`
+//!
+//! Each case parses a real feed from the fixtures, either as it is or with its entries repeated to make a large feed (as found in
+//! archives and podcast back catalogues), both into a `Feed` and as a stream of entries. Criterion reports the time per parse and
+//! throughput, and the change since the previous run or a saved baseline. Cases may be selected by passing part of their name e.g.
+//! `cargo bench -- large`.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use feed_rs::parser;
+
+// The number of entries in the large feeds
+const LARGE_FEED_ENTRIES: usize = 5_000;
+
+fn parse(c: &mut Criterion) {
+    let cases = vec![
+        ("atom", fixture("atom_example_7.xml")),
+        ("atom reddit", fixture("atom_example_reddit.xml")),
+        ("rss 1.0", fixture("rss_1.0_example_2.xml")),
+        ("rss 2.0", fixture("rss_2.0_ch9.xml")),
+        ("rss 2.0 spiegel", fixture("rss_2.0_spiegel.xml")),
+        ("json feed", fixture("jsonfeed_example_1.json")),
+        (
+            "large atom",
+            repeat_entries(&fixture("atom_example_7.xml"), "<entry", "</entry>", LARGE_FEED_ENTRIES),
+        ),
+        (
+            "large rss 2.0",
+            repeat_entries(&fixture("rss_2.0_ch9.xml"), "<item", "</item>", LARGE_FEED_ENTRIES),
+        ),
+    ];
+
+    for (name, source) in cases {
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.measurement_time(Duration::from_secs(2));
+        group.warm_up_time(Duration::from_millis(500));
+
+        group.bench_function("parse", |b| b.iter(|| parser::parse(source.as_slice()).unwrap()));
+        group.bench_function("streamed", |b| {
+            b.iter(|| {
+                for entry in parser::parse_entries(source.as_slice()) {
+                    entry.unwrap();
+                }
+            })
+        });
+        group.finish();
+    }
+}
+
+// Loads a fixture from the crate
+fn fixture(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixture").join(name);
+    fs::read(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+}
+
+// Builds a large feed with (roughly) the given number of entries, by repeating the entries of a smaller one
+fn repeat_entries(source: &[u8], start_tag: &str, end_tag: &str, count: usize) -> Vec<u8> {
+    let source = String::from_utf8_lossy(source);
+    let start = source.find(start_tag).expect("no entries in the source");
+    let end = source.rfind(end_tag).expect("no entries in the source") + end_tag.len();
+    let entries = &source[start..end];
+
+    let repeats = count / entries.matches(start_tag).count();
+    let mut feed = String::with_capacity(source.len() + entries.len() * repeats);
+    feed.push_str(&source[..start]);
+    for _ in 0..repeats {
+        feed.push_str(entries);
+    }
+    feed.push_str(&source[end..]);
+    feed.into_bytes()
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
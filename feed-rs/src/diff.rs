@@ -0,0 +1,197 @@
+use crate::model::{Entry, Feed};
+
+/// The entries that changed between two versions of a feed, as found by `diff`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedDiff {
+    /// The IDs of the entries only found in the new feed, in the order they appear in it
+    pub added: Vec<String>,
+    /// The IDs of the entries only found in the old feed, in the order they appear in it
+    pub removed: Vec<String>,
+    /// The entries found in both feeds which have changed, in the order they appear in the new feed
+    pub updated: Vec<EntryChanges>,
+}
+
+impl FeedDiff {
+    /// True if no entries were added, removed or updated
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// The fields of an entry which changed between two versions of a feed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntryChanges {
+    /// The ID of the entry
+    pub id: String,
+    /// The title, or its variants in other languages
+    pub title: bool,
+    /// The time the entry was last updated
+    pub updated: bool,
+    /// The authors
+    pub authors: bool,
+    /// The content
+    pub content: bool,
+    /// The links
+    pub links: bool,
+    /// The summary, or its variants in other languages
+    pub summary: bool,
+    /// The categories
+    pub categories: bool,
+    /// The contributors
+    pub contributors: bool,
+    /// The time the entry was first published
+    pub published: bool,
+    /// The rights, or the license
+    pub rights: bool,
+    /// The media objects (e.g. enclosures)
+    pub media: bool,
+    /// Anything else, such as the namespace extensions, language or source feed
+    pub other: bool,
+}
+
+impl EntryChanges {
+    /// True if any of the fields changed
+    pub fn any(&self) -> bool {
+        self.title
+            || self.updated
+            || self.authors
+            || self.content
+            || self.links
+            || self.summary
+            || self.categories
+            || self.contributors
+            || self.published
+            || self.rights
+            || self.media
+            || self.other
+    }
+
+    // Compares two versions of an entry, which are expected to have been normalised
+    fn between(old: &Entry, new: &Entry) -> EntryChanges {
+        EntryChanges {
+            id: new.id.clone(),
+            title: old.title != new.title || old.title_variants != new.title_variants,
+            updated: old.updated != new.updated,
+            authors: old.authors != new.authors,
+            content: old.content != new.content,
+            links: old.links != new.links,
+            summary: old.summary != new.summary || old.summary_variants != new.summary_variants,
+            categories: old.categories != new.categories,
+            contributors: old.contributors != new.contributors,
+            published: old.published != new.published,
+            rights: old.rights != new.rights || old.license != new.license,
+            media: old.media != new.media,
+            other: old.source != new.source
+                || old.language != new.language
+                || old.itunes != new.itunes
+                || old.podcast != new.podcast
+                || old.dublin_core != new.dublin_core
+                || old.geo != new.geo
+                || old.threading != new.threading
+                || old.extensions != new.extensions,
+        }
+    }
+}
+
+/// Finds the entries that were added, removed or updated between two fetches of a feed
+///
+/// Entries are matched by their ID. As with `Feed::semantically_eq()`, differences which do not change the meaning of an entry (such as
+/// runs of whitespace in its text) are ignored, and the position of an entry in the feed does not matter.
+///
+/// Note that entries without an ID are given one derived from their links or content when parsed, so an edit to such an entry is
+/// reported as its removal and the addition of a new one.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::{diff, parser};
+///
+/// let old = parser::parse(r#"<feed xmlns="http://www.w3.org/2005/Atom">
+///     <entry><id>urn:1</id><title>First</title></entry>
+///     <entry><id>urn:2</id><title>Second</title></entry>
+/// </feed>"#.as_bytes()).unwrap();
+/// let new = parser::parse(r#"<feed xmlns="http://www.w3.org/2005/Atom">
+///     <entry><id>urn:3</id><title>Third</title></entry>
+///     <entry><id>urn:1</id><title>First, edited</title></entry>
+/// </feed>"#.as_bytes()).unwrap();
+///
+/// let changes = diff::diff(&old, &new);
+/// assert_eq!(changes.added, vec!["urn:3"]);
+/// assert_eq!(changes.removed, vec!["urn:2"]);
+/// assert_eq!(changes.updated[0].id, "urn:1");
+/// assert!(changes.updated[0].title);
+/// ```
+pub fn diff(old: &Feed, new: &Feed) -> FeedDiff {
+    let (old, new) = (old.normalised(), new.normalised());
+    let mut diff = FeedDiff::default();
+
+    for entry in &new.entries {
+        match old.entries.iter().find(|other| other.id == entry.id) {
+            Some(other) => {
+                let changes = EntryChanges::between(other, entry);
+                if changes.any() {
+                    diff.updated.push(changes);
+                }
+            }
+            None => diff.added.push(entry.id.clone()),
+        }
+    }
+    for entry in &old.entries {
+        if !new.entries.iter().any(|other| other.id == entry.id) {
+            diff.removed.push(entry.id.clone());
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    // Verify entries are matched by ID, and only those with meaningful changes are reported as updated
+    #[test]
+    fn test_diff() {
+        let old = r#"<rss version="2.0"><channel>
+                <title>News</title>
+                <item><guid>urn:1</guid><title>First</title><pubDate>Mon, 01 Mar 2021 10:00:00 GMT</pubDate></item>
+                <item><guid>urn:2</guid><title>Second</title><description>Some  text</description></item>
+                <item><guid>urn:3</guid><title>Third</title><category>news</category></item>
+                <item><guid>urn:4</guid><title>Fourth</title></item>
+            </channel></rss>"#;
+        let new = r#"<rss version="2.0"><channel>
+                <title>News, renamed</title>
+                <item><guid>urn:5</guid><title>Fifth</title></item>
+                <item><guid>urn:3</guid><title>Third</title><category>sport</category></item>
+                <item><guid>urn:2</guid><title>Second</title><description>Some text</description></item>
+                <item><guid>urn:1</guid><title>First, edited</title><pubDate>Tue, 02 Mar 2021 10:00:00 GMT</pubDate></item>
+            </channel></rss>"#;
+        let old = parser::parse(old.as_bytes()).unwrap();
+        let new = parser::parse(new.as_bytes()).unwrap();
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.added, vec!["urn:5"]);
+        assert_eq!(diff.removed, vec!["urn:4"]);
+        assert_eq!(
+            diff.updated,
+            vec![
+                EntryChanges {
+                    id: "urn:3".into(),
+                    categories: true,
+                    ..Default::default()
+                },
+                EntryChanges {
+                    id: "urn:1".into(),
+                    title: true,
+                    published: true,
+                    ..Default::default()
+                },
+            ]
+        );
+        assert!(!diff.is_empty());
+
+        // A feed does not differ from itself
+        assert!(super::diff(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+use url::Url;
+
+use crate::util::html::{decode_entities, parse_tag};
+
+// Media types advertised for feeds in <link rel="alternate">
+const FEED_MEDIA_TYPES: &[&str] = &["application/rss+xml", "application/atom+xml", "application/feed+json", "application/json"];
+
+// Paths commonly used for the feed of a site, tried when the page does not advertise one
+const FALLBACK_PATHS: &[&str] = &["/feed", "/rss.xml", "/atom.xml"];
+
+/// A feed found by `find_feeds`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredFeed {
+    /// The absolute URL of the feed
+    pub url: Url,
+    /// The title of the feed, if given by the page
+    pub title: Option<String>,
+    /// The media type of the feed, if given by the page (e.g. "application/atom+xml")
+    pub media_type: Option<String>,
+    /// How the feed was found
+    pub source: DiscoverySource,
+}
+
+/// How a `DiscoveredFeed` was found
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiscoverySource {
+    /// The page advertises the feed with a `<link rel="alternate">`
+    Link,
+    /// The URL is a common location for a feed, which may not exist
+    Fallback,
+}
+
+/// Finds the feeds advertised by an HTML page
+///
+/// Feeds are advertised with `<link rel="alternate">` elements that have the media type of an RSS, Atom or JSON Feed, and are returned
+/// in the order they appear in the page. Relative URLs are resolved against the `<base>` of the page if it has one, or else the URL the
+/// page was fetched from.
+///
+/// If the page does not advertise any feeds, the common locations (`/feed`, `/rss.xml` and `/atom.xml`) on the site the page was fetched
+/// from are returned instead, whatever its `<base>`, marked as a `DiscoverySource::Fallback`. These are only guesses, so the caller should check they are valid feeds.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::discovery::{self, DiscoverySource};
+/// use url::Url;
+///
+/// let html = r#"<html><head>
+///     <link rel="alternate" type="application/atom+xml" title="Posts" href="/posts.atom">
+/// </head></html>"#;
+/// let feeds = discovery::find_feeds(html, &Url::parse("https://example.com/blog/").unwrap());
+/// assert_eq!(feeds[0].url.as_str(), "https://example.com/posts.atom");
+/// assert_eq!(feeds[0].title.as_deref(), Some("Posts"));
+///
+/// let feeds = discovery::find_feeds("<html></html>", &Url::parse("https://example.com/blog/").unwrap());
+/// assert_eq!(feeds[0].url.as_str(), "https://example.com/feed");
+/// assert_eq!(feeds[0].source, DiscoverySource::Fallback);
+/// ```
+pub fn find_feeds(html: &str, page_url: &Url) -> Vec<DiscoveredFeed> {
+    let mut base = page_url.clone();
+    let mut feeds: Vec<DiscoveredFeed> = Vec::new();
+
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+
+        // Feeds advertised in comments are not live
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let tag = match parse_tag(rest) {
+            Some(tag) if !tag.closing => tag,
+            _ => {
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag.length..];
+
+        match tag.name.as_str() {
+            "base" => {
+                if let Some(url) = tag.attribute("href").and_then(|href| base.join(&decode_entities(href)).ok()) {
+                    base = url;
+                }
+            }
+
+            "link" => {
+                let is_alternate = match tag.attribute("rel") {
+                    Some(rel) => rel.split_whitespace().any(|token| token.eq_ignore_ascii_case("alternate")),
+                    None => false,
+                };
+                let media_type = match tag.attribute("type") {
+                    Some(media_type) => media_type.trim().to_ascii_lowercase(),
+                    None => continue,
+                };
+                if !is_alternate || !FEED_MEDIA_TYPES.contains(&media_type.as_str()) {
+                    continue;
+                }
+
+                let url = match tag.attribute("href").and_then(|href| base.join(decode_entities(href).trim()).ok()) {
+                    Some(url) => url,
+                    None => continue,
+                };
+                if feeds.iter().any(|feed| feed.url == url) {
+                    continue;
+                }
+                let title = tag
+                    .attribute("title")
+                    .map(|title| decode_entities(title).trim().to_string())
+                    .filter(|title| !title.is_empty());
+                feeds.push(DiscoveredFeed {
+                    url,
+                    title,
+                    media_type: Some(media_type),
+                    source: DiscoverySource::Link,
+                });
+            }
+
+            // The links are in the head, so there is no need to look through the rest of the page
+            "body" => break,
+
+            _ => {}
+        }
+    }
+
+    // The <base> may be elsewhere (e.g. a CDN), while the fallbacks are locations on the site of the page
+    if feeds.is_empty() {
+        feeds = FALLBACK_PATHS
+            .iter()
+            .filter_map(|path| page_url.join(path).ok())
+            .map(|url| DiscoveredFeed {
+                url,
+                title: None,
+                media_type: None,
+                source: DiscoverySource::Fallback,
+            })
+            .collect();
+    }
+
+    feeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify the advertised feeds are found, and other links ignored
+    #[test]
+    fn test_find_feeds() {
+        let html = r#"<!DOCTYPE html>
+            <html>
+            <head>
+                <base href="https://cdn.example.com/site/">
+                <link rel="stylesheet" type="text/css" href="style.css">
+                <!-- <link rel="alternate" type="application/rss+xml" href="old.rss"> -->
+                <LINK REL="Alternate" TYPE="application/RSS+xml" TITLE="Fish &amp; Chips" HREF="rss.xml?a=1&amp;b=2">
+                <link rel="alternate" type="application/atom+xml" href="https://example.com/atom.xml"/>
+                <link rel="alternate" type="application/atom+xml" title="Duplicate" href="https://example.com/atom.xml"/>
+                <link rel="alternate" type="application/feed+json" href="/feed.json" title=" ">
+                <link rel="alternate" hreflang="fr" href="/fr/">
+                <link rel="alternate" type="text/html" href="/mobile/">
+            </head>
+            <body>
+                <link rel="alternate" type="application/rss+xml" href="/body.rss">
+            </body>
+            </html>"#;
+        let feeds = find_feeds(html, &Url::parse("https://example.com/").unwrap());
+        let found: Vec<(&str, Option<&str>, Option<&str>)> = feeds
+            .iter()
+            .map(|feed| (feed.url.as_str(), feed.title.as_deref(), feed.media_type.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "https://cdn.example.com/site/rss.xml?a=1&b=2",
+                    Some("Fish & Chips"),
+                    Some("application/rss+xml")
+                ),
+                ("https://example.com/atom.xml", None, Some("application/atom+xml")),
+                ("https://cdn.example.com/feed.json", None, Some("application/feed+json")),
+            ]
+        );
+        assert!(feeds.iter().all(|feed| feed.source == DiscoverySource::Link));
+    }
+
+    // Verify the common locations are suggested when the page does not advertise a feed
+    #[test]
+    fn test_fallback_paths() {
+        let feeds = find_feeds(
+            "<html><head><title>Blog</title></head></html>",
+            &Url::parse("https://example.com/blog/post?id=1").unwrap(),
+        );
+        let urls: Vec<&str> = feeds.iter().map(|feed| feed.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/feed", "https://example.com/rss.xml", "https://example.com/atom.xml"]
+        );
+        assert!(feeds.iter().all(|feed| feed.source == DiscoverySource::Fallback));
+
+        // The locations are on the site of the page, rather than where its <base> points
+        let feeds = find_feeds(
+            r#"<html><head><base href="https://cdn.example.net/assets/"></head></html>"#,
+            &Url::parse("https://example.com/blog/").unwrap(),
+        );
+        assert_eq!(feeds[0].url.as_str(), "https://example.com/feed");
+    }
+}
@@ -0,0 +1,23 @@
+//! The errors returned when parsing a feed fails
+//!
+//! Every parse entry point (e.g. `parser::parse()`) returns a `ParseError`, which distinguishes:
+//! * malformed XML (`ParseError::XmlReader`) or JSON (`ParseError::JsonSerde`)
+//! * a document that is not a feed (`ParseErrorKind::NoFeedRoot`)
+//! * content required by the spec that is missing (`ParseErrorKind::MissingContent`), or an invalid timestamp
+//!   (`ParseErrorKind::InvalidTimestamp`), both only for a strict parser
+//! * a failure to read the source (`ParseError::IoError`)
+//!
+//! `ParseError::position()` gives the line and column at which malformed XML or JSON was found.
+//!
+//! # Examples
+//!
+//! ```
+//! use feed_rs::error::{ParseError, Position};
+//! use feed_rs::parser;
+//!
+//! let error: ParseError = parser::parse("<rss version=\"2.0\">\n<channel></rss>".as_bytes()).unwrap_err();
+//! assert_eq!(error.position().map(|position| position.line), Some(2));
+//! ```
+
+pub use crate::parser::{ParseErrorKind, ParseFeedError as ParseError, ParseFeedResult as ParseResult};
+pub use crate::xml::Position;
@@ -0,0 +1,86 @@
+//! Metadata from the Dublin Core element set (`http://purl.org/dc/elements/1.1/`)
+//!
+//! [Dublin Core spec]: https://www.dublincore.org/specifications/dublin-core/dces/
+
+/// The `dc:*` elements found on a channel or item, as the text found in the source
+///
+/// Each element may be repeated, so every value is retained in document order. The parser also maps the elements with an equivalent in
+/// the core model (e.g. "dc:creator" to the authors, "dc:subject" to the categories).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DublinCoreExtension {
+    /// "dc:contributor": an entity responsible for making contributions to the resource
+    pub contributors: Vec<String>,
+    /// "dc:coverage": the spatial or temporal topic of the resource
+    pub coverage: Vec<String>,
+    /// "dc:creator": an entity primarily responsible for making the resource
+    pub creators: Vec<String>,
+    /// "dc:date": a point or period of time associated with the resource (typically W3CDTF e.g. "2000-01-01T12:00+00:00")
+    pub dates: Vec<String>,
+    /// "dc:description": an account of the resource
+    pub descriptions: Vec<String>,
+    /// "dc:format": the file format, physical medium, or dimensions of the resource
+    pub formats: Vec<String>,
+    /// "dc:identifier": an unambiguous reference to the resource
+    pub identifiers: Vec<String>,
+    /// "dc:language": a language of the resource
+    pub languages: Vec<String>,
+    /// "dc:publisher": an entity responsible for making the resource available
+    pub publishers: Vec<String>,
+    /// "dc:relation": a related resource
+    pub relations: Vec<String>,
+    /// "dc:rights": information about rights held in and over the resource
+    pub rights: Vec<String>,
+    /// "dc:source": a related resource from which the described resource is derived
+    pub sources: Vec<String>,
+    /// "dc:subject": the topic of the resource
+    pub subjects: Vec<String>,
+    /// "dc:title": a name given to the resource
+    pub titles: Vec<String>,
+    /// "dc:type": the nature or genre of the resource
+    pub types: Vec<String>,
+}
+
+impl DublinCoreExtension {
+    // The names of the elements in the element set
+    pub(crate) const ELEMENTS: [&'static str; 15] = [
+        "contributor",
+        "coverage",
+        "creator",
+        "date",
+        "description",
+        "format",
+        "identifier",
+        "language",
+        "publisher",
+        "relation",
+        "rights",
+        "source",
+        "subject",
+        "title",
+        "type",
+    ];
+
+    // Records the value of an element, ignoring those that are not part of the element set
+    pub(crate) fn record(&mut self, name: &str, value: String) {
+        let values = match name {
+            "contributor" => &mut self.contributors,
+            "coverage" => &mut self.coverage,
+            "creator" => &mut self.creators,
+            "date" => &mut self.dates,
+            "description" => &mut self.descriptions,
+            "format" => &mut self.formats,
+            "identifier" => &mut self.identifiers,
+            "language" => &mut self.languages,
+            "publisher" => &mut self.publishers,
+            "relation" => &mut self.relations,
+            "rights" => &mut self.rights,
+            "source" => &mut self.sources,
+            "subject" => &mut self.subjects,
+            "title" => &mut self.titles,
+            "type" => &mut self.types,
+            _ => return,
+        };
+        values.push(value);
+    }
+}
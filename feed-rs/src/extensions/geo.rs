@@ -0,0 +1,65 @@
+//! Geographic locations from GeoRSS (`http://www.georss.org/georss`) and W3C Basic Geo (`http://www.w3.org/2003/01/geo/wgs84_pos#`)
+//!
+//! [GeoRSS spec]: http://docs.opengeospatial.org/wp/16-129r1/16-129r1.html
+//! [W3C Basic Geo]: https://www.w3.org/2003/01/geo/
+
+/// The location of an item, from the GeoRSS Simple ("georss:point" etc), GeoRSS GML ("georss:where") or W3C ("geo:lat" and "geo:long")
+/// elements
+///
+/// Coordinates are WGS84 latitude and longitude in decimal degrees. Where a feed uses more than one vocabulary for the same geometry the
+/// GeoRSS element is preferred.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeoExtension {
+    /// "georss:point", "gml:Point" or "geo:lat" + "geo:long": a single location
+    pub point: Option<GeoPoint>,
+    /// "georss:line" or "gml:LineString": a path between two or more locations
+    pub line: Option<Vec<GeoPoint>>,
+    /// "georss:polygon" or "gml:Polygon": the exterior of an area, which starts and ends with the same location
+    pub polygon: Option<Vec<GeoPoint>>,
+    /// "georss:box" or "gml:Envelope": a rectangular region
+    pub bounding_box: Option<GeoBox>,
+}
+
+/// A location as a latitude and longitude
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// A rectangular region, given by its lower (south west) and upper (north east) corners
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeoBox {
+    pub lower: GeoPoint,
+    pub upper: GeoPoint,
+}
+
+impl GeoExtension {
+    // True if no geometry has been found
+    pub(crate) fn is_empty(&self) -> bool {
+        self.point.is_none() && self.line.is_none() && self.polygon.is_none() && self.bounding_box.is_none()
+    }
+}
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> GeoPoint {
+        GeoPoint { latitude, longitude }
+    }
+
+    // Parses a list of "latitude longitude" pairs, which may be separated by spaces or commas
+    pub(crate) fn parse_list(text: &str) -> Option<Vec<GeoPoint>> {
+        let coordinates = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f64>().ok().filter(|f| f.is_finite()))
+            .collect::<Option<Vec<f64>>>()?;
+        if coordinates.is_empty() || coordinates.len() % 2 != 0 {
+            return None;
+        }
+
+        Some(coordinates.chunks(2).map(|pair| GeoPoint::new(pair[0], pair[1])).collect())
+    }
+}
@@ -0,0 +1,104 @@
+//! Podcast metadata from the iTunes namespace (`http://www.itunes.com/dtds/podcast-1.0.dtd`)
+//!
+//! [iTunes podcast spec]: https://help.apple.com/itc/podcasts_connect/#/itcb54353390
+
+use std::time::Duration;
+
+/// The `itunes:*` elements found at the channel level of a podcast feed
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ITunesFeedExtension {
+    /// "itunes:author": the group responsible for creating the show
+    pub author: Option<String>,
+    /// "itunes:block": true if the show should be hidden from the podcast directory
+    pub block: Option<bool>,
+    /// "itunes:category": the categories (and subcategories) of the show
+    pub categories: Vec<ITunesCategory>,
+    /// "itunes:complete": true if no more episodes will be published
+    pub complete: Option<bool>,
+    /// "itunes:explicit": true if the show contains explicit content ("yes", "true" or "explicit"), false if it does not ("no", "false" or "clean")
+    pub explicit: Option<bool>,
+    /// "itunes:image": the URL of the show artwork
+    pub image: Option<String>,
+    /// "itunes:keywords": the comma separated keywords for the show
+    pub keywords: Vec<String>,
+    /// "itunes:new-feed-url": the URL the show has moved to
+    pub new_feed_url: Option<String>,
+    /// "itunes:owner": contact details for the owner of the show
+    pub owner: Option<ITunesOwner>,
+    /// "itunes:subtitle": a short description of the show
+    pub subtitle: Option<String>,
+    /// "itunes:summary": a description of the show
+    pub summary: Option<String>,
+    /// "itunes:title": the title of the show, if different to the channel title
+    pub title: Option<String>,
+    /// "itunes:type": either "episodic" or "serial"
+    pub show_type: Option<String>,
+}
+
+/// The `itunes:*` elements found on an item (episode) of a podcast feed
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ITunesItemExtension {
+    /// "itunes:author": the author of the episode, if different to the show
+    pub author: Option<String>,
+    /// "itunes:block": true if the episode should be hidden from the podcast directory
+    pub block: Option<bool>,
+    /// "itunes:duration": the length of the episode
+    pub duration: Option<Duration>,
+    /// "itunes:episode": the episode number
+    pub episode: Option<u32>,
+    /// "itunes:episodeType": one of "full", "trailer" or "bonus"
+    pub episode_type: Option<String>,
+    /// "itunes:explicit": true if the episode contains explicit content (as per `ITunesFeedExtension::explicit`)
+    pub explicit: Option<bool>,
+    /// "itunes:image": the URL of the episode artwork
+    pub image: Option<String>,
+    /// "itunes:keywords": the comma separated keywords for the episode
+    pub keywords: Vec<String>,
+    /// "itunes:season": the season number
+    pub season: Option<u32>,
+    /// "itunes:subtitle": a short description of the episode
+    pub subtitle: Option<String>,
+    /// "itunes:summary": a description of the episode
+    pub summary: Option<String>,
+    /// "itunes:title": the title of the episode, without the show or episode number
+    pub title: Option<String>,
+}
+
+/// An "itunes:category", which may be nested to give a subcategory
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ITunesCategory {
+    /// The "text" attribute naming the category
+    pub text: String,
+    /// Any categories nested within this one
+    pub subcategories: Vec<ITunesCategory>,
+}
+
+impl ITunesCategory {
+    pub fn new(text: &str) -> ITunesCategory {
+        ITunesCategory {
+            text: text.to_owned(),
+            subcategories: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ITunesCategory {
+    pub fn subcategory(mut self, subcategory: ITunesCategory) -> Self {
+        self.subcategories.push(subcategory);
+        self
+    }
+}
+
+/// The "itunes:owner" of a show
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ITunesOwner {
+    /// "itunes:name"
+    pub name: Option<String>,
+    /// "itunes:email"
+    pub email: Option<String>,
+}
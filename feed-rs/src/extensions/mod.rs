@@ -0,0 +1,99 @@
+//! Typed models for namespaced extensions to the core feed formats
+//!
+//! Where an extension has a close equivalent in the core model (e.g. `itunes:image` and the feed logo) the parser continues to map it there,
+//! so these are only needed by callers that care about the extension itself (e.g. podcast clients).
+
+pub mod dublincore;
+pub mod geo;
+pub mod itunes;
+pub mod podcast;
+pub mod threading;
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::parser::XmlEvent;
+
+/// Elements from namespaces the parser does not otherwise understand, keyed by namespace URI
+///
+/// The elements for each namespace are in document order.
+pub type ExtensionMap = HashMap<String, Vec<ExtensionElement>>;
+
+/// An element from a namespace the parser does not otherwise understand
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtensionElement {
+    /// Name of the element, without the namespace prefix
+    pub name: String,
+    /// Attributes of the element, as they appeared in the source
+    pub attributes: BTreeMap<String, String>,
+    /// Text content of the element with surrounding whitespace removed, if any
+    ///
+    /// The text either side of child elements is joined as found, so the whitespace between words is kept.
+    pub value: Option<String>,
+    /// Child elements in document order
+    pub children: Vec<ExtensionElement>,
+}
+
+impl ExtensionElement {
+    // Builds the element from the events of its subtree, which start and end with those for the element itself
+    pub(crate) fn from_events(events: &[XmlEvent]) -> ExtensionElement {
+        let mut stack: Vec<ExtensionElement> = Vec::new();
+        let mut root = ExtensionElement::default();
+
+        for event in events {
+            match event {
+                // Namespace declarations are not kept, as the namespace is already identified by the map's key
+                XmlEvent::Start { name, attributes } => stack.push(ExtensionElement {
+                    name: name.clone(),
+                    attributes: attributes
+                        .iter()
+                        .filter(|(name, _)| name != "xmlns" && !name.starts_with("xmlns:"))
+                        .cloned()
+                        .collect(),
+                    ..Default::default()
+                }),
+                XmlEvent::Text(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.value.get_or_insert_with(String::new).push_str(text);
+                    }
+                }
+                XmlEvent::End { .. } => {
+                    if let Some(mut element) = stack.pop() {
+                        element.value = element.value.map(|value| value.trim().to_owned()).filter(|value| !value.is_empty());
+                        match stack.last_mut() {
+                            Some(parent) => parent.children.push(element),
+                            None => root = element,
+                        }
+                    }
+                }
+            }
+        }
+
+        root
+    }
+}
+
+#[cfg(test)]
+impl ExtensionElement {
+    pub fn new(name: &str) -> Self {
+        ExtensionElement {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn attribute(mut self, name: &str, value: &str) -> Self {
+        self.attributes.insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    pub fn child(mut self, child: ExtensionElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn value(mut self, value: &str) -> Self {
+        self.value = Some(value.to_owned());
+        self
+    }
+}
@@ -0,0 +1,131 @@
+//! Podcast metadata from the Podcasting 2.0 namespace (`https://podcastindex.org/namespace/1.0`)
+//!
+//! Only the more widely supported elements are modelled, with the rest recorded in the extensions of the feed or entry.
+//!
+//! [Podcast namespace]: https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/1.0.md
+
+use std::time::Duration;
+
+/// The `podcast:*` elements found at the channel level of a podcast feed
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastFeedExtension {
+    /// "podcast:funding": where listeners can support the show
+    pub funding: Vec<PodcastFunding>,
+    /// "podcast:person": the people involved in the show
+    pub persons: Vec<PodcastPerson>,
+    /// "podcast:value": how listeners can pay the show as they listen
+    pub value: Option<PodcastValue>,
+}
+
+/// The `podcast:*` elements found on an item (episode) of a podcast feed
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastItemExtension {
+    /// "podcast:transcript": transcripts or captions of the episode, in each of the formats provided
+    pub transcripts: Vec<PodcastTranscript>,
+    /// "podcast:chapters": the chapters of the episode
+    pub chapters: Option<PodcastChapters>,
+    /// "podcast:person": the people involved in the episode
+    pub persons: Vec<PodcastPerson>,
+    /// "podcast:soundbite": parts of the episode suitable for sharing
+    pub soundbites: Vec<PodcastSoundbite>,
+    /// "podcast:value": how listeners can pay for the episode as they listen, overriding that of the show
+    pub value: Option<PodcastValue>,
+}
+
+/// A "podcast:transcript" of an episode
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastTranscript {
+    /// "url": where the transcript is found
+    pub url: String,
+    /// "type": the format of the transcript (e.g. "text/vtt" or "application/srt")
+    pub media_type: String,
+    /// "language": the language of the transcript, if different to the feed
+    pub language: Option<String>,
+    /// "rel": "captions" if the transcript is timed, so can be shown as captions
+    pub rel: Option<String>,
+}
+
+/// The "podcast:chapters" of an episode
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastChapters {
+    /// "url": where the chapters are found
+    pub url: String,
+    /// "type": the format of the chapters (e.g. "application/json+chapters")
+    pub media_type: String,
+}
+
+/// A "podcast:funding" link for a show
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastFunding {
+    /// "url": where listeners can support the show
+    pub url: String,
+    /// The text to show for the link, if any
+    pub text: Option<String>,
+}
+
+/// A "podcast:person" involved in a show or episode
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastPerson {
+    /// The name of the person
+    pub name: String,
+    /// "role": what they did (e.g. "host" or "guest"), which the spec defaults to "host"
+    pub role: Option<String>,
+    /// "group": the group of the role (e.g. "cast" or "writing"), which the spec defaults to "cast"
+    pub group: Option<String>,
+    /// "img": the URL of a picture of the person
+    pub img: Option<String>,
+    /// "href": the URL of a page about the person
+    pub href: Option<String>,
+}
+
+/// A "podcast:soundbite" within an episode
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastSoundbite {
+    /// "startTime": where the soundbite starts, from the start of the episode
+    pub start_time: Duration,
+    /// "duration": how long the soundbite is
+    pub duration: Duration,
+    /// The title of the soundbite, if any
+    pub title: Option<String>,
+}
+
+/// A "podcast:value" block, describing how payments are split between recipients
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastValue {
+    /// "type": the service used to make payments (e.g. "lightning")
+    pub value_type: String,
+    /// "method": how payments are made (e.g. "keysend")
+    pub method: String,
+    /// "suggested": the suggested payment per minute of listening
+    pub suggested: Option<String>,
+    /// "podcast:valueRecipient": who receives the payments
+    pub recipients: Vec<PodcastValueRecipient>,
+}
+
+/// A "podcast:valueRecipient" of payments
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PodcastValueRecipient {
+    /// "name": who the recipient is
+    pub name: Option<String>,
+    /// "type": the type of the address (e.g. "node")
+    pub recipient_type: String,
+    /// "address": where payments are sent
+    pub address: String,
+    /// "split": the share of each payment, relative to the splits of the other recipients
+    pub split: u32,
+    /// "customKey": the name of a record the receiving service needs to route the payment, if any
+    pub custom_key: Option<String>,
+    /// "customValue": the value of the record named by `custom_key`
+    pub custom_value: Option<String>,
+    /// "fee": true if the split is a fee taken from each payment before it is shared between the other recipients
+    pub fee: bool,
+}
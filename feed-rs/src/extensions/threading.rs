@@ -0,0 +1,57 @@
+//! Replies and comment threads from the Atom Threading Extensions (`http://purl.org/syndication/thread/1.0`), along with the comment
+//! metadata of the Slash (`http://purl.org/rss/1.0/modules/slash/`) and Well-Formed Web (`http://wellformedweb.org/CommentAPI/`) modules
+//!
+//! [RFC 4685]: https://tools.ietf.org/html/rfc4685
+//! [Slash]: https://web.resource.org/rss/1.0/modules/slash/
+//! [Well-Formed Web]: https://www.rssboard.org/comment-api
+
+use chrono::{DateTime, Utc};
+
+/// Where an entry sits in a conversation, from the "thr:in-reply-to" and "thr:total" elements and links with the "replies" relation
+///
+/// Links to replies are also in the entry's links. Blog feeds more commonly give the number of comments and the comment feed with
+/// "slash:comments" and "wfw:commentRss", which are kept as they were found rather than merged with the threading elements.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThreadingExtension {
+    /// "thr:in-reply-to": the resources this entry is a response to
+    pub in_reply_to: Vec<InReplyTo>,
+    /// "thr:total": the total number of responses to this entry
+    pub total: Option<u64>,
+    /// "link" with the "replies" relation: where the responses to this entry can be found
+    pub replies: Vec<Replies>,
+    /// "slash:comments": the number of comments on this entry
+    pub comments: Option<u64>,
+    /// "wfw:commentRss": the URL of the feed of comments on this entry
+    pub comment_rss: Option<String>,
+    /// "wfw:comment": the URL that comments on this entry can be posted to
+    pub comment_api: Option<String>,
+}
+
+/// A resource the entry is a response to
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InReplyTo {
+    /// "ref": the ID of the resource (e.g. the ID of the entry being replied to)
+    pub reference: String,
+    /// "href": a link to the resource
+    pub href: Option<String>,
+    /// "type": the media type of the link
+    pub media_type: Option<String>,
+    /// "source": a link to the feed containing the resource
+    pub source: Option<String>,
+}
+
+/// A link to the responses to the entry
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Replies {
+    /// "href": where the responses are found, typically a comment feed
+    pub href: String,
+    /// "type": the media type of the link
+    pub media_type: Option<String>,
+    /// "thr:count": the number of responses at the link
+    pub count: Option<u64>,
+    /// "thr:updated": when the most recent response at the link was made
+    pub updated: Option<DateTime<Utc>>,
+}
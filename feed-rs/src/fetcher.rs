@@ -0,0 +1,381 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderName, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+
+use crate::model;
+use crate::parser::{Builder, ParseFeedError, Parser};
+
+pub type FetchFeedResult<T> = std::result::Result<T, FetchFeedError>;
+
+lazy_static! {
+    // Shared by every fetch so connections are reused, accepting compressed responses and identifying the library to servers
+    static ref CLIENT: Client = Client::builder()
+        .gzip(true)
+        .user_agent(concat!("feed-rs/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .expect("unable to initialise the HTTP client");
+}
+
+/// An error returned when fetching a feed fails
+#[derive(Debug)]
+pub enum FetchFeedError {
+    // The request could not be made, or the response could not be read
+    Http(reqwest::Error),
+    // The server responded with an error
+    Status(u16),
+    // The response was not a valid feed
+    Parse(ParseFeedError),
+}
+
+impl From<reqwest::Error> for FetchFeedError {
+    fn from(err: reqwest::Error) -> Self {
+        FetchFeedError::Http(err)
+    }
+}
+
+impl From<ParseFeedError> for FetchFeedError {
+    fn from(err: ParseFeedError) -> Self {
+        FetchFeedError::Parse(err)
+    }
+}
+
+impl fmt::Display for FetchFeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchFeedError::Http(he) => write!(f, "unable to fetch feed: {}", he),
+            FetchFeedError::Status(status) => write!(f, "unable to fetch feed: HTTP status {}", status),
+            FetchFeedError::Parse(pe) => write!(f, "unable to parse fetched feed: {}", pe),
+        }
+    }
+}
+
+impl Error for FetchFeedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchFeedError::Http(he) => Some(he),
+            FetchFeedError::Status(_) => None,
+            FetchFeedError::Parse(pe) => Some(pe),
+        }
+    }
+}
+
+/// The outcome of fetching a feed
+#[derive(Debug)]
+pub struct FetchResult {
+    /// The parsed feed, or None if the server reported it has not been modified since the previous fetch
+    pub feed: Option<model::Feed>,
+    /// The `ETag` header of the response, to be passed to the next fetch
+    pub etag: Option<String>,
+    /// The `Last-Modified` header of the response, to be passed to the next fetch
+    pub last_modified: Option<String>,
+    /// The HTTP status of the response
+    pub status: u16,
+}
+
+/// Fetches and parses the feed at the URL
+///
+/// Redirects are followed, with relative links in the feed resolved against the final URL. The charset from the `Content-Type` of
+/// the response is passed to the parser, so documents that do not declare their encoding are still decoded correctly.
+///
+/// Every fetch shares one HTTP client, which reuses connections, accepts gzip compressed responses and sends a `User-Agent` of
+/// "feed-rs/" and the version of the library.
+///
+/// # Examples
+///
+/// ```no_run
+/// use feed_rs::fetcher;
+/// let result = fetcher::fetch("https://example.com/feed.xml").unwrap();
+/// println!("{:?}", result.feed.unwrap().title);
+/// ```
+pub fn fetch(url: &str) -> FetchFeedResult<FetchResult> {
+    fetch_if_modified(url, None, None)
+}
+
+/// Fetches and parses the feed at the URL, if it has changed since the previous fetch
+///
+/// The `ETag` and `Last-Modified` values from a previous `FetchResult` are sent as a conditional request. If the server responds that
+/// the feed is unchanged then the returned `feed` is None, and the validators are carried over from the previous fetch.
+///
+/// # Examples
+///
+/// ```no_run
+/// use feed_rs::fetcher;
+/// let first = fetcher::fetch("https://example.com/feed.xml").unwrap();
+/// let second = fetcher::fetch_if_modified("https://example.com/feed.xml", first.etag.as_deref(), first.last_modified.as_deref()).unwrap();
+/// if second.feed.is_none() {
+///     println!("not modified");
+/// }
+/// ```
+pub fn fetch_if_modified(url: &str, etag: Option<&str>, last_modified: Option<&str>) -> FetchFeedResult<FetchResult> {
+    fetch_with_parser(&Builder::new().build(), url, etag, last_modified)
+}
+
+/// Fetches the feed at the URL as `fetch_if_modified()` does, parsing it with a parser configured by the caller (e.g. with limits on
+/// the size of the response or the number of entries)
+///
+/// # Examples
+///
+/// ```no_run
+/// use feed_rs::{fetcher, parser};
+/// let parser = parser::Builder::new().max_entries(10).build();
+/// let result = fetcher::fetch_with_parser(&parser, "https://example.com/feed.xml", None, None).unwrap();
+/// println!("{:?}", result.feed.unwrap().title);
+/// ```
+pub fn fetch_with_parser(parser: &Parser, url: &str, etag: Option<&str>, last_modified: Option<&str>) -> FetchFeedResult<FetchResult> {
+    let mut request = CLIENT.get(url);
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request.send()?;
+
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(FetchResult {
+            feed: None,
+            etag: header(response.headers(), ETAG).or_else(|| etag.map(String::from)),
+            last_modified: header(response.headers(), LAST_MODIFIED).or_else(|| last_modified.map(String::from)),
+            status: status.as_u16(),
+        });
+    }
+    if !status.is_success() {
+        return Err(FetchFeedError::Status(status.as_u16()));
+    }
+
+    read_feed(parser, response)
+}
+
+/// Fetches a feed split over several documents (RFC 5005), combining the entries of every page into one feed
+///
+/// Paged feeds are followed through their `Feed::next_page()` links, and archived feeds back through their "prev-archive" links (see
+/// `Feed::archive_links()`), until a page has neither that leads to a page not already fetched, or `max_pages` have been fetched. The pages
+/// are combined with `Feed::merge()`, so the feed level fields are those of the first page and an entry found on several pages is only kept
+/// once. Nothing is fetched if `max_pages` is zero, and an empty feed is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use feed_rs::fetcher;
+/// let feed = fetcher::fetch_pages("https://example.com/feed.xml", 100).unwrap();
+/// println!("{} entries", feed.entries.len());
+/// ```
+pub fn fetch_pages(url: &str, max_pages: usize) -> FetchFeedResult<model::Feed> {
+    fetch_pages_with_parser(&Builder::new().build(), url, max_pages)
+}
+
+/// Fetches a feed split over several documents as `fetch_pages()` does, parsing each page with a parser configured by the caller
+pub fn fetch_pages_with_parser(parser: &Parser, url: &str, max_pages: usize) -> FetchFeedResult<model::Feed> {
+    if max_pages == 0 {
+        return Ok(model::Feed::new(model::FeedType::Atom));
+    }
+
+    let mut feed = fetch_page(parser, url)?;
+    let mut fetched = HashSet::new();
+    fetched.insert(url.to_owned());
+    let mut next = following_page(&feed, &fetched);
+
+    while let Some(url) = next.take() {
+        if fetched.len() >= max_pages {
+            break;
+        }
+        fetched.insert(url.clone());
+
+        // The pages are fetched from the newest to the oldest, and merging keeps the newer feed level fields and entries
+        let mut page = fetch_page(parser, &url)?;
+        next = following_page(&page, &fetched);
+        page.merge(feed);
+        feed = page;
+    }
+
+    Ok(feed)
+}
+
+// The URL of the page after this one: the next page of a paged feed, or else the previous archive of an archived feed, skipping those
+// already fetched
+fn following_page(page: &model::Feed, fetched: &HashSet<String>) -> Option<String> {
+    page.next_page()
+        .into_iter()
+        .chain(page.archive_links().filter(|link| link.has_rel("prev-archive")))
+        .map(|link| &link.href)
+        .find(|href| !fetched.contains(*href))
+        .cloned()
+}
+
+// Fetches one page of a feed, which the server should not report as unmodified as the request is unconditional
+fn fetch_page(parser: &Parser, url: &str) -> FetchFeedResult<model::Feed> {
+    let result = fetch_with_parser(parser, url, None, None)?;
+    result.feed.ok_or(FetchFeedError::Status(result.status))
+}
+
+// Parses the body of a successful response
+fn read_feed(parser: &Parser, response: Response) -> FetchFeedResult<FetchResult> {
+    let status = response.status().as_u16();
+    let uri = response.url().to_string();
+    let etag = header(response.headers(), ETAG);
+    let last_modified = header(response.headers(), LAST_MODIFIED);
+    let content_type = header(response.headers(), CONTENT_TYPE);
+
+    let feed = match content_type {
+        Some(content_type) => parser.parse_with_charset(response, Some(&uri), &content_type)?,
+        None => parser.parse_with_uri(response, Some(&uri))?,
+    };
+
+    Ok(FetchResult {
+        feed: Some(feed),
+        etag,
+        last_modified,
+        status,
+    })
+}
+
+// Returns the header as a string, if present and valid
+fn header(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers.get(name).and_then(|value| value.to_str().ok()).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    // Serves each of the responses in turn, returning the request headers that were received
+    fn serve(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut headers = Vec::new();
+                for line in BufReader::new(stream.try_clone().unwrap()).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    headers.push(line.to_lowercase());
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(headers);
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    // Verify the validators are returned, and sent back on the next fetch
+    #[test]
+    fn test_conditional_fetch() {
+        let body = "<rss version=\"2.0\"><channel><title>Caf\u{e9}</title><link>/home</link></channel></rss>";
+        let (url, server) = serve(vec![
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml; charset=utf-8\r\nETag: \"v1\"\r\nLast-Modified: Mon, 01 Mar 2021 10:00:00 GMT\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+            "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".into(),
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".into(),
+        ]);
+
+        let first = fetch(&url).unwrap();
+        assert_eq!(first.status, 200);
+        assert_eq!(first.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(first.last_modified.as_deref(), Some("Mon, 01 Mar 2021 10:00:00 GMT"));
+        let feed = first.feed.unwrap();
+        assert_eq!(feed.title.unwrap().content, "Caf\u{e9}");
+        assert_eq!(feed.links[0].href, url.replace("/feed.xml", "/home"));
+
+        let second = fetch_if_modified(&url, first.etag.as_deref(), first.last_modified.as_deref()).unwrap();
+        assert_eq!(second.status, 304);
+        assert!(second.feed.is_none());
+        assert_eq!(second.etag, first.etag);
+        assert_eq!(second.last_modified, first.last_modified);
+
+        match fetch(&url) {
+            Err(FetchFeedError::Status(404)) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains(&format!("user-agent: feed-rs/{}", env!("CARGO_PKG_VERSION"))));
+        assert!(requests[0]
+            .iter()
+            .any(|header| header.starts_with("accept-encoding: ") && header.contains("gzip")));
+        assert!(requests[1].contains(&"if-none-match: \"v1\"".to_string()));
+        assert!(requests[1].contains(&"if-modified-since: mon, 01 mar 2021 10:00:00 gmt".to_string()));
+        assert!(!requests[0].iter().any(|header| header.starts_with("if-")));
+    }
+
+    // Verify the feed is parsed with the caller's parser
+    #[test]
+    fn test_fetch_with_parser() {
+        let body = "<rss version=\"2.0\"><channel><item><title>One</title></item><item><title>Two</title></item></channel></rss>";
+        let (url, server) = serve(vec![format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )]);
+
+        let parser = Builder::new().max_entries(1).build();
+        let feed = fetch_with_parser(&parser, &url, None, None).unwrap().feed.unwrap();
+        assert_eq!(feed.entries.len(), 1);
+        server.join().unwrap();
+    }
+
+    // Verify the pages of a feed are followed through its next and then prev-archive links, skipping and then stopping at pages already
+    // fetched
+    #[test]
+    fn test_fetch_pages() {
+        let page = |title: &str, links: &str, ids: &[u32]| {
+            let entries = ids
+                .iter()
+                .map(|id| format!("<entry><id>urn:{}</id><title>{}</title></entry>", id, id))
+                .collect::<String>();
+            let body = format!(
+                r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title>{}{}</feed>"#,
+                title, links, entries
+            );
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/atom+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        };
+        let (url, server) = serve(vec![
+            page("Latest", r#"<link rel="next" href="/page2"/>"#, &[4, 3]),
+            page(
+                "Page 2",
+                r#"<link rel="next" href="/feed.xml"/><link rel="prev-archive" href="/archive1"/>"#,
+                &[3, 2],
+            ),
+            page(
+                "Archive",
+                r#"<link rel="current" href="/feed.xml"/><link rel="next-archive" href="/feed.xml"/><link rel="next" href="/feed.xml"/>"#,
+                &[1],
+            ),
+        ]);
+
+        let feed = fetch_pages(&url, 10).unwrap();
+        assert_eq!(feed.title.unwrap().content, "Latest");
+        assert_eq!(
+            feed.entries.iter().map(|entry| entry.id.as_str()).collect::<Vec<_>>(),
+            vec!["urn:4", "urn:3", "urn:2", "urn:1"]
+        );
+
+        let requests = server.join().unwrap();
+        let paths = requests.iter().map(|headers| headers[0].as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["get /feed.xml http/1.1", "get /page2 http/1.1", "get /archive1 http/1.1"]);
+
+        // Nothing is fetched when no pages are allowed
+        let feed = fetch_pages("http://127.0.0.1:9/feed.xml", 0).unwrap();
+        assert!(feed.entries.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-//! This crate provides a parser and common data model over Atom and RSS feeds.
+//! This crate provides a parser, writer and common data model over Atom and RSS feeds.
 //!
 //! It uses [xml-rs](https://crates.io/crates/xml-rs) a light-weight, streaming XML parser to minimise memory usage
 //! and avoids copying (clone) where possible.
@@ -12,6 +12,24 @@
 //! # Usage
 //!
 //! The parser consists of a single method (parser::parse) which accepts an stream representing an XML document and returns a Feed.
+//! Where the default behaviour needs to be adjusted, a parser can be configured with `parser::Builder`.
+//! Very large feeds can instead be parsed one entry at a time with `parser::parse_entries`.
+//! Failures are returned as an `error::ParseError`, with the position of malformed XML or JSON in the source.
+//! The format of a document can be determined without parsing it with `parser::detect`.
+//! An HTTP response fetched by the caller can be parsed with `parser::parse_response`, which uses its headers to guide the parser.
+//!
+//! The model can be written back out as Atom 1.0 or RSS 2.0 with `writer::write_atom` and `writer::write_rss2`, and a feed with a long
+//! history split into the pages of an Atom paged feed with `writer::write_atom_pages`.
+//! Compact output, and whether the XML declaration and unused namespaces are written, can be chosen with `writer::Builder`.
+//! Subscription lists can be imported and exported as OPML with `opml::Opml`.
+//! The feeds advertised by a web page can be found with `discovery::find_feeds`.
+//! The entries that changed between two fetches of a feed can be found with `diff::diff`.
+//!
+//! Enabling the `serde` feature derives `Serialize` and `Deserialize` on the model, so parsed feeds can be cached or returned from web APIs.
+//! The `sanitize` feature adds `Content::sanitized()` and `Text::sanitized()`, which remove unsafe markup (e.g. scripts and event handlers) from HTML.
+//! The `tokio` feature adds `parser::parse_async`, which parses from an `AsyncRead` (e.g. an HTTP response body) without blocking the runtime.
+//! The `fetch` feature adds `fetcher::fetch`, which retrieves and parses a feed over HTTP, supporting conditional requests with `ETag` and `Last-Modified`.
+//! The `test-util` feature adds `test_util`, which checks the model parsed from a corpus of feeds against snapshots (e.g. for testing namespace handlers).
 
 // TODO review the Rust doc guidelines and fix up links
 // TODO improve tests with Coverage analysis e.g. https://github.com/mozilla/grcov
@@ -28,5 +46,16 @@ extern crate serde;
 mod util;
 mod xml;
 
+pub mod diff;
+pub mod discovery;
+pub mod error;
+pub mod extensions;
+#[cfg(feature = "fetch")]
+pub mod fetcher;
 pub mod model;
+pub mod opml;
 pub mod parser;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod validate;
+pub mod writer;
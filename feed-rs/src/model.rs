@@ -1,15 +1,67 @@
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::hash::Hasher;
+#[cfg(feature = "intern")]
+use std::sync::Arc;
 use std::time::Duration;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
 use mime::Mime;
-
+use siphasher::sip128::{Hasher128, SipHasher};
+
+use crate::extensions::dublincore::DublinCoreExtension;
+use crate::extensions::geo::{GeoExtension, GeoPoint};
+use crate::extensions::itunes::{ITunesCategory, ITunesFeedExtension, ITunesItemExtension, ITunesOwner};
+use crate::extensions::podcast::{
+    PodcastChapters, PodcastFeedExtension, PodcastFunding, PodcastItemExtension, PodcastPerson, PodcastSoundbite, PodcastTranscript, PodcastValue,
+    PodcastValueRecipient,
+};
+use crate::extensions::threading::{InReplyTo, Replies, ThreadingExtension};
+use crate::extensions::{ExtensionElement, ExtensionMap};
 use crate::parser::util;
 #[cfg(test)]
-use crate::parser::util::timestamp_rfc2822_lenient;
+use crate::util::dates::timestamp_rfc2822_lenient;
 #[cfg(test)]
-use crate::parser::util::timestamp_rfc3339_lenient;
+use crate::util::dates::timestamp_rfc3339_lenient;
+use crate::util::html::strip_tags;
+use crate::util::language::normalise_language_tag;
 use url::Url;
 
+/// The namespace of the content hash written for each entry by `writer::Builder::content_hashes()`, as the `hash` element
+pub const CONTENT_HASH_NAMESPACE: &str = "urn:feed-rs:content-hash";
+
+// Keys for the content hash, which must not change or the hashes already written would no longer match
+const CONTENT_HASH_KEY1: u64 = 0x5be0_cd19_137e_2179;
+const CONTENT_HASH_KEY2: u64 = 0x1f83_d9ab_fb41_bd6b;
+
+/// The type of the strings that may be shared between entries and feeds, i.e. the names of people and the terms and schemes of categories
+///
+/// With the "intern" feature these are `Arc<str>`, so they can be shared through a `parser::Interner`; otherwise they are `String`.
+#[cfg(feature = "intern")]
+pub type SharedString = Arc<str>;
+/// The type of the strings that may be shared between entries and feeds, i.e. the names of people and the terms and schemes of categories
+///
+/// With the "intern" feature these are `Arc<str>`, so they can be shared through a `parser::Interner`; otherwise they are `String`.
+#[cfg(not(feature = "intern"))]
+pub type SharedString = String;
+
+// Converts a parsed string to a SharedString, which moves it without the "intern" feature
+#[allow(clippy::useless_conversion)]
+pub(crate) fn shared_string(string: String) -> SharedString {
+    string.into()
+}
+
+// Records the name of each of the listed fields that differs between two values (see Feed::semantic_differences())
+macro_rules! compare_fields {
+    ($differences:ident, $prefix:expr, $a:ident, $b:ident, $($field:ident),+) => {
+        $(
+            if $a.$field != $b.$field {
+                $differences.push(format!("{}{}", $prefix, stringify!($field)));
+            }
+        )+
+    };
+}
+
 /// Combined model for a syndication feed (i.e. RSS1, RSS 2, Atom, JSON Feed)
 ///
 /// The model is based on the Atom standard as a start with RSS1+2 mapped on to it e.g.
@@ -29,24 +81,31 @@ use url::Url;
 ///
 /// Certain elements are not mapped given their limited utility:
 ///   * RSS 2:
-///     * channel - docs (pointer to the spec), cloud (for callbacks), textInput (text box e.g. for search)
-///     * item - comments (link to comments on the article), source (pointer to the channel, but our data model links items to a channel)
+///     * item - comments (link to comments on the article)
 ///   * RSS 1:
-///     * channel - rdf:about attribute (pointer to feed), textinput (text box e.g. for search)
+///     * channel - rdf:about attribute (pointer to feed)
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Feed {
     /// Type of this feed (e.g. RSS2, Atom etc)
     pub feed_type: FeedType,
+    /// The version of the format, as declared by the source or implied by its namespace
+    /// * Atom: "1.0", or "0.3" for the legacy format
+    /// * RSS 0.9x and 2: the "version" attribute of the root element (e.g. "0.91" or "2.0")
+    /// * RSS 1: "1.0"
+    /// * JSON Feed: the last part of the version URL (e.g. "1.1" for "https://jsonfeed.org/version/1.1")
+    pub version: Option<String>,
     /// A unique identifier for this feed
     /// * Atom (required): Identifies the feed using a universally unique and permanent URI.
-    /// * RSS doesn't require an ID so it is initialised to the hash of the first link or a UUID if not found
+    /// * RSS doesn't require an ID so it is initialised to the hash of the first link, or of the title and description if not found
     pub id: String,
     /// The title of the feed
     /// * Atom (required): Contains a human readable title for the feed. Often the same as the title of the associated website. This value should not be blank.
     /// * RSS 1 + 2 (required) "title": The name of the channel. It's how people refer to your service.
     /// * JSON Feed: is the name of the feed
     pub title: Option<Text>,
+    /// Every title found for the feed, in document order, if enabled with `parser::Builder::text_variants()`
+    pub title_variants: Vec<TextVariant>,
     /// The time at which the feed was last modified. If not provided in the source, or invalid, it is `None`.
     /// * Atom (required): Indicates the last time the feed was modified in a significant way.
     /// * RSS 2 (optional) "lastBuildDate": The last time the content of the channel changed.
@@ -60,21 +119,27 @@ pub struct Feed {
     /// * RSS 1 + 2 (required): Phrase or sentence describing the channel.
     /// * JSON Feed: description of the feed
     pub description: Option<Text>,
+    /// Every description found for the feed (e.g. "description" and "itunes:summary"), in document order, if enabled with `parser::Builder::text_variants()`
+    pub description_variants: Vec<TextVariant>,
     /// Links to related pages
     /// * Atom (recommended): Identifies a related Web page.
     /// * RSS 1 + 2 (required): The URL to the HTML website corresponding to the channel.
-    /// * JSON Feed: the homepage and feed URLs
+    /// * JSON Feed: the homepage, feed and next page URLs, and the WebSub hubs
     pub links: Vec<Link>,
 
     /// Structured classification of the feed
     /// * Atom (optional): Specifies a category that the feed belongs to. A feed may have multiple category elements.
     /// * RSS 2 (optional) "category": Specify one or more categories that the channel belongs to.
     pub categories: Vec<Category>,
+    /// RSS 2 (optional) "cloud": the rssCloud service to register with to be notified of updates to the channel.
+    pub cloud: Option<Cloud>,
     /// People who have contributed to the feed
     /// * Atom (optional): Names one contributor to the feed. A feed may have multiple contributor elements.
     /// * RSS 2 (optional) "managingEditor": Email address for person responsible for editorial content.
     /// * RSS 2 (optional) "webMaster": Email address for person responsible for technical issues relating to channel.
     pub contributors: Vec<Person>,
+    /// RSS 2 (optional) "docs": A URL that points to the documentation for the format used in the RSS file.
+    pub docs: Option<String>,
     /// Information on the software used to build the feed
     /// * Atom (optional): Identifies the software used to generate the feed, for debugging and other purposes.
     /// * RSS 2 (optional): A string indicating the program used to generate the channel.
@@ -84,6 +149,8 @@ pub struct Feed {
     /// * JSON Feed: is the URL of an image for the feed suitable to be used in a source list.
     pub icon: Option<Image>,
     /// RSS 2 (optional): The language the channel is written in.
+    ///
+    /// This is the language as found in the source, which may not be a valid language tag (see `language_tag()`).
     pub language: Option<String>,
     /// An image used to visually identify the feed
     /// * Atom (optional): Identifies a larger image which provides visual identification for the feed.
@@ -93,14 +160,37 @@ pub struct Feed {
     /// RSS 2 (optional): The publication date for the content in the channel.
     pub published: Option<DateTime<Utc>>,
     /// Rating for the content
-    /// * Populated from the media or itunes namespaces
+    /// * RSS 0.91 + 2 (optional) "rating": The PICS rating for the channel.
+    /// * Populated from the media or itunes namespaces otherwise
     pub rating: Option<MediaRating>,
     /// Rights restricting content within the feed
     /// * Atom (optional): Conveys information about rights, e.g. copyrights, held in and over the feed.
     /// * RSS 2 (optional) "copyright": Copyright notice for content in the channel.
     pub rights: Option<Text>,
+    /// The license the content of the feed is published under, with the relation "license"
+    /// * Atom (optional): the first link with the "license" relation (RFC 4946), which is also in the links
+    /// * RSS 2 (optional) "creativeCommons:license": The URL of the Creative Commons license for the channel.
+    pub license: Option<Link>,
     /// RSS 2 (optional): It's a number of minutes that indicates how long a channel can be cached before refreshing from the source.
     pub ttl: Option<u32>,
+    /// A text box shown with the feed, typically to search the site
+    /// * RSS 0.9x + 2 (optional) "textInput": Specifies a text input box that can be displayed with the channel.
+    /// * RSS 1 (optional) "textinput": Provides a text input box for the channel, alongside the items.
+    pub text_input: Option<TextInput>,
+    /// When the publisher suggests the feed should (and should not) be fetched
+    /// * RSS 0.9x + 2 (optional) "skipHours" and "skipDays"
+    /// * RSS 1 + 2, Atom: the syndication module ("sy:updatePeriod" etc)
+    pub update_hints: Option<UpdateHints>,
+    /// Stylesheets the publisher associated with the document through `<?xml-stylesheet?>` processing instructions (XML feeds only)
+    pub stylesheets: Vec<Stylesheet>,
+    /// Extension for podcasts, populated if the feed uses the iTunes namespace (RSS 2 only)
+    pub itunes: Option<ITunesFeedExtension>,
+    /// Extension for podcasts, populated if the feed uses the Podcasting 2.0 namespace (RSS 2 only)
+    pub podcast: Option<PodcastFeedExtension>,
+    /// Extension for Dublin Core metadata, populated if the feed uses the Dublin Core namespace (RSS 1 + 2 only)
+    pub dublin_core: Option<DublinCoreExtension>,
+    /// Elements at the feed level from namespaces the parser does not otherwise understand (e.g. "slash:" or custom namespaces), keyed by namespace URI
+    pub extensions: ExtensionMap,
 
     /// The individual items within the feed
     /// * Atom (optional): Individual entries within the feed (e.g. a blog post)
@@ -109,17 +199,42 @@ pub struct Feed {
 }
 
 impl Feed {
-    pub(crate) fn new(feed_type: FeedType) -> Self {
+    /// Creates an empty feed of the given type, e.g. to build a feed for the writer
+    ///
+    /// Unlike a parsed feed, no ID is generated so it should be set by the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use feed_rs::model::{Entry, Feed, FeedType, Link};
+    ///
+    /// let mut feed = Feed::new(FeedType::Atom);
+    /// feed.id = "urn:example:feed".into();
+    /// feed.entries.push(Entry {
+    ///     id: "urn:example:entry:1".into(),
+    ///     links: vec![Link {
+    ///         href: "https://example.com/1".into(),
+    ///         ..Default::default()
+    ///     }],
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn new(feed_type: FeedType) -> Self {
         Feed {
             feed_type,
+            version: None,
             id: "".into(),
             title: None,
+            title_variants: Vec::new(),
             updated: None,
             authors: Vec::new(),
             description: None,
+            description_variants: Vec::new(),
             links: Vec::new(),
             categories: Vec::new(),
+            cloud: None,
             contributors: Vec::new(),
+            docs: None,
             generator: None,
             icon: None,
             language: None,
@@ -127,10 +242,265 @@ impl Feed {
             published: None,
             rating: None,
             rights: None,
+            license: None,
             ttl: None,
+            text_input: None,
+            update_hints: None,
+            stylesheets: Vec::new(),
+            itunes: None,
+            podcast: None,
+            dublin_core: None,
+            extensions: ExtensionMap::new(),
             entries: Vec::new(),
         }
     }
+
+    /// Estimates the number of bytes this feed (including its entries) holds on the heap
+    ///
+    /// The estimate accounts for the capacity of strings and collections, but not allocator overhead, so treat it as a guide for capacity planning rather than an exact figure.
+    /// Strings shared with other holders (e.g. through a `parser::Interner`) are charged evenly to each of their holders, so the feed's share
+    /// shrinks as other feeds come to hold the same strings, and the sizes of all the holders add up to the memory they hold between them.
+    pub fn heap_size(&self) -> usize {
+        HeapSize::heap_size(self)
+    }
+
+    /// The time the feed was last updated, falling back to the most recent entry time if the source does not say
+    ///
+    /// Unlike `updated` this may be derived from the entries (their `updated` or else `published` time), so is suited to sorting or
+    /// display rather than reflecting what the publisher declared.
+    pub fn effective_updated(&self) -> Option<DateTime<Utc>> {
+        self.updated.or_else(|| self.entries.iter().filter_map(Entry::timestamp).max())
+    }
+
+    /// The language of the feed as a BCP 47 tag with the conventional casing (e.g. "en-US" for "EN_us"), for use as a consistent key
+    ///
+    /// The English names of common languages (e.g. "english") are mapped to their code. `None` is returned if the feed does not
+    /// declare a language, or it is not a well-formed tag; note that the subtags are not checked against the registry.
+    pub fn language_tag(&self) -> Option<String> {
+        self.language.as_deref().and_then(normalise_language_tag)
+    }
+
+    /// The WebSub (formerly PubSubHubbub) hubs that push updates to this feed, i.e. the links with a "hub" relation
+    ///
+    /// A client subscribes to one of these hubs using the feed's `self_link()` as the topic.
+    pub fn hub_links(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| link.has_rel("hub"))
+    }
+
+    /// The canonical URL of the feed itself, i.e. the first link with a "self" relation
+    pub fn self_link(&self) -> Option<&Link> {
+        self.links.iter().find(|link| link.has_rel("self"))
+    }
+
+    /// The next page of a paged feed (RFC 5005), i.e. the first link with a "next" relation
+    ///
+    /// JSON Feed's "next_url" is recorded as such a link. See `fetcher::fetch_pages()` to fetch each page in turn.
+    pub fn next_page(&self) -> Option<&Link> {
+        self.links.iter().find(|link| link.has_rel("next"))
+    }
+
+    /// The links of an archived feed (RFC 5005), i.e. those with a "current", "prev-archive" or "next-archive" relation
+    ///
+    /// These lead from the current feed back through the archive documents holding its older entries, and between those documents.
+    pub fn archive_links(&self) -> impl Iterator<Item = &Link> {
+        self.links
+            .iter()
+            .filter(|link| link.has_rel("current") || link.has_rel("prev-archive") || link.has_rel("next-archive"))
+    }
+
+    /// The next time the feed should be fetched, given the time it was last fetched
+    ///
+    /// This is at least the TTL and the update interval of the syndication module after the last fetch (aligned to the update base if
+    /// there is one), moved on past any skipped hours and days. With no hints from the publisher it is the last fetch time itself, so
+    /// callers should still apply their own minimum and maximum interval between fetches.
+    pub fn next_fetch(&self, last_fetch: DateTime<Utc>) -> DateTime<Utc> {
+        let default_hints = UpdateHints::default();
+        let hints = self.update_hints.as_ref().unwrap_or(&default_hints);
+
+        let mut next = match (hints.update_interval(), hints.update_base) {
+            // Updates are scheduled from the base, so fetch once the next one is due
+            (Some(interval), Some(base)) if base < last_fetch && interval > chrono::Duration::zero() => {
+                let periods = (last_fetch - base).num_seconds() / interval.num_seconds().max(1) + 1;
+                base + chrono::Duration::seconds(interval.num_seconds().max(1) * periods)
+            }
+            (Some(interval), _) => last_fetch + interval,
+            (None, _) => last_fetch,
+        };
+        if let Some(ttl) = self.ttl {
+            next = next.max(last_fetch + chrono::Duration::minutes(ttl as i64));
+        }
+
+        // Move on to the start of the next hour until it is not skipped, giving up if every hour of the week is
+        for _ in 0..(24 * 7) {
+            if !hints.skip_hours.contains(&next.hour()) && !hints.skip_days.contains(&next.weekday()) {
+                break;
+            }
+            next = next
+                .with_minute(0)
+                .and_then(|t| t.with_second(0))
+                .and_then(|t| t.with_nanosecond(0))
+                .unwrap_or(next)
+                + chrono::Duration::hours(1);
+        }
+
+        next
+    }
+
+    /// Combines a newer fetch of this feed into it, keeping the entries already seen
+    ///
+    /// The feed level fields are taken from the newer feed, apart from `updated` which keeps the latest of the two. Entries are matched by
+    /// their ID or else their link, and the newer version of a matched entry is kept (going by its `updated` or else `published` time, with
+    /// the newer feed winning if neither says). Entries that were not already in the feed are added ahead of the existing ones, in the order
+    /// of the newer feed, as feeds list their latest entries first.
+    pub fn merge(&mut self, mut other: Feed) {
+        let entries = std::mem::take(&mut other.entries);
+        let existing = std::mem::replace(self, other);
+        self.updated = self.updated.max(existing.updated);
+
+        let mut merged = existing.entries;
+        let mut added: Vec<Entry> = Vec::new();
+        for entry in entries {
+            match merged.iter_mut().chain(added.iter_mut()).find(|seen| seen.is_same_entry(&entry)) {
+                Some(seen) if entry.is_newer_than(seen) => *seen = entry,
+                Some(_) => {}
+                None => added.push(entry),
+            }
+        }
+        added.append(&mut merged);
+        self.entries = added;
+    }
+
+    /// Compares two feeds, ignoring differences which do not change their meaning (see `semantic_differences()`)
+    pub fn semantically_eq(&self, other: &Feed) -> bool {
+        self.semantic_differences(other).is_empty()
+    }
+
+    /// Describes how this feed differs from another, ignoring differences which do not change their meaning
+    ///
+    /// Entries are matched by ID rather than position, and runs of whitespace in text are treated as a single space. Timestamps and
+    /// namespace prefixes are already normalised by the parser, so differences in their formatting are also ignored.
+    ///
+    /// Note that feeds and entries without an ID are given one derived from their links or content when parsed, so will only match if those do.
+    ///
+    /// Each difference names the field concerned, e.g. "title" or "entries[urn:uuid:1225c695].summary", or notes an entry found in
+    /// only one of the feeds. The result is empty if the feeds are semantically equal.
+    pub fn semantic_differences(&self, other: &Feed) -> Vec<String> {
+        let (a, b) = (self.normalised(), other.normalised());
+        let mut differences = Vec::new();
+        compare_fields!(
+            differences,
+            "",
+            a,
+            b,
+            feed_type,
+            version,
+            id,
+            title,
+            title_variants,
+            updated,
+            authors,
+            description,
+            description_variants,
+            links,
+            categories,
+            cloud,
+            contributors,
+            docs,
+            generator,
+            icon,
+            language,
+            logo,
+            published,
+            rating,
+            rights,
+            license,
+            ttl,
+            text_input,
+            update_hints,
+            stylesheets,
+            itunes,
+            podcast,
+            dublin_core,
+            extensions
+        );
+
+        // Match up the entries by their ID
+        for entry in &a.entries {
+            match b.entries.iter().find(|other| other.id == entry.id) {
+                Some(other) => {
+                    let prefix = format!("entries[{}].", entry.id);
+                    compare_fields!(
+                        differences,
+                        prefix,
+                        entry,
+                        other,
+                        title,
+                        title_variants,
+                        updated,
+                        authors,
+                        content,
+                        links,
+                        summary,
+                        summary_variants,
+                        categories,
+                        contributors,
+                        published,
+                        source,
+                        rights,
+                        license,
+                        language,
+                        media,
+                        itunes,
+                        podcast,
+                        dublin_core,
+                        geo,
+                        threading,
+                        extensions
+                    );
+                }
+                None => differences.push(format!("entries[{}]: only in this feed", entry.id)),
+            }
+        }
+        for entry in &b.entries {
+            if !a.entries.iter().any(|other| other.id == entry.id) {
+                differences.push(format!("entries[{}]: only in the other feed", entry.id));
+            }
+        }
+
+        differences
+    }
+
+    // A copy of the feed with the text normalised for comparison
+    pub(crate) fn normalised(&self) -> Feed {
+        let mut feed = self.clone();
+        normalise_text(&mut feed.title);
+        normalise_text(&mut feed.description);
+        normalise_text(&mut feed.rights);
+        feed.title_variants
+            .iter_mut()
+            .chain(feed.description_variants.iter_mut())
+            .for_each(|v| normalise_whitespace(&mut v.text.content));
+
+        for entry in feed.entries.iter_mut() {
+            normalise_text(&mut entry.title);
+            normalise_text(&mut entry.summary);
+            normalise_text(&mut entry.rights);
+            entry
+                .title_variants
+                .iter_mut()
+                .chain(entry.summary_variants.iter_mut())
+                .for_each(|v| normalise_whitespace(&mut v.text.content));
+            if let Some(body) = entry.content.as_mut().and_then(|content| content.body.as_mut()) {
+                normalise_whitespace(body);
+            }
+            for media in entry.media.iter_mut() {
+                normalise_text(&mut media.title);
+                normalise_text(&mut media.description);
+            }
+        }
+
+        feed
+    }
 }
 
 #[cfg(test)]
@@ -155,7 +525,8 @@ impl Feed {
         self
     }
 
-    pub fn entry(mut self, entry: Entry) -> Self {
+    pub fn entry(mut self, mut entry: Entry) -> Self {
+        entry.document_index = self.entries.len();
         self.entries.push(entry);
         self
     }
@@ -190,6 +561,31 @@ impl Feed {
         self
     }
 
+    pub fn rating(mut self, rating: MediaRating) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    pub fn cloud(mut self, cloud: Cloud) -> Self {
+        self.cloud = Some(cloud);
+        self
+    }
+
+    pub fn text_input(mut self, text_input: TextInput) -> Self {
+        self.text_input = Some(text_input);
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_owned());
+        self
+    }
+
+    pub fn docs(mut self, docs: &str) -> Self {
+        self.docs = Some(docs.to_owned());
+        self
+    }
+
     pub fn published_rfc2822(mut self, pub_date: &str) -> Self {
         self.published = timestamp_rfc2822_lenient(pub_date);
         self
@@ -200,11 +596,41 @@ impl Feed {
         self
     }
 
+    pub fn license(mut self, license: Link) -> Self {
+        self.license = Some(license);
+        self
+    }
+
     pub fn rights(mut self, rights: Text) -> Self {
         self.rights = Some(rights);
         self
     }
 
+    pub fn stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheets.push(stylesheet);
+        self
+    }
+
+    pub fn itunes(mut self, itunes: ITunesFeedExtension) -> Self {
+        self.itunes = Some(itunes);
+        self
+    }
+
+    pub fn podcast(mut self, podcast: PodcastFeedExtension) -> Self {
+        self.podcast = Some(podcast);
+        self
+    }
+
+    pub fn dublin_core(mut self, dublin_core: DublinCoreExtension) -> Self {
+        self.dublin_core = Some(dublin_core);
+        self
+    }
+
+    pub fn extension(mut self, namespace: &str, element: ExtensionElement) -> Self {
+        self.extensions.entry(namespace.to_owned()).or_default().push(element);
+        self
+    }
+
     pub fn title(mut self, title: Text) -> Self {
         self.title = Some(title);
         self
@@ -215,6 +641,11 @@ impl Feed {
         self
     }
 
+    pub fn update_hints(mut self, hints: UpdateHints) -> Self {
+        self.update_hints = Some(hints);
+        self
+    }
+
     pub fn updated(mut self, updated: Option<DateTime<Utc>>) -> Self {
         self.updated = updated;
         self
@@ -232,10 +663,13 @@ impl Feed {
 }
 
 /// Type of a feed (RSS, Atom etc)
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FeedType {
+    /// Atom 1.0, or the legacy Atom 0.3
     Atom,
     JSON,
+    /// RSS 0.91 and 0.92 (along with the 0.93 and 0.94 drafts), which are parsed with the reduced element set of RSS 2.0
     RSS0,
     RSS1,
     RSS2,
@@ -245,18 +679,24 @@ pub enum FeedType {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Entry {
-    /// A unique identifier for this item with a feed. If not supplied it is initialised to a hash of the first link or a UUID if not available.
+    /// A unique identifier for this item with a feed. If not supplied it is initialised to a hash of the first link, or of the title and content if not available.
     /// * Atom (required): Identifies the entry using a universally unique and permanent URI.
     /// * RSS 2 (optional) "guid": A string that uniquely identifies the item.
     /// * RSS 1: does not specify a unique ID as a separate item, but does suggest the URI should be "the same as the link" so we use a hash of the link if found
     /// * JSON Feed: is unique for that item for that feed over time.
     pub id: String,
+    /// Whether the ID is also the URL of the item, which is `None` unless the format says
+    /// * RSS 2 "guid" isPermaLink: true unless the attribute is "false". If the item has no "link", an http(s) URL in its guid is used as its link.
+    pub is_permalink: Option<bool>,
     /// Title of this item within the feed
     /// * Atom, RSS 1(required): Contains a human readable title for the entry.
     /// * RSS 2 (optional): The title of the item.
     /// * JSON Feed: The title of the item.
     pub title: Option<Text>,
+    /// Every title found for the item (e.g. "title", "dc:title" and "itunes:title"), in document order, if enabled with `parser::Builder::text_variants()`
+    pub title_variants: Vec<TextVariant>,
     /// Time at which this item was last modified. If not provided in the source, or invalid, it is `None`.
+    /// Values are never synthesised (e.g. from the feed's update time), so `None` reliably means the source has no such date.
     /// * Atom (required): Indicates the last time the entry was modified in a significant way.
     /// * RSS doesn't specify this field.
     /// * JSON Feed: the last modification date of this item
@@ -274,15 +714,17 @@ pub struct Entry {
     pub content: Option<Content>,
     /// Links associated with this item
     /// * Atom (recommended): Identifies a related Web page.
-    /// * RSS 2 (optional): The URL of the item.
+    /// * RSS 2 (optional): The URL of the item, or the guid if it is a permalink and there is no "link".
     /// * RSS 1 (required): The item's URL.
     /// * JSON Feed: the url and external URL for the item is the first items, then each subsequent attachment
     pub links: Vec<Link>,
-    /// A short summary of the item
+    /// A short summary of the item (see also `best_description()`)
     /// * Atom (recommended): Conveys a short summary, abstract, or excerpt of the entry.
     /// * RSS 1+2 (optional): The item synopsis.
     /// * JSON Feed: the summary for the item, or the text content if no summary is provided and both text and html content are specified
     pub summary: Option<Text>,
+    /// Every summary found for the item (e.g. "description" and "itunes:summary"), in document order, if enabled with `parser::Builder::text_variants()`
+    pub summary_variants: Vec<TextVariant>,
 
     /// Structured classification of the item
     /// * Atom (optional): Specifies a category that the entry belongs to. A feed may have multiple category elements.
@@ -296,35 +738,252 @@ pub struct Entry {
     /// * RSS 2 (optional) "pubDate": Indicates when the item was published.
     /// * JSON Feed: the date at which the item was published
     pub published: Option<DateTime<Utc>>,
-    /// Atom (optional): If an entry is copied from one feed into another feed, then this contains the source feed metadata.
-    pub source: Option<String>,
+    /// The feed the entry was copied from, so it can be attributed to the original publisher
+    /// * Atom (optional): If an entry is copied from one feed into another feed, then this contains the source feed metadata.
+    /// * RSS 2 (optional) "source": The RSS channel that the item came from, with its name as the title and URL as a "self" link.
+    pub source: Option<FeedSource>,
     /// Atom (optional): Conveys information about rights, e.g. copyrights, held in and over the feed.
     pub rights: Option<Text>,
+    /// The license the entry is published under, with the relation "license"
+    /// * Atom (optional): the first link with the "license" relation (RFC 4946), which is also in the links
+    /// * RSS 2 (optional) "creativeCommons:license": The URL of the Creative Commons license for the item.
+    pub license: Option<Link>,
+    /// The language the item is written in, for feeds whose items are in different languages
+    /// * Atom: the xml:lang in effect for the entry, declared on it or inherited from the feed
+    /// * RSS 1 + 2 (optional) "dc:language", or else the xml:lang in effect for the item
+    ///
+    /// The language of the feed as a whole (e.g. RSS "language") is not copied here, so this is `None` unless the item or its
+    /// ancestors declare one. As with `Feed::language` it is as found in the source (see `language_tag()`).
+    pub language: Option<String>,
 
     /// Extension for MediaRSS - https://www.rssboard.org/media-rss
     /// A MediaObject will be created in two cases:
     /// 1) each "media:group" element encountered in the feed
     /// 2) a default for any other "media:*" elements found at the item level
+    ///
+    /// RSS enclosures and Atom links with the "enclosure" relation are added to the default object as media content, so podcast
+    /// episodes are found in the same place whatever the format.
+    ///
     /// See the Atom tests for youtube and newscred for examples
     pub media: Vec<MediaObject>,
+    /// Extension for podcasts, populated if the item has any elements from the iTunes namespace (RSS 2 only)
+    ///
+    /// Where possible these are also mapped on to the rest of the model e.g. "itunes:duration" to the default media object.
+    pub itunes: Option<ITunesItemExtension>,
+    /// Extension for podcasts, populated if the item has any elements from the Podcasting 2.0 namespace (RSS 2 only)
+    ///
+    /// "podcast:person" is also mapped on to the contributors of the item.
+    pub podcast: Option<PodcastItemExtension>,
+    /// Extension for Dublin Core metadata, populated if the item has any elements from the Dublin Core namespace (RSS 1 + 2 only)
+    pub dublin_core: Option<DublinCoreExtension>,
+    /// Extension for the location of the item, populated from the GeoRSS or W3C Basic Geo elements (RSS 1 + 2 and Atom)
+    pub geo: Option<GeoExtension>,
+    /// Extension for comment threads, populated from the Atom Threading, Slash and Well-Formed Web elements and links to replies (RSS 1 + 2 and Atom)
+    pub threading: Option<ThreadingExtension>,
+    /// Elements within the item from namespaces the parser does not otherwise understand (e.g. "slash:department"), keyed by namespace URI
+    pub extensions: ExtensionMap,
+    /// The position of the item in the source document, counting from 0
+    ///
+    /// `Feed::entries` are in document order, apart from RSS 1.0 where they follow the channel's `rdf:Seq`, so this allows the
+    /// original order to be recovered after the entries are sorted, deduplicated or merged (see `Feed::merge()`).
+    pub document_index: usize,
+    /// The XML of the item exactly as found in the source (after decoding to UTF-8), if enabled with `parser::Builder::raw_entries()`
+    ///
+    /// This is for consumers that need what was received rather than the model (e.g. archives, or verifying signatures), so it is not
+    /// considered by `Feed::semantic_differences()`. JSON Feed items are not recorded.
+    pub raw: Option<String>,
 }
 
 impl Default for Entry {
     fn default() -> Self {
         Entry {
             id: "".into(),
+            is_permalink: None,
             title: None,
+            title_variants: Vec::new(),
             updated: None,
             authors: Vec::new(),
             content: None,
             links: Vec::new(),
             summary: None,
+            summary_variants: Vec::new(),
             categories: Vec::new(),
             contributors: Vec::new(),
             published: None,
             source: None,
             rights: None,
+            license: None,
+            language: None,
             media: Vec::new(),
+            itunes: None,
+            podcast: None,
+            dublin_core: None,
+            geo: None,
+            threading: None,
+            extensions: ExtensionMap::new(),
+            document_index: 0,
+            raw: None,
+        }
+    }
+}
+
+impl Entry {
+    /// Estimates the number of bytes this entry holds on the heap (see `Feed::heap_size()`)
+    pub fn heap_size(&self) -> usize {
+        HeapSize::heap_size(self)
+    }
+
+    /// The best available date for this item, for sorting or display: the time it was last updated, or else when it was published
+    ///
+    /// Dates are never synthesised (see `updated`), so this is `None` only if the source has neither.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.updated.or(self.published)
+    }
+
+    /// The language of the item as a BCP 47 tag with the conventional casing, as for `Feed::language_tag()`
+    ///
+    /// `None` is returned if the item does not declare a language (see `language`), or it is not a well-formed tag.
+    pub fn language_tag(&self) -> Option<String> {
+        self.language.as_deref().and_then(normalise_language_tag)
+    }
+
+    /// The most complete description of this item available, for display
+    ///
+    /// Podcast feeds often supply different text in each of "content:encoded", "description" and "itunes:summary", and all are retained
+    /// (in `content`, `summary` and the media `description` respectively). This prefers the full content, then the summary and finally the
+    /// description of the first media object that has one.
+    pub fn best_description(&self) -> Option<&str> {
+        self.content
+            .as_ref()
+            .and_then(|content| content.body.as_deref())
+            .or_else(|| self.summary.as_ref().map(|summary| summary.content.as_str()))
+            .or_else(|| {
+                self.media
+                    .iter()
+                    .find_map(|media| media.description.as_ref())
+                    .map(|description| description.content.as_str())
+            })
+    }
+
+    /// The body of this item, preferring the inline content, then a link to content held elsewhere and finally the summary
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use feed_rs::model::Body;
+    /// use feed_rs::parser;
+    ///
+    /// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><entry>
+    ///     <id>urn:1</id><summary>The summary</summary><content type="html">&lt;p&gt;The content&lt;/p&gt;</content>
+    /// </entry></feed>"#;
+    /// let feed = parser::parse(xml.as_bytes()).unwrap();
+    /// match feed.entries[0].body() {
+    ///     Some(Body::Inline(content)) => assert_eq!(content.body.as_deref(), Some("<p>The content</p>")),
+    ///     body => panic!("unexpected body {:?}", body),
+    /// }
+    /// ```
+    pub fn body(&self) -> Option<Body<'_>> {
+        let content = self.content.as_ref();
+        content
+            .filter(|content| content.body.is_some())
+            .map(Body::Inline)
+            .or_else(|| content.and_then(|content| content.src.as_ref()).map(Body::External))
+            .or_else(|| self.summary.as_ref().map(Body::Summary))
+    }
+
+    /// A plain text summary of this item for previews, of at most `max_len` bytes
+    ///
+    /// This is the summary, or else the inline content, with any HTML markup removed and whitespace collapsed. Markup is removed
+    /// whatever the type of the text, as RSS descriptions are usually HTML without saying so. Longer text is cut at
+    /// the last word that fits (or the last character, for a single long word) without adding an ellipsis.
+    pub fn summary_text(&self, max_len: usize) -> Option<String> {
+        let text = match (&self.summary, self.content.as_ref().and_then(|content| content.body.as_ref())) {
+            (Some(summary), _) => strip_tags(&summary.content),
+            (None, Some(body)) => strip_tags(body),
+            (None, None) => return None,
+        };
+        if text.len() <= max_len {
+            return Some(text);
+        }
+
+        let mut end = max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        // Cut at the end of the last word that fits, unless the cut already falls between words
+        if !text[end..].starts_with(' ') {
+            end = text[..end].rfind(' ').filter(|space| *space > 0).unwrap_or(end);
+        }
+        Some(text[..end].to_owned())
+    }
+
+    /// A hash of the content of this item, for telling cheaply whether it has changed
+    ///
+    /// This covers the ID, title, summary, inline content, links and time published, which survive being written in either format and
+    /// parsed again. The time updated is left out as RSS 2.0 has no place for it. `writer::Builder::content_hashes()` writes the hash
+    /// out with each entry, to be checked with `verify_content_hash()` once parsed.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = SipHasher::new_with_keys(CONTENT_HASH_KEY1, CONTENT_HASH_KEY2);
+        let mut links: Vec<&str> = self.links.iter().map(|link| link.href.as_str()).collect();
+        links.sort_unstable();
+        let texts = [
+            Some(self.id.as_str()),
+            self.title.as_ref().map(|title| title.content.as_str()),
+            self.summary.as_ref().map(|summary| summary.content.as_str()),
+            self.content.as_ref().and_then(|content| content.body.as_deref()),
+        ];
+        // Each part is followed by a separator so the parts cannot run together
+        for part in texts.iter().copied().chain(links.into_iter().map(Some)) {
+            hasher.write(part.unwrap_or_default().as_bytes());
+            hasher.write_u8(0xff);
+        }
+        hasher.write_i64(self.published.map_or(0, |published| published.timestamp()));
+        let hash = hasher.finish128();
+        format!("{:016x}{:016x}", hash.h1, hash.h2)
+    }
+
+    /// Checks the content hash written with this item (see `content_hash()`) against its content as parsed
+    ///
+    /// `None` is returned if no hash was written, and otherwise whether it matches, so a mismatch means the item was changed after it
+    /// was written (e.g. by a proxy).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use feed_rs::{parser, writer};
+    /// let feed = parser::parse(r#"<rss version="2.0"><channel><item><guid>1</guid><title>Item</title></item></channel></rss>"#.as_bytes()).unwrap();
+    ///
+    /// let mut rss = Vec::new();
+    /// writer::Builder::new().content_hashes(true).build().write_rss2(&feed, &mut rss).unwrap();
+    /// let parsed = parser::parse(rss.as_slice()).unwrap();
+    /// assert_eq!(parsed.entries[0].verify_content_hash(), Some(true));
+    /// assert_eq!(feed.entries[0].verify_content_hash(), None);
+    /// ```
+    pub fn verify_content_hash(&self) -> Option<bool> {
+        self.extensions
+            .get(CONTENT_HASH_NAMESPACE)
+            .and_then(|elements| elements.iter().find(|element| element.name == "hash"))
+            .map(|element| element.value.as_deref().map(str::trim) == Some(self.content_hash().as_str()))
+    }
+
+    // Whether the entries are versions of the same item, i.e. they share an ID or link (see `Feed::merge()`)
+    pub(crate) fn is_same_entry(&self, other: &Entry) -> bool {
+        fn link(entry: &Entry) -> Option<&str> {
+            entry
+                .links
+                .iter()
+                .find(|link| link.rel.is_none() || link.has_rel("alternate"))
+                .map(|link| link.href.as_str())
+        }
+        self.id == other.id || (link(self).is_some() && link(self) == link(other))
+    }
+
+    // Whether this version of an entry should replace the other, preferring this one if neither has a time (see `Feed::merge()`)
+    fn is_newer_than(&self, other: &Entry) -> bool {
+        match (self.timestamp(), other.timestamp()) {
+            (Some(this), Some(other)) => this >= other,
+            (None, Some(_)) => false,
+            (_, None) => true,
         }
     }
 }
@@ -356,6 +1015,16 @@ impl Entry {
         self
     }
 
+    pub fn is_permalink(mut self, is_permalink: bool) -> Self {
+        self.is_permalink = Some(is_permalink);
+        self
+    }
+
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_owned());
+        self
+    }
+
     pub fn link(mut self, link: Link) -> Self {
         self.links.push(link);
         self
@@ -371,11 +1040,21 @@ impl Entry {
         self
     }
 
+    pub fn license(mut self, license: Link) -> Self {
+        self.license = Some(license);
+        self
+    }
+
     pub fn rights(mut self, rights: Text) -> Self {
         self.rights = Some(rights);
         self
     }
 
+    pub fn source(mut self, source: FeedSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     pub fn summary(mut self, summary: Text) -> Self {
         self.summary = Some(summary);
         self
@@ -405,22 +1084,54 @@ impl Entry {
         self.media.push(media);
         self
     }
+
+    pub fn itunes(mut self, itunes: ITunesItemExtension) -> Self {
+        self.itunes = Some(itunes);
+        self
+    }
+
+    pub fn podcast(mut self, podcast: PodcastItemExtension) -> Self {
+        self.podcast = Some(podcast);
+        self
+    }
+
+    pub fn dublin_core(mut self, dublin_core: DublinCoreExtension) -> Self {
+        self.dublin_core = Some(dublin_core);
+        self
+    }
+
+    pub fn geo(mut self, geo: GeoExtension) -> Self {
+        self.geo = Some(geo);
+        self
+    }
+
+    pub fn threading(mut self, threading: ThreadingExtension) -> Self {
+        self.threading = Some(threading);
+        self
+    }
+
+    pub fn extension(mut self, namespace: &str, element: ExtensionElement) -> Self {
+        self.extensions.entry(namespace.to_owned()).or_default().push(element);
+        self
+    }
 }
 
 /// Represents the category of a feed or entry
 ///
 /// [Atom spec]: http://www.atomenabled.org/developers/syndication/#category
 /// [RSS 2 spec]: https://validator.w3.org/feed/docs/rss2.html#ltcategorygtSubelementOfLtitemgt
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Category {
     /// The category as a human readable string
     /// * Atom (required): Identifies the category.
     /// * RSS 2: The value of the element is a forward-slash-separated string that identifies a hierarchic location in the indicated taxonomy. Processors may establish conventions for the interpretation of categories.
     /// * JSON Feed: the value of the tag
-    pub term: String,
-    /// Atom (optional): Identifies the categorization scheme via a URI.
-    pub scheme: Option<String>,
+    pub term: SharedString,
+    /// The categorization scheme
+    /// * Atom (optional): Identifies the categorization scheme via a URI.
+    /// * RSS 2 (optional) "domain": A string that identifies a categorization taxonomy.
+    pub scheme: Option<SharedString>,
     /// Atom (optional): Provides a human-readable label for display.
     pub label: Option<String>,
 }
@@ -433,6 +1144,14 @@ impl Category {
             label: None,
         }
     }
+
+    /// The levels of the taxonomy in the term, from the broadest to the most specific
+    ///
+    /// RSS 2 categories are forward-slash-separated locations in a hierarchy (e.g. "Business/Industries/Publishing"), so the term is
+    /// split at each slash, ignoring empty levels and the whitespace around them. A term without a slash is a single level.
+    pub fn path(&self) -> Vec<&str> {
+        self.term.split('/').map(str::trim).filter(|level| !level.is_empty()).collect()
+    }
 }
 
 #[cfg(test)]
@@ -443,7 +1162,7 @@ impl Category {
     }
 
     pub fn scheme(mut self, scheme: &str) -> Self {
-        self.scheme = Some(scheme.to_owned());
+        self.scheme = Some(scheme.into());
         self
     }
 }
@@ -452,10 +1171,11 @@ impl Category {
 ///
 /// [Atom spec]: http://www.atomenabled.org/developers/syndication/#contentElement
 /// [RSS 2.0]: https://validator.w3.org/feed/docs/rss2.html#ltenclosuregtSubelementOfLtitemgt
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Content {
     /// Atom
+    /// * If the type attribute is xhtml, this is the markup inside the div that wraps it.
     /// * If the type attribute ends in +xml or /xml, then an xml document of this type is contained inline.
     /// * If the type attribute starts with text, then an escaped document of this type is contained inline.
     /// * Otherwise a base64 encoded document of the indicated media type is contained inline.
@@ -463,6 +1183,7 @@ pub struct Content {
     /// Type of content
     /// * Atom: The type attribute is either text, html, xhtml, in which case the content element is defined identically to other text constructs.
     /// * RSS 2: Type says what its type is, a standard MIME type
+    #[cfg_attr(feature = "serde", serde(with = "serde_mime"))]
     pub content_type: Mime,
     /// RSS 2.0: Length of the content in bytes
     pub length: Option<u64>,
@@ -470,6 +1191,8 @@ pub struct Content {
     /// * Atom: If the src attribute is present, it represents the URI of where the content can be found. The type attribute, if present, is the media type of the content.
     /// * RSS 2.0: where the enclosure is located
     pub src: Option<Link>,
+    /// The language of the content, from the xml:lang in effect for it or else the language of the feed (see `Text::lang`)
+    pub lang: Option<String>,
 }
 
 impl Default for Content {
@@ -479,10 +1202,100 @@ impl Default for Content {
             content_type: mime::TEXT_PLAIN,
             length: None,
             src: None,
+            lang: None,
         }
     }
 }
 
+impl Content {
+    /// Decodes the body of inline content with a binary media type (e.g. "image/png"), which Atom requires to be base64 encoded
+    ///
+    /// Returns None for textual or XML content, or if the body is not valid base64.
+    pub fn decoded_body(&self) -> Option<Vec<u8>> {
+        let textual = self.content_type.type_() == mime::TEXT
+            || self.content_type.suffix() == Some(mime::XML)
+            || self.content_type.subtype() == mime::XML
+            || self.content_type == mime::APPLICATION_JSON;
+        if textual {
+            return None;
+        }
+
+        // Encoded content is commonly wrapped over several lines
+        let encoded: String = self.body.as_ref()?.chars().filter(|c| !c.is_whitespace()).collect();
+        base64::decode(encoded).ok()
+    }
+
+    /// Classifies the content, so a reader can choose how to show it (e.g. as an image, or an audio or video player)
+    ///
+    /// The media type is used if it is specific enough, and otherwise the extension of the `src` (e.g. ".mp3"), since the type of
+    /// out of line content is optional and falls back to "text/plain". Returns None if the kind cannot be determined.
+    pub fn kind(&self) -> Option<ContentKind> {
+        let by_type = match (self.content_type.type_(), self.content_type.subtype()) {
+            (mime::IMAGE, _) => Some(ContentKind::Image),
+            (mime::AUDIO, _) => Some(ContentKind::Audio),
+            (mime::VIDEO, _) => Some(ContentKind::Video),
+            (mime::TEXT, mime::HTML) => Some(ContentKind::Html),
+            (mime::APPLICATION, subtype) if subtype == "xhtml" => Some(ContentKind::Html),
+            _ => None,
+        };
+        by_type.or_else(|| self.src.as_ref().and_then(|src| ContentKind::from_extension(&src.href)))
+    }
+}
+
+/// The best available body of an entry, as found by `Entry::body()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Body<'a> {
+    /// Content held within the entry
+    Inline(&'a Content),
+    /// Content held elsewhere, at the URI of its "src" (e.g. Atom content with out of line content)
+    External(&'a Link),
+    /// The summary, for entries without any content
+    Summary(&'a Text),
+}
+
+/// The kind of an entry's `Content`, as found by `Content::kind()`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContentKind {
+    Image,
+    Audio,
+    Video,
+    /// An HTML or XHTML document
+    Html,
+}
+
+impl ContentKind {
+    // Classifies a URL from the extension of its path, ignoring any query or fragment
+    fn from_extension(url: &str) -> Option<ContentKind> {
+        let path = url.split(['?', '#']).next()?;
+        let (name, extension) = path.rsplit_once('.')?;
+        if name.ends_with('/') || extension.contains('/') {
+            return None;
+        }
+        match extension.to_ascii_lowercase().as_str() {
+            "apng" | "avif" | "bmp" | "gif" | "ico" | "jpeg" | "jpg" | "png" | "svg" | "webp" => Some(ContentKind::Image),
+            "aac" | "flac" | "m4a" | "mp3" | "oga" | "ogg" | "opus" | "wav" => Some(ContentKind::Audio),
+            "m4v" | "mkv" | "mov" | "mp4" | "ogv" | "webm" => Some(ContentKind::Video),
+            "htm" | "html" | "xhtml" => Some(ContentKind::Html),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "sanitize")]
+impl Content {
+    /// Returns a copy of the content with any unsafe markup (e.g. scripts, frames and event handlers) removed from an HTML body
+    ///
+    /// Content of other types is returned unchanged.
+    pub fn sanitized(&self) -> Content {
+        let mut content = self.clone();
+        if content.content_type == mime::TEXT_HTML {
+            content.body = content.body.map(|body| crate::util::sanitize::sanitize_html(&body));
+        }
+        content
+    }
+}
+
 #[cfg(test)]
 impl Content {
     pub fn body(mut self, body: &str) -> Self {
@@ -495,6 +1308,11 @@ impl Content {
         self
     }
 
+    pub fn lang(mut self, lang: &str) -> Self {
+        self.lang = Some(lang.to_owned());
+        self
+    }
+
     pub fn length(mut self, length: u64) -> Self {
         self.length = Some(length);
         self
@@ -506,10 +1324,60 @@ impl Content {
     }
 }
 
+/// The feed an entry was copied from, so that aggregators can preserve the original attribution
+///
+/// An RSS 2 "source" only has the title and a link to the channel.
+///
+/// [Atom spec]: https://tools.ietf.org/html/rfc4287#section-4.2.11
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FeedSource {
+    /// Atom: The ID of the source feed
+    pub id: Option<String>,
+    /// Atom: The title of the source feed
+    pub title: Option<Text>,
+    /// Atom: The time the source feed was last modified
+    pub updated: Option<DateTime<Utc>>,
+    /// Atom: The authors of the source feed
+    pub authors: Vec<Person>,
+    /// Atom: Links to the source feed and its website
+    pub links: Vec<Link>,
+    /// Atom: The rights held in and over the source feed
+    pub rights: Option<Text>,
+}
+
+#[cfg(test)]
+impl FeedSource {
+    pub fn author(mut self, person: Person) -> Self {
+        self.authors.push(person);
+        self
+    }
+
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_owned());
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        self.links.push(link);
+        self
+    }
+
+    pub fn title(mut self, title: Text) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn updated_rfc3339(mut self, updated: &str) -> Self {
+        self.updated = timestamp_rfc3339_lenient(updated);
+        self
+    }
+}
+
 /// Information on the tools used to generate the feed
 ///
 /// Atom: Identifies the software used to generate the feed, for debugging and other purposes.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Generator {
     /// Atom: Additional data
@@ -549,7 +1417,7 @@ impl Generator {
 /// [Atom spec]:  http://www.atomenabled.org/developers/syndication/#optionalFeedElements
 /// [RSS 2 spec]: https://validator.w3.org/feed/docs/rss2.html#ltimagegtSubelementOfLtchannelgt
 /// [RSS 1 spec]: https://validator.w3.org/feed/docs/rss1.html#s5.4
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Image {
     /// Link to the image
@@ -562,8 +1430,12 @@ pub struct Image {
     pub link: Option<Link>,
 
     /// RSS 2 (optional): width of the image
+    /// * `None` if not provided or invalid; the spec default of 88 is not substituted
+    /// * Values over the spec maximum of 144 are clamped to it
     pub width: Option<u32>,
     /// RSS 2 (optional): height of the image
+    /// * `None` if not provided or invalid; the spec default of 31 is not substituted
+    /// * Values over the spec maximum of 400 are clamped to it
     pub height: Option<u32>,
     /// RSS 2 (optional): contains text that is included in the TITLE attribute of the link formed around the image in the HTML rendering.
     pub description: Option<String>,
@@ -613,7 +1485,7 @@ impl Image {
 /// Represents a link to an associated resource for the feed or entry.
 ///
 /// [Atom spec]: http://www.atomenabled.org/developers/syndication/#link
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Link {
     /// Link to additional content
@@ -623,8 +1495,11 @@ pub struct Link {
     pub href: String,
     /// A single link relationship type.
     pub rel: Option<String>,
-    /// Indicates the media type of the resource.
-    pub media_type: Option<String>,
+    /// Indicates the media type of the resource, with the type and subtype in lower case (e.g. "text/html")
+    ///
+    /// Values that are not valid media types (e.g. "text / html") are ignored.
+    #[cfg_attr(feature = "serde", serde(with = "serde_mime::option"))]
+    pub media_type: Option<Mime>,
     /// Indicates the language of the referenced resource.
     pub href_lang: Option<String>,
     /// Human readable information about the link, typically for display purposes.
@@ -649,6 +1524,14 @@ impl Link {
             length: None,
         }
     }
+
+    // Checks the relation type, allowing for feeds that list several space separated types as HTML does
+    pub(crate) fn has_rel(&self, rel: &str) -> bool {
+        match &self.rel {
+            Some(rels) => rels.split_whitespace().any(|r| r.eq_ignore_ascii_case(rel)),
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -664,7 +1547,7 @@ impl Link {
     }
 
     pub fn media_type(mut self, media_type: &str) -> Self {
-        self.media_type = Some(media_type.to_owned());
+        self.media_type = Some(media_type.parse().unwrap());
         self
     }
 
@@ -681,7 +1564,8 @@ impl Link {
 
 /// The top-level representation of a media object
 /// i.e. combines "media:*" elements from the RSS Media spec such as those under a media:group
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaObject {
     /// Title of the object (from the media:title element)
     pub title: Option<Text>,
@@ -708,21 +1592,6 @@ impl MediaObject {
     }
 }
 
-impl Default for MediaObject {
-    fn default() -> Self {
-        MediaObject {
-            title: None,
-            content: Vec::new(),
-            duration: None,
-            thumbnails: Vec::new(),
-            texts: Vec::new(),
-            description: None,
-            community: None,
-            credits: Vec::new(),
-        }
-    }
-}
-
 #[cfg(test)]
 impl MediaObject {
     pub fn community(mut self, community: MediaCommunity) -> Self {
@@ -767,7 +1636,8 @@ impl MediaObject {
 }
 
 /// Represents a "media:community" item from the RSS Media spec
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaCommunity {
     /// Star rating
     pub stars_avg: Option<f64>,
@@ -811,12 +1681,16 @@ impl MediaCommunity {
 }
 
 /// Represents a "media:content" item from the RSS Media spec
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaContent {
     /// The direct URL
     pub url: Option<Url>,
     /// Standard MIME type
+    #[cfg_attr(feature = "serde", serde(with = "serde_mime::option"))]
     pub content_type: Option<Mime>,
+    /// The type of object (e.g. "image", "audio", "video", "document" or "executable"), which may be simpler to use than the MIME type
+    pub medium: Option<String>,
     /// Height and width
     pub height: Option<u32>,
     pub width: Option<u32>,
@@ -859,6 +1733,11 @@ impl MediaContent {
         self.size = Some(size);
         self
     }
+
+    pub fn medium(mut self, medium: &str) -> Self {
+        self.medium = Some(medium.to_owned());
+        self
+    }
 }
 
 impl MediaContent {
@@ -866,6 +1745,7 @@ impl MediaContent {
         MediaContent {
             url: None,
             content_type: None,
+            medium: None,
             height: None,
             width: None,
             duration: None,
@@ -876,7 +1756,8 @@ impl MediaContent {
 }
 
 /// Represents a "media:credit" item from the RSS Media spec
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaCredit {
     /// The entity being credited
     pub entity: String,
@@ -890,6 +1771,7 @@ impl MediaCredit {
 
 /// Rating of the feed, item or media within the content
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaRating {
     // The scheme (defaults to "simple" per the spec)
     pub urn: String,
@@ -897,6 +1779,12 @@ pub struct MediaRating {
     pub value: String,
 }
 
+impl Default for MediaRating {
+    fn default() -> Self {
+        MediaRating::new(String::new())
+    }
+}
+
 impl MediaRating {
     pub(crate) fn new(value: String) -> MediaRating {
         MediaRating { urn: "simple".into(), value }
@@ -909,7 +1797,8 @@ impl MediaRating {
 }
 
 /// Represents a "media:text" item from the RSS Media spec
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaText {
     /// The text
     pub text: Text,
@@ -930,7 +1819,8 @@ impl MediaText {
 }
 
 /// Represents a "media:thumbnail" item from the RSS Media spec
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MediaThumbnail {
     /// The thumbnail image
     pub image: Image,
@@ -947,17 +1837,20 @@ impl MediaThumbnail {
 /// Represents an author, contributor etc.
 ///
 /// [Atom spec]: http://www.atomenabled.org/developers/syndication/#person
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Person {
     /// Atom: human-readable name for the person.
     /// JSON Feed: human-readable name for the person.
-    pub name: String,
+    pub name: SharedString,
     /// Atom: home page for the person.
     /// JSON Feed: link to media (Twitter etc) for the person
     pub uri: Option<String>,
     /// Atom: An email address for the person.
     pub email: Option<String>,
+    /// RSS 2: the element naming the person, which is "managingEditor" or "webMaster" for the channel and "author" for an item.
+    /// People known only by their email address are also named after it.
+    pub role: Option<String>,
 }
 
 impl Person {
@@ -966,6 +1859,7 @@ impl Person {
             name: name.trim().into(),
             uri: None,
             email: None,
+            role: None,
         }
     }
 
@@ -973,6 +1867,11 @@ impl Person {
         self.email = Some(email.to_owned());
         self
     }
+
+    pub fn role(mut self, role: &str) -> Self {
+        self.role = Some(role.to_owned());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -983,13 +1882,52 @@ impl Person {
     }
 }
 
+/// A stylesheet associated with the feed document, typically used to render the raw feed in a browser
+///
+/// [W3C recommendation]: https://www.w3.org/TR/xml-stylesheet/
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stylesheet {
+    /// Location of the stylesheet (the "href" pseudo-attribute)
+    pub href: String,
+    /// Type of the stylesheet e.g. "text/xsl" (the "type" pseudo-attribute)
+    pub media_type: Option<String>,
+}
+
+impl Stylesheet {
+    pub(crate) fn new(href: String) -> Stylesheet {
+        Stylesheet { href, media_type: None }
+    }
+}
+
+#[cfg(test)]
+impl Stylesheet {
+    pub fn media_type(mut self, media_type: &str) -> Self {
+        self.media_type = Some(media_type.to_owned());
+        self
+    }
+}
+
 /// Textual content, or link to the content, for a given entry.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Text {
+    #[cfg_attr(feature = "serde", serde(with = "serde_mime"))]
     pub content_type: Mime,
     pub src: Option<String>,
     pub content: String,
+    /// The language of the text
+    ///
+    /// This is the xml:lang in effect for the element, declared on it or inherited from its ancestors, or else the language of the feed
+    /// as a whole (e.g. RSS "language" or "dc:language"). The text of media objects only has the xml:lang in effect for it. Either is
+    /// as found in the source (see `Feed::language_tag()`).
+    pub lang: Option<String>,
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Text::new(String::new())
+    }
 }
 
 impl Text {
@@ -998,14 +1936,847 @@ impl Text {
             content_type: mime::TEXT_PLAIN,
             src: None,
             content,
+            lang: None,
         }
     }
 }
 
+#[cfg(feature = "sanitize")]
+impl Text {
+    /// Returns a copy of the text with any unsafe markup (e.g. scripts, frames and event handlers) removed from HTML content
+    ///
+    /// Text of other types is returned unchanged.
+    pub fn sanitized(&self) -> Text {
+        let mut text = self.clone();
+        if text.content_type == mime::TEXT_HTML {
+            text.content = crate::util::sanitize::sanitize_html(&text.content);
+        }
+        text
+    }
+}
+
 #[cfg(test)]
 impl Text {
     pub fn content_type(mut self, content_type: &str) -> Self {
         self.content_type = content_type.parse::<Mime>().unwrap();
         self
     }
+
+    pub fn lang(mut self, lang: &str) -> Self {
+        self.lang = Some(lang.to_owned());
+        self
+    }
+}
+
+/// The rssCloud service which notifies subscribers of updates to a feed, so it need not be polled
+///
+/// [RSS 2 spec]: https://www.rssboard.org/rsscloud-interface
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cloud {
+    /// "domain": the host of the web service
+    pub domain: String,
+    /// "port": the TCP port of the web service
+    pub port: Option<u16>,
+    /// "path": the location of the responder on the host
+    pub path: String,
+    /// "registerProcedure": the name of the procedure to call to request notification (not needed for "http-post")
+    pub register_procedure: Option<String>,
+    /// "protocol": the protocol of the web service, one of "xml-rpc", "soap" or "http-post"
+    pub protocol: String,
+}
+
+/// A text box to show with the feed, which submits its contents to a page on the site
+///
+/// [RSS 2 spec]: https://www.rssboard.org/rss-specification#lttextinputgtSubelementOfLtchannelgt
+/// [RSS 1 spec]: https://validator.w3.org/feed/docs/rss1.html#s5.6
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextInput {
+    /// "title": the label of the Submit button
+    pub title: Option<String>,
+    /// "description": explains the text box
+    pub description: Option<String>,
+    /// "name": the name of the text object, passed to the page with its contents
+    pub name: String,
+    /// "link": the URL of the page which processes the request
+    pub link: String,
+}
+
+/// Hints from the publisher on how often the feed should be fetched
+///
+/// [RSS 2 spec]: https://www.rssboard.org/skip-hours-days
+/// [Syndication module]: http://web.resource.org/rss/1.0/modules/syndication/
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpdateHints {
+    /// RSS 0.9x + 2 "skipHours": hours of the day (0 to 23, in UTC) during which the feed should not be fetched
+    pub skip_hours: Vec<u32>,
+    /// RSS 0.9x + 2 "skipDays": days of the week (in UTC) on which the feed should not be fetched
+    pub skip_days: Vec<Weekday>,
+    /// Syndication "sy:updatePeriod": the period over which the feed is updated
+    pub update_period: Option<UpdatePeriod>,
+    /// Syndication "sy:updateFrequency": the number of times the feed is updated in each period (defaults to 1 when a period is given)
+    pub update_frequency: Option<u32>,
+    /// Syndication "sy:updateBase": the time from which the update schedule is calculated
+    pub update_base: Option<DateTime<Utc>>,
+}
+
+impl UpdateHints {
+    /// The time between updates to the feed according to the syndication module, if it says
+    pub fn update_interval(&self) -> Option<chrono::Duration> {
+        let frequency = self.update_frequency.unwrap_or(1).max(1);
+        // Frequencies beyond the range of the divisor are as good as continuous, so are held at its maximum rather than wrapping
+        let frequency = i32::try_from(frequency).unwrap_or(i32::MAX);
+        self.update_period.map(|period| period.duration() / frequency)
+    }
+}
+
+/// The period over which a feed is updated (see `UpdateHints::update_period`)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpdatePeriod {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl UpdatePeriod {
+    pub(crate) fn parse(text: &str) -> Option<UpdatePeriod> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "hourly" => Some(UpdatePeriod::Hourly),
+            "daily" => Some(UpdatePeriod::Daily),
+            "weekly" => Some(UpdatePeriod::Weekly),
+            "monthly" => Some(UpdatePeriod::Monthly),
+            "yearly" => Some(UpdatePeriod::Yearly),
+            _ => None,
+        }
+    }
+
+    // The length of the period, taking months and years to be their average length
+    fn duration(self) -> chrono::Duration {
+        match self {
+            UpdatePeriod::Hourly => chrono::Duration::hours(1),
+            UpdatePeriod::Daily => chrono::Duration::days(1),
+            UpdatePeriod::Weekly => chrono::Duration::weeks(1),
+            UpdatePeriod::Monthly => chrono::Duration::seconds(2_629_746),
+            UpdatePeriod::Yearly => chrono::Duration::seconds(31_556_952),
+        }
+    }
+}
+
+/// A variant of a text field (such as a title) as it was found in the source
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextVariant {
+    /// Qualified name of the element the text was read from e.g. "title", "dc:title"
+    pub element: String,
+    /// The text of the element
+    pub text: Text,
+}
+
+impl TextVariant {
+    pub(crate) fn new(element: &str, text: Text) -> TextVariant {
+        TextVariant {
+            element: element.to_owned(),
+            text,
+        }
+    }
+}
+
+// Collapses each run of whitespace in the text to a single space, ignoring leading and trailing whitespace
+fn normalise_whitespace(text: &mut String) {
+    *text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+}
+
+fn normalise_text(text: &mut Option<Text>) {
+    if let Some(text) = text {
+        normalise_whitespace(&mut text.content);
+    }
+}
+
+// Mime does not implement Serialize/Deserialize so we (de)serialise it as its string form
+#[cfg(feature = "serde")]
+mod serde_mime {
+    use mime::Mime;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(mime: &Mime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(mime.as_ref())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Mime, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use mime::Mime;
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(mime: &Option<Mime>, serializer: S) -> Result<S::Ok, S::Error> {
+            match mime {
+                Some(mime) => serializer.serialize_some(mime.as_ref()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Mime>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|mime| mime.parse().map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+// Estimates the heap allocations owned by a value (see Feed::heap_size())
+//
+// The model types are destructured so adding a field without accounting for it here fails to compile
+trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+#[cfg(feature = "intern")]
+impl HeapSize for Arc<str> {
+    fn heap_size(&self) -> usize {
+        // Shared strings are charged evenly to each of their holders, including the interner (see Interner::heap_size())
+        (self.len() + 2 * std::mem::size_of::<usize>()) / Arc::strong_count(self)
+    }
+}
+
+impl HeapSize for Mime {
+    fn heap_size(&self) -> usize {
+        // Well known types are static, but we can't tell them apart from parsed ones so assume the source is owned
+        self.as_ref().len()
+    }
+}
+
+impl HeapSize for Url {
+    fn heap_size(&self) -> usize {
+        self.as_str().len()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * std::mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl HeapSize for Feed {
+    fn heap_size(&self) -> usize {
+        let Feed {
+            feed_type: _,
+            version,
+            id,
+            title,
+            title_variants,
+            updated: _,
+            authors,
+            description,
+            description_variants,
+            links,
+            categories,
+            cloud,
+            contributors,
+            docs,
+            generator,
+            icon,
+            language,
+            logo,
+            published: _,
+            rating,
+            rights,
+            license,
+            ttl: _,
+            text_input,
+            update_hints,
+            stylesheets,
+            itunes,
+            podcast,
+            dublin_core,
+            extensions,
+            entries,
+        } = self;
+
+        version.heap_size()
+            + id.heap_size()
+            + title.heap_size()
+            + title_variants.heap_size()
+            + authors.heap_size()
+            + description.heap_size()
+            + description_variants.heap_size()
+            + links.heap_size()
+            + categories.heap_size()
+            + cloud.heap_size()
+            + contributors.heap_size()
+            + docs.heap_size()
+            + generator.heap_size()
+            + icon.heap_size()
+            + language.heap_size()
+            + logo.heap_size()
+            + rating.heap_size()
+            + rights.heap_size()
+            + license.heap_size()
+            + text_input.heap_size()
+            + update_hints.heap_size()
+            + stylesheets.heap_size()
+            + itunes.heap_size()
+            + podcast.heap_size()
+            + dublin_core.heap_size()
+            + extensions.heap_size()
+            + entries.heap_size()
+    }
+}
+
+impl HeapSize for Entry {
+    fn heap_size(&self) -> usize {
+        let Entry {
+            id,
+            is_permalink: _,
+            title,
+            title_variants,
+            updated: _,
+            authors,
+            content,
+            links,
+            summary,
+            summary_variants,
+            categories,
+            contributors,
+            published: _,
+            source,
+            rights,
+            license,
+            language,
+            media,
+            itunes,
+            podcast,
+            dublin_core,
+            geo,
+            threading,
+            extensions,
+            document_index: _,
+            raw,
+        } = self;
+
+        id.heap_size()
+            + title.heap_size()
+            + title_variants.heap_size()
+            + authors.heap_size()
+            + content.heap_size()
+            + links.heap_size()
+            + summary.heap_size()
+            + summary_variants.heap_size()
+            + categories.heap_size()
+            + contributors.heap_size()
+            + source.heap_size()
+            + rights.heap_size()
+            + license.heap_size()
+            + language.heap_size()
+            + media.heap_size()
+            + itunes.heap_size()
+            + podcast.heap_size()
+            + dublin_core.heap_size()
+            + geo.heap_size()
+            + threading.heap_size()
+            + extensions.heap_size()
+            + raw.heap_size()
+    }
+}
+
+impl HeapSize for Category {
+    fn heap_size(&self) -> usize {
+        let Category { term, scheme, label } = self;
+        term.heap_size() + scheme.heap_size() + label.heap_size()
+    }
+}
+
+impl HeapSize for Content {
+    fn heap_size(&self) -> usize {
+        let Content {
+            body,
+            content_type,
+            length: _,
+            src,
+            lang,
+        } = self;
+        body.heap_size() + content_type.heap_size() + src.heap_size() + lang.heap_size()
+    }
+}
+
+impl HeapSize for FeedSource {
+    fn heap_size(&self) -> usize {
+        let FeedSource {
+            id,
+            title,
+            updated: _,
+            authors,
+            links,
+            rights,
+        } = self;
+        id.heap_size() + title.heap_size() + authors.heap_size() + links.heap_size() + rights.heap_size()
+    }
+}
+
+impl HeapSize for Cloud {
+    fn heap_size(&self) -> usize {
+        let Cloud {
+            domain,
+            port: _,
+            path,
+            register_procedure,
+            protocol,
+        } = self;
+        domain.heap_size() + path.heap_size() + register_procedure.heap_size() + protocol.heap_size()
+    }
+}
+
+impl HeapSize for TextInput {
+    fn heap_size(&self) -> usize {
+        let TextInput {
+            title,
+            description,
+            name,
+            link,
+        } = self;
+        title.heap_size() + description.heap_size() + name.heap_size() + link.heap_size()
+    }
+}
+
+impl HeapSize for UpdateHints {
+    fn heap_size(&self) -> usize {
+        let UpdateHints {
+            skip_hours,
+            skip_days,
+            update_period: _,
+            update_frequency: _,
+            update_base: _,
+        } = self;
+        skip_hours.capacity() * std::mem::size_of::<u32>() + skip_days.capacity() * std::mem::size_of::<Weekday>()
+    }
+}
+
+impl HeapSize for Generator {
+    fn heap_size(&self) -> usize {
+        let Generator { content, uri, version } = self;
+        content.heap_size() + uri.heap_size() + version.heap_size()
+    }
+}
+
+impl HeapSize for Image {
+    fn heap_size(&self) -> usize {
+        let Image {
+            uri,
+            title,
+            link,
+            width: _,
+            height: _,
+            description,
+        } = self;
+        uri.heap_size() + title.heap_size() + link.heap_size() + description.heap_size()
+    }
+}
+
+impl HeapSize for Link {
+    fn heap_size(&self) -> usize {
+        let Link {
+            href,
+            rel,
+            media_type,
+            href_lang,
+            title,
+            length: _,
+        } = self;
+        href.heap_size() + rel.heap_size() + media_type.heap_size() + href_lang.heap_size() + title.heap_size()
+    }
+}
+
+impl HeapSize for MediaObject {
+    fn heap_size(&self) -> usize {
+        let MediaObject {
+            title,
+            content,
+            duration: _,
+            thumbnails,
+            texts,
+            description,
+            community: _,
+            credits,
+        } = self;
+        title.heap_size() + content.heap_size() + thumbnails.heap_size() + texts.heap_size() + description.heap_size() + credits.heap_size()
+    }
+}
+
+impl HeapSize for MediaContent {
+    fn heap_size(&self) -> usize {
+        let MediaContent {
+            url,
+            content_type,
+            medium,
+            height: _,
+            width: _,
+            duration: _,
+            size: _,
+            rating,
+        } = self;
+        url.heap_size() + content_type.heap_size() + medium.heap_size() + rating.heap_size()
+    }
+}
+
+impl HeapSize for MediaCredit {
+    fn heap_size(&self) -> usize {
+        self.entity.heap_size()
+    }
+}
+
+impl HeapSize for MediaRating {
+    fn heap_size(&self) -> usize {
+        let MediaRating { urn, value } = self;
+        urn.heap_size() + value.heap_size()
+    }
+}
+
+impl HeapSize for MediaText {
+    fn heap_size(&self) -> usize {
+        self.text.heap_size()
+    }
+}
+
+impl HeapSize for MediaThumbnail {
+    fn heap_size(&self) -> usize {
+        self.image.heap_size()
+    }
+}
+
+impl HeapSize for Person {
+    fn heap_size(&self) -> usize {
+        let Person { name, uri, email, role } = self;
+        name.heap_size() + uri.heap_size() + email.heap_size() + role.heap_size()
+    }
+}
+
+impl HeapSize for Stylesheet {
+    fn heap_size(&self) -> usize {
+        let Stylesheet { href, media_type } = self;
+        href.heap_size() + media_type.heap_size()
+    }
+}
+
+impl HeapSize for Text {
+    fn heap_size(&self) -> usize {
+        let Text {
+            content_type,
+            src,
+            content,
+            lang,
+        } = self;
+        content_type.heap_size() + src.heap_size() + content.heap_size() + lang.heap_size()
+    }
+}
+
+impl HeapSize for TextVariant {
+    fn heap_size(&self) -> usize {
+        let TextVariant { element, text } = self;
+        element.heap_size() + text.heap_size()
+    }
+}
+
+impl HeapSize for ITunesFeedExtension {
+    fn heap_size(&self) -> usize {
+        let ITunesFeedExtension {
+            author,
+            block: _,
+            categories,
+            complete: _,
+            explicit: _,
+            image,
+            keywords,
+            new_feed_url,
+            owner,
+            subtitle,
+            summary,
+            title,
+            show_type,
+        } = self;
+
+        author.heap_size()
+            + categories.heap_size()
+            + image.heap_size()
+            + keywords.heap_size()
+            + new_feed_url.heap_size()
+            + owner.heap_size()
+            + subtitle.heap_size()
+            + summary.heap_size()
+            + title.heap_size()
+            + show_type.heap_size()
+    }
+}
+
+impl HeapSize for ITunesItemExtension {
+    fn heap_size(&self) -> usize {
+        let ITunesItemExtension {
+            author,
+            block: _,
+            duration: _,
+            episode: _,
+            episode_type,
+            explicit: _,
+            image,
+            keywords,
+            season: _,
+            subtitle,
+            summary,
+            title,
+        } = self;
+
+        author.heap_size()
+            + episode_type.heap_size()
+            + image.heap_size()
+            + keywords.heap_size()
+            + subtitle.heap_size()
+            + summary.heap_size()
+            + title.heap_size()
+    }
+}
+
+impl HeapSize for ITunesCategory {
+    fn heap_size(&self) -> usize {
+        let ITunesCategory { text, subcategories } = self;
+        text.heap_size() + subcategories.heap_size()
+    }
+}
+
+impl HeapSize for ITunesOwner {
+    fn heap_size(&self) -> usize {
+        let ITunesOwner { name, email } = self;
+        name.heap_size() + email.heap_size()
+    }
+}
+
+impl HeapSize for PodcastFeedExtension {
+    fn heap_size(&self) -> usize {
+        let PodcastFeedExtension { funding, persons, value } = self;
+        funding.heap_size() + persons.heap_size() + value.heap_size()
+    }
+}
+
+impl HeapSize for PodcastItemExtension {
+    fn heap_size(&self) -> usize {
+        let PodcastItemExtension {
+            transcripts,
+            chapters,
+            persons,
+            soundbites,
+            value,
+        } = self;
+        transcripts.heap_size() + chapters.heap_size() + persons.heap_size() + soundbites.heap_size() + value.heap_size()
+    }
+}
+
+impl HeapSize for PodcastTranscript {
+    fn heap_size(&self) -> usize {
+        let PodcastTranscript {
+            url,
+            media_type,
+            language,
+            rel,
+        } = self;
+        url.heap_size() + media_type.heap_size() + language.heap_size() + rel.heap_size()
+    }
+}
+
+impl HeapSize for PodcastChapters {
+    fn heap_size(&self) -> usize {
+        let PodcastChapters { url, media_type } = self;
+        url.heap_size() + media_type.heap_size()
+    }
+}
+
+impl HeapSize for PodcastFunding {
+    fn heap_size(&self) -> usize {
+        let PodcastFunding { url, text } = self;
+        url.heap_size() + text.heap_size()
+    }
+}
+
+impl HeapSize for PodcastPerson {
+    fn heap_size(&self) -> usize {
+        let PodcastPerson { name, role, group, img, href } = self;
+        name.heap_size() + role.heap_size() + group.heap_size() + img.heap_size() + href.heap_size()
+    }
+}
+
+impl HeapSize for PodcastSoundbite {
+    fn heap_size(&self) -> usize {
+        let PodcastSoundbite {
+            start_time: _,
+            duration: _,
+            title,
+        } = self;
+        title.heap_size()
+    }
+}
+
+impl HeapSize for PodcastValue {
+    fn heap_size(&self) -> usize {
+        let PodcastValue {
+            value_type,
+            method,
+            suggested,
+            recipients,
+        } = self;
+        value_type.heap_size() + method.heap_size() + suggested.heap_size() + recipients.heap_size()
+    }
+}
+
+impl HeapSize for PodcastValueRecipient {
+    fn heap_size(&self) -> usize {
+        let PodcastValueRecipient {
+            name,
+            recipient_type,
+            address,
+            split: _,
+            custom_key,
+            custom_value,
+            fee: _,
+        } = self;
+        name.heap_size() + recipient_type.heap_size() + address.heap_size() + custom_key.heap_size() + custom_value.heap_size()
+    }
+}
+
+impl<V: HeapSize> HeapSize for HashMap<String, V> {
+    fn heap_size(&self) -> usize {
+        // Each bucket holds a key and value (the hash table's own overheads are not included)
+        self.capacity() * std::mem::size_of::<(String, V)>() + self.iter().map(|(key, value)| key.heap_size() + value.heap_size()).sum::<usize>()
+    }
+}
+
+impl<V: HeapSize> HeapSize for BTreeMap<String, V> {
+    fn heap_size(&self) -> usize {
+        self.iter()
+            .map(|(key, value)| std::mem::size_of::<(String, V)>() + key.heap_size() + value.heap_size())
+            .sum::<usize>()
+    }
+}
+
+impl HeapSize for ExtensionElement {
+    fn heap_size(&self) -> usize {
+        let ExtensionElement {
+            name,
+            attributes,
+            value,
+            children,
+        } = self;
+        name.heap_size() + attributes.heap_size() + value.heap_size() + children.heap_size()
+    }
+}
+
+impl HeapSize for GeoExtension {
+    fn heap_size(&self) -> usize {
+        let GeoExtension {
+            point: _,
+            line,
+            polygon,
+            bounding_box: _,
+        } = self;
+        line.heap_size() + polygon.heap_size()
+    }
+}
+
+impl HeapSize for ThreadingExtension {
+    fn heap_size(&self) -> usize {
+        let ThreadingExtension {
+            in_reply_to,
+            total: _,
+            replies,
+            comments: _,
+            comment_rss,
+            comment_api,
+        } = self;
+        in_reply_to.heap_size() + replies.heap_size() + comment_rss.heap_size() + comment_api.heap_size()
+    }
+}
+
+impl HeapSize for InReplyTo {
+    fn heap_size(&self) -> usize {
+        let InReplyTo {
+            reference,
+            href,
+            media_type,
+            source,
+        } = self;
+        reference.heap_size() + href.heap_size() + media_type.heap_size() + source.heap_size()
+    }
+}
+
+impl HeapSize for Replies {
+    fn heap_size(&self) -> usize {
+        let Replies {
+            href,
+            media_type,
+            count: _,
+            updated: _,
+        } = self;
+        href.heap_size() + media_type.heap_size()
+    }
+}
+
+impl HeapSize for GeoPoint {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for DublinCoreExtension {
+    fn heap_size(&self) -> usize {
+        let DublinCoreExtension {
+            contributors,
+            coverage,
+            creators,
+            dates,
+            descriptions,
+            formats,
+            identifiers,
+            languages,
+            publishers,
+            relations,
+            rights,
+            sources,
+            subjects,
+            titles,
+            types,
+        } = self;
+
+        contributors.heap_size()
+            + coverage.heap_size()
+            + creators.heap_size()
+            + dates.heap_size()
+            + descriptions.heap_size()
+            + formats.heap_size()
+            + identifiers.heap_size()
+            + languages.heap_size()
+            + publishers.heap_size()
+            + relations.heap_size()
+            + rights.heap_size()
+            + sources.heap_size()
+            + subjects.heap_size()
+            + titles.heap_size()
+            + types.heap_size()
+    }
 }
@@ -0,0 +1,124 @@
+//! Subscription lists in OPML (Outline Processor Markup Language), as imported and exported by feed readers
+//!
+//! [OPML 2.0 spec]: http://opml.org/spec2.opml
+
+use std::io::Read;
+
+use chrono::{DateTime, Utc};
+
+use crate::parser::{self, ParseFeedResult};
+use crate::writer::{self, WriteFeedResult};
+
+/// An OPML document, such as a list of subscriptions
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Opml {
+    /// The version of OPML the document conforms to (e.g. "2.0")
+    pub version: String,
+    /// The title of the document
+    pub title: Option<String>,
+    /// When the document was created
+    pub date_created: Option<DateTime<Utc>>,
+    /// The name of the owner of the document
+    pub owner_name: Option<String>,
+    /// The email address of the owner of the document
+    pub owner_email: Option<String>,
+    /// The top level outlines in the body of the document
+    pub outlines: Vec<Outline>,
+}
+
+/// An outline within the document, which is either a subscription (with an `xml_url`) or a folder of other outlines
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Outline {
+    /// The text displayed for the outline (e.g. the name of the feed or folder)
+    pub text: String,
+    /// The title of the feed, which is often the same as the text
+    pub title: Option<String>,
+    /// The type of the outline (e.g. "rss" for a subscription)
+    pub outline_type: Option<String>,
+    /// The URL of the feed
+    pub xml_url: Option<String>,
+    /// The URL of the web site the feed belongs to
+    pub html_url: Option<String>,
+    /// The outlines nested within this one, if it is a folder
+    pub outlines: Vec<Outline>,
+}
+
+impl Opml {
+    /// Parses an OPML document
+    ///
+    /// The encoding declared in the XML prolog is used to decode the source. Attributes not in the model (e.g. "isComment") are ignored.
+    /// If the source is not an OPML document the error is `ParseErrorKind::NoFeedRoot`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use feed_rs::opml::Opml;
+    /// let xml = r#"<opml version="2.0">
+    ///     <head><title>Subscriptions</title></head>
+    ///     <body>
+    ///         <outline text="News">
+    ///             <outline text="Example" type="rss" xmlUrl="http://example.com/feed.xml"/>
+    ///         </outline>
+    ///     </body>
+    /// </opml>"#;
+    /// let opml = Opml::parse(xml.as_bytes()).unwrap();
+    /// let urls: Vec<&str> = opml.feeds().filter_map(|outline| outline.xml_url.as_deref()).collect();
+    /// assert_eq!(urls, vec!["http://example.com/feed.xml"]);
+    /// ```
+    pub fn parse<R: Read>(source: R) -> ParseFeedResult<Opml> {
+        parser::opml::parse(source)
+    }
+
+    /// Writes the document as OPML 2.0
+    pub fn write<W: std::io::Write>(&self, out: W) -> WriteFeedResult<()> {
+        writer::opml::write(self, out)
+    }
+
+    /// Returns the document as an OPML 2.0 string
+    pub fn to_xml(&self) -> String {
+        let mut out = Vec::new();
+        // Writing to memory cannot fail, and the writer only produces UTF-8
+        self.write(&mut out).expect("writing OPML to memory failed");
+        String::from_utf8(out).expect("OPML was not written as UTF-8")
+    }
+
+    /// Returns the subscriptions in the document (i.e. the outlines with a feed URL), including those within folders, in document order
+    pub fn feeds(&self) -> impl Iterator<Item = &Outline> {
+        let mut feeds = Vec::new();
+        collect_feeds(&self.outlines, &mut feeds);
+        feeds.into_iter()
+    }
+}
+
+impl Outline {
+    /// Creates a subscription to the feed at the URL
+    pub fn feed(text: &str, xml_url: &str) -> Outline {
+        Outline {
+            text: text.to_owned(),
+            outline_type: Some("rss".to_owned()),
+            xml_url: Some(xml_url.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a folder containing the outlines
+    pub fn folder(text: &str, outlines: Vec<Outline>) -> Outline {
+        Outline {
+            text: text.to_owned(),
+            outlines,
+            ..Default::default()
+        }
+    }
+}
+
+// Adds the outlines with a feed URL to the list, descending into folders
+fn collect_feeds<'a>(outlines: &'a [Outline], feeds: &mut Vec<&'a Outline>) {
+    for outline in outlines {
+        if outline.xml_url.is_some() {
+            feeds.push(outline);
+        }
+        collect_feeds(&outline.outlines, feeds);
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::{self, Cursor, Read};
+use std::panic;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::Semaphore;
+use tokio::task::{self, JoinError};
+
+use crate::model;
+use crate::parser::{self, ParseFeedError, ParseFeedResult};
+
+// Size of each read from the source
+const CHUNK_SIZE: usize = 16 * 1024;
+
+// Number of chunks that may be waiting for the parser, before reading from the source is paused
+const CHUNKS_IN_FLIGHT: usize = 4;
+
+/// Parses a feed from an asynchronous source, such as the body of an HTTP response
+///
+/// The source is read on the calling task and passed a chunk at a time to a parse on Tokio's blocking thread pool, so neither the
+/// whole body is buffered nor a runtime thread blocked. Reading pauses if the parser falls behind. This must be called from within a
+/// Tokio runtime.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// # let mut runtime = tokio::runtime::Builder::new().basic_scheduler().build().unwrap();
+/// # runtime.block_on(async {
+/// let xml = r#"<rss version="2.0"><channel><title>Async</title></channel></rss>"#;
+/// let feed = parser::parse_async(xml.as_bytes()).await.unwrap();
+/// assert_eq!(feed.title.unwrap().content, "Async");
+/// # });
+/// ```
+pub async fn parse_async<R: AsyncRead + Unpin>(mut source: R) -> ParseFeedResult<model::Feed> {
+    // Chunks are only read once the parser has room for them, so the channel never fills
+    let (sender, receiver) = sync_channel(CHUNKS_IN_FLIGHT);
+    let credits = Arc::new(Semaphore::new(CHUNKS_IN_FLIGHT));
+    let reader = ChannelReader {
+        receiver,
+        credits: credits.clone(),
+        chunk: Cursor::new(Vec::new()),
+    };
+    let parse = task::spawn_blocking(move || parser::parse(reader));
+
+    loop {
+        credits.acquire().await.forget();
+
+        let mut chunk = vec![0; CHUNK_SIZE];
+        let result = match source.read(&mut chunk).await {
+            Ok(0) => break,
+            Ok(length) => {
+                chunk.truncate(length);
+                Ok(chunk)
+            }
+            Err(e) => Err(e),
+        };
+
+        // The parser may have finished (e.g. with an error) without needing the rest of the source
+        let failed = result.is_err();
+        if sender.send(result).is_err() || failed {
+            break;
+        }
+    }
+    drop(sender);
+
+    // A panic in the parser is passed on to the caller, as if the parse had been on this thread
+    match parse.await.map_err(JoinError::try_into_panic) {
+        Ok(result) => result,
+        Err(Ok(panic)) => panic::resume_unwind(panic),
+        Err(Err(e)) => Err(ParseFeedError::IoError(io::Error::new(io::ErrorKind::Interrupted, e))),
+    }
+}
+
+// Presents the chunks sent by parse_async() to the parser as a blocking reader
+struct ChannelReader {
+    receiver: Receiver<io::Result<Vec<u8>>>,
+    credits: Arc<Semaphore>,
+    chunk: Cursor<Vec<u8>>,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.position() as usize == self.chunk.get_ref().len() {
+            match self.receiver.recv() {
+                Ok(Ok(chunk)) => {
+                    self.chunk = Cursor::new(chunk);
+                    self.credits.add_permits(1);
+                }
+                Ok(Err(e)) => return Err(e),
+
+                // The end of the source
+                Err(_) => return Ok(0),
+            }
+        }
+
+        Read::read(&mut self.chunk, buf)
+    }
+}
+
+impl Drop for ChannelReader {
+    // Lets parse_async() continue if it is waiting for room, so it finds the parser has gone
+    fn drop(&mut self) {
+        self.credits.add_permits(CHUNKS_IN_FLIGHT);
+    }
+}
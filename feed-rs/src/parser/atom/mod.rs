@@ -1,32 +1,48 @@
 use std::io::BufRead;
 
+use chrono::{DateTime, Utc};
 use mime::Mime;
 
-use crate::model::{Category, Content, Entry, Feed, FeedType, Generator, Image, Link, MediaObject, Person, Text};
+use crate::model::{shared_string, Category, Content, Entry, Feed, FeedSource, FeedType, Generator, Image, Link, MediaContent, MediaObject, Person, Text};
+use crate::parser::dublincore::{handle_dublin_core_terms_channel_element, handle_dublin_core_terms_item_element};
+use crate::parser::geo::GeoHandler;
 use crate::parser::mediarss;
 use crate::parser::mediarss::handle_media_element;
-use crate::parser::util::{if_some_then, timestamp_rfc3339_lenient};
-use crate::parser::{ParseErrorKind, ParseFeedError, ParseFeedResult};
-use crate::xml::{Element, NS};
+use crate::parser::syndication::handle_syndication_element;
+use crate::parser::threading::{handle_entry_link, handle_threading_element};
+use crate::parser::util::{add_link, if_some_then, parse_length, parse_media_type, parse_uri, resolve_uri};
+use crate::parser::{ParseErrorKind, ParseFeedError, ParseFeedResult, Parser};
+use crate::util::dates::TimestampFormat;
+use crate::xml::{Element, ATOM_NAMESPACES, NS};
 
 #[cfg(test)]
 mod tests;
 
 /// Parses an Atom feed into our model
-pub(crate) fn parse_feed<R: BufRead>(root: Element<R>) -> ParseFeedResult<Feed> {
+pub(crate) fn parse_feed<R: BufRead>(parser: &Parser, root: Element<R>) -> ParseFeedResult<Feed> {
     let mut feed = Feed::new(FeedType::Atom);
+    feed.language = root.xml_lang.clone();
+
+    // Atom 0.3 declares its version, while 1.0 is identified by its namespace
+    feed.version = Some(root.attr_value("version").map_or_else(|| "1.0".to_owned(), |version| version.trim().to_owned()));
+
+    // The ID of the feed is only required from Atom 1.0
+    let id_required = feed.version.as_deref() != Some("0.3");
+    // The position of the next entry in the document, counting those that are filtered out or dropped
+    let mut document_index = 0;
     for child in root.children() {
         let child = child?;
-        match child.ns_and_tag() {
+        match child.ns_and_tag_in(ATOM_NAMESPACES) {
             (None, "id") => if_some_then(child.child_as_text(), |id| feed.id = id),
 
-            (None, "title") => feed.title = handle_text(child)?,
+            (None, "title") => feed.title = parser.text_variant(&mut feed.title_variants, "title", handle_text(child)?),
 
-            (None, "updated") => if_some_then(child.child_as_text(), |text| feed.updated = timestamp_rfc3339_lenient(&text)),
+            // Atom 0.3 has "modified" rather than "updated"
+            (None, "updated") | (None, "modified") => feed.updated = handle_timestamp(parser, child)?,
 
             (None, "author") => if_some_then(handle_person(child)?, |person| feed.authors.push(person)),
 
-            (None, "link") => if_some_then(handle_link(child), |link| feed.links.push(link)),
+            (None, "link") => if_some_then(handle_link(child), |link| add_link(link, &mut feed.links, &mut feed.license)),
 
             (None, "category") => if_some_then(handle_category(child), |category| feed.categories.push(category)),
 
@@ -38,27 +54,65 @@ pub(crate) fn parse_feed<R: BufRead>(root: Element<R>) -> ParseFeedResult<Feed>
 
             (None, "logo") => feed.logo = handle_image(child),
 
-            (None, "rights") => feed.rights = handle_text(child)?,
-
-            (None, "subtitle") => feed.description = handle_text(child)?,
-
-            (None, "entry") => if_some_then(handle_entry(child)?, |entry| feed.entries.push(entry)),
+            // Atom 0.3 has "copyright" rather than "rights"
+            (None, "rights") | (None, "copyright") => feed.rights = handle_text(child)?,
+
+            (None, "subtitle") => feed.description = parser.text_variant(&mut feed.description_variants, "subtitle", handle_text(child)?),
+
+            // Atom 0.3 has "tagline" rather than "subtitle"
+            (None, "tagline") => feed.description = parser.text_variant(&mut feed.description_variants, "tagline", handle_text(child)?),
+
+            (Some(NS::Syndication), _) => handle_syndication_element(parser, child, &mut feed)?,
+
+            // Dublin Core dates are used if the feed has none of its own
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_channel_element(parser, child, &mut feed)?,
+
+            (None, "entry") => {
+                let parsed = handle_entry(parser, child)?.map(|mut entry| {
+                    entry.document_index = document_index;
+                    entry
+                });
+                document_index += 1;
+                // An entry cut short by the limit on the size of the source is left out
+                if root.is_truncated() {
+                    break;
+                }
+                if_some_then(parsed.filter(|entry| parser.entry_wanted(entry)), |mut entry| {
+                    parser.intern_entry_strings(&mut entry);
+                    feed.entries.push(entry)
+                });
+                parser.entry_parsed(root.bytes_consumed(), feed.entries.len());
+                if parser.is_cancelled() {
+                    return Err(ParseFeedError::Cancelled(Box::new(feed)));
+                }
+                if parser.entry_limit_reached(feed.entries.len()) {
+                    break;
+                }
+            }
 
-            // Nothing required for unknown elements
-            _ => {}
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut feed.extensions)?,
         }
     }
 
+    if id_required && feed.id.is_empty() {
+        parser.missing_content("feed.id")?;
+    }
+
     Ok(feed)
 }
 
 /// Parses an Atom entry into our model
 ///
 /// Note that the entry is wrapped in an empty Feed to keep the API consistent
-pub(crate) fn parse_entry<R: BufRead>(root: Element<R>) -> ParseFeedResult<Feed> {
+pub(crate) fn parse_entry<R: BufRead>(parser: &Parser, root: Element<R>) -> ParseFeedResult<Feed> {
     let mut feed = Feed::new(FeedType::Atom);
+    feed.version = Some("1.0".into());
 
-    if_some_then(handle_entry(root)?, |entry| feed.entries.push(entry));
+    if_some_then(handle_entry(parser, root)?.filter(|entry| parser.entry_wanted(entry)), |mut entry| {
+        parser.intern_entry_strings(&mut entry);
+        feed.entries.push(entry)
+    });
 
     Ok(feed)
 }
@@ -71,7 +125,7 @@ fn handle_category<R: BufRead>(element: Element<R>) -> Option<Category> {
 
         for attr in element.attributes {
             match attr.name.as_str() {
-                "scheme" => category.scheme = Some(attr.value.clone()),
+                "scheme" => category.scheme = Some(attr.value.as_str().into()),
                 "label" => category.label = Some(attr.value.clone()),
 
                 // Nothing required for unknown attributes
@@ -88,9 +142,34 @@ fn handle_category<R: BufRead>(element: Element<R>) -> Option<Category> {
 
 // Handles an Atom <content> element
 fn handle_content<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Content>> {
+    let lang = element.xml_lang.clone();
+    Ok(handle_content_body(element)?.map(|content| Content { lang, ..content }))
+}
+
+// Handles the body of an Atom <content> element, which may be inline or out of line
+fn handle_content_body<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Content>> {
     // Extract the content type so we can parse the body
     let content_type = element.attr_value("type");
 
+    // Atom 0.3 content with a mode of "xml" carries its (X)HTML inline, in the same way as an Atom 1.0 "xhtml" type
+    let content_type = match (content_type, element.attr_value("mode")) {
+        (Some(ct), Some(mode)) if mode == "xml" && (ct == "text/html" || ct == "application/xhtml+xml") => Some(String::from("xhtml")),
+        (ct, _) => ct,
+    };
+
+    // Out of line content per "If the src attribute is present, it represents the URI of where the content can be found"
+    if let Some(src) = element.attr_value("src") {
+        let content_type = match content_type.as_deref() {
+            Some("html") | Some("xhtml") => mime::TEXT_HTML,
+            ct => ct.and_then(|ct| ct.parse::<Mime>().ok()).unwrap_or(mime::TEXT_PLAIN),
+        };
+        return Ok(Some(Content {
+            content_type,
+            src: Some(Link::new(src, element.xml_base.as_ref())),
+            ..Default::default()
+        }));
+    }
+
     // from http://www.atomenabled.org/developers/syndication/#contentElement
     match content_type.as_deref() {
         // Should be handled as a text element per "In the most common case, the type attribute is either text, html, xhtml, in which case the content element is defined identically to other text constructs"
@@ -143,50 +222,89 @@ fn handle_content<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Con
 }
 
 // Handles an Atom <entry>
-fn handle_entry<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry>> {
+pub(crate) fn handle_entry<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<Option<Entry>> {
     // Create a default MediaRSS content object for non-grouped elements
     let mut media_obj = MediaObject::default();
 
     // Parse the entry
     let mut entry = Entry::default();
+    let mut geo = GeoHandler::default();
     for child in element.children() {
         let child = child?;
-        match child.ns_and_tag() {
+        match child.ns_and_tag_in(ATOM_NAMESPACES) {
             // Extract the fields from the spec
             (None, "id") => if_some_then(child.child_as_text(), |id| entry.id = id),
 
-            (None, "title") => entry.title = handle_text(child)?,
+            (None, "title") => entry.title = parser.text_variant(&mut entry.title_variants, "title", handle_text(child)?),
 
-            (None, "updated") => if_some_then(child.child_as_text(), |text| entry.updated = timestamp_rfc3339_lenient(&text)),
+            // Atom 0.3 has "modified" rather than "updated"
+            (None, "updated") | (None, "modified") => entry.updated = handle_timestamp(parser, child)?,
 
             (None, "author") => if_some_then(handle_person(child)?, |person| entry.authors.push(person)),
 
             (None, "content") => entry.content = handle_content(child)?,
 
-            (None, "link") => if_some_then(handle_link(child), |link| entry.links.push(link)),
+            (None, "link") => handle_entry_link(parser, child, &mut entry)?,
 
-            (None, "summary") => entry.summary = handle_text(child)?,
+            (None, "summary") => entry.summary = parser.text_variant(&mut entry.summary_variants, "summary", handle_text(child)?),
 
             (None, "category") => if_some_then(handle_category(child), |category| entry.categories.push(category)),
 
             (None, "contributor") => if_some_then(handle_person(child)?, |person| entry.contributors.push(person)),
 
             // Some feeds have "pubDate" instead of "published"
-            (None, "published") | (None, "pubDate") => if_some_then(child.child_as_text(), |text| entry.published = timestamp_rfc3339_lenient(&text)),
+            // Atom 0.3 has "issued" rather than "published"
+            (None, "published") | (None, "pubDate") | (None, "issued") => entry.published = handle_timestamp(parser, child)?,
+
+            // Atom 0.3 also has an optional "created", which is only used if the entry was not issued
+            (None, "created") if entry.published.is_none() => entry.published = handle_timestamp(parser, child)?,
 
             (None, "rights") => entry.rights = handle_text(child)?,
 
+            (None, "source") => entry.source = Some(handle_source(parser, child)?),
+
+            // GeoRSS and W3C Basic Geo elements populate the location
+            (Some(NS::GeoRSS), _) | (Some(NS::W3CGeo), _) => geo.handle_element(parser, child, &mut entry.extensions)?,
+
+            // Dublin Core dates are used if the entry has none of its own
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_item_element(parser, child, &mut entry)?,
+
+            // Threading, Slash and Well-Formed Web elements populate the threading extension
+            (Some(NS::Threading), _) | (Some(NS::Slash), _) | (Some(NS::WellFormedWeb), _) => handle_threading_element(parser, child, &mut entry)?,
+
             // MediaRSS group creates a new object for this group of elements
             (Some(NS::MediaRSS), "group") => if_some_then(mediarss::handle_media_group(child)?, |obj| entry.media.push(obj)),
 
+            // MediaRSS titles and descriptions that are not grouped are also variants of the entry's own
+            (Some(NS::MediaRSS), "title") => media_obj.title = parser.text_variant(&mut entry.title_variants, "media:title", mediarss::handle_text(child)?),
+            (Some(NS::MediaRSS), "description") => {
+                media_obj.description = parser.text_variant(&mut entry.summary_variants, "media:description", mediarss::handle_text(child)?)
+            }
+
             // MediaRSS tags that are not grouped are parsed into the default object
             (Some(NS::MediaRSS), _) => handle_media_element(child, &mut media_obj)?,
 
-            // Nothing required for unknown elements
-            _ => {}
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut entry.extensions)?,
         }
     }
 
+    // The source of the entry is kept if enabled with `parser::Builder::raw_entries()`
+    entry.raw = element.raw();
+    entry.geo = geo.finish();
+
+    // Atom has no element for the language, so it is the xml:lang declared on the entry or inherited from the feed
+    entry.language = element.xml_lang.clone();
+
+    if entry.id.is_empty() {
+        parser.missing_content("entry.id")?;
+    }
+
+    // Enclosure links (e.g. the audio of a podcast episode) are also media content, as for an RSS <enclosure>
+    for link in entry.links.iter().filter(|link| link.has_rel("enclosure")) {
+        if_some_then(handle_enclosure(link), |content| media_obj.content.push(content));
+    }
+
     // If a media:content item was found in this entry, then attach it
     if !media_obj.content.is_empty() {
         entry.media.push(media_obj);
@@ -195,6 +313,49 @@ fn handle_entry<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry
     Ok(Some(entry))
 }
 
+// Maps a link with the "enclosure" relation to media content, which requires a valid URL
+fn handle_enclosure(link: &Link) -> Option<MediaContent> {
+    let mut content = MediaContent::new();
+    content.url = Some(parse_uri(&link.href, None)?);
+    content.content_type = link.media_type.clone();
+    content.size = link.length;
+    Some(content)
+}
+
+// Handles an Atom <source>, which has the metadata of the feed the entry was copied from
+fn handle_source<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<FeedSource> {
+    let mut source = FeedSource::default();
+    for child in element.children() {
+        let child = child?;
+        match child.ns_and_tag_in(ATOM_NAMESPACES) {
+            (None, "id") => source.id = child.child_as_text(),
+
+            (None, "title") => source.title = handle_text(child)?,
+
+            (None, "updated") => source.updated = handle_timestamp(parser, child)?,
+
+            (None, "author") => if_some_then(handle_person(child)?, |person| source.authors.push(person)),
+
+            (None, "link") => if_some_then(handle_link(child), |link| source.links.push(link)),
+
+            (None, "rights") => source.rights = handle_text(child)?,
+
+            // The rest of the feed metadata is not needed for attribution
+            _ => {}
+        }
+    }
+
+    Ok(source)
+}
+
+// Handles an Atom <updated>, <published> etc
+fn handle_timestamp<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<Option<DateTime<Utc>>> {
+    match element.child_as_text() {
+        Some(text) => parser.parse_timestamp(&text, TimestampFormat::Rfc3339),
+        None => Ok(None),
+    }
+}
+
 // Handles an Atom <generator>
 fn handle_generator<R: BufRead>(element: Element<R>) -> Option<Generator> {
     element.child_as_text().map(|content| {
@@ -202,7 +363,8 @@ fn handle_generator<R: BufRead>(element: Element<R>) -> Option<Generator> {
 
         for attr in element.attributes {
             match attr.name.as_str() {
-                "uri" => generator.uri = Some(attr.value.clone()),
+                // Atom 0.3 has "url" rather than "uri"
+                "uri" | "url" => generator.uri = Some(attr.value.clone()),
                 "version" => generator.version = Some(attr.value.clone()),
                 // Nothing required for unknown attributes
                 _ => {}
@@ -215,11 +377,11 @@ fn handle_generator<R: BufRead>(element: Element<R>) -> Option<Generator> {
 
 // Handles an Atom <icon> or <logo>
 fn handle_image<R: BufRead>(element: Element<R>) -> Option<Image> {
-    element.child_as_text().map(Image::new)
+    element.child_as_text().map(|uri| Image::new(resolve_uri(uri, element.xml_base.as_ref())))
 }
 
 // Handles an Atom <link>
-fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
+pub(crate) fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
     // Always need an href
     element.attr_value("href").map(|href| {
         let mut link = Link::new(&href, element.xml_base.as_ref());
@@ -227,10 +389,10 @@ fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
         for attr in element.attributes {
             match attr.name.as_str() {
                 "rel" => link.rel = Some(attr.value.clone()),
-                "type" => link.media_type = Some(attr.value.clone()),
+                "type" => link.media_type = parse_media_type(&attr.value),
                 "hreflang" => link.href_lang = Some(attr.value.clone()),
                 "title" => link.title = Some(attr.value.clone()),
-                "length" => link.length = attr.value.parse::<u64>().ok(),
+                "length" => link.length = parse_length(&attr.value),
 
                 // Nothing required for unrecognised attributes
                 _ => {}
@@ -252,12 +414,16 @@ fn handle_person<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Pers
 
     for child in element.children() {
         let child = child?;
-        let tag_name = child.name.as_str();
+        let tag_name = match child.ns_and_tag_in(ATOM_NAMESPACES) {
+            (None, tag_name) => tag_name,
+            _ => continue,
+        };
         let child_text = child.child_as_text();
         match (tag_name, child_text) {
             // Extract the fields from the spec
-            ("name", Some(name)) => person.name = name,
-            ("uri", uri) => person.uri = uri,
+            ("name", Some(name)) => person.name = shared_string(name),
+            // Atom 0.3 has "url" rather than "uri"
+            ("uri", uri) | ("url", uri) => person.uri = uri,
             ("email", email) => person.email = email,
 
             // Nothing required for unknown elements
@@ -277,15 +443,23 @@ pub(crate) fn handle_text<R: BufRead>(element: Element<R>) -> ParseFeedResult<Op
         "text" => Ok(mime::TEXT_PLAIN),
         "html" | "xhtml" => Ok(mime::TEXT_HTML),
 
+        // Atom 0.3 uses MIME types
+        "text/plain" => Ok(mime::TEXT_PLAIN),
+        "text/html" | "application/xhtml+xml" => Ok(mime::TEXT_HTML),
+
         // Unknown content type
         _ => Err(ParseFeedError::ParseError(ParseErrorKind::UnknownMimeType(type_attr.into()))),
     }?;
 
-    element
-        .children_as_string()?
+    // XHTML is inline markup rather than escaped text, as is the (X)HTML of Atom 0.3 elements with a mode of "xml"
+    let xhtml = type_attr == "xhtml" || type_attr == "application/xhtml+xml" || element.attr_value("mode").as_deref() == Some("xml");
+    let content = if xhtml { element.children_as_xhtml()? } else { element.children_as_string()? };
+
+    content
         .map(|content| {
             let mut text = Text::new(content);
             text.content_type = mime;
+            text.lang = element.xml_lang.clone();
             Some(text)
         })
         // Need the text for a text element
@@ -1,18 +1,25 @@
+use chrono::{TimeZone, Utc};
+
+use crate::extensions::geo::{GeoExtension, GeoPoint};
+use crate::extensions::threading::{InReplyTo, Replies, ThreadingExtension};
+use crate::extensions::ExtensionElement;
 use crate::model::{
-    Category, Content, Entry, Feed, FeedType, Generator, Image, Link, MediaCommunity, MediaContent, MediaObject, MediaText, MediaThumbnail, Person, Text,
+    Category, Content, ContentKind, Entry, Feed, FeedSource, FeedType, Generator, Image, Link, MediaCommunity, MediaContent, MediaObject, MediaText,
+    MediaThumbnail, Person, Text,
 };
 use crate::parser;
 use crate::util::test;
 
 // Verify we can parse a more complete example than the one provided in the standard
 #[test]
+#[rustfmt::skip]
 fn test_example_1() {
     // Parse the feed
     let test_data = test::fixture_as_string("atom_example_1.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::Atom)
+    let expected = Feed::new(FeedType::Atom).version("1.0")
         .title(Text::new("dive into mark".into()))
         .description(Text::new("\n        A <em>lot</em> of effort\n        went into making this effortless\n    ".into())
             .content_type("text/html"))
@@ -45,9 +52,14 @@ fn test_example_1() {
                 .length(1337))
             .contributor(Person::new("Sam Ruby"))
             .contributor(Person::new("Joe Gregorio"))
-            .content(Content::default()
+            .content(Content::default().lang("en")
                 .content_type("text/html")
-                .body("\n            <div>\n                <p>\n                    <i>[Update: The Atom draft is finished.]</i>\n                </p>\n            </div>\n        "))
+                .body("\n                <p>\n                    <i>[Update: The Atom draft is finished.]</i>\n                </p>\n            "))
+            .media(MediaObject::default()
+                .content(MediaContent::new()
+                    .url("http://example.org/audio/ph34r_my_podcast.mp3")
+                    .content_type("audio/mpeg")
+                    .size(1337)))
             .published_rfc3339("2003-12-13T08:29:29-04:00"));
 
     // Check
@@ -61,24 +73,26 @@ fn test_example_2() {
     let test_data = test::fixture_as_string("atom_example_2.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
-    let expected = Feed::new(FeedType::Atom)
+    let expected = Feed::new(FeedType::Atom).version("1.0")
+        .language("en")
         .id("tag:theregister.co.uk,2005:feed/theregister.co.uk/science/")
-        .title(Text::new("The Register - Science".into()))
+        .title(Text::new("The Register - Science".into()).lang("en"))
         .link(Link::new("https://www.theregister.co.uk/science/headlines.atom", None)
             .rel("self")
             .media_type("application/atom+xml"))
         .link(Link::new("https://www.theregister.co.uk/science/", None)
             .rel("alternate")
             .media_type("text/html"))
-        .rights(Text::new("Copyright © 2019, Situation Publishing".into()))
+        .rights(Text::new("Copyright © 2019, Situation Publishing".into()).lang("en"))
         .author(Person::new("Team Register")
             .email("webmaster@theregister.co.uk")
             .uri("https://www.theregister.co.uk/odds/about/contact/"))
         .icon(Image::new("https://www.theregister.co.uk/Design/graphics/icons/favicon.png".into()))
-        .description(Text::new("Biting the hand that feeds IT — sci/tech news and views for the world".into()))
+        .description(Text::new("Biting the hand that feeds IT — sci/tech news and views for the world".into()).lang("en"))
         .logo(Image::new("https://www.theregister.co.uk/Design/graphics/Reg_default/The_Register_r.png".into()))
         .updated_rfc3339("2019-07-31T11:54:28Z")
         .entry(Entry::default()
+            .language("en")
             .id("tag:theregister.co.uk,2005:story204156")
             .updated_rfc3339("2019-07-31T11:54:28Z")
             .author(Person::new("Richard Speed")
@@ -86,11 +100,12 @@ fn test_example_2() {
             .link(Link::new("http://go.theregister.com/feed/www.theregister.co.uk/2019/07/31/orbitbeyond_drops_nasa_moon_contract/", None)
                 .rel("alternate")
                 .media_type("text/html"))
-            .title(Text::new("Will someone plz dump our shizz on the Moon, NASA begs as one of the space biz vendors drops out".into())
+            .title(Text::new("Will someone plz dump our shizz on the Moon, NASA begs as one of the space biz vendors drops out".into()).lang("en")
                 .content_type("text/html"))
-            .summary(Text::new("<h4>OrbitBeyond begone: Getting to the Moon is <i>hard</i></h4> <p>NASA made a slew of announcements yesterday aimed at bigging up the agency's efforts to get commercial companies involved with its deep space ambitions – despite one vendor dumping plans for a 2020 lunar landing.…</p>".into())
+            .summary(Text::new("<h4>OrbitBeyond begone: Getting to the Moon is <i>hard</i></h4> <p>NASA made a slew of announcements yesterday aimed at bigging up the agency's efforts to get commercial companies involved with its deep space ambitions – despite one vendor dumping plans for a 2020 lunar landing.…</p>".into()).lang("en")
                 .content_type("text/html")))
         .entry(Entry::default()
+            .language("en")
             .id("tag:theregister.co.uk,2005:story204131")
             .updated_rfc3339("2019-07-30T05:41:09Z")
             .author(Person::new("Kieren McCarthy")
@@ -98,9 +113,9 @@ fn test_example_2() {
             .link(Link::new("http://go.theregister.com/feed/www.theregister.co.uk/2019/07/30/french_arming_satellites/", None)
                 .rel("alternate")
                 .media_type("text/html"))
-            .title(Text::new("Satellites with lasers and machine guns coming! China's new plans? Trump's Space Force? Nope, the French".into())
+            .title(Text::new("Satellites with lasers and machine guns coming! China's new plans? Trump's Space Force? Nope, the French".into()).lang("en")
                 .content_type("text/html"))
-            .summary(Text::new(r#"<h4>After all, what could possibly go wrong, apart from everything?</h4> <p>France is threatening to stick submachine guns on its next generation of satellites as part of an "active space defense" strategy that would enable it to shoot down other space hardware.…</p>"#.to_owned())
+            .summary(Text::new(r#"<h4>After all, what could possibly go wrong, apart from everything?</h4> <p>France is threatening to stick submachine guns on its next generation of satellites as part of an "active space defense" strategy that would enable it to shoot down other space hardware.…</p>"#.to_owned()).lang("en")
                 .content_type("text/html")));
 
     // Check
@@ -114,7 +129,7 @@ fn test_example_3() {
     let test_data = test::fixture_as_string("atom_example_3.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
-    let expected = Feed::new(FeedType::Atom)
+    let expected = Feed::new(FeedType::Atom).version("1.0")
         .title(Text::new("The Akamai Blog".into()))
         .link(Link::new("https://blogs.akamai.com/", None)
             .rel("alternate")
@@ -128,7 +143,12 @@ fn test_example_3() {
             .media_type("application/atom+xml"))
         .link(Link::new("http://pubsubhubbub.appspot.com/", None)
             .rel("hub"))
+        .extension("http://rssnamespace.org/feedburner/ext/1.0", ExtensionElement::new("info").attribute("uri", "theakamaiblog"))
         .entry(Entry::default()
+            .extension(
+                "http://rssnamespace.org/feedburner/ext/1.0",
+                ExtensionElement::new("origLink").value("https://blogs.akamai.com/2019/07/time-to-transfer-risk-why-security-complexity-vpns-are-no-longer-sustainable.html"),
+            )
             .title(Text::new("Time to Transfer Risk: Why Security Complexity & VPNs Are No Longer Sustainable".into()))
             .link(Link::new("http://feedproxy.google.com/~r/TheAkamaiBlog/~3/NnQEuqRSyug/time-to-transfer-risk-why-security-complexity-vpns-are-no-longer-sustainable.html", None)
                 .rel("alternate")
@@ -146,7 +166,7 @@ fn test_example_3() {
             .category(Category::new("zerotrust")
                 .label("Zero Trust")
                 .scheme("http://www.sixapart.com/ns/types#tag"))
-            .content(Content::default()
+            .content(Content::default().lang("en-us")
                 .body(r#"
         <p>We all heed the gospel of patching, but as recent incidents made clear, even cutting-edge disruptors struggle to patch everything, everywhere, and all the time.</p>
         <img src="http://feeds.feedburner.com/~r/TheAkamaiBlog/~4/NnQEuqRSyug" height="1" width="1" alt=""/>"#)
@@ -163,7 +183,7 @@ fn test_example_4() {
     let test_data = test::fixture_as_string("atom_example_4.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
-    let expected = Feed::new(FeedType::Atom)
+    let expected = Feed::new(FeedType::Atom).version("1.0")
         .author(Person::new("ebm-papst"))
         .link(Link::new("http://www.ebmpapst.com/en/ebmpapst_productnews_atom_feed.xml", None)
             .rel("self")
@@ -191,7 +211,7 @@ fn test_example_5() {
     let test_data = test::fixture_as_string("atom_example_5.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
-    let expected = Feed::new(FeedType::Atom)
+    let expected = Feed::new(FeedType::Atom).version("1.0")
         .title(Text::new("USGS Magnitude 2.5+ Earthquakes, Past Hour".into()))
         .updated_rfc3339("2019-07-31T13:17:27Z")
         .author(Person::new("U.S. Geological Survey")
@@ -201,6 +221,8 @@ fn test_example_5() {
             .rel("self"))
         .icon(Image::new("https://earthquake.usgs.gov/favicon.ico".into()))
         .entry(Entry::default()
+            .geo(GeoExtension { point: Some(GeoPoint::new(40.3465004, -124.4599991)), ..Default::default() })
+            .extension("http://www.georss.org/georss", ExtensionElement::new("elev").value("-29350"))
             .id("urn:earthquake-usgs-gov:nc:73239366")
             .title(Text::new("M 3.6 - 15km W of Petrolia, CA".into()))
             .updated_rfc3339("2019-07-31T13:07:31.364Z")
@@ -231,6 +253,8 @@ fn test_example_6() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     let expected = Feed::new(FeedType::Atom)
+        .version("1.0")
+        .language("en-US")
         .id("tag:github.com,2008:https://github.com/feed-rs/feed-rs/releases")
         .link(
             Link::new("https://github.com/feed-rs/feed-rs/releases", None)
@@ -242,10 +266,11 @@ fn test_example_6() {
                 .rel("self")
                 .media_type("application/atom+xml"),
         )
-        .title(Text::new("Release notes from feed-rs".into()))
+        .title(Text::new("Release notes from feed-rs".into()).lang("en-US"))
         .updated_rfc3339("2020-01-19T16:01:56+11:00")
         .entry(
             Entry::default()
+                .language("en-US")
                 .id("tag:github.com,2008:Repository/90976281/v0.2.0")
                 .updated_rfc3339("2020-01-19T16:08:59+11:00")
                 .link(
@@ -253,9 +278,10 @@ fn test_example_6() {
                         .rel("alternate")
                         .media_type("text/html"),
                 )
-                .title(Text::new("0.2.0".into()))
+                .title(Text::new("0.2.0".into()).lang("en-US"))
                 .content(
                     Content::default()
+                        .lang("en-US")
                         .body(
                             r#"<p>A range of maintenance work, including:</p>
             <ul>
@@ -273,6 +299,7 @@ fn test_example_6() {
         )
         .entry(
             Entry::default()
+                .language("en-US")
                 .id("tag:github.com,2008:Repository/90976281/0.1.3")
                 .updated_rfc3339("2017-07-07T21:47:46+10:00")
                 .link(
@@ -280,12 +307,18 @@ fn test_example_6() {
                         .rel("alternate")
                         .media_type("text/html"),
                 )
-                .title(Text::new("0.1.3".into()))
-                .content(Content::default().body(r#"<p>Update version to 0.1.3</p>"#).content_type("text/html"))
+                .title(Text::new("0.1.3".into()).lang("en-US"))
+                .content(
+                    Content::default()
+                        .lang("en-US")
+                        .body(r#"<p>Update version to 0.1.3</p>"#)
+                        .content_type("text/html"),
+                )
                 .author(Person::new("kumabook")),
         )
         .entry(
             Entry::default()
+                .language("en-US")
                 .id("tag:github.com,2008:Repository/90976281/0.1.1")
                 .updated_rfc3339("2017-06-16T18:49:36+10:00")
                 .link(
@@ -293,9 +326,10 @@ fn test_example_6() {
                         .rel("alternate")
                         .media_type("text/html"),
                 )
-                .title(Text::new("0.1.1".into()))
+                .title(Text::new("0.1.1".into()).lang("en-US"))
                 .content(
                     Content::default()
+                        .lang("en-US")
                         .body(r#"<p>Handle rel attribute of link element of entry of atom</p>"#)
                         .content_type("text/html"),
                 )
@@ -303,6 +337,7 @@ fn test_example_6() {
         )
         .entry(
             Entry::default()
+                .language("en-US")
                 .id("tag:github.com,2008:Repository/90976281/0.1.0")
                 .updated_rfc3339("2017-06-15T16:44:26+10:00")
                 .link(
@@ -310,8 +345,13 @@ fn test_example_6() {
                         .rel("alternate")
                         .media_type("text/html"),
                 )
-                .title(Text::new("0.1.0".into()))
-                .content(Content::default().body(r#"<p>Update crate info to Cargo.toml</p>"#).content_type("text/html"))
+                .title(Text::new("0.1.0".into()).lang("en-US"))
+                .content(
+                    Content::default()
+                        .lang("en-US")
+                        .body(r#"<p>Update crate info to Cargo.toml</p>"#)
+                        .content_type("text/html"),
+                )
                 .author(Person::new("kumabook")),
         );
 
@@ -322,14 +362,13 @@ fn test_example_6() {
 // Verify that we don't trim essential whitespace
 #[test]
 fn test_example_7() {
-    let expected_body = r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>This is a follow up from <a href="https://who-t.blogspot.com/2018/12/high-resolution-wheel-scrolling-on.html">the kernel support for high-resolution wheel scrolling</a> which you totally forgot about because it's already more then a year in the past and seriously, who has the attention span these days to remember this. Anyway, I finally found time and motivation to pick this up again and I started lining up the pieces like cans, for it only to be shot down by the commentary of strangers on the internet. The <a href="https://gitlab.freedesktop.org/wayland/wayland/-/merge_requests/72">Wayland merge request</a> lists the various pieces (libinput, wayland, weston, mutter, gtk and Xwayland) but for the impatient there's also an <a href="https://copr.fedorainfracloud.org/coprs/whot/high-resolution-wheel-scrolling/">Fedora 32 COPR</a>. For all you weirdos inexplicably not running the latest Fedora, well, you'll have to compile this yourself, just like I did. </p> <p>Let's recap: in v5.0 the kernel added new axes <b>REL_WHEEL_HI_RES</b> and <b>REL_HWHEEL_HI_RES</b> for all devices. On devices that actually support high-resolution wheel scrolling (Logitech and Microsoft mice, primarily) you'll get multiple hires events before the now-legacy <b>REL_WHEEL</b> events. On all other devices those two are in sync. </p> <p>Integrating this into the userspace stack was a bit of a mess at first, but I think the solution is good enough, even if it has a rather verbose explanation on how to handle it. The actual patches to integrate ended up being relatively simple. So let's see why it's a bit weird: </p> <p>When Wayland started, back in WhoahReallyThatLongAgo, scrolling was specified as the <b>wl_pointer.axis</b> event with a value in pixels. This works fine for touchpads, not so much for wheels. The early versions of Weston decreed that one wheel click was 10 pixels [1] and, perhaps surprisingly, the world kept on turning. When libinput was forked from Weston <a href="https://who-t.blogspot.com/2015/01/providing-physical-movement-of-wheel.html">an early change</a> was that wheel events would have two values - degrees of movement and click count ("discrete steps"). The wayland protocol was expanded to include the discrete steps as <b>wl_pointer.axis_discrete</b> as well. Then backwards compatibility reared its ugly head and Mutter, Weston, GTK all basically said: one discrete step equals 10 pixels so we multiply the discrete value by 10 and, perhaps surprisingly, the world kept on turning. </p> <p>This worked out well enough for a few years but with high resolution wheels we ran into a problem. Discrete steps are integers, so we can't send partial values. And the protocol is defined in a way that any tweaking of the behaviour would result in broken clients which, perhaps surprisingly, is a Bad Thing. This lead to the current proposal of separate events. <b>LIBINPUT_EVENT_POINTER_AXIS_WHEEL</b> and for Wayland the <b>wl_pointer.axis_v120</b> event, linked to above. These events are (like the kernel events) a parallel event stream to the previous events and effectively replace the <b>LIBINPUT_EVENT_POINTER_AXIS</b> and Wayland <b>wl_pointer.axis/axis_discrete</b> pair for wheel events (not so for touchpad or button scrolling though). </p> <p>The compositor side of things is relatively simple: take the events from libinput and pass the hires ones as v120 events and the lowres ones as v120 events with a value of zero. The client side takes the v120 events and uses them over <b>wl_pointer.axis/axis_discrete</b> unless one is zero in which case you can discard all axis events in that <b>wl_pointer.frame</b>. Since most client implementation already have the support for smooth scrolling (because, well, touchpads do exist) it's relatively simple to integrate - the new events just feed into the smooth scrolling code. And since you already have to do wheel emulation for that (because, well, old clients exist) wheel emulation is handled easily too. </p> <p>All that to provide buttery smooth [2] wheel scrolling. Or not, if your hardware doesn't support it. In which case, well, live with the warm fuzzy feeling that someone else has a better user experience now. Or soon, anyway. </p> <p><small>[1] with, I suspect, the scientific measurement of "yeah, that seems about alright"<br></br>[2] like butter out of a fridge, so still chunky but at least less so than before<br></br></small></p></div>
-    "#;
+    let expected_body = r#"<p>This is a follow up from <a href="https://who-t.blogspot.com/2018/12/high-resolution-wheel-scrolling-on.html">the kernel support for high-resolution wheel scrolling</a> which you totally forgot about because it's already more then a year in the past and seriously, who has the attention span these days to remember this. Anyway, I finally found time and motivation to pick this up again and I started lining up the pieces like cans, for it only to be shot down by the commentary of strangers on the internet. The <a href="https://gitlab.freedesktop.org/wayland/wayland/-/merge_requests/72">Wayland merge request</a> lists the various pieces (libinput, wayland, weston, mutter, gtk and Xwayland) but for the impatient there's also an <a href="https://copr.fedorainfracloud.org/coprs/whot/high-resolution-wheel-scrolling/">Fedora 32 COPR</a>. For all you weirdos inexplicably not running the latest Fedora, well, you'll have to compile this yourself, just like I did. </p> <p>Let's recap: in v5.0 the kernel added new axes <b>REL_WHEEL_HI_RES</b> and <b>REL_HWHEEL_HI_RES</b> for all devices. On devices that actually support high-resolution wheel scrolling (Logitech and Microsoft mice, primarily) you'll get multiple hires events before the now-legacy <b>REL_WHEEL</b> events. On all other devices those two are in sync. </p> <p>Integrating this into the userspace stack was a bit of a mess at first, but I think the solution is good enough, even if it has a rather verbose explanation on how to handle it. The actual patches to integrate ended up being relatively simple. So let's see why it's a bit weird: </p> <p>When Wayland started, back in WhoahReallyThatLongAgo, scrolling was specified as the <b>wl_pointer.axis</b> event with a value in pixels. This works fine for touchpads, not so much for wheels. The early versions of Weston decreed that one wheel click was 10 pixels [1] and, perhaps surprisingly, the world kept on turning. When libinput was forked from Weston <a href="https://who-t.blogspot.com/2015/01/providing-physical-movement-of-wheel.html">an early change</a> was that wheel events would have two values - degrees of movement and click count ("discrete steps"). The wayland protocol was expanded to include the discrete steps as <b>wl_pointer.axis_discrete</b> as well. Then backwards compatibility reared its ugly head and Mutter, Weston, GTK all basically said: one discrete step equals 10 pixels so we multiply the discrete value by 10 and, perhaps surprisingly, the world kept on turning. </p> <p>This worked out well enough for a few years but with high resolution wheels we ran into a problem. Discrete steps are integers, so we can't send partial values. And the protocol is defined in a way that any tweaking of the behaviour would result in broken clients which, perhaps surprisingly, is a Bad Thing. This lead to the current proposal of separate events. <b>LIBINPUT_EVENT_POINTER_AXIS_WHEEL</b> and for Wayland the <b>wl_pointer.axis_v120</b> event, linked to above. These events are (like the kernel events) a parallel event stream to the previous events and effectively replace the <b>LIBINPUT_EVENT_POINTER_AXIS</b> and Wayland <b>wl_pointer.axis/axis_discrete</b> pair for wheel events (not so for touchpad or button scrolling though). </p> <p>The compositor side of things is relatively simple: take the events from libinput and pass the hires ones as v120 events and the lowres ones as v120 events with a value of zero. The client side takes the v120 events and uses them over <b>wl_pointer.axis/axis_discrete</b> unless one is zero in which case you can discard all axis events in that <b>wl_pointer.frame</b>. Since most client implementation already have the support for smooth scrolling (because, well, touchpads do exist) it's relatively simple to integrate - the new events just feed into the smooth scrolling code. And since you already have to do wheel emulation for that (because, well, old clients exist) wheel emulation is handled easily too. </p> <p>All that to provide buttery smooth [2] wheel scrolling. Or not, if your hardware doesn't support it. In which case, well, live with the warm fuzzy feeling that someone else has a better user experience now. Or soon, anyway. </p> <p><small>[1] with, I suspect, the scientific measurement of "yeah, that seems about alright"<br></br>[2] like butter out of a fridge, so still chunky but at least less so than before<br></br></small></p>"#;
     // Parse the feed
     let test_data = test::fixture_as_string("atom_example_7.xml");
     let feed = parser::parse(test_data.as_bytes()).unwrap();
     let body = feed
         .entries
-        .get(0)
+        .first()
         .map(|e| e.content.as_ref())
         .unwrap()
         .map(|c| c.body.as_ref())
@@ -358,6 +397,7 @@ fn test_spec_1() {
 
     // Expected feed
     let expected = Feed::new(FeedType::Atom)
+        .version("1.0")
         .id("urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6")
         .title(Text::new("Example Feed".into()))
         .link(Link::new("http://example.org/", None).rel("alternate"))
@@ -382,15 +422,15 @@ fn test_spec_1() {
 fn test_pub_spec_1() {
     // Parse the feed
     let test_data = test::fixture_as_string("atom_pub_spec_1.xml");
-    let actual = parser::parse(test_data.as_bytes()).unwrap().id(""); // Clear randomly generated UUID
+    let actual = parser::parse(test_data.as_bytes()).unwrap().id(""); // Clear the generated ID
 
     // Expected feed
-    let expected = Feed::new(FeedType::Atom).entry(
+    let expected = Feed::new(FeedType::Atom).version("1.0").entry(
         Entry::default()
             .title(Text::new("Atom-Powered Robots Run Amok".into()))
             .id("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a")
             .updated_rfc3339("2003-12-13T18:30:02Z")
-            .author(Person::new("John Doe".into()))
+            .author(Person::new("John Doe"))
             .content(Content::default().content_type("text/plain").body("Some text.")),
     );
 
@@ -404,12 +444,18 @@ fn test_entry() {
     let test_data = test::fixture_as_string("atom_entry_1.xml");
     let actual = parser::parse(test_data.as_bytes()).unwrap().id("");
 
-    let expected = Feed::new(FeedType::Atom).entry(
+    let expected = Feed::new(FeedType::Atom).version("1.0").entry(
         Entry::default()
+            .extension(
+                "http://www.svnit.ac.in/coed/mtech/research/2009/khuba/",
+                ExtensionElement::new("Semantics")
+                    .attribute("available", "OfflineAtURL")
+                    .value("http://www.daman.nic.in/khuba/ontology/camera.owl"),
+            )
             .title(Text::new("Specifications".into()))
             .id("urn:uuid:988EF5C55CDEA24EDE1251744888912")
             .updated_rfc3339("2009-08-31T18:55:12.569Z")
-            .author(Person::new("S. A. Khuba".into()))
+            .author(Person::new("S. A. Khuba"))
             .category(Category::new("45121504").scheme("http://www.unspsc.org/UNv1111201").label("Digital Camera"))
             .contributor(Person::new("Shri. S. A. Khuba"))
             .content(
@@ -470,6 +516,7 @@ fn test_mediarss_newscred() {
             MediaContent::new()
                 .url("https://www.example.com/Zz1hNjRiMWFjMzdhYWIzNTEwNjk2YjIzYjc5NWQxNWFlMA==/.jpeg")
                 .content_type("image/jpeg")
+                .medium("image")
                 .width(2048)
                 .height(1365),
         )
@@ -484,3 +531,336 @@ fn test_mediarss_newscred() {
     let media_obj = &entry.media[0];
     assert_eq!(media_obj, &expected);
 }
+
+// Verify we can parse the legacy Atom 0.3 format
+#[test]
+fn test_atom_0_3() {
+    // Parse the feed
+    let test_data = test::fixture_as_string("atom_0.3_example_1.xml");
+    let actual = parser::parse(test_data.as_bytes()).unwrap();
+
+    // Expected feed
+    let expected = Feed::new(FeedType::Atom)
+        .version("0.3")
+        .language("en")
+        .title(Text::new("dive into mark".into()).lang("en").content_type("text/html"))
+        .link(Link::new("http://diveintomark.org/", None).rel("alternate").media_type("text/html"))
+        .description(
+            Text::new("A <em>lot</em> of effort went into making this effortless".into())
+                .lang("en")
+                .content_type("text/html"),
+        )
+        .updated_rfc3339("2003-12-13T18:30:02Z")
+        .id("tag:diveintomark.org,2003:3")
+        .generator(Generator::new("Example Toolkit").uri("http://www.example.com/").version("1.0"))
+        .rights(Text::new("Copyright (c) 2003, Mark Pilgrim".into()).lang("en"))
+        .entry(
+            Entry::default()
+                .language("en")
+                .title(Text::new("Atom 0.3 snapshot".into()).lang("en"))
+                .link(
+                    Link::new("http://diveintomark.org/2003/12/13/atom03", None)
+                        .rel("alternate")
+                        .media_type("text/html"),
+                )
+                .id("tag:diveintomark.org,2003:3.2397")
+                .author(Person::new("Mark Pilgrim").uri("http://diveintomark.org/").email("f8dy@example.com"))
+                .published_rfc3339("2003-12-13T08:29:29-04:00")
+                .updated_rfc3339("2003-12-13T18:30:02Z")
+                .summary(Text::new("The Atom 0.3 snapshot is out.".into()).lang("en"))
+                .content(
+                    Content::default()
+                        .lang("en")
+                        .content_type("text/html")
+                        .body("<p>The <i>Atom 0.3</i> snapshot is out.</p>"),
+                ),
+        )
+        .entry(
+            Entry::default()
+                .language("en")
+                .title(Text::new("Created only".into()).lang("en"))
+                .id("tag:diveintomark.org,2003:3.2398")
+                .published_rfc3339("2003-12-14T08:00:00Z")
+                .updated_rfc3339("2003-12-14T09:00:00Z")
+                .content(Content::default().lang("en").content_type("text/html").body("<p>Inline</p>")),
+        );
+
+    // Check
+    assert_eq!(actual, expected);
+}
+
+// Verify inline content with a binary media type can be decoded from base64
+#[test]
+fn test_content_base64() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>urn:entry:1</id>
+                <content type="application/octet-stream">
+                    SGVsbG8s
+                    IHdvcmxk
+                </content>
+            </entry>
+            <entry>
+                <id>urn:entry:2</id>
+                <content type="html">&lt;p&gt;SGVsbG8s&lt;/p&gt;</content>
+            </entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    // The body keeps the encoded form, which is only decoded for binary types
+    let content = feed.entries[0].content.as_ref().unwrap();
+    assert_eq!(content.content_type, "application/octet-stream");
+    assert_eq!(content.decoded_body(), Some(b"Hello, world".to_vec()));
+    assert_eq!(feed.entries[1].content.as_ref().unwrap().decoded_body(), None);
+}
+
+// Verify elements are matched on their namespace rather than the prefix in use
+#[test]
+fn test_namespace_prefix() {
+    let xml = r#"<atom:feed xmlns:atom="http://www.w3.org/2005/Atom">
+            <atom:id>urn:feed</atom:id>
+            <atom:title>Prefixed</atom:title>
+            <atom:link href="http://example.com/"/>
+            <atom:entry>
+                <atom:id>urn:entry:1</atom:id>
+                <a10:link xmlns:a10="http://www.w3.org/2005/Atom" rel="related" href="http://example.com/related"/>
+            </atom:entry>
+        </atom:feed>"#;
+    let actual = parser::parse(xml.as_bytes()).unwrap();
+
+    let expected = Feed::new(FeedType::Atom)
+        .version("1.0")
+        .id("urn:feed")
+        .title(Text::new("Prefixed".into()))
+        .link(Link::new("http://example.com/", None).rel("alternate"))
+        .entry(
+            Entry::default()
+                .id("urn:entry:1")
+                .link(Link::new("http://example.com/related", None).rel("related")),
+        );
+    assert_eq!(actual, expected);
+}
+
+// Verify the metadata of the feed an entry was copied from is retained
+#[test]
+fn test_source() {
+    let test_data = test::fixture_as_string("atom_source.xml");
+    let actual = parser::parse(test_data.as_bytes()).unwrap();
+
+    let expected = Feed::new(FeedType::Atom)
+        .version("1.0")
+        .id("urn:aggregator")
+        .title(Text::new("Aggregator".into()))
+        .updated_rfc3339("2005-07-31T12:29:29Z")
+        .entry(
+            Entry::default()
+                .id("urn:entry:1")
+                .title(Text::new("Copied entry".into()))
+                .updated_rfc3339("2005-07-31T12:29:29Z")
+                .source(FeedSource {
+                    rights: Some(Text::new("© 2005 Example, Inc.".into())),
+                    ..FeedSource::default()
+                        .id("http://example.org/")
+                        .title(Text::new("Fourty-Two".into()))
+                        .updated_rfc3339("2003-12-13T18:30:02Z")
+                        .author(Person::new("John Doe"))
+                        .link(Link::new("http://example.org/feed.atom", None).rel("self"))
+                }),
+        );
+    assert_eq!(actual, expected);
+}
+
+// Verify the threading extension records replies and the entries being replied to
+#[test]
+fn test_threading() {
+    let test_data = test::fixture_as_string("atom_threading.xml");
+    let actual = parser::parse(test_data.as_bytes()).unwrap();
+
+    let original = &actual.entries[0];
+    assert_eq!(
+        original.threading,
+        Some(ThreadingExtension {
+            total: Some(10),
+            replies: vec![Replies {
+                href: "http://www.example.org/entries/1/comments.xml".into(),
+                media_type: Some("application/atom+xml".into()),
+                count: Some(10),
+                updated: Some(Utc.with_ymd_and_hms(2005, 7, 28, 12, 10, 0).unwrap()),
+            }],
+            ..Default::default()
+        })
+    );
+    // The link to the replies is also one of the entry's links
+    assert_eq!(original.links[1].rel.as_deref(), Some("replies"));
+
+    let response = &actual.entries[1];
+    assert_eq!(
+        response.threading,
+        Some(ThreadingExtension {
+            in_reply_to: vec![InReplyTo {
+                reference: "tag:entries.com,2005:1".into(),
+                href: Some("http://www.example.org/entries/1".into()),
+                media_type: Some("application/xhtml+xml".into()),
+                source: Some("http://www.example.org/myfeed".into()),
+            }],
+            ..Default::default()
+        })
+    );
+}
+
+// Verify out of line content is resolved against the xml:base, and classified from its type or else the extension of its URL
+#[test]
+fn test_content_src() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:base="http://example.com/podcast/">
+            <entry><id>urn:1</id><content type="audio/mpeg" src="episodes/1"/></entry>
+            <entry xml:base="http://media.example.com/"><id>urn:2</id><content src="video/2.MP4?token=abc"/></entry>
+            <entry><id>urn:3</id><content type="html" src="/posts/3"/></entry>
+            <entry><id>urn:4</id><content src="downloads.v2/notes"/></entry>
+        </feed>"#;
+    let actual = parser::parse(xml.as_bytes()).unwrap();
+
+    let content = actual.entries.iter().map(|entry| entry.content.as_ref().unwrap()).collect::<Vec<_>>();
+    let sources = content.iter().map(|content| content.src.as_ref().unwrap().href.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![
+            "http://example.com/podcast/episodes/1",
+            "http://media.example.com/video/2.MP4?token=abc",
+            "http://example.com/posts/3",
+            "http://example.com/podcast/downloads.v2/notes",
+        ]
+    );
+    let kinds = content.iter().map(|content| content.kind()).collect::<Vec<_>>();
+    assert_eq!(kinds, vec![Some(ContentKind::Audio), Some(ContentKind::Video), Some(ContentKind::Html), None]);
+}
+
+// Verify link media types are parsed and normalised, and those that are malformed are ignored
+#[test]
+fn test_link_media_type() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <link href="http://example.com/1" type="TEXT/HTML"/>
+            <link href="http://example.com/2" type=" application/atom+xml; charset=utf-8 "/>
+            <link href="http://example.com/3" type="text / html"/>
+            <link href="http://example.com/4" type="html"/>
+        </feed>"#;
+    let actual = parser::parse(xml.as_bytes()).unwrap();
+
+    let media_types = actual.links.iter().map(|link| link.media_type.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        media_types,
+        vec![Some(mime::TEXT_HTML), Some("application/atom+xml; charset=utf-8".parse().unwrap()), None, None]
+    );
+    let media_type = actual.links[1].media_type.as_ref().unwrap();
+    assert_eq!(
+        (media_type.type_(), media_type.subtype().as_str(), media_type.suffix()),
+        (mime::APPLICATION, "atom", Some(mime::XML))
+    );
+}
+
+// Verify each of the Atom 1.0 and 0.3 dates is recorded as the time the entry was updated or published, without one standing in for the other
+#[test]
+fn test_updated_and_published() {
+    let entry = |elements: &str| {
+        let xml = format!(r#"<feed xmlns="http://www.w3.org/2005/Atom"><entry><id>urn:1</id>{}</entry></feed>"#, elements);
+        let entry = parser::parse(xml.as_bytes()).unwrap().entries.remove(0);
+        (entry.updated, entry.published, entry.timestamp())
+    };
+    let updated = Utc.with_ymd_and_hms(2021, 3, 2, 10, 0, 0).unwrap();
+    let published = Utc.with_ymd_and_hms(2021, 3, 1, 10, 0, 0).unwrap();
+
+    assert_eq!(
+        entry("<updated>2021-03-02T10:00:00Z</updated><published>2021-03-01T10:00:00Z</published>"),
+        (Some(updated), Some(published), Some(updated))
+    );
+    assert_eq!(entry("<updated>2021-03-02T10:00:00Z</updated>"), (Some(updated), None, Some(updated)));
+    assert_eq!(entry("<published>2021-03-01T10:00:00Z</published>"), (None, Some(published), Some(published)));
+    assert_eq!(entry(""), (None, None, None));
+
+    // Atom 0.3 has "modified" and "issued", with "created" only used if the entry was not issued
+    assert_eq!(
+        entry("<modified>2021-03-02T10:00:00Z</modified><issued>2021-03-01T10:00:00Z</issued><created>2021-02-01T10:00:00Z</created>"),
+        (Some(updated), Some(published), Some(updated))
+    );
+    assert_eq!(entry("<created>2021-03-01T10:00:00Z</created>"), (None, Some(published), Some(published)));
+}
+
+// Verify XHTML content and text constructs keep their inner markup, without the div that wraps it
+#[test]
+fn test_xhtml_content() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <title type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml">Fish &amp; <em>Chips</em></div></title>
+            <entry>
+                <id>urn:1</id>
+                <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p class="intro">Links:</p><ul><li><a href="http://example.com/?a=1&amp;b=2" title='Say "hi"'>One</a></li><li>Two<ol><li>2 &lt; 3</li></ol></li></ul></div></content>
+            </entry>
+            <entry>
+                <id>urn:2</id>
+                <content type="xhtml"><p>One</p><p>Two</p></content>
+            </entry>
+        </feed>"#;
+    let actual = parser::parse(xml.as_bytes()).unwrap();
+
+    let title = actual.title.unwrap();
+    assert_eq!(title.content, "Fish &amp; <em>Chips</em>");
+    assert_eq!(title.content_type, mime::TEXT_HTML);
+
+    let bodies = actual
+        .entries
+        .iter()
+        .map(|entry| entry.content.as_ref().and_then(|content| content.body.as_deref()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bodies,
+        vec![
+            r#"<p class="intro">Links:</p><ul><li><a href="http://example.com/?a=1&amp;b=2" title="Say &quot;hi&quot;">One</a></li><li>Two<ol><li>2 &lt; 3</li></ol></li></ul>"#,
+            // Without a wrapping div, the content is kept as it is
+            "<p>One</p><p>Two</p>",
+        ]
+    );
+}
+
+// Verify the first link with the "license" relation is the license of the feed or entry, and is still one of the links
+#[test]
+fn test_license_link() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <link href="http://example.com/"/>
+            <link rel="license" href="http://creativecommons.org/licenses/by/4.0/"/>
+            <entry>
+                <id>urn:1</id>
+                <link rel="license" type="text/html" href="http://creativecommons.org/licenses/by-nc/4.0/"/>
+                <link rel="license" href="http://creativecommons.org/licenses/by-sa/4.0/"/>
+            </entry>
+            <entry><id>urn:2</id><link rel="alternate" href="http://example.com/2"/></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    assert_eq!(
+        feed.license,
+        Some(Link::new("http://creativecommons.org/licenses/by/4.0/", None).rel("license"))
+    );
+    assert_eq!(feed.links.len(), 2);
+    let entry = &feed.entries[0];
+    assert_eq!(
+        entry.license,
+        Some(
+            Link::new("http://creativecommons.org/licenses/by-nc/4.0/", None)
+                .rel("license")
+                .media_type("text/html")
+        )
+    );
+    assert_eq!(entry.links.len(), 2);
+    assert_eq!(feed.entries[1].license, None);
+}
+
+// Verify the language of an entry is the xml:lang in effect for it
+#[test]
+fn test_entry_language() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en">
+            <entry><id>urn:1</id></entry>
+            <entry xml:lang="ja"><id>urn:2</id></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    assert_eq!(feed.entries[0].language.as_deref(), Some("en"));
+    assert_eq!(feed.entries[1].language.as_deref(), Some("ja"));
+}
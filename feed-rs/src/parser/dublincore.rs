@@ -0,0 +1,164 @@
+use crate::extensions::dublincore::DublinCoreExtension;
+use crate::extensions::ExtensionMap;
+use crate::model::{Category, Entry, Feed, Person, Text};
+use crate::parser::{util, ParseFeedResult, Parser};
+use crate::util::dates::TimestampFormat;
+use crate::xml::Element;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+// The terms holding a date, which are parsed if they may be mapped on to the model
+const DATE_TERMS: [&str; 3] = ["created", "issued", "modified"];
+
+// Process <dc> elements at channel level, recording them in the Dublin Core extension and mapping them on to the Feed object as required
+pub(crate) fn handle_dublin_core_channel_element<R: BufRead>(parser: &Parser, element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+    // Unknown elements are passed to the caller's handler, if registered
+    if !DublinCoreExtension::ELEMENTS.contains(&element.name.as_str()) {
+        return parser.handle_unknown_element(&element, &mut feed.extensions);
+    }
+
+    let text = match element.child_as_text() {
+        Some(text) => text,
+        None => return Ok(()),
+    };
+
+    match element.name.as_str() {
+        "contributor" => feed.contributors.push(Person::new(&text)),
+
+        "creator" => add_creators(parser, &text, &mut feed.authors),
+
+        "date" if feed.published.is_none() => feed.published = parser.parse_timestamp(&text, TimestampFormat::W3cDtf)?,
+
+        "description" => {
+            if let Some(description) = parser.text_variant(&mut feed.description_variants, "dc:description", Some(Text::new(text.clone()))) {
+                if feed.description.is_none() {
+                    feed.description = Some(description);
+                }
+            }
+        }
+
+        "language" if feed.language.is_none() => feed.language = Some(text.clone()),
+
+        "rights" if feed.rights.is_none() => feed.rights = Some(Text::new(text.clone())),
+
+        "subject" => feed.categories.push(Category::new(&text)),
+
+        "title" => {
+            if let Some(title) = parser.text_variant(&mut feed.title_variants, "dc:title", Some(Text::new(text.clone()))) {
+                if feed.title.is_none() {
+                    feed.title = Some(title);
+                }
+            }
+        }
+
+        // Other elements are only recorded in the extension
+        _ => {}
+    }
+
+    feed.dublin_core.get_or_insert_with(Default::default).record(&element.name, text);
+    Ok(())
+}
+
+// Process <dc> elements at item level, recording them in the Dublin Core extension and mapping them on to the Entry object as required
+pub(crate) fn handle_dublin_core_item_element<R: BufRead>(parser: &Parser, element: Element<R>, entry: &mut Entry) -> ParseFeedResult<()> {
+    // Unknown elements are passed to the caller's handler, if registered
+    if !DublinCoreExtension::ELEMENTS.contains(&element.name.as_str()) {
+        return parser.handle_unknown_element(&element, &mut entry.extensions);
+    }
+
+    let text = match element.child_as_text() {
+        Some(text) => text,
+        None => return Ok(()),
+    };
+
+    match element.name.as_str() {
+        "contributor" => entry.contributors.push(Person::new(&text)),
+
+        "creator" => add_creators(parser, &text, &mut entry.authors),
+
+        "date" if entry.published.is_none() => entry.published = parser.parse_timestamp(&text, TimestampFormat::W3cDtf)?,
+
+        "description" => {
+            if let Some(summary) = parser.text_variant(&mut entry.summary_variants, "dc:description", Some(Text::new(text.clone()))) {
+                if entry.summary.is_none() {
+                    entry.summary = Some(summary);
+                }
+            }
+        }
+
+        "language" if entry.language.is_none() => entry.language = Some(text.clone()),
+
+        "rights" if entry.rights.is_none() => entry.rights = Some(Text::new(text.clone())),
+
+        "subject" => entry.categories.push(Category::new(&text)),
+
+        "title" => {
+            if let Some(title) = parser.text_variant(&mut entry.title_variants, "dc:title", Some(Text::new(text.clone()))) {
+                if entry.title.is_none() {
+                    entry.title = Some(title);
+                }
+            }
+        }
+
+        // Other elements are only recorded in the extension
+        _ => {}
+    }
+
+    entry.dublin_core.get_or_insert_with(Default::default).record(&element.name, text);
+    Ok(())
+}
+
+// Adds the person named by a <dc:creator>, or if splitting author names, each of the people it lists that is not already known
+fn add_creators(parser: &Parser, text: &str, authors: &mut Vec<Person>) {
+    if !parser.split_author_names {
+        authors.push(Person::new(text));
+        return;
+    }
+
+    let mut known: HashSet<String> = authors.iter().map(|author| author.name.to_ascii_lowercase()).collect();
+    for name in util::split_names(text) {
+        if known.insert(name.to_ascii_lowercase()) {
+            authors.push(Person::new(&name));
+        }
+    }
+}
+
+// Process <dcterms> elements at channel level, using their dates if the feed has none of its own and recording the other terms in the extensions
+pub(crate) fn handle_dublin_core_terms_channel_element<R: BufRead>(parser: &Parser, element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+    let name = element.name.clone();
+    let date = handle_dublin_core_terms_element(parser, element, &mut feed.extensions)?;
+    match name.as_str() {
+        "modified" if feed.updated.is_none() => feed.updated = date,
+        "created" | "issued" if feed.published.is_none() => feed.published = date,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// Process <dcterms> elements at item level, using their dates if the entry has none of its own and recording the other terms in the extensions
+pub(crate) fn handle_dublin_core_terms_item_element<R: BufRead>(parser: &Parser, element: Element<R>, entry: &mut Entry) -> ParseFeedResult<()> {
+    let name = element.name.clone();
+    let date = handle_dublin_core_terms_element(parser, element, &mut entry.extensions)?;
+    match name.as_str() {
+        "modified" if entry.updated.is_none() => entry.updated = date,
+        "created" | "issued" if entry.published.is_none() => entry.published = date,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// The terms holding a date are parsed, while the others are passed to the caller's handler and recorded as for any other namespace
+fn handle_dublin_core_terms_element<R: BufRead>(parser: &Parser, element: Element<R>, extensions: &mut ExtensionMap) -> ParseFeedResult<Option<DateTime<Utc>>> {
+    if !DATE_TERMS.contains(&element.name.as_str()) {
+        parser.handle_unknown_element(&element, extensions)?;
+        return Ok(None);
+    }
+
+    match element.child_as_text() {
+        Some(text) => parser.parse_timestamp(&text, TimestampFormat::W3cDtf),
+        None => Ok(None),
+    }
+}
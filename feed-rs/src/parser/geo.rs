@@ -0,0 +1,138 @@
+use crate::extensions::geo::{GeoBox, GeoExtension, GeoPoint};
+use crate::extensions::ExtensionMap;
+use crate::parser::{ParseFeedResult, Parser};
+use crate::xml::{Element, NS};
+use std::io::BufRead;
+
+// Collects the location of an item from the GeoRSS and W3C Basic Geo elements it contains
+//
+// W3C Basic Geo has the latitude and longitude in separate elements, so the location is only known once the whole item has been seen.
+#[derive(Default)]
+pub(crate) struct GeoHandler {
+    geo: GeoExtension,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl GeoHandler {
+    // Process <georss> or <geo> elements at item level
+    pub(crate) fn handle_element<R: BufRead>(&mut self, parser: &Parser, element: Element<R>, extensions: &mut ExtensionMap) -> ParseFeedResult<()> {
+        match element.ns_and_tag() {
+            // GeoRSS Simple
+            (Some(NS::GeoRSS), "point") => self.geo.point = handle_point(element.child_as_text()),
+            (Some(NS::GeoRSS), "line") => self.geo.line = handle_line(element.child_as_text()),
+            (Some(NS::GeoRSS), "polygon") => self.geo.polygon = handle_polygon(element.child_as_text()),
+            (Some(NS::GeoRSS), "box") => self.geo.bounding_box = handle_box(element.child_as_text()),
+
+            // GeoRSS GML
+            (Some(NS::GeoRSS), "where") => {
+                for child in element.children() {
+                    self.handle_gml(child?)?;
+                }
+            }
+
+            // W3C Basic Geo, where the coordinates may also be wrapped in a <geo:Point>
+            (Some(NS::W3CGeo), "lat") => self.latitude = handle_coordinate(element.child_as_text()),
+            (Some(NS::W3CGeo), "long") => self.longitude = handle_coordinate(element.child_as_text()),
+            (Some(NS::W3CGeo), "Point") => {
+                for child in element.children() {
+                    self.handle_element(parser, child?, extensions)?;
+                }
+            }
+
+            // Other elements (e.g. "georss:elev") are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&element, extensions)?,
+        }
+
+        Ok(())
+    }
+
+    // Returns the location, if any was found
+    pub(crate) fn finish(self) -> Option<GeoExtension> {
+        let mut geo = self.geo;
+        if geo.point.is_none() {
+            if let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) {
+                geo.point = Some(GeoPoint::new(latitude, longitude));
+            }
+        }
+
+        if geo.is_empty() {
+            None
+        } else {
+            Some(geo)
+        }
+    }
+
+    // Handles the GML geometry within a <georss:where>
+    fn handle_gml<R: BufRead>(&mut self, element: Element<R>) -> ParseFeedResult<()> {
+        match element.name.as_str() {
+            "Point" => self.geo.point = handle_point(gml_text(element, &["pos"])?),
+            "LineString" => self.geo.line = handle_line(gml_text(element, &["posList"])?),
+            "Polygon" => self.geo.polygon = handle_polygon(gml_text(element, &["exterior", "LinearRing", "posList"])?),
+            "Envelope" => {
+                let (mut lower, mut upper) = (None, None);
+                for child in element.children() {
+                    let child = child?;
+                    match child.name.as_str() {
+                        "lowerCorner" => lower = handle_point(child.child_as_text()),
+                        "upperCorner" => upper = handle_point(child.child_as_text()),
+                        _ => {}
+                    }
+                }
+                if let (Some(lower), Some(upper)) = (lower, upper) {
+                    self.geo.bounding_box = Some(GeoBox { lower, upper });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+// Finds the text of the element at the path below this one
+fn gml_text<R: BufRead>(element: Element<R>, path: &[&str]) -> ParseFeedResult<Option<String>> {
+    if path.is_empty() {
+        return Ok(element.child_as_text());
+    }
+
+    for child in element.children() {
+        let child = child?;
+        if child.name == path[0] {
+            return gml_text(child, &path[1..]);
+        }
+    }
+    Ok(None)
+}
+
+// A point is a single pair of coordinates
+fn handle_point(text: Option<String>) -> Option<GeoPoint> {
+    text.and_then(|text| GeoPoint::parse_list(&text))
+        .filter(|points| points.len() == 1)
+        .map(|points| points[0])
+}
+
+// A line has at least two points
+fn handle_line(text: Option<String>) -> Option<Vec<GeoPoint>> {
+    text.and_then(|text| GeoPoint::parse_list(&text)).filter(|points| points.len() >= 2)
+}
+
+// A polygon has at least four points, as the first is repeated at the end to close it
+fn handle_polygon(text: Option<String>) -> Option<Vec<GeoPoint>> {
+    text.and_then(|text| GeoPoint::parse_list(&text))
+        .filter(|points| points.len() >= 4 && points.first() == points.last())
+}
+
+// A box is given by its lower and upper corners
+fn handle_box(text: Option<String>) -> Option<GeoBox> {
+    text.and_then(|text| GeoPoint::parse_list(&text))
+        .filter(|points| points.len() == 2)
+        .map(|points| GeoBox {
+            lower: points[0],
+            upper: points[1],
+        })
+}
+
+fn handle_coordinate(text: Option<String>) -> Option<f64> {
+    text.and_then(|text| text.trim().parse::<f64>().ok()).filter(|f| f.is_finite())
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::model;
+
+/// Shares the strings that feeds repeat, such as the names of authors and the terms and schemes of categories (see `Builder::interner()`)
+///
+/// Each distinct string is held once, however many entries refer to it, including those of other feeds parsed with the same interner.
+/// Interners are cheap to clone, and all clones share the same strings.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use feed_rs::parser::{self, Interner};
+///
+/// let interner = Interner::new();
+/// let parser = parser::Builder::new().interner(interner.clone()).build();
+/// let xml = r#"<rss version="2.0"><channel>
+///     <item><guid>1</guid><category>Rust</category></item>
+///     <item><guid>2</guid><category>Rust</category></item>
+/// </channel></rss>"#;
+/// let feed = parser.parse(xml.as_bytes()).unwrap();
+/// assert!(Arc::ptr_eq(&feed.entries[0].categories[0].term, &feed.entries[1].categories[0].term));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner(Arc<Mutex<HashSet<Arc<str>>>>);
+
+impl Interner {
+    /// Create a new interner, initially holding no strings
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Returns the shared copy of the string, which is added if it has not been seen before
+    pub fn intern(&self, string: &str) -> Arc<str> {
+        let mut strings = self.0.lock().unwrap();
+        match strings.get(string) {
+            Some(shared) => shared.clone(),
+            None => {
+                let shared: Arc<str> = string.into();
+                strings.insert(shared.clone());
+                shared
+            }
+        }
+    }
+
+    /// The number of distinct strings held
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// True if no strings are held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimates the number of bytes the shared strings hold on the heap
+    ///
+    /// Each shared string is charged evenly to the interner and the feeds holding it (see `Feed::heap_size()`), so this is the interner's
+    /// share plus its own table, and the memory held by a collection of feeds parsed with this interner is the sum of their sizes and this.
+    pub fn heap_size(&self) -> usize {
+        let strings = self.0.lock().unwrap();
+        let shared: usize = strings
+            .iter()
+            .map(|string| (string.len() + 2 * std::mem::size_of::<usize>()) / Arc::strong_count(string))
+            .sum();
+        shared + strings.capacity() * std::mem::size_of::<Arc<str>>()
+    }
+
+    /// Drops the strings that are no longer referred to by any feed, such as those of feeds that have since been discarded
+    pub fn purge(&self) {
+        self.0.lock().unwrap().retain(|shared| Arc::strong_count(shared) > 1);
+    }
+
+    // Replaces the string with its shared copy, adding it if it has not been seen before
+    fn share(strings: &mut HashSet<Arc<str>>, string: &mut Arc<str>) {
+        match strings.get(&**string) {
+            Some(shared) => *string = shared.clone(),
+            None => {
+                strings.insert(string.clone());
+            }
+        }
+    }
+
+    // Shares the repeated strings of the feed itself, its entries having been shared as they were parsed
+    pub(crate) fn intern_feed(&self, feed: &mut model::Feed) {
+        let mut strings = self.0.lock().unwrap();
+        Interner::intern_people(&mut strings, &mut feed.authors);
+        Interner::intern_people(&mut strings, &mut feed.contributors);
+        Interner::intern_categories(&mut strings, &mut feed.categories);
+    }
+
+    // Shares the repeated strings of an entry
+    pub(crate) fn intern_entry(&self, entry: &mut model::Entry) {
+        let mut strings = self.0.lock().unwrap();
+        Interner::intern_people(&mut strings, &mut entry.authors);
+        Interner::intern_people(&mut strings, &mut entry.contributors);
+        Interner::intern_categories(&mut strings, &mut entry.categories);
+        if let Some(source) = &mut entry.source {
+            Interner::intern_people(&mut strings, &mut source.authors);
+        }
+    }
+
+    fn intern_people(strings: &mut HashSet<Arc<str>>, people: &mut [model::Person]) {
+        for person in people {
+            Interner::share(strings, &mut person.name);
+        }
+    }
+
+    fn intern_categories(strings: &mut HashSet<Arc<str>>, categories: &mut [model::Category]) {
+        for category in categories {
+            Interner::share(strings, &mut category.term);
+            if let Some(scheme) = &mut category.scheme {
+                Interner::share(strings, scheme);
+            }
+        }
+    }
+}
@@ -1,68 +1,151 @@
-use crate::model::{Category, Feed, Image, MediaCredit, MediaObject, MediaRating, MediaThumbnail, Person};
-use crate::parser::atom::handle_text;
-use crate::parser::util::{if_some_then, parse_npt};
-use crate::parser::ParseFeedResult;
+use crate::extensions::itunes::{ITunesCategory, ITunesOwner};
+use crate::model::{Category, Entry, Feed, Image, MediaCredit, MediaObject, MediaRating, MediaThumbnail, Person};
+use crate::parser::atom;
+use crate::parser::util::{if_some_then, parse_npt, resolve_uri};
+use crate::parser::{ParseFeedResult, Parser};
 use crate::xml::{Element, NS};
 use std::io::BufRead;
 use std::time::Duration;
 
-// Process <itunes> elements at channel level updating the Feed object as required
-pub(crate) fn handle_itunes_channel_element<R: BufRead>(element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+// Process <itunes> elements at channel level, recording them in the iTunes extension and updating the Feed object as required
+pub(crate) fn handle_itunes_channel_element<R: BufRead>(parser: &Parser, element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+    let mut itunes = feed.itunes.take().unwrap_or_default();
+
     match element.ns_and_tag() {
-        (Some(NS::Itunes), "image") => if_some_then(handle_image(element), |image| {
+        (Some(NS::Itunes), "image") => if_some_then(element.attr_value("href"), |href| {
             // Assign to feed logo if not already set
             if feed.logo.is_none() {
-                feed.logo = Some(image.image);
+                feed.logo = Some(Image::new(resolve_uri(href.clone(), element.xml_base.as_ref())));
             }
+            itunes.image = Some(href);
         }),
 
-        (Some(NS::Itunes), "category") => if_some_then(handle_category(element), |category| feed.categories.push(category)),
+        (Some(NS::Itunes), "category") => if_some_then(handle_category(element)?, |category| {
+            feed.categories.push(Category::new(&category.text));
+            itunes.categories.push(category);
+        }),
 
-        (Some(NS::Itunes), "explicit") => if_some_then(handle_explicit(element), |rating| {
+        (Some(NS::Itunes), "explicit") => if_some_then(element.child_as_text(), |text| {
             // Assign if not already set from media
             if feed.rating.is_none() {
-                feed.rating = Some(rating);
+                feed.rating = handle_explicit_rating(&text);
             }
+            itunes.explicit = parse_flag(&text);
+        }),
+
+        (Some(NS::Itunes), "author") => if_some_then(element.child_as_text(), |person| {
+            feed.authors.push(Person::new(&person));
+            itunes.author = Some(person);
         }),
 
-        (Some(NS::Itunes), "author") => if_some_then(element.child_as_text(), |person| feed.authors.push(Person::new(&person))),
-        (Some(NS::Itunes), "owner") => if_some_then(handle_owner(element)?, |owner| feed.contributors.push(owner)),
+        (Some(NS::Itunes), "owner") => {
+            let owner = handle_owner(element)?;
+            if let ITunesOwner {
+                name: Some(name),
+                email: Some(email),
+            } = &owner
+            {
+                feed.contributors.push(Person::new(name).email(email));
+            }
+            itunes.owner = Some(owner);
+        }
+
+        // Itunes summary is another variant of the description
+        (Some(NS::Itunes), "summary") => if_some_then(
+            parser.text_variant(&mut feed.description_variants, "itunes:summary", atom::handle_text(element)?),
+            |description| {
+                itunes.summary = Some(description.content.clone());
+                if feed.description.is_none() {
+                    feed.description = Some(description);
+                }
+            },
+        ),
+
+        (Some(NS::Itunes), "block") => itunes.block = element.child_as_text().and_then(|text| parse_flag(&text)),
+        (Some(NS::Itunes), "complete") => itunes.complete = element.child_as_text().and_then(|text| parse_flag(&text)),
+        (Some(NS::Itunes), "keywords") => itunes.keywords = handle_keywords(element),
+        (Some(NS::Itunes), "new-feed-url") => itunes.new_feed_url = element.child_as_text(),
+        (Some(NS::Itunes), "subtitle") => itunes.subtitle = element.child_as_text(),
+        (Some(NS::Itunes), "title") => itunes.title = element.child_as_text(),
+        (Some(NS::Itunes), "type") => itunes.show_type = element.child_as_text(),
 
         // Nothing required for unknown elements
         _ => {}
     }
 
+    feed.itunes = Some(itunes);
     Ok(())
 }
 
-// Process <itunes> elements at item level and turn them into something that looks like MediaRSS objects.
-pub(crate) fn handle_itunes_item_element<R: BufRead>(element: Element<R>, media_obj: &mut MediaObject) -> ParseFeedResult<()> {
+// Process <itunes> elements at item level, recording them in the iTunes extension and turning them into something that looks like MediaRSS objects.
+// "itunes:title" and "itunes:summary" are also variants of the entry title and summary
+pub(crate) fn handle_itunes_item_element<R: BufRead>(
+    parser: &Parser,
+    element: Element<R>,
+    entry: &mut Entry,
+    media_obj: &mut MediaObject,
+) -> ParseFeedResult<()> {
+    let mut itunes = entry.itunes.take().unwrap_or_default();
+
     match element.ns_and_tag() {
-        (Some(NS::Itunes), "title") => media_obj.title = handle_text(element)?,
+        (Some(NS::Itunes), "image") => if_some_then(element.attr_value("href"), |href| {
+            media_obj
+                .thumbnails
+                .push(MediaThumbnail::new(Image::new(resolve_uri(href.clone(), element.xml_base.as_ref()))));
+            itunes.image = Some(href);
+        }),
 
-        (Some(NS::Itunes), "image") => if_some_then(handle_image(element), |thumbnail| media_obj.thumbnails.push(thumbnail)),
+        (Some(NS::Itunes), "duration") => if_some_then(handle_duration(element), |duration| {
+            media_obj.duration = Some(duration);
+            itunes.duration = Some(duration);
+        }),
 
-        (Some(NS::Itunes), "duration") => if_some_then(handle_duration(element), |duration| media_obj.duration = Some(duration)),
+        (Some(NS::Itunes), "author") => if_some_then(element.child_as_text(), |author| {
+            media_obj.credits.push(MediaCredit::new(author.clone()));
+            itunes.author = Some(author);
+        }),
 
-        (Some(NS::Itunes), "author") => if_some_then(handle_author(element), |credit| media_obj.credits.push(credit)),
+        (Some(NS::Itunes), "title") => {
+            media_obj.title = parser.text_variant(&mut entry.title_variants, "itunes:title", atom::handle_text(element)?);
+            itunes.title = media_obj.title.as_ref().map(|title| title.content.clone());
+        }
 
-        (Some(NS::Itunes), "summary") => media_obj.description = handle_text(element)?,
+        (Some(NS::Itunes), "summary") => {
+            media_obj.description = parser.text_variant(&mut entry.summary_variants, "itunes:summary", atom::handle_text(element)?);
+            itunes.summary = media_obj.description.as_ref().map(|summary| summary.content.clone());
+        }
+
+        (Some(NS::Itunes), "block") => itunes.block = element.child_as_text().and_then(|text| parse_flag(&text)),
+        (Some(NS::Itunes), "episode") => itunes.episode = element.child_as_text().and_then(|text| text.trim().parse().ok()),
+        (Some(NS::Itunes), "episodeType") => itunes.episode_type = element.child_as_text(),
+        (Some(NS::Itunes), "explicit") => itunes.explicit = element.child_as_text().and_then(|text| parse_flag(&text)),
+        (Some(NS::Itunes), "keywords") => itunes.keywords = handle_keywords(element),
+        (Some(NS::Itunes), "season") => itunes.season = element.child_as_text().and_then(|text| text.trim().parse().ok()),
+        (Some(NS::Itunes), "subtitle") => itunes.subtitle = element.child_as_text(),
 
         // Nothing required for unknown elements
         _ => {}
     }
 
+    entry.itunes = Some(itunes);
     Ok(())
 }
 
-// Handles <itunes:author>
-fn handle_author<R: BufRead>(element: Element<R>) -> Option<MediaCredit> {
-    element.child_as_text().map(MediaCredit::new)
-}
+// Handles <itunes:category>, including any nested subcategories
+fn handle_category<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<ITunesCategory>> {
+    let mut category = match element.attr_value("text") {
+        Some(text) => ITunesCategory::new(&text),
+        None => return Ok(None),
+    };
 
-// Handles <itunes:category>
-fn handle_category<R: BufRead>(element: Element<R>) -> Option<Category> {
-    element.attr_value("text").map(|text| Category::new(&text))
+    for child in element.children() {
+        let child = child?;
+        if let (Some(NS::Itunes), "category") = child.ns_and_tag() {
+            if_some_then(handle_category(child)?, |subcategory| category.subcategories.push(subcategory));
+        }
+    }
+
+    Ok(Some(category))
 }
 
 // Handles <itunes:duration>
@@ -71,37 +154,44 @@ fn handle_duration<R: BufRead>(element: Element<R>) -> Option<Duration> {
 }
 
 // Handles <itunes:explicit> by mapping to {true|false} and wrapping in MediaRating instance
-fn handle_explicit<R: BufRead>(element: Element<R>) -> Option<MediaRating> {
-    element
-        .child_as_text()
-        .filter(|v| v.to_lowercase() == "true")
-        .map(|v| MediaRating::new(v).urn("itunes"))
+fn handle_explicit_rating(text: &str) -> Option<MediaRating> {
+    if text.to_lowercase() == "true" {
+        Some(MediaRating::new(text.to_owned()).urn("itunes"))
+    } else {
+        None
+    }
 }
 
-// Handles <itunes:image>
-fn handle_image<R: BufRead>(element: Element<R>) -> Option<MediaThumbnail> {
-    element.attr_value("href").map(|url| MediaThumbnail::new(Image::new(url)))
+// Handles <itunes:keywords>, a comma separated list
+fn handle_keywords<R: BufRead>(element: Element<R>) -> Vec<String> {
+    element.child_as_text().map_or_else(Vec::new, |text| {
+        text.split(',').map(str::trim).filter(|keyword| !keyword.is_empty()).map(String::from).collect()
+    })
 }
 
 // Handles <itunes:owner>
-fn handle_owner<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Person>> {
-    let mut email = None;
-    let mut name = None;
+fn handle_owner<R: BufRead>(element: Element<R>) -> ParseFeedResult<ITunesOwner> {
+    let mut owner = ITunesOwner::default();
 
     for child in element.children() {
         let child = child?;
         match child.ns_and_tag() {
-            (Some(NS::Itunes), "email") => email = child.child_as_text(),
-            (Some(NS::Itunes), "name") => name = child.child_as_text(),
+            (Some(NS::Itunes), "email") => owner.email = child.child_as_text(),
+            (Some(NS::Itunes), "name") => owner.name = child.child_as_text(),
 
             // Nothing required for unknown elements
             _ => {}
         }
     }
 
-    Ok(if let (Some(email), Some(name)) = (email, name) {
-        Some(Person::new(&name).email(&email))
-    } else {
-        None
-    })
+    Ok(owner)
+}
+
+// Parses the yes/no style values used by <itunes:explicit>, <itunes:block> and <itunes:complete>
+fn parse_flag(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "yes" | "true" | "explicit" => Some(true),
+        "no" | "false" | "clean" => Some(false),
+        _ => None,
+    }
 }
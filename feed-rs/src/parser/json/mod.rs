@@ -1,19 +1,27 @@
 use std::io::Read;
 
 use mime::Mime;
+use url::Url;
 
 use crate::model::{Category, Content, Entry, Feed, FeedType, Image, Link, Person, Text};
-use crate::parser::util::{if_some_then, timestamp_rfc3339_lenient};
-use crate::parser::{ParseFeedError, ParseFeedResult};
+use crate::parser::util::{if_some_then, parse_media_type, resolve_uri};
+use crate::parser::{ParseFeedError, ParseFeedResult, Parser};
+use crate::util::dates::TimestampFormat;
 
 #[cfg(test)]
 mod tests;
 
 /// Parses a JSON feed into our model
-pub(crate) fn parse<R: Read>(stream: R) -> ParseFeedResult<Feed> {
-    let parsed = serde_json::from_reader(stream);
+pub(crate) fn parse<R: Read>(parser: &Parser, mut stream: R, uri: Option<&str>) -> ParseFeedResult<Feed> {
+    // Read the whole document up front, which is faster for serde than reading incrementally and gives us the size for progress reporting
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer)?;
+
+    let parsed = serde_json::from_slice(&buffer);
     if let Ok(json_feed) = parsed {
-        Ok(convert(json_feed))
+        // Relative URLs are resolved against the source of the feed, if known
+        let base = uri.and_then(|uri| Url::parse(uri).ok());
+        convert(parser, json_feed, buffer.len() as u64, base.as_ref())
     } else {
         // Unable to parse the JSON
         Err(ParseFeedError::JsonSerde(parsed.err().unwrap()))
@@ -21,37 +29,68 @@ pub(crate) fn parse<R: Read>(stream: R) -> ParseFeedResult<Feed> {
 }
 
 // Convert the JSON Feed into our standard model
-fn convert(jf: JsonFeed) -> Feed {
+fn convert(parser: &Parser, jf: JsonFeed, bytes_consumed: u64, base: Option<&Url>) -> ParseFeedResult<Feed> {
     let mut feed = Feed::new(FeedType::JSON);
+    feed.version = jf.version.rsplit('/').next().filter(|version| !version.is_empty()).map(String::from);
+
+    // Convert feed level fields, limiting the length of the text as the XML reader does
+    feed.title = Some(Text::new(parser.limit_text(jf.title)?));
 
-    // Convert feed level fields
-    feed.title = Some(Text::new(jf.title));
+    if_some_then(jf.home_page_url, |uri| feed.links.push(Link::new(&uri, base)));
 
-    if_some_then(jf.home_page_url, |uri| feed.links.push(Link::new(&uri, None)));
+    if_some_then(jf.feed_url, |uri| {
+        let mut link = Link::new(&uri, base);
+        link.rel = Some("self".into());
+        feed.links.push(link);
+    });
+
+    // The next page of a paginated feed is a link, as for Atom paging (RFC 5005)
+    if_some_then(jf.next_url, |uri| {
+        let mut link = Link::new(&uri, base);
+        link.rel = Some("next".into());
+        feed.links.push(link);
+    });
 
-    if_some_then(jf.feed_url, |uri| feed.links.push(Link::new(&uri, None)));
+    // WebSub hubs are recorded as links, as they are in Atom and RSS
+    for hub in jf.hubs.unwrap_or_default() {
+        let mut link = Link::new(&hub.url, base);
+        link.rel = Some("hub".into());
+        feed.links.push(link);
+    }
 
-    if_some_then(jf.description, |text| feed.description = Some(Text::new(text)));
+    if_some_then(parser.limit_optional_text(jf.description)?, |text| feed.description = Some(Text::new(text)));
 
-    if_some_then(jf.icon, |uri| feed.logo = Some(Image::new(uri)));
+    if_some_then(jf.icon, |uri| feed.logo = Some(Image::new(resolve_uri(uri, base))));
 
-    if_some_then(jf.favicon, |uri| feed.icon = Some(Image::new(uri)));
+    if_some_then(jf.favicon, |uri| feed.icon = Some(Image::new(resolve_uri(uri, base))));
 
-    if_some_then(handle_person(jf.author), |person| feed.authors.push(person));
+    if_some_then(handle_person(parser, jf.author)?, |person| feed.authors.push(person));
 
     // Convert items within the JSON feed
-    jf.items.into_iter().for_each(|ji| {
-        feed.entries.push(handle_item(ji));
-    });
+    for (document_index, ji) in jf.items.into_iter().enumerate() {
+        let mut entry = handle_item(parser, ji, base)?;
+        entry.document_index = document_index;
+        if parser.entry_wanted(&entry) {
+            parser.intern_entry_strings(&mut entry);
+            feed.entries.push(entry);
+        }
+        parser.entry_parsed(bytes_consumed, feed.entries.len());
+        if parser.is_cancelled() {
+            return Err(ParseFeedError::Cancelled(Box::new(feed)));
+        }
+        if parser.entry_limit_reached(feed.entries.len()) {
+            break;
+        }
+    }
 
-    feed
+    Ok(feed)
 }
 
 // Handles an attachment
-fn handle_attachment(attachment: JsonAttachment) -> Link {
-    let mut link = Link::new(&attachment.url, None);
+fn handle_attachment(attachment: JsonAttachment, base: Option<&Url>) -> Link {
+    let mut link = Link::new(&attachment.url, base);
 
-    link.media_type = Some(attachment.mime_type);
+    link.media_type = parse_media_type(&attachment.mime_type);
     link.title = attachment.title;
     link.length = attachment.size_in_bytes;
 
@@ -61,7 +100,7 @@ fn handle_attachment(attachment: JsonAttachment) -> Link {
 // Handles HTML or plain text content
 fn handle_content(content: Option<String>, content_type: Mime) -> Option<Content> {
     content.map(|body| Content {
-        length: Some(body.as_bytes().len() as u64),
+        length: Some(body.len() as u64),
         body: Some(body.trim().into()),
         content_type,
         ..Default::default()
@@ -69,22 +108,22 @@ fn handle_content(content: Option<String>, content_type: Mime) -> Option<Content
 }
 
 // Converts a JSON feed item into our model
-fn handle_item(ji: JsonItem) -> Entry {
+fn handle_item(parser: &Parser, ji: JsonItem, base: Option<&Url>) -> ParseFeedResult<Entry> {
     let mut entry = Entry {
         id: ji.id,
         ..Default::default()
     };
 
-    if_some_then(ji.url, |uri| entry.links.push(Link::new(uri, None)));
+    if_some_then(ji.url, |uri| entry.links.push(Link::new(uri, base)));
 
-    if_some_then(ji.external_url, |uri| entry.links.push(Link::new(uri, None)));
+    if_some_then(ji.external_url, |uri| entry.links.push(Link::new(uri, base)));
 
-    if_some_then(ji.title, |text| entry.title = Some(Text::new(text)));
+    if_some_then(parser.limit_optional_text(ji.title)?, |text| entry.title = Some(Text::new(text)));
 
     // Content HTML, content text and summary are mapped across to our model with the preference toward HTML and explicit summary fields
-    entry.content = handle_content(ji.content_html, mime::TEXT_HTML);
-    entry.summary = ji.summary.map(Text::new);
-    if let Some(content_text) = handle_content(ji.content_text, mime::TEXT_PLAIN) {
+    entry.content = handle_content(parser.limit_optional_text(ji.content_html)?, mime::TEXT_HTML);
+    entry.summary = parser.limit_optional_text(ji.summary)?.map(Text::new);
+    if let Some(content_text) = handle_content(parser.limit_optional_text(ji.content_text)?, mime::TEXT_PLAIN) {
         // If we don't have HTML content, use the text content as the entry content
         // otherwise, if the summary was not provided, we push the text there
 
@@ -95,36 +134,43 @@ fn handle_item(ji: JsonItem) -> Entry {
         }
     }
 
-    if_some_then(ji.date_published, |published| entry.published = timestamp_rfc3339_lenient(&published));
+    if let Some(published) = ji.date_published {
+        entry.published = parser.parse_timestamp(&published, TimestampFormat::Rfc3339)?;
+    }
 
-    if_some_then(ji.date_modified, |modified| entry.updated = timestamp_rfc3339_lenient(&modified));
+    if let Some(modified) = ji.date_modified {
+        entry.updated = parser.parse_timestamp(&modified, TimestampFormat::Rfc3339)?;
+    }
 
-    if_some_then(handle_person(ji.author), |person| entry.authors.push(person));
+    if_some_then(handle_person(parser, ji.author)?, |person| entry.authors.push(person));
 
-    if_some_then(ji.tags, |tags| {
-        tags.into_iter().map(|t| Category::new(&t)).for_each(|category| entry.categories.push(category))
-    });
+    for tag in ji.tags.unwrap_or_default() {
+        entry.categories.push(Category::new(&parser.limit_text(tag)?));
+    }
 
     if_some_then(ji.attachments, |attachments| {
-        attachments.into_iter().map(handle_attachment).for_each(|link| entry.links.push(link))
+        attachments
+            .into_iter()
+            .map(|attachment| handle_attachment(attachment, base))
+            .for_each(|link| entry.links.push(link))
     });
 
-    entry
+    Ok(entry)
 }
 
 // Converts an author object into our model
-fn handle_person(author: Option<JsonAuthor>) -> Option<Person> {
+fn handle_person(parser: &Parser, author: Option<JsonAuthor>) -> ParseFeedResult<Option<Person>> {
     if let Some(ja) = author {
-        if ja.name.is_some() {
-            let mut person = Person::new(&ja.name.unwrap());
+        if let Some(name) = ja.name {
+            let mut person = Person::new(&parser.limit_text(name)?);
 
             person.uri = ja.url;
 
-            return Some(person);
+            return Ok(Some(person));
         }
     }
 
-    None
+    Ok(None)
 }
 
 #[derive(Debug, Deserialize)]
@@ -133,10 +179,12 @@ struct JsonFeed {
     pub title: String,
     pub home_page_url: Option<String>,
     pub feed_url: Option<String>,
+    pub next_url: Option<String>,
     pub description: Option<String>,
     pub icon: Option<String>,
     pub favicon: Option<String>,
     pub author: Option<JsonAuthor>,
+    pub hubs: Option<Vec<JsonHub>>,
     pub items: Vec<JsonItem>,
 }
 
@@ -154,6 +202,14 @@ struct JsonAuthor {
     url: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct JsonHub {
+    #[allow(dead_code)]
+    #[serde(rename = "type")]
+    hub_type: String,
+    url: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct JsonItem {
     pub id: String,
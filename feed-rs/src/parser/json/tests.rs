@@ -10,21 +10,21 @@ fn test_example_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::JSON)
+    let expected = Feed::new(FeedType::JSON).version("1")
         .id(&actual.id)                 // not in test content
         .updated(actual.updated)        // not in test content
         .title(Text::new("Daring Fireball".into()))
         .link(Link::new("https://daringfireball.net/", None))
-        .link(Link::new("https://daringfireball.net/feeds/json", None))
+        .link(Link::new("https://daringfireball.net/feeds/json", None).rel("self"))
         .author(Person::new("John Gruber")
-            .uri("https://twitter.com/gruber".into()))
+            .uri("https://twitter.com/gruber"))
         .logo(Image::new("https://daringfireball.net/graphics/apple-touch-icon.png".into()))
         .icon(Image::new("https://daringfireball.net/graphics/favicon-64.png".into()))
         .entry(Entry::default()
             .title(Text::new("How Jeff Bezos’s iPhone X Was Hacked".into()))
             .published_rfc3339("2020-01-24T23:46:57Z")
             .updated_rfc3339("2020-01-24T23:46:57Z")
-            .id("https://daringfireball.net/linked/2020/01/24/bezos-iphone-x".into())
+            .id("https://daringfireball.net/linked/2020/01/24/bezos-iphone-x")
             .link(Link::new("https://daringfireball.net/linked/2020/01/24/bezos-iphone-x", None))
             .link(Link::new("https://www.nytimes.com/2020/01/22/technology/jeff-bezos-hack-iphone.html", None))
             .author(Person::new("John Gruber"))
@@ -36,7 +36,7 @@ fn test_example_1() {
             .title(Text::new("Instagram for Windows 95".into()))
             .published_rfc3339("2020-01-21T01:07:00Z")
             .updated_rfc3339("2020-01-21T20:58:36Z")
-            .id("https://daringfireball.net/linked/2020/01/20/instagram-for-win95".into())
+            .id("https://daringfireball.net/linked/2020/01/20/instagram-for-win95")
             .link(Link::new("https://daringfireball.net/linked/2020/01/20/instagram-for-win95", None))
             .link(Link::new("https://www.behance.net/gallery/41023081/Instagram-for-Win95?utm_source=morning_brew", None))
             .author(Person::new("John Gruber"))
@@ -57,18 +57,18 @@ fn test_spec_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::JSON)
+    let expected = Feed::new(FeedType::JSON).version("1")
         .id(&actual.id)                 // not in test content
         .updated(actual.updated)        // not in test content
         .title(Text::new("JSON Feed".into()))
         .description(Text::new("JSON Feed is a pragmatic syndication format for blogs, microblogs, and other time-based content.".into()))
         .link(Link::new("https://jsonfeed.org/", None))
-        .link(Link::new("https://jsonfeed.org/feed.json", None))
+        .link(Link::new("https://jsonfeed.org/feed.json", None).rel("self"))
         .author(Person::new("Brent Simmons and Manton Reece")
-            .uri("https://jsonfeed.org/".into()))
+            .uri("https://jsonfeed.org/"))
         .entry(Entry::default()
             .updated(actual.entries[0].updated)             // not in test content
-            .id("https://jsonfeed.org/2017/05/17/announcing_json_feed".into())
+            .id("https://jsonfeed.org/2017/05/17/announcing_json_feed")
             .title(Text::new("Announcing JSON Feed".into()))
             .published_rfc3339("2017-05-17T08:02:12-07:00")
             .link(Link::new("https://jsonfeed.org/2017/05/17/announcing_json_feed", None))
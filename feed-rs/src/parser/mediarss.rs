@@ -115,10 +115,12 @@ fn handle_media_content<R: BufRead>(element: Element<R>, media_obj: &mut MediaOb
 
             "type" => if_ok_then_some(attr.value.parse::<Mime>(), |v| content.content_type = v),
 
+            "medium" => content.medium = Some(attr.value.trim().to_lowercase()).filter(|medium| !medium.is_empty()),
+
             "width" => if_ok_then_some(attr.value.parse::<u32>(), |v| content.width = v),
             "height" => if_ok_then_some(attr.value.parse::<u32>(), |v| content.height = v),
 
-            "fileSize" => if_ok_then_some(attr.value.parse::<u64>(), |v| content.size = v),
+            "fileSize" => content.size = util::parse_length(&attr.value),
 
             "duration" => if_ok_then_some(attr.value.parse::<u64>(), |v| content.duration = v.map(Duration::from_secs)),
 
@@ -132,16 +134,8 @@ fn handle_media_content<R: BufRead>(element: Element<R>, media_obj: &mut MediaOb
             (Some(NS::MediaRSS), "rating") => content.rating = handle_media_rating(child),
 
             // These elements are modelled as fields on the parent MediaObject, but only set if the parent field does not already have a value
-            (Some(NS::MediaRSS), "title") => {
-                if media_obj.title.is_none() {
-                    media_obj.title = handle_text(child)?
-                }
-            }
-            (Some(NS::MediaRSS), "description") => {
-                if media_obj.description.is_none() {
-                    media_obj.description = handle_text(child)?
-                }
-            }
+            (Some(NS::MediaRSS), "title") if media_obj.title.is_none() => media_obj.title = handle_text(child)?,
+            (Some(NS::MediaRSS), "description") if media_obj.description.is_none() => media_obj.description = handle_text(child)?,
 
             // These elements are accumulated in the corresponding field of the parent MediaObject
             (Some(NS::MediaRSS), "text") => if_some_then(handle_media_text(child), |text| media_obj.texts.push(text)),
@@ -209,6 +203,7 @@ fn handle_media_text<R: BufRead>(element: Element<R>) -> Option<MediaText> {
         // Parse out the actual text of this element
         let mut text = Text::new(t);
         text.content_type = mime.map_or(mime::TEXT_PLAIN, |m| m);
+        text.lang = element.xml_lang.clone();
         let mut media_text = MediaText::new(text);
 
         // Add the time boundaries if we found them
@@ -242,7 +237,7 @@ fn handle_media_thumbnail<R: BufRead>(element: Element<R>) -> Option<MediaThumbn
 
     // We need url at least to assemble the image
     if let Some(url) = url {
-        let mut image = Image::new(url);
+        let mut image = Image::new(util::resolve_uri(url, element.xml_base.as_ref()));
         image.width = width;
         image.height = height;
 
@@ -256,7 +251,7 @@ fn handle_media_thumbnail<R: BufRead>(element: Element<R>) -> Option<MediaThumbn
 }
 
 // Handles a title or description element
-fn handle_text<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Text>> {
+pub(crate) fn handle_text<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Text>> {
     // Find type, defaulting to "plain" if not present
     let type_attr = element.attributes.iter().find(|a| &a.name == "type").map_or("plain", |a| a.value.as_str());
 
@@ -273,6 +268,7 @@ fn handle_text<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Text>>
         .map(|content| {
             let mut text = Text::new(content);
             text.content_type = mime;
+            text.lang = element.xml_lang.clone();
             Some(text)
         })
         // Need the text for a text element
@@ -1,29 +1,61 @@
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
 use std::hash::Hasher;
 use std::io::{BufRead, BufReader, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use siphasher::sip128::{Hasher128, SipHasher};
 
+use crate::extensions::{ExtensionElement, ExtensionMap};
 use crate::model;
+use crate::util::dates::TimestampFormat;
+use crate::util::encoding;
+use crate::util::limit::{Flag, LimitedReader};
 use crate::xml;
 
+pub use crate::xml::Position;
+
+#[cfg(feature = "tokio")]
+pub use asynchronous::parse_async;
+#[cfg(feature = "intern")]
+pub use interner::Interner;
+#[cfg(feature = "rayon")]
+pub use parallel::parse_many;
+
+#[cfg(feature = "tokio")]
+mod asynchronous;
 mod atom;
+#[cfg(feature = "intern")]
+mod interner;
 mod json;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod rss0;
 mod rss1;
 mod rss2;
 
+pub(crate) mod dublincore;
+pub(crate) mod geo;
 pub(crate) mod itunes;
 pub(crate) mod mediarss;
+pub(crate) mod opml;
+pub(crate) mod podcast;
+pub(crate) mod syndication;
+pub(crate) mod threading;
 pub(crate) mod util;
 
+#[cfg(test)]
+mod tests;
+
 pub type ParseFeedResult<T> = std::result::Result<T, ParseFeedError>;
 
 /// An error returned when parsing a feed from a source fails
 #[derive(Debug)]
 pub enum ParseFeedError {
-    // TODO add line number/position
+    // The source is not a feed, or does not follow its spec (see ParseErrorKind)
     ParseError(ParseErrorKind),
     // IO error
     IoError(std::io::Error),
@@ -31,6 +63,26 @@ pub enum ParseFeedError {
     JsonSerde(serde_json::error::Error),
     // Underlying issue with XML (poorly formatted etc)
     XmlReader(xml::XmlError),
+    /// The parse was cancelled through a `CancellationToken`; the entries parsed up to that point are returned
+    ///
+    /// When streaming with `parse_entries()` the entries have already been returned, so the feed has none.
+    Cancelled(Box<model::Feed>),
+}
+
+impl ParseFeedError {
+    /// The position in the source at which the error was found, if known (i.e. for malformed XML or JSON)
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            ParseFeedError::XmlReader(xml::XmlError::Parser { position, .. })
+            | ParseFeedError::XmlReader(xml::XmlError::ExternalEntity { position })
+            | ParseFeedError::XmlReader(xml::XmlError::LimitExceeded { position, .. }) => *position,
+            ParseFeedError::JsonSerde(je) if je.line() > 0 => Some(Position {
+                line: je.line() as u64,
+                column: je.column() as u64,
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl From<serde_json::error::Error> for ParseFeedError {
@@ -58,6 +110,7 @@ impl fmt::Display for ParseFeedError {
             ParseFeedError::IoError(ie) => write!(f, "unable to read feed: {}", ie),
             ParseFeedError::JsonSerde(je) => write!(f, "unable to parse JSON: {}", je),
             ParseFeedError::XmlReader(xe) => write!(f, "unable to parse XML: {}", xe),
+            ParseFeedError::Cancelled(feed) => write!(f, "parsing cancelled after {} entries", feed.entries.len()),
         }
     }
 }
@@ -82,6 +135,14 @@ pub enum ParseErrorKind {
     UnknownMimeType(String),
     /// Required content within the source was not found e.g. the XML child text element for a "content" element
     MissingContent(&'static str),
+    /// A timestamp could not be parsed as the format required by the spec (only returned by a strict parser)
+    InvalidTimestamp(String),
+    /// The source is larger than the limit set with `Builder::max_input_bytes()` (only returned by a strict parser)
+    InputTooLarge,
+    /// The HTTP response passed to `parse_response()` does not have a successful status, so its body is not a feed
+    UnsuccessfulStatus(u16),
+    /// A field of a JSON Feed is longer than the limit set with `Builder::max_text_length()` (only returned by a strict parser)
+    TextTooLong,
 }
 
 impl fmt::Display for ParseErrorKind {
@@ -90,6 +151,40 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::NoFeedRoot => f.write_str("no root element"),
             ParseErrorKind::UnknownMimeType(mime) => write!(f, "unsupported content type {}", mime),
             ParseErrorKind::MissingContent(elem) => write!(f, "missing content element {}", elem),
+            ParseErrorKind::InvalidTimestamp(text) => write!(f, "invalid timestamp {}", text),
+            ParseErrorKind::InputTooLarge => f.write_str("input exceeds the size limit"),
+            ParseErrorKind::UnsuccessfulStatus(status) => write!(f, "unsuccessful HTTP status {}", status),
+            ParseErrorKind::TextTooLong => f.write_str("text exceeds the length limit"),
+        }
+    }
+}
+
+/// A problem with the source that a lenient parser worked around, reported to the handler registered with `Builder::warning_handler()`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Warning {
+    /// A timestamp could not be parsed, so was ignored
+    InvalidTimestamp(String),
+    /// Content required by the spec was not found (e.g. "entry.id" for an Atom entry without an ID, which is generated instead)
+    MissingContent(&'static str),
+    /// An element from another namespace that the parser does not understand, which was recorded in the extensions of the feed or entry
+    UnknownElement { namespace: String, element: String },
+    /// More than one entry in the feed has this ID
+    DuplicateId(String),
+    /// The language of the feed is not a well-formed language tag, so `Feed::language_tag()` is not available
+    InvalidLanguage(String),
+    /// This number of bytes before the root element of the feed (e.g. an HTML page it was wrapped in) were skipped (see `Builder::salvage()`)
+    SkippedContent(usize),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::InvalidTimestamp(text) => write!(f, "invalid timestamp {}", text),
+            Warning::MissingContent(elem) => write!(f, "missing content element {}", elem),
+            Warning::UnknownElement { namespace, element } => write!(f, "unknown element {} in namespace {}", element, namespace),
+            Warning::DuplicateId(id) => write!(f, "duplicate entry ID {}", id),
+            Warning::InvalidLanguage(language) => write!(f, "invalid language {}", language),
+            Warning::SkippedContent(bytes) => write!(f, "skipped {} bytes before the feed", bytes),
         }
     }
 }
@@ -103,13 +198,17 @@ pub fn parse<R: Read>(source: R) -> ParseFeedResult<model::Feed> {
 ///
 /// # Arguments
 ///
-/// * `input` - A source of content such as a string, file etc.
-/// * `uri` - Source of the content, used to resolve relative URLs in XML based feeds
+/// * `source` - A source of content such as a byte slice, file etc.
+/// * `uri` - Source of the content, used to resolve relative URLs
+///
+/// Byte order marks are handled, with UTF-16 sources transcoded to UTF-8. Whitespace or other garbage before the XML declaration
+/// (such as the blank line or warning emitted by a misconfigured PHP script) is skipped, if it is within the first read from the source.
 ///
 /// NOTE: feed-rs uses the encoding attribute in the XML prolog to decode content.
 /// HTTP libraries (such as reqwest) provide a `text()` method which applies the content-encoding header and decodes the source into UTF-8.
 /// This then causes feed-rs to fail when it attempts to interpret the UTF-8 stream as a different character set.
-/// Instead, pass the raw, encoded source to feed-rs e.g. the `.bytes()` method if using reqwest.
+/// Instead, pass the raw, encoded source to feed-rs e.g. the `.bytes()` method if using reqwest, and the charset from the `Content-Type`
+/// header (if any) to `parse_with_charset()`.
 ///
 /// # Examples
 ///
@@ -131,39 +230,1481 @@ pub fn parse<R: Read>(source: R) -> ParseFeedResult<model::Feed> {
 ///
 /// ```
 pub fn parse_with_uri<R: Read>(source: R, uri: Option<&str>) -> ParseFeedResult<model::Feed> {
-    // Buffer the reader for performance (e.g. when streaming from a network) and so we can peek to determine the type of content
-    let mut input = BufReader::new(source);
+    Builder::new().build().parse_with_uri(source, uri)
+}
 
-    // Determine whether this is XML or JSON and call the appropriate parser
-    input.fill_buf()?;
-    let first_char = input.buffer().iter().find(|b| **b == b'<' || **b == b'{').map(|b| *b as char);
-    let result = match first_char {
-        Some('<') => parse_xml(input, uri),
+/// Parse the input, decoding it with the character set from an external source such as the HTTP `Content-Type` header
+///
+/// The charset may be a label (e.g. "windows-1251") or a content type with a charset parameter (e.g. "application/rss+xml; charset=Shift_JIS").
+/// It takes precedence over the encoding declared in the XML prolog, though not over a byte order mark. Unknown charsets are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// let xml = b"<rss version=\"2.0\"><channel><title>Caf\xe9</title></channel></rss>";
+/// let feed = parser::parse_with_charset(&xml[..], None, "text/xml; charset=ISO-8859-1").unwrap();
+/// assert_eq!(feed.title.unwrap().content, "Café");
+/// ```
+pub fn parse_with_charset<R: Read>(source: R, uri: Option<&str>, charset: &str) -> ParseFeedResult<model::Feed> {
+    Builder::new().build().parse_with_charset(source, uri, charset)
+}
 
-        Some('{') => parse_json(input),
+/// Parse the body of an HTTP response, using its headers to guide the parser
+///
+/// This is for clients that make the request themselves (e.g. with an async HTTP client), and pass the status, headers and body of
+/// the response. The headers are given as name and value pairs, with names matched case-insensitively:
+/// * `Content-Type`: the charset decodes the body, as for `parse_with_charset()`, and media types which cannot be a feed (images,
+///   audio and video) are rejected with `ParseErrorKind::UnknownMimeType` rather than parsed. An XML or JSON media type (e.g.
+///   `application/rss+xml` or `application/feed+json`) is looked for first when detecting the format of the body.
+/// * `Content-Language`: the language of a feed which does not declare one (the first, if a list is given), applied before the
+///   language is checked and the namespace handlers run
+/// * `Content-Location`: the URI relative links are resolved against, itself resolved against the parser's `Builder::base_uri()`
+///
+/// A status outside of the 2xx range is returned as `ParseErrorKind::UnsuccessfulStatus`, since the body is not the feed.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// let body = b"<rss version=\"2.0\"><channel><title>Caf\xe9</title></channel></rss>";
+/// let headers = vec![("content-type", "application/rss+xml; charset=ISO-8859-1"), ("Content-Language", "fr")];
+/// let feed = parser::parse_response(200, headers, &body[..]).unwrap();
+/// assert_eq!(feed.title.unwrap().content, "Café");
+/// assert_eq!(feed.language.as_deref(), Some("fr"));
+/// ```
+pub fn parse_response<'a, H, R>(status: u16, headers: H, body: R) -> ParseFeedResult<model::Feed>
+where
+    H: IntoIterator<Item = (&'a str, &'a str)>,
+    R: Read,
+{
+    Builder::new().build().parse_response(status, headers, body)
+}
 
-        _ => Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
-    };
+/// Parses the entries of a feed one at a time, rather than collecting them in to a `Feed`
+///
+/// This keeps memory use flat for very large feeds (e.g. archives with tens of thousands of items), as each entry is parsed as the
+/// iterator is advanced. The feed level elements are skipped. JSON Feeds are parsed in full before the first entry is returned.
+/// Entries are returned in document order, so the `rdf:Seq` ordering of an RSS 1.0 channel is not applied. The language of the
+/// feed is only inherited by the text of entries which follow it in the document.
+///
+/// A failure ends the iteration, with the error returned as the last item.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// let xml = r#"<rss version="2.0"><channel><item><title>One</title></item><item><title>Two</title></item></channel></rss>"#;
+/// let titles: Vec<String> = parser::parse_entries(xml.as_bytes())
+///     .map(|entry| entry.unwrap().title.unwrap().content)
+///     .collect();
+/// assert_eq!(titles, vec!["One", "Two"]);
+/// ```
+pub fn parse_entries<R: Read>(source: R) -> Entries<R> {
+    Builder::new().build().parse_entries(source)
+}
 
-    // Post processing as required
-    if let Ok(mut feed) = result {
-        assign_missing_ids(&mut feed, uri);
+/// Determines the format of a feed without parsing it
+///
+/// Only the root element (or for JSON, the presence of the JSON Feed version) is examined, so this is cheap enough to triage
+/// large numbers of documents. `None` is returned if the source is not a feed that `parse()` would recognise.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::model::FeedType;
+/// use feed_rs::parser;
+/// assert_eq!(parser::detect(br#"<rss version="2.0"><channel></channel></rss>"#), Some(FeedType::RSS2));
+/// assert_eq!(parser::detect(b"<html></html>"), None);
+/// ```
+pub fn detect(source: &[u8]) -> Option<model::FeedType> {
+    let source = &source[encoding::declaration_offset(source)..];
+    match source.iter().find(|b| **b == b'<' || **b == b'{') {
+        Some(b'<') => {
+            let element_source = xml::ElementSource::new(source, None, &xml::XmlConfig::default()).ok()?;
+            let root = element_source.root().ok()??;
+            root_feed_type(&root)
+        }
 
-        Ok(feed)
-    } else {
+        // JSON Feeds identify themselves with a version URL
+        Some(b'{') => {
+            let marker = b"https://jsonfeed.org/version/";
+            if source.windows(marker.len()).any(|window| window == marker) {
+                Some(model::FeedType::JSON)
+            } else {
+                None
+            }
+        }
+
+        _ => None,
+    }
+}
+
+/// Callback invoked as entries are parsed (see `Builder::progress_handler()`)
+pub type ProgressHandler = dyn Fn(&Progress) + Send + Sync;
+
+/// Progress through the source, reported to the handler registered with `Builder::progress_handler()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// Number of bytes consumed from the source so far
+    pub bytes_consumed: u64,
+    /// Number of entries parsed so far
+    pub entries_emitted: usize,
+}
+
+// Versions of RSS parsed as RSS 0.9x (0.93 and 0.94 were never finalised, but are found in the wild)
+const RSS0_VERSIONS: &[&str] = &["0.91", "0.92", "0.93", "0.94"];
+
+// Determines the format of a feed from its root element, or None if it is not one we recognise
+fn root_feed_type<R: BufRead>(root: &xml::Element<R>) -> Option<model::FeedType> {
+    let version = root.attr_value("version").map(|version| version.trim().to_owned());
+    match (root.name.as_str(), version.as_deref()) {
+        ("feed", _) | ("entry", _) => Some(model::FeedType::Atom),
+        ("rss", Some("2.0")) => Some(model::FeedType::RSS2),
+        ("rss", Some(version)) if RSS0_VERSIONS.contains(&version) => Some(model::FeedType::RSS0),
+        ("RDF", _) => Some(model::FeedType::RSS1),
+        _ => None,
+    }
+}
+
+// Namespaces of the feed formats themselves, whose unknown elements are not recorded as extensions
+const FORMAT_NAMESPACES: &[&str] = &[
+    "http://backend.userland.com/rss2",
+    "http://my.netscape.com/rdf/simple/0.9/",
+    "http://purl.org/atom/ns#",
+    "http://purl.org/rss/1.0/",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+    "http://www.w3.org/2005/Atom",
+];
+
+/// Callback invoked with each problem a lenient parser works around (see `Builder::warning_handler()`)
+pub type WarningHandler = dyn Fn(&Warning) + Send + Sync;
+
+/// Custom parsing for timestamps, returning `None` to fall back to the built in parsing (see `Builder::timestamp_parser()`)
+pub type TimestampParser = dyn Fn(&str) -> Option<DateTime<Utc>> + Send + Sync;
+
+/// Decides whether an entry is kept once it has been parsed (see `Builder::entry_filter()`)
+pub type EntryFilter = dyn Fn(&model::Entry) -> bool + Send + Sync;
+
+/// Generates the ID of a feed or entry that does not have one from its links, title and the source URI (see `Builder::id_generator()`)
+pub type IdGenerator = dyn Fn(&[model::Link], Option<&model::Text>, Option<&str>) -> String + Send + Sync;
+
+/// Callback invoked with the path and XML events of elements the parser does not understand (see `Builder::unknown_element_handler()`)
+pub type UnknownElementHandler = dyn Fn(&[String], &[XmlEvent]) + Send + Sync;
+
+/// Populates custom data from the elements of a namespace the parser does not model (see `Builder::namespace_handler()`)
+///
+/// Elements from namespaces without built-in support are recorded in the `extensions` of the feed or entry they were found in. Once
+/// the feed (or, when streaming, each entry) has been parsed, the elements recorded for the handler's namespace are passed to it in
+/// document order. Those it handles are removed from the extensions, while the rest are left in place.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::extensions::ExtensionElement;
+/// use feed_rs::model::Entry;
+/// use feed_rs::parser::{self, NamespaceHandler};
+///
+/// // Maps <my:rating> on to the categories of the entry
+/// struct RatingHandler;
+///
+/// impl NamespaceHandler for RatingHandler {
+///     fn namespace(&self) -> &str {
+///         "http://example.com/rating"
+///     }
+///
+///     fn handle_entry_element(&self, element: &ExtensionElement, entry: &mut Entry) -> bool {
+///         match (element.name.as_str(), &element.value) {
+///             ("rating", Some(rating)) => {
+///                 entry.categories.push(feed_rs::model::Category::new(rating));
+///                 true
+///             }
+///             _ => false,
+///         }
+///     }
+/// }
+///
+/// let xml = r#"<rss version="2.0" xmlns:my="http://example.com/rating"><channel><item><my:rating>5</my:rating></item></channel></rss>"#;
+/// let feed = parser::Builder::new().namespace_handler(RatingHandler).build().parse(xml.as_bytes()).unwrap();
+/// assert_eq!(&*feed.entries[0].categories[0].term, "5");
+/// assert!(feed.entries[0].extensions.is_empty());
+/// ```
+pub trait NamespaceHandler: Send + Sync {
+    /// The URI of the namespace whose elements are passed to this handler
+    fn namespace(&self) -> &str;
+
+    /// Handles an element of the namespace found at the feed (or channel) level, returning true if it should be removed from the
+    /// extensions of the feed
+    fn handle_feed_element(&self, _element: &ExtensionElement, _feed: &mut model::Feed) -> bool {
+        false
+    }
+
+    /// Handles an element of the namespace found in an entry, returning true if it should be removed from the extensions of the entry
+    fn handle_entry_element(&self, _element: &ExtensionElement, _entry: &mut model::Entry) -> bool {
+        false
+    }
+}
+
+/// A raw XML event from an element the parser does not understand
+///
+/// Element names have their namespace prefix removed, and text has been unescaped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum XmlEvent {
+    /// An element start tag with its attributes
+    Start { name: String, attributes: Vec<(String, String)> },
+    /// Text or CDATA content
+    Text(String),
+    /// An element end tag
+    End { name: String },
+}
+
+impl XmlEvent {
+    // Converts the internal XML event into the public representation
+    fn from_xml(event: &xml::XmlEvent) -> XmlEvent {
+        match event {
+            xml::XmlEvent::Start { name, attributes, .. } => XmlEvent::Start {
+                name: name.clone(),
+                attributes: attributes.iter().map(|nv| (nv.name.clone(), nv.value.clone())).collect(),
+            },
+            xml::XmlEvent::Text(text) => XmlEvent::Text(text.clone()),
+            xml::XmlEvent::End { name } => XmlEvent::End { name: name.clone() },
+        }
+    }
+}
+
+/// How entries that share an ID are treated (see `Builder::duplicate_ids()`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DuplicateIds {
+    /// Every entry is kept, so the IDs may not be unique (the default)
+    KeepAll,
+    /// Only the first entry with each ID is kept
+    KeepFirst,
+    /// Only the last entry with each ID is kept, in its place in the document
+    KeepLast,
+    /// Every entry is kept, with the second and later entries with an ID given a suffix to make it unique (e.g. "urn:1-2")
+    Rename,
+}
+
+/// Where the "pubDate" of an RSS item is recorded (see `Builder::pub_date()`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PubDate {
+    /// `Entry::published`, as the spec describes it as when the item was published (the default)
+    Published,
+    /// `Entry::updated`, for feeds which change the date when an item is edited
+    Updated,
+    /// Both `Entry::published` and `Entry::updated`
+    Both,
+}
+
+/// Requests cancellation of in-progress parses (see `Builder::cancellation_token()`)
+///
+/// Tokens are cheap to clone, and all clones share the same state so one can be handed to the parser while another is kept by the caller.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new token, initially not cancelled
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Requests that any parse using this token stops at the next entry boundary
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// True if `cancel()` has been called on this token or one of its clones
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// What is known about a source from outside the document (e.g. the headers of an HTTP response)
+#[derive(Default)]
+struct SourceHints<'a> {
+    // The character set to decode the source with, rather than detecting it
+    encoding: Option<&'static encoding_rs::Encoding>,
+    // The format of the source, looked for first when detecting it
+    format: Option<DeclaredFormat>,
+    // The language of a feed which does not declare one
+    language: Option<&'a str>,
+}
+
+// The format declared by the media type of a source
+#[derive(Clone, Copy)]
+enum DeclaredFormat {
+    Xml,
+    Json,
+}
+
+impl DeclaredFormat {
+    // Media types that are certainly not a feed are rejected, while the others are left to the detection of the format
+    fn for_media_type(media_type: &mime::Mime) -> ParseFeedResult<Option<DeclaredFormat>> {
+        if media_type.type_() == mime::IMAGE || media_type.type_() == mime::AUDIO || media_type.type_() == mime::VIDEO {
+            return Err(ParseFeedError::ParseError(ParseErrorKind::UnknownMimeType(media_type.essence_str().to_owned())));
+        }
+
+        // Structured syntax suffixes name the format of the specific types (e.g. "application/rss+xml" or "application/feed+json")
+        let syntax = media_type.suffix().unwrap_or_else(|| media_type.subtype());
+        Ok(if syntax == mime::XML {
+            Some(DeclaredFormat::Xml)
+        } else if syntax == mime::JSON {
+            Some(DeclaredFormat::Json)
+        } else {
+            None
+        })
+    }
+}
+
+/// Parser for the supported feed formats, configured through a `Builder`
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// let parser = parser::Builder::new()
+///     .progress_handler(|progress| println!("parsed {} entries", progress.entries_emitted))
+///     .build();
+/// let feed = parser.parse(r#"<rss version="2.0"><channel><item><title>Entry</title></item></channel></rss>"#.as_bytes()).unwrap();
+/// assert_eq!(feed.entries.len(), 1);
+/// ```
+pub struct Parser {
+    progress_handler: Option<Box<ProgressHandler>>,
+    warning_handler: Option<Box<WarningHandler>>,
+    unknown_element_handler: Option<Box<UnknownElementHandler>>,
+    namespace_handlers: Vec<Box<dyn NamespaceHandler>>,
+    timestamp_parser: Option<Box<TimestampParser>>,
+    id_generator: Option<Box<IdGenerator>>,
+    entry_filter: Option<Box<EntryFilter>>,
+    since: Option<DateTime<Utc>>,
+    cancellation_token: Option<CancellationToken>,
+    read_buffer_capacity: usize,
+    xml_config: xml::XmlConfig,
+    text_variants: bool,
+    base_uri: Option<String>,
+    strict: bool,
+    max_entries: Option<usize>,
+    max_input_bytes: Option<u64>,
+    duplicate_ids: DuplicateIds,
+    salvage: bool,
+    pub_date: PubDate,
+    split_author_names: bool,
+    #[cfg(feature = "intern")]
+    interner: Option<Interner>,
+}
+
+impl Parser {
+    /// Convenience for `parse_with_uri()` with `None` as the base_uri
+    pub fn parse<R: Read>(&self, source: R) -> ParseFeedResult<model::Feed> {
+        self.parse_with_uri(source, None)
+    }
+
+    /// Parse the input (Atom, a flavour of RSS or JSON Feed) into our model (see `parser::parse_with_uri()`)
+    pub fn parse_with_uri<R: Read>(&self, source: R, uri: Option<&str>) -> ParseFeedResult<model::Feed> {
+        self.parse_limited(source, uri, &SourceHints::default())
+    }
+
+    /// Parse the input, decoding it with the character set from an external source (see `parser::parse_with_charset()`)
+    pub fn parse_with_charset<R: Read>(&self, source: R, uri: Option<&str>, charset: &str) -> ParseFeedResult<model::Feed> {
+        let hints = SourceHints {
+            encoding: encoding::encoding_for_charset(charset),
+            ..SourceHints::default()
+        };
+        self.parse_limited(source, uri, &hints)
+    }
+
+    /// Parse the body of an HTTP response, using its headers to guide the parser (see `parser::parse_response()`)
+    pub fn parse_response<'a, H, R>(&self, status: u16, headers: H, body: R) -> ParseFeedResult<model::Feed>
+    where
+        H: IntoIterator<Item = (&'a str, &'a str)>,
+        R: Read,
+    {
+        if !(200..300).contains(&status) {
+            return Err(ParseFeedError::ParseError(ParseErrorKind::UnsuccessfulStatus(status)));
+        }
+
+        let mut hints = SourceHints::default();
+        let mut content_location = None;
+        for (name, value) in headers {
+            if name.eq_ignore_ascii_case("content-type") {
+                hints.encoding = encoding::encoding_for_charset(value);
+                if let Ok(media_type) = value.parse::<mime::Mime>() {
+                    hints.format = DeclaredFormat::for_media_type(&media_type)?;
+                }
+            } else if name.eq_ignore_ascii_case("content-language") {
+                hints.language = value.split(',').map(str::trim).find(|language| !language.is_empty());
+            } else if name.eq_ignore_ascii_case("content-location") {
+                content_location = Some(value.trim());
+            }
+        }
+
+        // A relative location is resolved against the URI the parser was built with, as that is where the request was sent
+        let base = self.base_uri.as_deref().and_then(|base| url::Url::parse(base).ok());
+        let uri = content_location.and_then(|location| url::Url::options().base_url(base.as_ref()).parse(location).ok());
+
+        self.parse_limited(body, uri.as_ref().map(url::Url::as_str), &hints)
+    }
+
+    // Parses the source, up to the limit set with max_input_bytes()
+    fn parse_limited<R: Read>(&self, source: R, uri: Option<&str>, hints: &SourceHints) -> ParseFeedResult<model::Feed> {
+        let source = LimitedReader::new(source, self.max_input_bytes);
+        let exceeded = source.exceeded();
+        let result = self.parse_source(source, uri, hints, &exceeded);
+
+        // A lenient parser returns what it could make of the truncated source
+        if exceeded.get() && self.strict {
+            return Err(ParseFeedError::ParseError(ParseErrorKind::InputTooLarge));
+        }
         result
     }
+
+    // Parses the source, detecting its format, then completes the feed
+    fn parse_source<R: Read>(&self, source: R, uri: Option<&str>, hints: &SourceHints, truncated: &Flag) -> ParseFeedResult<model::Feed> {
+        // The URI of this source takes precedence over the one the parser was built with
+        let uri = uri.or(self.base_uri.as_deref());
+
+        let result = self.parse_format(source, uri, hints.encoding, hints.format, truncated);
+
+        // Post processing as required (including the partial feed from a cancelled parse)
+        match result {
+            Ok(mut feed) => {
+                self.complete_feed(&mut feed, uri, hints);
+                Ok(feed)
+            }
+            Err(ParseFeedError::Cancelled(mut feed)) => {
+                self.complete_feed(&mut feed, uri, hints);
+                Err(ParseFeedError::Cancelled(feed))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Completes the parsed feed, applying the defaults from outside the document first so the checks and handlers see them
+    fn complete_feed(&self, feed: &mut model::Feed, uri: Option<&str>, hints: &SourceHints) {
+        if feed.language.is_none() {
+            feed.language = hints.language.map(str::to_owned);
+        }
+        util::tidy_categories(feed);
+        util::inherit_language(feed);
+        self.assign_missing_ids(feed, uri);
+        self.check_duplicate_ids(feed);
+        self.handle_duplicate_ids(feed);
+        self.check_language(feed);
+        self.apply_namespace_handlers(feed);
+        self.intern_strings(feed);
+    }
+
+    // Parses the source with the parser for its format, transcoding it to UTF-8 first if it has (or is given) another encoding
+    fn parse_format<R: Read>(
+        &self,
+        source: R,
+        uri: Option<&str>,
+        encoding: Option<&'static encoding_rs::Encoding>,
+        format: Option<DeclaredFormat>,
+        truncated: &Flag,
+    ) -> ParseFeedResult<model::Feed> {
+        // Buffer the reader for performance (e.g. when streaming from a network) and so we can peek to determine the type of content
+        let mut input = BufReader::with_capacity(self.read_buffer_capacity, source);
+        if let Some(encoding) = encoding {
+            return self.parse_transcoded(input, uri, encoding, format, truncated);
+        }
+        if let Some(encoding) = self.prepare_input(&mut input)? {
+            return self.parse_transcoded(input, uri, encoding, format, truncated);
+        }
+
+        // Determine whether this is XML or JSON and call the appropriate parser
+        let first_char = input.buffer().iter().find(|b| **b == b'<' || **b == b'{').map(|b| *b as char);
+        match (first_char, format) {
+            // Output before the start of the declared format (e.g. an error message printed by the server) is skipped
+            (Some('<'), Some(DeclaredFormat::Json)) => match input.buffer().iter().position(|b| *b == b'{') {
+                Some(offset) => {
+                    input.consume(offset);
+                    self.parse_json(input, uri)
+                }
+                None => self.parse_xml(input, uri, truncated),
+            },
+            (Some('{'), Some(DeclaredFormat::Xml)) => match input.buffer().iter().position(|b| *b == b'<') {
+                Some(offset) => {
+                    input.consume(offset);
+                    self.parse_xml(input, uri, truncated)
+                }
+                None => self.parse_json(input, uri),
+            },
+
+            (Some('<'), _) => self.parse_xml(input, uri, truncated),
+
+            (Some('{'), _) => self.parse_json(input, uri),
+
+            _ => Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
+        }
+    }
+
+    // Transcodes the source to UTF-8 before parsing it
+    fn parse_transcoded<R: Read>(
+        &self,
+        mut source: R,
+        uri: Option<&str>,
+        encoding: &'static encoding_rs::Encoding,
+        format: Option<DeclaredFormat>,
+        truncated: &Flag,
+    ) -> ParseFeedResult<model::Feed> {
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+        let text = encoding::transcode(&bytes, encoding);
+        self.parse_format(text.as_bytes(), uri, None, format, truncated)
+    }
+
+    // Reads the start of the source, skipping anything emitted before the XML declaration (e.g. a blank line or warning from a
+    // misconfigured server) and, if salvaging, before the root element
+    //
+    // The XML parser requires an ASCII compatible encoding, so the encoding of a UTF-16 source is returned for it to be transcoded first.
+    fn prepare_input<R: Read>(&self, input: &mut BufReader<R>) -> std::io::Result<Option<&'static encoding_rs::Encoding>> {
+        input.fill_buf()?;
+        if let Some(encoding) = encoding::encoding_for_bom(input.buffer()) {
+            return Ok(Some(encoding));
+        }
+
+        let offset = encoding::declaration_offset(input.buffer());
+        input.consume(offset);
+        self.skip_to_root(input);
+        Ok(None)
+    }
+
+    /// Parses the entries of a feed one at a time (see `parser::parse_entries()`)
+    ///
+    /// The parser is consumed, as it is needed as the iterator is advanced. The progress handler and cancellation token apply as they would for `parse()`.
+    pub fn parse_entries<R: Read>(self, source: R) -> Entries<R> {
+        let source = LimitedReader::new(source, self.max_input_bytes);
+        let input_exceeded = source.exceeded();
+        let state = EntriesState::new(&self, BufReader::with_capacity(self.read_buffer_capacity, source));
+        Entries {
+            parser: self,
+            state,
+            input_exceeded: Some(input_exceeded),
+            entries_emitted: 0,
+        }
+    }
+
+    // Called by the format parsers each time an entry is added to the feed
+    pub(crate) fn entry_parsed(&self, bytes_consumed: u64, entries_emitted: usize) {
+        if let Some(handler) = &self.progress_handler {
+            handler(&Progress {
+                bytes_consumed,
+                entries_emitted,
+            });
+        }
+    }
+
+    // Called by the format parsers for problems with the source that they work around, with the warning only built if there is a handler
+    pub(crate) fn warn<F: FnOnce() -> Warning>(&self, warning: F) {
+        if let Some(handler) = &self.warning_handler {
+            handler(&warning());
+        }
+    }
+
+    // Called by the format parsers when an element the spec requires is missing, which fails the parse if strict
+    pub(crate) fn missing_content(&self, element: &'static str) -> ParseFeedResult<()> {
+        if self.strict {
+            return Err(ParseFeedError::ParseError(ParseErrorKind::MissingContent(element)));
+        }
+        self.warn(|| Warning::MissingContent(element));
+        Ok(())
+    }
+
+    // Checked by the format parsers between entries to see if they should stop
+    pub(crate) fn is_cancelled(&self) -> bool {
+        match &self.cancellation_token {
+            Some(token) => token.is_cancelled(),
+            None => false,
+        }
+    }
+
+    // Checked by the format parsers after each entry to see if the limit set with max_entries() has been reached
+    pub(crate) fn entry_limit_reached(&self, entries: usize) -> bool {
+        match self.max_entries {
+            Some(max_entries) => entries >= max_entries,
+            None => false,
+        }
+    }
+
+    // Checked by the format parsers for each entry, to see if it passes the filters set with entry_filter() and since()
+    pub(crate) fn entry_wanted(&self, entry: &model::Entry) -> bool {
+        if let Some(since) = self.since {
+            // Entries without a timestamp are kept, as there is no way to tell if they are new
+            if let Some(timestamp) = entry.updated.max(entry.published) {
+                if timestamp < since {
+                    return false;
+                }
+            }
+        }
+        match &self.entry_filter {
+            Some(filter) => filter(entry),
+            None => true,
+        }
+    }
+
+    // Applies the limit set with max_text_length() to text that is not read by the XML reader, which limits its own (i.e. JSON fields)
+    pub(crate) fn limit_text(&self, mut text: String) -> ParseFeedResult<String> {
+        if let Some(max_text_length) = self.xml_config.max_text_length {
+            if text.len() > max_text_length {
+                if self.strict {
+                    return Err(ParseFeedError::ParseError(ParseErrorKind::TextTooLong));
+                }
+                xml::truncate_text(&mut text, max_text_length);
+            }
+        }
+        Ok(text)
+    }
+
+    // Applies the limit set with max_text_length() to an optional field
+    pub(crate) fn limit_optional_text(&self, text: Option<String>) -> ParseFeedResult<Option<String>> {
+        text.map(|text| self.limit_text(text)).transpose()
+    }
+
+    // Parses a timestamp with the caller's parser if registered, then the built in parsing for the format
+    //
+    // Invalid timestamps are an error for a strict parser, and otherwise ignored.
+    pub(crate) fn parse_timestamp(&self, text: &str, format: TimestampFormat) -> ParseFeedResult<Option<DateTime<Utc>>> {
+        if let Some(timestamp) = self.timestamp_parser.as_ref().and_then(|parser| parser(text)) {
+            return Ok(Some(timestamp));
+        }
+
+        if self.strict {
+            match format.parse_strict(text) {
+                Some(timestamp) => Ok(Some(timestamp)),
+                None => Err(ParseFeedError::ParseError(ParseErrorKind::InvalidTimestamp(text.trim().to_owned()))),
+            }
+        } else {
+            let timestamp = format.parse_lenient(text);
+            if timestamp.is_none() && !text.trim().is_empty() {
+                self.warn(|| Warning::InvalidTimestamp(text.trim().to_owned()));
+            }
+            Ok(timestamp)
+        }
+    }
+
+    // Assigns IDs to missing feed + entries as required
+    fn assign_missing_ids(&self, feed: &mut model::Feed, uri: Option<&str>) {
+        if feed.id.is_empty() {
+            let content = [feed.description.as_ref().map(|text| text.content.as_str())];
+            feed.id = self.create_id(&feed.links, &feed.title, uri, &content);
+        }
+
+        for entry in feed.entries.iter_mut() {
+            if entry.id.is_empty() {
+                entry.id = self.create_entry_id(entry, uri);
+            }
+        }
+    }
+
+    // Reports each ID shared by more than one entry
+    fn check_duplicate_ids(&self, feed: &model::Feed) {
+        if self.warning_handler.is_none() {
+            return;
+        }
+
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for entry in &feed.entries {
+            if !seen.insert(entry.id.as_str()) && reported.insert(entry.id.as_str()) {
+                self.warn(|| Warning::DuplicateId(entry.id.clone()));
+            }
+        }
+    }
+
+    // Skips to the root element of a feed if salvaging is enabled and it follows something that cannot be part of the XML prolog
+    fn skip_to_root<R: Read>(&self, input: &mut BufReader<R>) {
+        if self.salvage {
+            if let Some(offset) = encoding::root_offset(input.buffer()) {
+                self.warn(|| Warning::SkippedContent(offset));
+                input.consume(offset);
+            }
+        }
+    }
+
+    // Applies the policy for entries that share an ID
+    fn handle_duplicate_ids(&self, feed: &mut model::Feed) {
+        match self.duplicate_ids {
+            DuplicateIds::KeepAll => {}
+            DuplicateIds::KeepFirst => {
+                let mut seen = HashSet::new();
+                feed.entries.retain(|entry| seen.insert(entry.id.clone()));
+            }
+            DuplicateIds::KeepLast => {
+                let mut seen = HashSet::new();
+                feed.entries.reverse();
+                feed.entries.retain(|entry| seen.insert(entry.id.clone()));
+                feed.entries.reverse();
+            }
+            DuplicateIds::Rename => {
+                let mut seen: HashSet<String> = feed.entries.iter().map(|entry| entry.id.clone()).collect();
+                let mut kept = HashSet::new();
+                // The next suffix for each ID, so the suffixes already used are not tried again for each duplicate
+                let mut next_suffix = HashMap::new();
+                for entry in feed.entries.iter_mut() {
+                    if kept.insert(entry.id.clone()) {
+                        continue;
+                    }
+                    // The suffix counts the entries with the ID, skipping any that would clash with another entry
+                    let suffix = next_suffix.entry(entry.id.clone()).or_insert(2);
+                    let id = loop {
+                        let id = format!("{}-{}", entry.id, suffix);
+                        *suffix += 1;
+                        if !seen.contains(&id) {
+                            break id;
+                        }
+                    };
+                    seen.insert(id.clone());
+                    entry.id = id;
+                }
+            }
+        }
+    }
+
+    // Reports a language which cannot be normalised into a language tag
+    fn check_language(&self, feed: &model::Feed) {
+        if let Some(language) = &feed.language {
+            if feed.language_tag().is_none() {
+                self.warn(|| Warning::InvalidLanguage(language.clone()));
+            }
+        }
+    }
+
+    // Creates the ID for an entry that does not have one
+    fn create_entry_id(&self, entry: &model::Entry, uri: Option<&str>) -> String {
+        let content = [
+            entry.summary.as_ref().map(|text| text.content.as_str()),
+            entry.content.as_ref().and_then(|content| content.body.as_deref()),
+        ];
+        self.create_id(&entry.links, &entry.title, uri, &content)
+    }
+
+    // Creates an ID with the caller's generator if registered, or else from the first link
+    fn create_id(&self, links: &[model::Link], title: &Option<model::Text>, uri: Option<&str>, content: &[Option<&str>]) -> String {
+        match &self.id_generator {
+            Some(generator) => generator(links, title.as_ref(), uri),
+            None => create_id(links, title, uri, content),
+        }
+    }
+
+    // Passes the extensions of the feed and its entries to the namespace handlers registered for them
+    fn apply_namespace_handlers(&self, feed: &mut model::Feed) {
+        for handler in &self.namespace_handlers {
+            if let Some(elements) = feed.extensions.remove(handler.namespace()) {
+                let remaining: Vec<ExtensionElement> = elements.into_iter().filter(|element| !handler.handle_feed_element(element, feed)).collect();
+                if !remaining.is_empty() {
+                    feed.extensions.insert(handler.namespace().to_owned(), remaining);
+                }
+            }
+        }
+        for entry in &mut feed.entries {
+            self.apply_entry_namespace_handlers(entry);
+        }
+    }
+
+    // Passes the extensions of an entry to the namespace handlers registered for them
+    fn apply_entry_namespace_handlers(&self, entry: &mut model::Entry) {
+        for handler in &self.namespace_handlers {
+            if let Some(elements) = entry.extensions.remove(handler.namespace()) {
+                let remaining: Vec<ExtensionElement> = elements.into_iter().filter(|element| !handler.handle_entry_element(element, entry)).collect();
+                if !remaining.is_empty() {
+                    entry.extensions.insert(handler.namespace().to_owned(), remaining);
+                }
+            }
+        }
+    }
+
+    // Shares the repeated strings of the feed with those held by the interner, if there is one
+    #[cfg(feature = "intern")]
+    fn intern_strings(&self, feed: &mut model::Feed) {
+        if let Some(interner) = &self.interner {
+            interner.intern_feed(feed);
+        }
+    }
+
+    #[cfg(not(feature = "intern"))]
+    fn intern_strings(&self, _feed: &mut model::Feed) {}
+
+    // Called by the format parsers for each entry they keep, so its strings are shared before the next is parsed rather than after the feed
+    #[cfg(feature = "intern")]
+    pub(crate) fn intern_entry_strings(&self, entry: &mut model::Entry) {
+        if let Some(interner) = &self.interner {
+            // Trimming the categories afterwards would replace the shared strings with copies
+            util::tidy_entry_categories(entry);
+            interner.intern_entry(entry);
+        }
+    }
+
+    #[cfg(not(feature = "intern"))]
+    pub(crate) fn intern_entry_strings(&self, _entry: &mut model::Entry) {}
+
+    // Called by the format parsers for elements they do not recognise at the feed and entry level
+    //
+    // Elements from other namespaces are recorded in the extensions of the feed or entry they were found in.
+    pub(crate) fn handle_unknown_element<R: BufRead>(&self, element: &xml::Element<R>, extensions: &mut ExtensionMap) -> ParseFeedResult<()> {
+        let namespace_uri = element.namespace_uri.as_deref().filter(|uri| !FORMAT_NAMESPACES.contains(uri));
+        if self.unknown_element_handler.is_none() && namespace_uri.is_none() {
+            return Ok(());
+        }
+
+        // Gather the events for the element itself and its subtree
+        let mut events = vec![XmlEvent::Start {
+            name: element.name.clone(),
+            attributes: element.attributes.iter().map(|nv| (nv.name.clone(), nv.value.clone())).collect(),
+        }];
+        element.visit_children(|event| events.push(XmlEvent::from_xml(event)))?;
+        events.push(XmlEvent::End { name: element.name.clone() });
+
+        if let Some(handler) = &self.unknown_element_handler {
+            handler(&element.path(), &events);
+        }
+        if let Some(uri) = namespace_uri {
+            self.warn(|| Warning::UnknownElement {
+                namespace: uri.to_owned(),
+                element: element.name.clone(),
+            });
+            extensions.entry(uri.to_owned()).or_default().push(ExtensionElement::from_events(&events));
+        }
+
+        Ok(())
+    }
+
+    // Records the text as a variant of a field (if enabled), returning it for assignment to the field itself
+    pub(crate) fn text_variant(&self, variants: &mut Vec<model::TextVariant>, element: &str, text: Option<model::Text>) -> Option<model::Text> {
+        if self.text_variants {
+            if let Some(text) = &text {
+                variants.push(model::TextVariant::new(element, text.clone()));
+            }
+        }
+        text
+    }
+
+    // The settings for the XML reader, with the flag set if the source is truncated by max_input_bytes()
+    fn xml_config(&self, truncated: &Flag) -> xml::XmlConfig {
+        xml::XmlConfig {
+            truncated: self.max_input_bytes.map(|_| truncated.clone()),
+            ..self.xml_config.clone()
+        }
+    }
+
+    // Handles JSON content
+    fn parse_json<R: BufRead>(&self, source: R, uri: Option<&str>) -> ParseFeedResult<model::Feed> {
+        json::parse(self, source, uri)
+    }
+
+    // Handles XML content
+    fn parse_xml<R: BufRead>(&self, source: R, uri: Option<&str>, truncated: &Flag) -> ParseFeedResult<model::Feed> {
+        // Set up the source of XML elements from the input
+        let element_source = xml::ElementSource::new(source, uri, &self.xml_config(truncated))?;
+        let root = match element_source.root() {
+            Ok(Some(root)) => root,
+            // Documents rejected as unsafe are reported as such, rather than as not being a feed
+            Err(e @ xml::XmlError::ExternalEntity { .. }) | Err(e @ xml::XmlError::LimitExceeded { .. }) => return Err(e.into()),
+            _ => return Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
+        };
+
+        // Dispatch to the correct parser
+        let mut feed = match root_feed_type(&root) {
+            Some(model::FeedType::Atom) if root.name == "entry" => atom::parse_entry(self, root)?,
+            Some(model::FeedType::Atom) => atom::parse_feed(self, root)?,
+            Some(model::FeedType::RSS2) => rss2::parse(self, root)?,
+            Some(model::FeedType::RSS0) => rss0::parse(self, root)?,
+            Some(model::FeedType::RSS1) => rss1::parse(self, root)?,
+
+            // Couldn't find a recognised feed within the provided XML stream
+            _ => return Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
+        };
+
+        // Stylesheets are declared in the prolog, so have been read by the time we reach the root
+        feed.stylesheets = element_source.stylesheets().iter().filter_map(|pi| util::parse_stylesheet(pi)).collect();
+
+        Ok(feed)
+    }
+}
+
+/// Iterator over the entries of a feed, created by `parser::parse_entries()`
+pub struct Entries<R: Read> {
+    parser: Parser,
+    state: EntriesState<R>,
+    entries_emitted: usize,
+    // Set if the source was truncated by the limit on its size, until the error is returned by a strict parser
+    input_exceeded: Option<Flag>,
+}
+
+impl<R: Read> Iterator for Entries<R> {
+    type Item = ParseFeedResult<model::Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.entry_limit_reached(self.entries_emitted) {
+            return None;
+        }
+
+        // A cancellation is returned once, after which the iterator is finished
+        if self.parser.is_cancelled() {
+            let feed_type = match &mut self.state {
+                EntriesState::Xml { format, .. } | EntriesState::Buffered(format, _) => *format,
+                EntriesState::Failed(error) => return error.take().map(Err),
+            };
+            self.state = EntriesState::Failed(None);
+            return Some(Err(ParseFeedError::Cancelled(Box::new(model::Feed::new(feed_type)))));
+        }
+
+        let result = match &mut self.state {
+            EntriesState::Xml {
+                source,
+                format,
+                depth,
+                language,
+                document_index,
+            } => next_xml_entry(&self.parser, source, format, *depth, language, document_index),
+            EntriesState::Buffered(_, entries) => entries.next().map(Ok),
+            EntriesState::Failed(error) => error.take().map(Err),
+        };
+
+        match result {
+            Some(Ok(mut entry)) => {
+                util::tidy_entry_categories(&mut entry);
+                if let EntriesState::Xml { language: Some(language), .. } = &self.state {
+                    util::inherit_entry_language(&mut entry, language);
+                }
+                if entry.id.is_empty() {
+                    entry.id = self.parser.create_entry_id(&entry, self.parser.base_uri.as_deref());
+                }
+                self.parser.apply_entry_namespace_handlers(&mut entry);
+                self.parser.intern_entry_strings(&mut entry);
+                self.entries_emitted += 1;
+                if let EntriesState::Xml { source, .. } = &self.state {
+                    self.parser.entry_parsed(source.bytes_consumed(), self.entries_emitted);
+                }
+                Some(Ok(entry))
+            }
+            Some(Err(error)) => {
+                // Nothing further can be parsed after an error
+                self.state = EntriesState::Failed(None);
+                Some(Err(error))
+            }
+            // A strict parser fails once the entries before the limit on the input have been returned
+            None if self.parser.strict && self.input_exceeded.take().filter(|exceeded| exceeded.get()).is_some() => {
+                self.state = EntriesState::Failed(None);
+                Some(Err(ParseFeedError::ParseError(ParseErrorKind::InputTooLarge)))
+            }
+            None => None,
+        }
+    }
+}
+
+// Where the entries for an Entries iterator come from
+enum EntriesState<R: Read> {
+    // Entries are parsed from the elements found at this depth in the document
+    Xml {
+        source: Box<xml::ElementSource<BufReader<LimitedReader<R>>>>,
+        format: model::FeedType,
+        depth: u32,
+        // The language of the feed, once it has been seen
+        language: Option<String>,
+        // The position of the next entry in the document, counting those that are filtered out or dropped
+        document_index: usize,
+    },
+    // Entries which have already been parsed (e.g. a JSON Feed)
+    Buffered(model::FeedType, std::vec::IntoIter<model::Entry>),
+    // Parsing failed, with the error yet to be returned
+    Failed(Option<ParseFeedError>),
+}
+
+impl<R: Read> EntriesState<R> {
+    // Finds where the entries are in the source
+    fn new(parser: &Parser, mut input: BufReader<LimitedReader<R>>) -> EntriesState<R> {
+        let buffered = |result: ParseFeedResult<model::Feed>| match result {
+            Ok(mut feed) => {
+                util::inherit_language(&mut feed);
+                EntriesState::Buffered(feed.feed_type, feed.entries.into_iter())
+            }
+            Err(error) => EntriesState::Failed(Some(error)),
+        };
+
+        // A UTF-16 source is transcoded and parsed in full, as the XML parser requires an ASCII compatible encoding
+        match parser.prepare_input(&mut input) {
+            Ok(Some(encoding)) => {
+                let exceeded = input.get_ref().exceeded();
+                return buffered(parser.parse_transcoded(input, parser.base_uri.as_deref(), encoding, None, &exceeded));
+            }
+            Ok(None) => {}
+            Err(error) => return EntriesState::Failed(Some(error.into())),
+        }
+
+        // Determine whether this is XML or JSON
+        let first_char = input.buffer().iter().find(|b| **b == b'<' || **b == b'{').map(|b| *b as char);
+        match first_char {
+            Some('<') => {}
+            Some('{') => return buffered(parser.parse_json(input, parser.base_uri.as_deref())),
+            _ => return EntriesState::Failed(Some(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))),
+        }
+
+        let config = parser.xml_config(&input.get_ref().exceeded());
+        let source = match xml::ElementSource::new(input, parser.base_uri.as_deref(), &config) {
+            Ok(source) => Box::new(source),
+            Err(error) => return EntriesState::Failed(Some(error.into())),
+        };
+        let format = match source.root() {
+            // A standalone Atom entry has nothing to stream
+            Ok(Some(root)) if root.name == "entry" => return buffered(atom::parse_entry(parser, root)),
+            Ok(Some(root)) => root_feed_type(&root),
+            Err(e @ xml::XmlError::ExternalEntity { .. }) | Err(e @ xml::XmlError::LimitExceeded { .. }) => return EntriesState::Failed(Some(e.into())),
+            _ => None,
+        };
+
+        // Entries are children of the root, except for RSS 0.9x + 2 where they are within the channel
+        let (format, depth) = match format {
+            Some(format @ model::FeedType::Atom) | Some(format @ model::FeedType::RSS1) => (format, 2),
+            Some(format @ model::FeedType::RSS0) | Some(format @ model::FeedType::RSS2) => loop {
+                match source.next_element_at_depth(2) {
+                    Ok(Some(element)) if element.ns_and_tag_in(xml::RSS_NAMESPACES) == (&None, "channel") => break (format, 3),
+                    Ok(Some(_)) => {}
+                    Ok(None) => return EntriesState::Failed(Some(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))),
+                    Err(error) => return EntriesState::Failed(Some(error.into())),
+                }
+            },
+
+            // Couldn't find a recognised feed within the provided XML stream
+            _ => return EntriesState::Failed(Some(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))),
+        };
+
+        EntriesState::Xml {
+            source,
+            format,
+            depth,
+            language: None,
+            document_index: 0,
+        }
+    }
+}
+
+// Parses the next entry from an XML source, skipping any other elements apart from the language of the feed
+fn next_xml_entry<R: BufRead>(
+    parser: &Parser,
+    source: &xml::ElementSource<R>,
+    format: &model::FeedType,
+    depth: u32,
+    language: &mut Option<String>,
+    document_index: &mut usize,
+) -> Option<ParseFeedResult<model::Entry>> {
+    loop {
+        let element = match source.next_element_at_depth(depth) {
+            Ok(Some(element)) => element,
+            Ok(None) => return None,
+            Err(error) => return Some(Err(error.into())),
+        };
+
+        let namespaces = match format {
+            model::FeedType::Atom => xml::ATOM_NAMESPACES,
+            _ => xml::RSS_NAMESPACES,
+        };
+        let parsed = match (format, element.ns_and_tag_in(namespaces)) {
+            (model::FeedType::Atom, (None, "entry")) => atom::handle_entry(parser, element),
+            (model::FeedType::RSS1, (None, "item")) => rss1::handle_item(parser, element),
+            (model::FeedType::RSS0, (None, "item")) | (model::FeedType::RSS2, (None, "item")) => rss2::handle_item(parser, element),
+            (model::FeedType::RSS0, (None, "language")) | (model::FeedType::RSS2, (None, "language")) => {
+                *language = element.child_as_text();
+                continue;
+            }
+            (_, (Some(xml::NS::DublinCore), "language")) if language.is_none() => {
+                *language = element.child_as_text();
+                continue;
+            }
+
+            // Other feed level elements are skipped
+            _ => continue,
+        };
+
+        let parsed = parsed.map(|entry| {
+            entry.map(|mut entry| {
+                entry.document_index = *document_index;
+                entry
+            })
+        });
+        *document_index += 1;
+        match parsed {
+            // An entry cut short by the limit on the size of the source is left out
+            Ok(Some(_)) if source.is_truncated() => return None,
+            Ok(Some(entry)) if parser.entry_wanted(&entry) => return Some(Ok(entry)),
+            Ok(_) => {}
+            Err(error) => return Some(Err(error)),
+        }
+    }
+}
+
+// Matches the default capacity of std::io::BufReader
+const DEFAULT_READ_BUFFER_CAPACITY: usize = 8 * 1024;
+
+// Enough to hold a byte order mark and the start of the document, which are peeked at to detect the encoding and format
+const MIN_READ_BUFFER_CAPACITY: usize = 16;
+
+/// Builds a `Parser` with non-default options
+///
+/// The handlers and callbacks registered with the builder must be `Send + Sync`, so a parser can be shared between threads, or
+/// moved to another along with the entries it is streaming.
+pub struct Builder {
+    progress_handler: Option<Box<ProgressHandler>>,
+    warning_handler: Option<Box<WarningHandler>>,
+    unknown_element_handler: Option<Box<UnknownElementHandler>>,
+    namespace_handlers: Vec<Box<dyn NamespaceHandler>>,
+    timestamp_parser: Option<Box<TimestampParser>>,
+    id_generator: Option<Box<IdGenerator>>,
+    entry_filter: Option<Box<EntryFilter>>,
+    since: Option<DateTime<Utc>>,
+    cancellation_token: Option<CancellationToken>,
+    read_buffer_capacity: usize,
+    xml_config: xml::XmlConfig,
+    text_variants: bool,
+    base_uri: Option<String>,
+    strict: bool,
+    max_entries: Option<usize>,
+    max_input_bytes: Option<u64>,
+    duplicate_ids: DuplicateIds,
+    salvage: bool,
+    pub_date: PubDate,
+    split_author_names: bool,
+    #[cfg(feature = "intern")]
+    interner: Option<Interner>,
 }
 
-// Assigns IDs to missing feed + entries as required
-fn assign_missing_ids(feed: &mut model::Feed, uri: Option<&str>) {
-    if feed.id.is_empty() {
-        feed.id = create_id(&feed.links, &feed.title, uri);
+impl Builder {
+    /// Create a new instance of the builder
+    pub fn new() -> Builder {
+        Builder::default()
     }
 
-    for entry in feed.entries.iter_mut() {
-        if entry.id.is_empty() {
-            entry.id = create_id(&entry.links, &entry.title, uri);
+    /// Create a new parser with the options accumulated in this builder
+    pub fn build(mut self) -> Parser {
+        // Text over the limit is only an error for a strict parser
+        self.xml_config.truncate_text = !self.strict;
+        Parser {
+            progress_handler: self.progress_handler,
+            warning_handler: self.warning_handler,
+            unknown_element_handler: self.unknown_element_handler,
+            namespace_handlers: self.namespace_handlers,
+            timestamp_parser: self.timestamp_parser,
+            id_generator: self.id_generator,
+            entry_filter: self.entry_filter,
+            since: self.since,
+            cancellation_token: self.cancellation_token,
+            read_buffer_capacity: self.read_buffer_capacity,
+            xml_config: self.xml_config,
+            text_variants: self.text_variants,
+            base_uri: self.base_uri,
+            strict: self.strict,
+            max_entries: self.max_entries,
+            max_input_bytes: self.max_input_bytes,
+            duplicate_ids: self.duplicate_ids,
+            salvage: self.salvage,
+            pub_date: self.pub_date,
+            split_author_names: self.split_author_names,
+            #[cfg(feature = "intern")]
+            interner: self.interner,
+        }
+    }
+
+    /// Fails the parse when the source violates its spec, rather than making a best effort to interpret it
+    ///
+    /// Timestamps must be in the format required by the spec (e.g. RFC 2822 for RSS), with `ParseErrorKind::InvalidTimestamp` returned
+    /// for those that are not. The elements the spec requires must be present, with `ParseErrorKind::MissingContent` returned for
+    /// those that are not: Atom feeds and entries must have an ID, and RSS 0.9x and 2.0 channels a title, link and description, with
+    /// a title or description for each item.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Makes a best effort to interpret sources that violate their spec, e.g. by accepting timestamps in common non-standard formats (the default)
+    pub fn lenient(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    /// Makes a best effort to read XML feeds that are not well-formed, rather than failing (defaults to false)
+    ///
+    /// Unescaped ampersands and unknown entities are always accepted, and in recovery mode:
+    /// * characters that XML does not allow (e.g. control characters) are removed
+    /// * mismatched end tags close the elements left open within them, and stray end tags are ignored
+    /// * a document that ends early (e.g. a truncated download) gives the entries completed before the end, as a limit on the size
+    ///   of the input does (see `max_input_bytes()`)
+    ///
+    /// The elements recovered may not be what the publisher intended, so this is best used as a fallback for feeds that fail to parse.
+    pub fn recover(mut self, enabled: bool) -> Self {
+        self.xml_config.recover = enabled;
+        self
+    }
+
+    /// Finds an XML feed within other content, such as an HTML error page it is wrapped in or notices printed by PHP (defaults to false)
+    ///
+    /// If the source does not start with a feed, it is parsed from the first `<rss`, `<feed` or `<rdf:RDF` tag, and the number of bytes
+    /// skipped is reported to the warning handler. Anything after the end of the feed is ignored. The start of the feed must be within the
+    /// first `read_buffer_capacity()` bytes of the source, and the encoding in any XML declaration that was skipped is not used.
+    pub fn salvage(mut self, enabled: bool) -> Self {
+        self.salvage = enabled;
+        self
+    }
+
+    /// Stops parsing once the given number of entries have been parsed, which bounds the time and memory spent on very large feeds
+    ///
+    /// Any feed level elements after the last entry parsed are not read.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Registers a function that decides which entries are kept, with those it returns false for left out of the feed
+    ///
+    /// The filter is called as each entry is parsed, so entries that are not needed (e.g. those already seen by an incremental crawl)
+    /// are dropped straight away rather than being held until the whole feed is parsed. The entry is as found in the source, so has
+    /// not had an ID generated (if it lacks one) or the language of the feed applied. Entries that are left out do not count towards
+    /// `max_entries()`.
+    ///
+    /// This is a filter on the parsed entry, so it saves the memory of the entries it rejects but not the time spent parsing them: the
+    /// elements of an entry may come in any order, so its ID, links and dates are only known once all of it has been read.
+    pub fn entry_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&model::Entry) -> bool + Send + Sync + 'static,
+    {
+        self.entry_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Leaves out entries that were last updated (or if never updated, published) before the given time, such as the last fetch of the feed
+    ///
+    /// Entries without either timestamp are kept. This may be combined with `entry_filter()`, in which case entries must pass both.
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Registers a function that is tried before the built in parsing of timestamps, e.g. for a site that uses its own format
+    ///
+    /// The built in parsing is used if the function returns `None`.
+    pub fn timestamp_parser<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> Option<DateTime<Utc>> + Send + Sync + 'static,
+    {
+        self.timestamp_parser = Some(Box::new(parser));
+        self
+    }
+
+    /// Registers a function that generates the ID of feeds and entries that do not have one
+    ///
+    /// The function is passed the links and title of the feed or entry, and the URI of the source if known. By default the ID is a hash of
+    /// the first link and title, or of the title and content if there are no links.
+    pub fn id_generator<F>(mut self, generator: F) -> Self
+    where
+        F: Fn(&[model::Link], Option<&model::Text>, Option<&str>) -> String + Send + Sync + 'static,
+    {
+        self.id_generator = Some(Box::new(generator));
+        self
+    }
+
+    /// Sets how entries which share an ID are treated, as some broken feeds repeat the same ID for every item (defaults to keeping them all)
+    ///
+    /// Entries without an ID are given one before this applies, so those generated from the same links or content count as duplicates.
+    /// Each duplicate ID is still reported to the warning handler. It does not apply to `parse_entries()`, which does not hold on to
+    /// the entries it has returned.
+    pub fn duplicate_ids(mut self, policy: DuplicateIds) -> Self {
+        self.duplicate_ids = policy;
+        self
+    }
+
+    /// Sets where the "pubDate" of RSS 0.9x and 2.0 items is recorded (defaults to `Entry::published`)
+    ///
+    /// The spec says it is when the item was published, but some feeds change it whenever an item is edited. As with other formats,
+    /// a field that is not set from the feed stays `None`; use `Entry::timestamp()` for the best available date whatever the mapping.
+    pub fn pub_date(mut self, mapping: PubDate) -> Self {
+        self.pub_date = mapping;
+        self
+    }
+
+    /// Sets whether a "dc:creator" listing several people (e.g. "Alice Smith, Bob Jones and Carol White") adds an author for each (defaults to false)
+    ///
+    /// Names are separated by commas, semicolons, "and" or "&", apart from a single comma between two words (e.g. "Smith, Alice"),
+    /// which is taken as a name written surname first, so leave this disabled for feeds where names themselves contain these
+    /// separators. When enabled, a person named by more than one "dc:creator" is only added once.
+    pub fn split_author_names(mut self, enabled: bool) -> Self {
+        self.split_author_names = enabled;
+        self
+    }
+
+    /// Shares the names of people and the terms and schemes of categories through the interner, rather than each holding a copy
+    ///
+    /// This reduces the memory held by feeds that repeat the same authors and categories in many entries, and by a collection of
+    /// feeds parsed with the same interner. The interner keeps the strings it has seen until they are purged (see `Interner::purge()`).
+    #[cfg(feature = "intern")]
+    pub fn interner(mut self, interner: Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// Sets the URI that relative links, images and content sources are resolved against, typically the URL the feed was fetched from
+    ///
+    /// Any `xml:base` in the document is applied on top of this, and a URI passed to `parse_with_uri()` takes precedence over it.
+    /// An invalid URI causes XML feeds to fail to parse, as it would if passed to `parse_with_uri()`.
+    pub fn base_uri(mut self, uri: &str) -> Self {
+        self.base_uri = Some(uri.to_owned());
+        self
+    }
+
+    /// Limits how deeply elements may be nested, failing the parse with `ParseFeedError::XmlReader` for documents that go deeper (defaults to 256)
+    ///
+    /// Documents from the open web are untrusted, and deeply nested elements are a way to exhaust the parser.
+    /// Entities declared in a document type are never expanded, and documents declaring an external entity are rejected, regardless
+    /// of this setting.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.xml_config.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limits the number of elements in the document, failing the parse with `ParseFeedError::XmlReader` for documents with more (unlimited by default)
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.xml_config.max_elements = Some(max_elements);
+        self
+    }
+
+    /// Limits how much of the source is read, in bytes (unlimited by default)
+    ///
+    /// A lenient parser stops reading at the limit and returns what it could parse up to that point, which for XML is the entries
+    /// completed before the limit (JSON cannot be parsed once truncated). A strict parser fails with `ParseErrorKind::InputTooLarge`.
+    pub fn max_input_bytes(mut self, max_input_bytes: u64) -> Self {
+        self.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+
+    /// Limits the length, in bytes, of the text of each element of an XML document and each text field of a JSON Feed (unlimited by default)
+    ///
+    /// The text of an element includes all of its text and CDATA sections, but not that of its children. A lenient parser truncates
+    /// longer text to the limit, while a strict parser fails with `ParseFeedError::XmlReader` (or `ParseErrorKind::TextTooLong` for JSON).
+    pub fn max_text_length(mut self, max_text_length: usize) -> Self {
+        self.xml_config.max_text_length = Some(max_text_length);
+        self
+    }
+
+    /// Sets the capacity, in bytes, of the buffer used to read from the source (defaults to 8 KiB)
+    ///
+    /// Smaller buffers bound memory use for embedded users, while larger buffers reduce the number of reads from slow sources.
+    /// Capacities below 16 bytes are raised to 16, as the start of the source must be buffered to detect its format.
+    pub fn read_buffer_capacity(mut self, capacity: usize) -> Self {
+        self.read_buffer_capacity = capacity.max(MIN_READ_BUFFER_CAPACITY);
+        self
+    }
+
+    /// Sets the initial capacity, in bytes, of the buffer each XML tag or text node is read into (defaults to 512 bytes)
+    ///
+    /// The buffer is reused for every node and only grows if a single node is larger than the capacity.
+    pub fn text_buffer_capacity(mut self, capacity: usize) -> Self {
+        self.xml_config.event_buffer_capacity = capacity;
+        self
+    }
+
+    /// Allows the parse to be cancelled by calling `cancel()` on (a clone of) the supplied token
+    ///
+    /// The token is checked after each entry in a feed, and if cancelled the parser returns `ParseFeedError::Cancelled` with the entries parsed so far.
+    /// The entries returned by `parse_entries()` end with a single `ParseFeedError::Cancelled` instead.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Registers a callback that is invoked after each entry in a feed is parsed, useful for reporting progress through very large feeds
+    ///
+    /// JSON Feed documents are read in a single pass before their entries are converted, so the bytes consumed is the size of the whole document.
+    pub fn progress_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Registers a callback that is invoked with each problem found in the source that did not stop it being parsed
+    ///
+    /// This is useful for validating feeds, and covers timestamps that could not be parsed, missing content required by the spec,
+    /// elements from other namespaces that the parser does not understand and entries sharing an ID. Duplicate IDs are only checked when the
+    /// whole feed is parsed, not by `parse_entries()`. A strict parser fails the parse for the first two instead.
+    pub fn warning_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&Warning) + Send + Sync + 'static,
+    {
+        self.warning_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Retains every title and description found in the source, not just the one chosen for the model (defaults to false)
+    ///
+    /// Feeds often provide several variants (e.g. "title" and "dc:title", or "description" and "itunes:summary"), and the model picks one
+    /// for `title`, `summary` etc. When enabled, all of them are also available in document order in `title_variants`, `summary_variants` etc,
+    /// which is useful for archival and debugging.
+    ///
+    /// Titles and descriptions within a "media:group" remain on their `MediaObject`, and JSON Feed only has a single field for each so is unaffected.
+    pub fn text_variants(mut self, enabled: bool) -> Self {
+        self.text_variants = enabled;
+        self
+    }
+
+    /// Keeps the XML of each entry exactly as it was found in the source, in `Entry::raw` (defaults to false)
+    ///
+    /// This is for consumers that need to store or process what was received (e.g. archives, or verifying signatures), and
+    /// costs a copy of each entry's source. JSON feeds are not affected.
+    pub fn raw_entries(mut self, enabled: bool) -> Self {
+        self.xml_config.record_raw = enabled;
+        self
+    }
+
+    /// Registers a callback that receives the raw XML events of elements the parser does not understand
+    ///
+    /// The handler is called for unrecognised children of the feed (or channel) and its entries, with the names of the elements from the
+    /// document root down to the unrecognised element, and the events for that element and its subtree. This allows custom data to be
+    /// extracted from a feed without changes to the parser.
+    pub fn unknown_element_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&[String], &[XmlEvent]) + Send + Sync + 'static,
+    {
+        self.unknown_element_handler = Some(Box::new(handler));
+        self.xml_config.record_path = true;
+        self
+    }
+
+    /// Registers a handler that populates custom data from the elements of a namespace the parser does not model
+    ///
+    /// Several handlers may be registered, each for a different namespace (see `NamespaceHandler`).
+    pub fn namespace_handler<H>(mut self, handler: H) -> Self
+    where
+        H: NamespaceHandler + 'static,
+    {
+        self.namespace_handlers.push(Box::new(handler));
+        self
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            progress_handler: None,
+            warning_handler: None,
+            unknown_element_handler: None,
+            namespace_handlers: Vec::new(),
+            timestamp_parser: None,
+            id_generator: None,
+            entry_filter: None,
+            since: None,
+            cancellation_token: None,
+            read_buffer_capacity: DEFAULT_READ_BUFFER_CAPACITY,
+            xml_config: xml::XmlConfig::default(),
+            text_variants: false,
+            base_uri: None,
+            strict: false,
+            max_entries: None,
+            max_input_bytes: None,
+            duplicate_ids: DuplicateIds::KeepAll,
+            salvage: false,
+            pub_date: PubDate::Published,
+            split_author_names: false,
+            #[cfg(feature = "intern")]
+            interner: None,
         }
     }
 }
@@ -171,9 +1712,12 @@ fn assign_missing_ids(feed: &mut model::Feed, uri: Option<&str>) {
 const LINK_HASH_KEY1: u64 = 0x5d78_4074_2887_2d60;
 const LINK_HASH_KEY2: u64 = 0x90ee_ca4c_90a5_e228;
 
-// Creates a unique ID from the first link, or a UUID if no links are available
-fn create_id(links: &[model::Link], title: &Option<model::Text>, uri: Option<&str>) -> String {
-    if let Some(link) = links.iter().next() {
+// Creates a unique ID from the first link, or the title and content (e.g. the summary of an entry) if no links are available
+//
+// Links without a relationship (i.e. the RSS <link>) are preferred over any embedded <atom:link> elements that precede them. The ID is
+// always derived from the source, so parsing the same document again gives the same IDs.
+fn create_id(links: &[model::Link], title: &Option<model::Text>, uri: Option<&str>, content: &[Option<&str>]) -> String {
+    if let Some(link) = links.iter().find(|link| link.rel.is_none()).or_else(|| links.first()) {
         // Generate a stable ID for this item based on the first link
         let mut hasher = SipHasher::new_with_keys(LINK_HASH_KEY1, LINK_HASH_KEY2);
         hasher.write(link.href.as_bytes());
@@ -190,33 +1734,13 @@ fn create_id(links: &[model::Link], title: &Option<model::Text>, uri: Option<&st
         let hash = hasher.finish128();
         format!("{:x}{:x}", hash.h1, hash.h2)
     } else {
-        // Generate a UUID as last resort
-        util::uuid_gen()
-    }
-}
-
-// Handles JSON content
-fn parse_json<R: BufRead>(source: R) -> ParseFeedResult<model::Feed> {
-    json::parse(source)
-}
-
-// Handles XML content
-fn parse_xml<R: BufRead>(source: R, uri: Option<&str>) -> ParseFeedResult<model::Feed> {
-    // Set up the source of XML elements from the input
-    let element_source = xml::ElementSource::new(source, uri)?;
-    if let Ok(Some(root)) = element_source.root() {
-        // Dispatch to the correct parser
-        let version = root.attr_value("version");
-        match (root.name.as_str(), version.as_deref()) {
-            ("feed", _) => return atom::parse_feed(root),
-            ("entry", _) => return atom::parse_entry(root),
-            ("rss", Some("2.0")) => return rss2::parse(root),
-            ("rss", Some("0.91")) | ("rss", Some("0.92")) => return rss0::parse(root),
-            ("RDF", _) => return rss1::parse(root),
-            _ => {}
-        };
+        // As a last resort use whatever content there is, with a separator so the parts cannot run together
+        let mut hasher = SipHasher::new_with_keys(LINK_HASH_KEY1, LINK_HASH_KEY2);
+        for part in title.iter().map(|title| Some(title.content.as_str())).chain(content.iter().copied()) {
+            hasher.write(part.unwrap_or_default().as_bytes());
+            hasher.write_u8(0xff);
+        }
+        let hash = hasher.finish128();
+        format!("{:x}{:x}", hash.h1, hash.h2)
     }
-
-    // Couldn't find a recognised feed within the provided XML stream
-    Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))
 }
@@ -0,0 +1,87 @@
+use std::io::{BufRead, BufReader, Read};
+
+use quick_xml::escape::unescape;
+
+use crate::opml::{Opml, Outline};
+use crate::parser::{ParseErrorKind, ParseFeedError, ParseFeedResult};
+use crate::util::dates::timestamp_rfc2822_lenient;
+use crate::xml::{self, Element};
+
+// Parses an OPML document into the model
+pub(crate) fn parse<R: Read>(source: R) -> ParseFeedResult<Opml> {
+    let element_source = xml::ElementSource::new(BufReader::new(source), None, &xml::XmlConfig::default())?;
+    let root = match element_source.root()? {
+        Some(root) if root.name == "opml" => root,
+        _ => return Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
+    };
+
+    let mut opml = Opml {
+        version: root.attr_value("version").unwrap_or_else(|| "1.0".to_owned()),
+        ..Default::default()
+    };
+    for child in root.children() {
+        let child = child?;
+        match child.name.as_str() {
+            "head" => handle_head(child, &mut opml)?,
+            "body" => opml.outlines = handle_outlines(child)?,
+            _ => {}
+        }
+    }
+
+    Ok(opml)
+}
+
+// Handles the <head>, which has metadata about the document
+fn handle_head<R: BufRead>(element: Element<R>, opml: &mut Opml) -> ParseFeedResult<()> {
+    for child in element.children() {
+        let child = child?;
+        let text = child.child_as_text().map(|text| text.trim().to_owned()).filter(|text| !text.is_empty());
+        match child.name.as_str() {
+            "title" => opml.title = text,
+            "dateCreated" => opml.date_created = text.and_then(|text| timestamp_rfc2822_lenient(&text)),
+            "ownerName" => opml.owner_name = text,
+            "ownerEmail" => opml.owner_email = text,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Handles the <outline> elements within the body or a folder
+fn handle_outlines<R: BufRead>(element: Element<R>) -> ParseFeedResult<Vec<Outline>> {
+    let mut outlines = Vec::new();
+    for child in element.children() {
+        let child = child?;
+        if child.name == "outline" {
+            // Some exporters omit the text, which is required, but do provide a title (or vice versa)
+            let title = attr_value(&child, "title");
+            let text = attr_value(&child, "text").or_else(|| title.clone()).unwrap_or_default();
+            outlines.push(Outline {
+                text,
+                title,
+                outline_type: attr_value(&child, "type"),
+                xml_url: attr_value(&child, "xmlUrl"),
+                html_url: attr_value(&child, "htmlUrl"),
+                outlines: handle_outlines(child)?,
+            });
+        }
+    }
+
+    Ok(outlines)
+}
+
+// Attribute names are matched regardless of case, as exporters vary (e.g. "xmlurl" and "xmlURL"), and empty values are ignored
+//
+// OPML carries all of its data in attributes, so entities (e.g. the "&amp;" in a query string) are decoded here.
+fn attr_value<R: BufRead>(element: &Element<R>, name: &str) -> Option<String> {
+    element
+        .attributes
+        .iter()
+        .find(|attr| attr.name.eq_ignore_ascii_case(name))
+        .map(|attr| match unescape(attr.value.as_bytes()) {
+            Ok(value) => String::from_utf8_lossy(&value).trim().to_owned(),
+            Err(_) => attr.value.trim().to_owned(),
+        })
+        .filter(|value| !value.is_empty())
+}
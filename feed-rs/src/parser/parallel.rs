@@ -0,0 +1,36 @@
+use rayon::prelude::*;
+
+use crate::model;
+use crate::parser::{Builder, ParseFeedResult, Parser};
+
+/// Parses a batch of feeds across threads, returning the result of each in the order they were given
+///
+/// The feeds are shared out across rayon's global thread pool, so a batch may be parsed alongside the caller's own rayon work. Use
+/// `Parser::parse_many()` to parse them with a configured parser.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// let feeds = vec![
+///     br#"<rss version="2.0"><channel><title>One</title></channel></rss>"#.to_vec(),
+///     b"not a feed".to_vec(),
+/// ];
+/// let results = parser::parse_many(feeds);
+/// assert_eq!(results[0].as_ref().unwrap().title.as_ref().unwrap().content, "One");
+/// assert!(results[1].is_err());
+/// ```
+pub fn parse_many<I: IntoIterator<Item = Vec<u8>>>(inputs: I) -> Vec<ParseFeedResult<model::Feed>> {
+    Builder::new().build().parse_many(inputs)
+}
+
+impl Parser {
+    /// Parses a batch of feeds across threads with this parser (see `parser::parse_many()`)
+    ///
+    /// The one parser is shared by all of the threads, so its handlers may be called from several threads at once.
+    pub fn parse_many<I: IntoIterator<Item = Vec<u8>>>(&self, inputs: I) -> Vec<ParseFeedResult<model::Feed>> {
+        // Each input is dropped once it has been parsed, rather than the batch being held until the end
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+        inputs.into_par_iter().map(|input| self.parse(input.as_slice())).collect()
+    }
+}
@@ -0,0 +1,162 @@
+use crate::extensions::podcast::{PodcastChapters, PodcastFunding, PodcastPerson, PodcastSoundbite, PodcastTranscript, PodcastValue, PodcastValueRecipient};
+use crate::model::{Entry, Feed, Person};
+use crate::parser::util::{if_some_then, resolve_uri};
+use crate::parser::{ParseFeedResult, Parser};
+use crate::xml::{Element, NS};
+use std::io::BufRead;
+use std::time::Duration;
+
+// Process <podcast> elements at channel level, recording them in the podcast extension and updating the Feed object as required
+pub(crate) fn handle_podcast_channel_element<R: BufRead>(parser: &Parser, element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+    match element.ns_and_tag() {
+        (Some(NS::Podcast), "funding") => if_some_then(handle_funding(&element), |funding| {
+            feed.podcast.get_or_insert_with(Default::default).funding.push(funding)
+        }),
+
+        (Some(NS::Podcast), "person") => if_some_then(handle_person(&element), |person| {
+            feed.contributors.push(contributor(&person));
+            feed.podcast.get_or_insert_with(Default::default).persons.push(person);
+        }),
+
+        (Some(NS::Podcast), "value") => if_some_then(handle_value(element)?, |value| {
+            feed.podcast.get_or_insert_with(Default::default).value = Some(value)
+        }),
+
+        // Unknown elements are passed to the caller's handler, if registered
+        _ => parser.handle_unknown_element(&element, &mut feed.extensions)?,
+    }
+
+    Ok(())
+}
+
+// Process <podcast> elements at item level, recording them in the podcast extension and updating the Entry object as required
+pub(crate) fn handle_podcast_item_element<R: BufRead>(parser: &Parser, element: Element<R>, entry: &mut Entry) -> ParseFeedResult<()> {
+    match element.ns_and_tag() {
+        (Some(NS::Podcast), "transcript") => if_some_then(handle_transcript(&element), |transcript| {
+            entry.podcast.get_or_insert_with(Default::default).transcripts.push(transcript)
+        }),
+
+        (Some(NS::Podcast), "chapters") => if_some_then(handle_chapters(&element), |chapters| {
+            entry.podcast.get_or_insert_with(Default::default).chapters = Some(chapters)
+        }),
+
+        (Some(NS::Podcast), "person") => if_some_then(handle_person(&element), |person| {
+            entry.contributors.push(contributor(&person));
+            entry.podcast.get_or_insert_with(Default::default).persons.push(person);
+        }),
+
+        (Some(NS::Podcast), "soundbite") => if_some_then(handle_soundbite(&element), |soundbite| {
+            entry.podcast.get_or_insert_with(Default::default).soundbites.push(soundbite)
+        }),
+
+        (Some(NS::Podcast), "value") => if_some_then(handle_value(element)?, |value| {
+            entry.podcast.get_or_insert_with(Default::default).value = Some(value)
+        }),
+
+        // Unknown elements are passed to the caller's handler, if registered
+        _ => parser.handle_unknown_element(&element, &mut entry.extensions)?,
+    }
+
+    Ok(())
+}
+
+// People involved in the show are also its contributors
+fn contributor(person: &PodcastPerson) -> Person {
+    Person {
+        uri: person.href.clone(),
+        ..Person::new(&person.name)
+    }
+}
+
+// Handles <podcast:chapters>, which requires a URL and type
+fn handle_chapters<R: BufRead>(element: &Element<R>) -> Option<PodcastChapters> {
+    Some(PodcastChapters {
+        url: resolve_uri(element.attr_value("url")?, element.xml_base.as_ref()),
+        media_type: element.attr_value("type")?,
+    })
+}
+
+// Handles <podcast:funding>, which requires a URL
+fn handle_funding<R: BufRead>(element: &Element<R>) -> Option<PodcastFunding> {
+    Some(PodcastFunding {
+        url: resolve_uri(element.attr_value("url")?, element.xml_base.as_ref()),
+        text: element.child_as_text().map(|text| text.trim().to_owned()).filter(|text| !text.is_empty()),
+    })
+}
+
+// Handles <podcast:person>, which requires a name
+fn handle_person<R: BufRead>(element: &Element<R>) -> Option<PodcastPerson> {
+    let name = element.child_as_text().map(|text| text.trim().to_owned()).filter(|name| !name.is_empty())?;
+    Some(PodcastPerson {
+        name,
+        role: element.attr_value("role"),
+        group: element.attr_value("group"),
+        img: element.attr_value("img").map(|img| resolve_uri(img, element.xml_base.as_ref())),
+        href: element.attr_value("href").map(|href| resolve_uri(href, element.xml_base.as_ref())),
+    })
+}
+
+// Handles <podcast:soundbite>, which requires a start time and duration in seconds
+fn handle_soundbite<R: BufRead>(element: &Element<R>) -> Option<PodcastSoundbite> {
+    Some(PodcastSoundbite {
+        start_time: parse_seconds(&element.attr_value("startTime")?)?,
+        duration: parse_seconds(&element.attr_value("duration")?)?,
+        title: element.child_as_text().map(|text| text.trim().to_owned()).filter(|title| !title.is_empty()),
+    })
+}
+
+// Handles <podcast:transcript>, which requires a URL and type
+fn handle_transcript<R: BufRead>(element: &Element<R>) -> Option<PodcastTranscript> {
+    Some(PodcastTranscript {
+        url: resolve_uri(element.attr_value("url")?, element.xml_base.as_ref()),
+        media_type: element.attr_value("type")?,
+        language: element.attr_value("language"),
+        rel: element.attr_value("rel"),
+    })
+}
+
+// Handles <podcast:value>, including its recipients
+fn handle_value<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<PodcastValue>> {
+    let mut value = match (element.attr_value("type"), element.attr_value("method")) {
+        (Some(value_type), Some(method)) => PodcastValue {
+            value_type,
+            method,
+            suggested: element.attr_value("suggested"),
+            recipients: Vec::new(),
+        },
+        _ => return Ok(None),
+    };
+
+    for child in element.children() {
+        let child = child?;
+        if let (Some(NS::Podcast), "valueRecipient") = child.ns_and_tag() {
+            if_some_then(handle_value_recipient(&child), |recipient| value.recipients.push(recipient));
+        }
+    }
+
+    Ok(Some(value))
+}
+
+// Handles <podcast:valueRecipient>, which requires a type, address and split
+fn handle_value_recipient<R: BufRead>(element: &Element<R>) -> Option<PodcastValueRecipient> {
+    Some(PodcastValueRecipient {
+        name: element.attr_value("name"),
+        recipient_type: element.attr_value("type")?,
+        address: element.attr_value("address")?,
+        split: element.attr_value("split")?.trim().parse().ok()?,
+        custom_key: element.attr_value("customKey"),
+        custom_value: element.attr_value("customValue"),
+        fee: matches!(element.attr_value("fee"), Some(fee) if fee.trim().eq_ignore_ascii_case("true")),
+    })
+}
+
+// Parses a number of seconds, which may have a fractional part
+//
+// Values that cannot be a duration (negative, not a number, or too large for one) are ignored rather than panicking.
+fn parse_seconds(text: &str) -> Option<Duration> {
+    text.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0 && *seconds < u64::MAX as f64)
+        .map(Duration::from_secs_f64)
+}
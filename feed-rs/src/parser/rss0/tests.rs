@@ -10,17 +10,17 @@ fn test_0_91_spec_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let entry0 = actual.entries.get(0).unwrap();
+    let entry0 = actual.entries.first().unwrap();
     let entry1 = actual.entries.get(1).unwrap();
-    let expected = Feed::new(FeedType::RSS0)
+    let expected = Feed::new(FeedType::RSS0).version("0.91")
         .id(actual.id.as_ref())     // not present in the test data
-        .title(Text::new("WriteTheWeb".into()))
+        .title(Text::new("WriteTheWeb".into()).lang("en-us"))
         .link(Link::new("http://writetheweb.com", None))
-        .description(Text::new("News for web users that write back".into()))
+        .description(Text::new("News for web users that write back".into()).lang("en-us"))
         .language("en-us")
-        .rights(Text::new("Copyright 2000, WriteTheWeb team.".into()))
-        .contributor(Person::new("managingEditor").email("editor@writetheweb.com"))
-        .contributor(Person::new("webMaster").email("webmaster@writetheweb.com"))
+        .rights(Text::new("Copyright 2000, WriteTheWeb team.".into()).lang("en-us"))
+        .contributor(Person::new("managingEditor").email("editor@writetheweb.com").role("managingEditor"))
+        .contributor(Person::new("webMaster").email("webmaster@writetheweb.com").role("webMaster"))
         .logo(Image::new("http://writetheweb.com/images/mynetscape88.gif".into())
             .title("WriteTheWeb")
             .link("http://writetheweb.com")
@@ -29,15 +29,15 @@ fn test_0_91_spec_1() {
             .description("News for web users that write back"))
         .updated(actual.updated)        // not in source data
         .entry(Entry::default()
-            .title(Text::new("Giving the world a pluggable Gnutella".into()))
+            .title(Text::new("Giving the world a pluggable Gnutella".into()).lang("en-us"))
             .link(Link::new("http://writetheweb.com/read.php?item=24", None))
-            .summary(Text::new("WorldOS is a framework on which to build programs that work like Freenet or Gnutella -allowing\n                distributed applications using peer-to-peer routing.\n            ".into()))
+            .summary(Text::new("WorldOS is a framework on which to build programs that work like Freenet or Gnutella -allowing\n                distributed applications using peer-to-peer routing.\n            ".into()).lang("en-us"))
             .id(entry0.id.as_ref())     // not in source data
             .updated(entry0.updated))   // not in source data
         .entry(Entry::default()
-            .title(Text::new("Syndication discussions hot up".into()))
+            .title(Text::new("Syndication discussions hot up".into()).lang("en-us"))
             .link(Link::new("http://writetheweb.com/read.php?item=23", None))
-            .summary(Text::new("After a period of dormancy, the Syndication mailing list has become active again, with\n                contributions from leaders in traditional media and Web syndication.\n            ".into()))
+            .summary(Text::new("After a period of dormancy, the Syndication mailing list has become active again, with\n                contributions from leaders in traditional media and Web syndication.\n            ".into()).lang("en-us"))
             .id(entry1.id.as_ref())     // not in source data
             .updated(entry1.updated)); // not in source data
 
@@ -85,21 +85,32 @@ fn test_0_92_spec_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let entry0 = actual.entries.get(0).unwrap();
+    let entry0 = actual.entries.first().unwrap();
     let entry1 = actual.entries.get(1).unwrap();
     let entry2 = actual.entries.get(2).unwrap();
-    let expected = Feed::new(FeedType::RSS0)
+    let expected = Feed::new(FeedType::RSS0).version("0.92")
         .id(actual.id.as_ref())     // not present in the test data
         .title(Text::new("Dave Winer: Grateful Dead".into()))
         .link(Link::new("http://www.scripting.com/blog/categories/gratefulDead.html", None))
         .description(Text::new("A high-fidelity Grateful Dead song every day. This is where we're experimenting with\n            enclosures on RSS news items that download when you're not using your computer. If it works (it will)\n            it will be the end of the Click-And-Wait multimedia experience on the Internet.\n        ".into()))
         .updated_rfc2822("Fri, 13 Apr 2001 19:23:02 GMT")
-        .contributor(Person::new("managingEditor").email("dave@userland.com (Dave Winer)"))
-        .contributor(Person::new("webMaster").email("dave@userland.com (Dave Winer)"))
+        .docs("http://backend.userland.com/rss092")
+        .cloud(Cloud {
+            domain: "data.ourfavoritesongs.com".into(),
+            port: Some(80),
+            path: "/RPC2".into(),
+            register_procedure: Some("ourFavoriteSongs.rssPleaseNotify".into()),
+            protocol: "xml-rpc".into(),
+        })
+        .contributor(Person::new("Dave Winer").email("dave@userland.com").role("managingEditor"))
+        .contributor(Person::new("Dave Winer").email("dave@userland.com").role("webMaster"))
         .entry(Entry::default()
             .summary(Text::new("Kevin Drennan started a <a href=\"http://deadend.editthispage.com/\">Grateful\n                Dead Weblog</a>. Hey it's cool, he even has a <a href=\"http://deadend.editthispage.com/directory/61\">directory</a>.\n                <i>A Frontier 7 feature.</i>\n            ".into()))
             .id(entry0.id.as_ref())     // not in source data
-            .updated(entry0.updated))   // not in source data
+            .updated(entry0.updated)    // not in source data
+            .source(FeedSource::default()
+                .title(Text::new("Scripting News".into()))
+                .link(Link::new("http://scriptingnews.userland.com/xml/scriptingNews2.xml", None).rel("self"))))
         .entry(Entry::default()
             .summary(Text::new("<a href=\"http://arts.ucsc.edu/GDead/AGDL/other1.html\">The Other One</a>,
                 live instrumental, One From The Vault. Very rhythmic very spacy, you can listen to it many times, and
@@ -119,3 +130,52 @@ fn test_0_92_spec_1() {
     // Check
     assert_eq!(actual, expected);
 }
+
+// Verify a Netscape RSS 0.91 feed (with a DOCTYPE and PICS rating) is parsed, as are the 0.93 and 0.94 drafts
+#[test]
+fn test_0_91_netscape() {
+    let test_data = test::fixture_as_string("rss_0.91_netscape.xml");
+    let actual = parser::parse(test_data.as_bytes()).unwrap();
+
+    let entry0 = actual.entries.first().unwrap();
+    let expected = Feed::new(FeedType::RSS0).version("0.91")
+        .id(actual.id.as_ref()) // not present in the test data
+        .title(Text::new("Scripting News".into()).lang("en-us"))
+        .link(Link::new("http://www.scripting.com/", None))
+        .description(Text::new("A weblog about scripting and stuff like that.".into()).lang("en-us"))
+        .docs("http://my.userland.com/stories/storyReader$11")
+        .text_input(TextInput {
+            title: Some("Search".into()),
+            description: Some("Search the archive".into()),
+            name: "q".into(),
+            link: "http://www.scripting.com/search".into(),
+        })
+        .language("en-us")
+        .rating(MediaRating::new("(PICS-1.1 \"http://www.rsac.org/ratingsv01.html\" l gen true comment \"RSACi North America Server\" for \"http://www.rsac.org\" on \"1996.04.16T08:15-0500\" r (n 0 s 0 v 0 l 0))".into()).urn("pics"))
+        .published_rfc2822("Thu, 08 Jul 1999 07:00:00 GMT")
+        .updated_rfc2822("Thu, 08 Jul 1999 16:20:26 GMT")
+        .logo(
+            Image::new("http://www.scripting.com/gifs/tinyScriptingNews.gif".into())
+                .title("Scripting News")
+                .link("http://www.scripting.com/"),
+        )
+        .update_hints(UpdateHints {
+            skip_hours: vec![1],
+            ..Default::default()
+        })
+        .entry(
+            Entry::default()
+                .title(Text::new("stuff".into()).lang("en-us"))
+                .link(Link::new("http://bar", None))
+                .summary(Text::new("This is an article about some stuff".into()).lang("en-us"))
+                .id(entry0.id.as_ref()) // not in source data
+                .updated(entry0.updated), // not in source data
+        );
+    assert_eq!(actual, expected);
+
+    for version in &["0.93", "0.94", " 0.92 "] {
+        let xml = test_data.replace(r#"<rss version="0.91">"#, &format!(r#"<rss version="{}">"#, version));
+        assert_eq!(parser::detect(xml.as_bytes()), Some(FeedType::RSS0), "{}", version);
+        assert_eq!(parser::parse(xml.as_bytes()).unwrap().title, expected.title, "{}", version);
+    }
+}
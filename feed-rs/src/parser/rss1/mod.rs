@@ -1,57 +1,141 @@
+use std::collections::HashMap;
 use std::io::BufRead;
 
-use chrono::{DateTime, Utc};
-
-use crate::model::{Content, Entry, Feed, FeedType, Image, Link, Person, Text};
-use crate::parser::util::{if_some_then, timestamp_rfc2822_lenient};
-use crate::parser::{util, ParseFeedResult};
-use crate::xml::{Element, NS};
+use mime::Mime;
+
+use crate::model::{Content, Entry, Feed, FeedType, Image, Link, MediaContent, MediaObject, Text};
+use crate::parser::atom;
+use crate::parser::dublincore::{
+    handle_dublin_core_channel_element, handle_dublin_core_item_element, handle_dublin_core_terms_channel_element, handle_dublin_core_terms_item_element,
+};
+use crate::parser::geo::GeoHandler;
+use crate::parser::rss2;
+use crate::parser::syndication::handle_syndication_element;
+use crate::parser::threading::{handle_entry_link, handle_threading_element};
+use crate::parser::util::{if_ok_then_some, if_some_then};
+use crate::parser::{util, ParseFeedError, ParseFeedResult, Parser};
+use crate::xml::{Element, NS, RSS_NAMESPACES};
 
 #[cfg(test)]
 mod tests;
 
 /// Parses an RSS 1.0 feed into our model
-pub(crate) fn parse<R: BufRead>(root: Element<R>) -> ParseFeedResult<Feed> {
+pub(crate) fn parse<R: BufRead>(parser: &Parser, root: Element<R>) -> ParseFeedResult<Feed> {
     let mut feed = Feed::new(FeedType::RSS1);
+    feed.version = Some("1.0".into());
+
+    // The channel lists its items in an rdf:Seq, which defines their order regardless of where they appear in the document
+    let mut sequence = Vec::new();
+    let mut item_resources = Vec::new();
+    // The position of the next entry in the document, counting those that are filtered out or dropped
+    let mut document_index = 0;
 
     for child in root.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "channel") => handle_channel(&mut feed, child)?,
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "channel") => handle_channel(parser, &mut feed, &mut sequence, child)?,
 
             (None, "image") => feed.logo = handle_image(child)?,
 
-            (None, "item") => if_some_then(handle_item(child)?, |entry| feed.entries.push(entry)),
+            (None, "textinput") => feed.text_input = rss2::handle_text_input(child)?,
+
+            (None, "item") => {
+                let resource = rdf_attr_value(&child, "about");
+                let parsed = handle_item(parser, child)?.map(|mut entry| {
+                    entry.document_index = document_index;
+                    entry
+                });
+                document_index += 1;
+                // An entry cut short by the limit on the size of the source is left out
+                if root.is_truncated() {
+                    break;
+                }
+                if_some_then(parsed.filter(|entry| parser.entry_wanted(entry)), |mut entry| {
+                    parser.intern_entry_strings(&mut entry);
+                    feed.entries.push(entry);
+                    item_resources.push(resource);
+                });
+                parser.entry_parsed(root.bytes_consumed(), feed.entries.len());
+                if parser.is_cancelled() {
+                    return Err(ParseFeedError::Cancelled(Box::new(feed)));
+                }
+                if parser.entry_limit_reached(feed.entries.len()) {
+                    break;
+                }
+            }
 
-            // Nothing required for unknown elements
-            _ => {}
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut feed.extensions)?,
         }
     }
 
+    order_items(&mut feed.entries, item_resources, &sequence);
+
     Ok(feed)
 }
 
+// Sorts the entries into the order of the channel's rdf:Seq
+//
+// Items missing from the sequence keep their relative order, after those that were listed
+fn order_items(entries: &mut Vec<Entry>, item_resources: Vec<Option<String>>, sequence: &[String]) {
+    if sequence.is_empty() {
+        return;
+    }
+
+    let positions: HashMap<&str, usize> = sequence.iter().enumerate().map(|(i, resource)| (resource.as_str(), i)).collect();
+    let mut ordered: Vec<(usize, Entry)> = item_resources
+        .iter()
+        .zip(entries.drain(..))
+        .map(|(resource, entry)| {
+            let position = resource.as_deref().and_then(|resource| positions.get(resource)).copied();
+            (position.unwrap_or(usize::MAX), entry)
+        })
+        .collect();
+    ordered.sort_by_key(|(position, _)| *position);
+    entries.extend(ordered.into_iter().map(|(_, entry)| entry));
+}
+
 // Handles the <channel> element
-fn handle_channel<R: BufRead>(feed: &mut Feed, channel: Element<R>) -> ParseFeedResult<()> {
+fn handle_channel<R: BufRead>(parser: &Parser, feed: &mut Feed, sequence: &mut Vec<String>, channel: Element<R>) -> ParseFeedResult<()> {
     for child in channel.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "title") => feed.title = handle_text(child),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "title") => feed.title = parser.text_variant(&mut feed.title_variants, "title", handle_text(child)),
 
             (None, "link") => if_some_then(handle_link(child), |link| feed.links.push(link)),
 
-            (None, "description") => feed.description = handle_text(child),
+            // Atom links (e.g. <atom:link rel="self">) are commonly embedded in RSS feeds
+            (Some(NS::Atom), "link") => if_some_then(atom::handle_link(child), |link| util::add_link(link, &mut feed.links, &mut feed.license)),
 
-            (Some(NS::DublinCore), "creator") => if_some_then(child.child_as_text(), |name| feed.authors.push(Person::new(&name))),
+            (None, "description") => feed.description = parser.text_variant(&mut feed.description_variants, "description", handle_text(child)),
 
-            (Some(NS::DublinCore), "date") => feed.published = handle_timestamp(child),
+            (Some(NS::DublinCore), _) => handle_dublin_core_channel_element(parser, child, feed)?,
 
-            (Some(NS::DublinCore), "language") => feed.language = child.child_as_text(),
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_channel_element(parser, child, feed)?,
 
-            (Some(NS::DublinCore), "rights") => feed.rights = handle_text(child),
+            (Some(NS::Syndication), _) => handle_syndication_element(parser, child, feed)?,
 
-            // Nothing required for unknown elements
-            _ => {}
+            (None, "items") => handle_items(child, sequence)?,
+
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut feed.extensions)?,
+        }
+    }
+
+    Ok(())
+}
+
+// Handles <items>, collecting the resource of each <rdf:li> within the <rdf:Seq>
+fn handle_items<R: BufRead>(element: Element<R>, sequence: &mut Vec<String>) -> ParseFeedResult<()> {
+    for child in element.children() {
+        let child = child?;
+        if let (None, "Seq") = child.ns_and_tag_in(RSS_NAMESPACES) {
+            for li in child.children() {
+                let li = li?;
+                if let (None, "li") = li.ns_and_tag_in(RSS_NAMESPACES) {
+                    if_some_then(rdf_attr_value(&li, "resource"), |resource| sequence.push(resource));
+                }
+            }
         }
     }
 
@@ -64,8 +148,8 @@ fn handle_image<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Image
 
     for child in element.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "url") => if_some_then(child.child_as_text(), |url| image.uri = url),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "url") => if_some_then(child.child_as_text(), |url| image.uri = util::resolve_uri(url, child.xml_base.as_ref())),
 
             (None, "title") => image.title = child.child_as_text(),
 
@@ -81,50 +165,71 @@ fn handle_image<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Image
 }
 
 // Handles <item>
-fn handle_item<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry>> {
+pub(crate) fn handle_item<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<Option<Entry>> {
     let mut entry = Entry::default();
+    let mut geo = GeoHandler::default();
 
     // Per https://www.w3.org/wiki/RssContent:
     //   How to encode content in a RSS 1.0 feed is an unsolved problem, many persons have made different problems and there's no consensus for a definitive solution.
     // But we see it in real feeds, so might as well add it in the same manner as RSS2.0 best practice
     let mut content_encoded: Option<Text> = None;
 
+    // Enclosures (from mod_enclosure) are collected into a media object, as per RSS 2.0
+    let mut media_obj = MediaObject::default();
+
     for child in element.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "title") => entry.title = handle_text(child),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "title") => entry.title = parser.text_variant(&mut entry.title_variants, "title", handle_text(child)),
 
             (None, "link") => if_some_then(handle_link(child), |link| entry.links.push(link)),
 
-            (None, "description") => entry.summary = handle_text(child),
+            (Some(NS::Atom), "link") => handle_entry_link(parser, child, &mut entry)?,
+
+            (None, "description") => entry.summary = parser.text_variant(&mut entry.summary_variants, "description", handle_text(child)),
 
             (Some(NS::Content), "encoded") => content_encoded = util::handle_encoded(child)?,
 
-            (Some(NS::DublinCore), "creator") => if_some_then(child.child_as_text(), |name| entry.authors.push(Person::new(&name))),
+            (Some(NS::DublinCore), _) => handle_dublin_core_item_element(parser, child, &mut entry)?,
 
-            (Some(NS::DublinCore), "date") => entry.published = handle_timestamp(child),
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_item_element(parser, child, &mut entry)?,
 
-            (Some(NS::DublinCore), "description") => {
-                if entry.summary.is_none() {
-                    entry.summary = handle_text(child)
-                }
-            }
+            (Some(NS::Enclosure), "enclosure") => handle_enclosure(child, &mut media_obj),
 
-            (Some(NS::DublinCore), "rights") => entry.rights = handle_text(child),
+            // GeoRSS and W3C Basic Geo elements populate the location
+            (Some(NS::GeoRSS), _) | (Some(NS::W3CGeo), _) => geo.handle_element(parser, child, &mut entry.extensions)?,
 
-            // Nothing required for unknown elements
-            _ => {}
+            // Threading, Slash and Well-Formed Web elements populate the threading extension
+            (Some(NS::Threading), _) | (Some(NS::Slash), _) | (Some(NS::WellFormedWeb), _) => handle_threading_element(parser, child, &mut entry)?,
+
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut entry.extensions)?,
         }
     }
 
+    // The source of the entry is kept if enabled with `parser::Builder::raw_entries()`
+    entry.raw = element.raw();
+    entry.geo = geo.finish();
+
+    // The language is from "dc:language", or else the xml:lang declared on the item or inherited from the channel
+    if entry.language.is_none() {
+        entry.language = element.xml_lang.clone();
+    }
+
+    // If we found any enclosures, then emit them
+    if media_obj.has_content() {
+        entry.media.push(media_obj);
+    }
+
     // Use content_encoded if we didn't find an enclosure above
     if entry.content.is_none() {
         if let Some(ce) = content_encoded {
             entry.content = Some(Content {
                 body: Some(ce.content),
-                content_type: ce.content_type,
+                content_type: mime::TEXT_HTML,
                 length: None,
                 src: ce.src.map(|s| Link::new(s, element.xml_base.as_ref())),
+                lang: ce.lang,
             });
         }
     }
@@ -138,6 +243,37 @@ fn handle_item<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry>
     })
 }
 
+// Handles <enc:enclosure> from http://www.rssboard.org/rss-1-0-modules-enclosure
+fn handle_enclosure<R: BufRead>(element: Element<R>, media_obj: &mut MediaObject) {
+    let mut content = MediaContent::new();
+
+    // Attributes are qualified (e.g. "rdf:resource", "enc:type") but the prefixes vary between feeds
+    for attr in &element.attributes {
+        match attr.name.split(':').next_back() {
+            Some("resource") => content.url = util::parse_uri(&attr.value, element.xml_base.as_ref()),
+            Some("length") => content.size = util::parse_length(&attr.value),
+            Some("type") => if_ok_then_some(attr.value.parse::<Mime>(), |mime| content.content_type = mime),
+
+            // Nothing required for unknown attributes
+            _ => {}
+        }
+    }
+
+    // Wrap in a media object if we have a sufficient definition of a media object
+    if content.url.is_some() {
+        media_obj.content.push(content);
+    }
+}
+
+// Finds an RDF attribute (e.g. "rdf:about") - the prefix varies between feeds so only the local name is matched
+fn rdf_attr_value<R: BufRead>(element: &Element<R>, name: &str) -> Option<String> {
+    element
+        .attributes
+        .iter()
+        .find(|attr| attr.name.split(':').next_back() == Some(name))
+        .map(|attr| attr.value.clone())
+}
+
 // Handles <link>
 fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
     element.child_as_text().map(|s| Link::new(s, element.xml_base.as_ref()))
@@ -145,14 +281,8 @@ fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
 
 // Handles <title>, <description>
 fn handle_text<R: BufRead>(element: Element<R>) -> Option<Text> {
-    element.child_as_text().map(Text::new)
-}
-
-// Handles date/time
-fn handle_timestamp<R: BufRead>(element: Element<R>) -> Option<DateTime<Utc>> {
-    if let Some(text) = element.child_as_text() {
-        timestamp_rfc2822_lenient(&text)
-    } else {
-        None
-    }
+    element.child_as_text().map(|content| Text {
+        lang: element.xml_lang.clone(),
+        ..Text::new(content)
+    })
 }
@@ -1,5 +1,11 @@
-use crate::model::{Entry, Feed, FeedType, Image, Link, Person, Text};
-use crate::parser;
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc};
+
+use crate::extensions::dublincore::DublinCoreExtension;
+use crate::extensions::ExtensionElement;
+use crate::model::{Category, Entry, Feed, FeedType, Image, Link, MediaContent, MediaObject, Person, Text, TextInput, UpdateHints, UpdatePeriod};
+use crate::parser::{self, Warning};
 use crate::util::test;
 
 // Example from the web
@@ -10,34 +16,53 @@ fn test_example_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let entry0 = actual.entries.get(0).unwrap();
+    let entry0 = actual.entries.first().unwrap();
     let entry1 = actual.entries.get(1).unwrap();
     let expected = Feed::new(FeedType::RSS1)
+        .version("1.0")
         .id(actual.id.as_ref()) // not present in the test data
-        .title(Text::new("Feed title".into()))
+        .title(Text::new("Feed title".into()).lang("ja"))
         .link(Link::new("http://www.example.com/main.html", None))
-        .description(Text::new("Site description".into()))
+        .description(Text::new("Site description".into()).lang("ja"))
         .updated(actual.updated) // not present in the test data
         .published_rfc3339("2017-06-13T09:00:00Z")
         .language("ja")
+        .dublin_core(DublinCoreExtension {
+            dates: vec!["2017-06-13T09:00:00+00:00".into()],
+            languages: vec!["ja".into()],
+            ..Default::default()
+        })
         .entry(
             Entry::default()
+                .language("ja")
                 .id("7d61c42a2d8ecf2289e789e1fb2035d1") // hash of the link
                 .updated(entry0.updated) // not present in the test data
-                .title(Text::new("記事1のタイトル".into()))
+                .title(Text::new("記事1のタイトル".into()).lang("ja"))
                 .link(Link::new("記事1のURL", None))
-                .summary(Text::new("記事1の内容".into()))
+                .summary(Text::new("記事1の内容".into()).lang("ja"))
                 .published_rfc3339("2017-06-13T09:00:00Z")
-                .author(Person::new("記事1の作者名")),
+                .author(Person::new("記事1の作者名"))
+                .dublin_core(DublinCoreExtension {
+                    creators: vec!["記事1の作者名".into()],
+                    dates: vec!["2017-06-13T09:00:00+00:00".into()],
+                    ..Default::default()
+                }),
         )
         .entry(
             Entry::default()
+                .language("ja")
                 .id("e342c1b080da9ffbfd10c0a6ba49395f") // hash of the link
                 .updated(entry1.updated) // not present in the test data
-                .title(Text::new("記事2のタイトル".into()))
+                .title(Text::new("記事2のタイトル".into()).lang("ja"))
                 .link(Link::new("記事2のURL", None))
-                .summary(Text::new("記事2の内容".into()))
-                .author(Person::new("記事2の作者名")),
+                .summary(Text::new("記事2の内容".into()).lang("ja"))
+                .author(Person::new("記事2の作者名"))
+                // The invalid date is retained in the extension
+                .dublin_core(DublinCoreExtension {
+                    creators: vec!["記事2の作者名".into()],
+                    dates: vec!["2017-06-13T03:18:00+00:0".into()],
+                    ..Default::default()
+                }),
         );
 
     // Check
@@ -70,13 +95,19 @@ fn test_spec_1() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let entry0 = actual.entries.get(0).unwrap();
+    let entry0 = actual.entries.first().unwrap();
     let entry1 = actual.entries.get(1).unwrap();
-    let expected = Feed::new(FeedType::RSS1)
+    let expected = Feed::new(FeedType::RSS1).version("1.0")
         .id(actual.id.as_ref())     // not present in the test data
         .title(Text::new("XML.com".into()))
         .link(Link::new("http://xml.com/pub", None))
         .description(Text::new("\n            XML.com features a rich mix of information and services\n            for the XML community.\n        ".into()))
+        .text_input(TextInput {
+            title: Some("Search XML.com".into()),
+            description: Some("Search XML.com's XML collection".into()),
+            name: "s".into(),
+            link: "http://search.xml.com".into(),
+        })
         .logo(Image::new("http://xml.com/universal/images/xml_tiny.gif".into())
             .link("http://www.xml.com")
             .title("XML.com"))
@@ -106,12 +137,18 @@ fn test_spec_2() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let entry0 = actual.entries.get(0).unwrap();
-    let expected = Feed::new(FeedType::RSS1)
+    let entry0 = actual.entries.first().unwrap();
+    let expected = Feed::new(FeedType::RSS1).version("1.0")
         .id(actual.id.as_ref()) // not present in the test data
         .title(Text::new("Meerkat".into()))
         .link(Link::new("http://meerkat.oreillynet.com", None))
         .description(Text::new("Meerkat: An Open Wire Service".into()))
+        .text_input(TextInput {
+            title: Some("Search Meerkat".into()),
+            description: Some("Search Meerkat's RSS Database...".into()),
+            name: "s".into(),
+            link: "http://meerkat.oreillynet.com/".into(),
+        })
         .logo(
             Image::new("http://meerkat.oreillynet.com/icons/meerkat-powered.jpg".into())
                 .link("http://meerkat.oreillynet.com")
@@ -120,8 +157,25 @@ fn test_spec_2() {
         .updated(actual.updated) // not present in the test data
         .author(Person::new("Rael Dornfest (mailto:rael@oreilly.com)"))
         .rights(Text::new("Copyright © 2000 O'Reilly & Associates, Inc.".into()))
+        .published_rfc3339("2000-01-01T12:00:00+00:00")
+        .dublin_core(DublinCoreExtension {
+            creators: vec!["Rael Dornfest (mailto:rael@oreilly.com)".into()],
+            dates: vec!["2000-01-01T12:00+00:00".into()],
+            publishers: vec!["The O'Reilly Network".into()],
+            rights: vec!["Copyright © 2000 O'Reilly & Associates, Inc.".into()],
+            ..Default::default()
+        })
+        .update_hints(UpdateHints {
+            update_period: Some(UpdatePeriod::Hourly),
+            update_frequency: Some(2),
+            update_base: Some(Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap()),
+            ..Default::default()
+        })
         .entry(
             Entry::default()
+                .extension("http://purl.org/rss/1.0/modules/company/", ExtensionElement::new("name").value("XML.com"))
+                .extension("http://purl.org/rss/1.0/modules/company/", ExtensionElement::new("market").value("NASDAQ"))
+                .extension("http://purl.org/rss/1.0/modules/company/", ExtensionElement::new("symbol").value("XML"))
                 .id("acf7c86547d5d594af6d8f3327e84b06") // hash of the link
                 .updated(entry0.updated) // not present in the test data
                 .title(Text::new("XML: A Disruptive Technology".into()))
@@ -130,10 +184,106 @@ fn test_spec_2() {
                     "\n            XML is placing increasingly heavy loads on the existing technical\n            infrastructure of the Internet.\n        "
                         .into(),
                 ))
-                .author(Person::new("Simon St.Laurent (mailto:simonstl@simonstl.com)".into()))
-                .rights(Text::new("Copyright © 2000 O'Reilly & Associates, Inc.".into())),
+                .author(Person::new("Simon St.Laurent (mailto:simonstl@simonstl.com)"))
+                .rights(Text::new("Copyright © 2000 O'Reilly & Associates, Inc.".into()))
+                .category(Category::new("XML"))
+                .dublin_core(DublinCoreExtension {
+                    creators: vec!["Simon St.Laurent (mailto:simonstl@simonstl.com)".into()],
+                    descriptions: vec![
+                        "\n            XML is placing increasingly heavy loads on the existing technical\n            infrastructure of the Internet.\n        ".into(),
+                    ],
+                    publishers: vec!["The O'Reilly Network".into()],
+                    rights: vec!["Copyright © 2000 O'Reilly & Associates, Inc.".into()],
+                    subjects: vec!["XML".into()],
+                    ..Default::default()
+                }),
         );
 
     // Check
     assert_eq!(actual, expected);
 }
+
+// Enclosures from mod_enclosure (http://www.rssboard.org/rss-1-0-modules-enclosure)
+#[test]
+fn test_enclosure() {
+    let test_data = test::fixture_as_string("rss_1.0_enclosure.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    // The enclosure should be mapped to a media object, as with RSS 2.0
+    let expected = vec![MediaObject::default().content(
+        MediaContent::new()
+            .url("http://www.example.com/episodes/1.mp3")
+            .content_type("audio/mpeg")
+            .size(12216320),
+    )];
+    assert_eq!(feed.entries[0].media, expected);
+}
+
+// Items are ordered by the channel's rdf:Seq, regardless of where they appear in the document
+#[test]
+fn test_seq_order() {
+    let test_data = test::fixture_as_string("rss_1.0_seq_order.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    // Items missing from the sequence follow those that were listed
+    let titles: Vec<&str> = feed.entries.iter().map(|entry| entry.title.as_ref().unwrap().content.as_str()).collect();
+    assert_eq!(titles, vec!["First", "Second", "Third", "Unlisted"]);
+
+    // The position in the document is kept, and is the order the entries are streamed in
+    let indexes: Vec<usize> = feed.entries.iter().map(|entry| entry.document_index).collect();
+    let streamed: Vec<(usize, String)> = parser::parse_entries(test_data.as_bytes())
+        .map(|entry| entry.unwrap())
+        .map(|entry| (entry.document_index, entry.title.unwrap().content))
+        .collect();
+    let mut ordered: Vec<(usize, String)> = feed
+        .entries
+        .into_iter()
+        .map(|entry| (entry.document_index, entry.title.unwrap().content))
+        .collect();
+    ordered.sort();
+    assert_ne!(indexes, vec![0, 1, 2, 3]);
+    assert_eq!(streamed, ordered);
+}
+
+// Dates from the Dublin Core terms (http://purl.org/dc/terms/) are used, as feeds often have no others
+#[test]
+fn test_dcterms_dates() {
+    let test_data = test::fixture_as_string("rss_1.0_dcterms.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+    assert_eq!(feed.updated, Some(Utc.with_ymd_and_hms(2021, 3, 4, 9, 30, 0).unwrap()));
+
+    // "modified" and "created" map on to the updated and published times
+    let entry = &feed.entries[0];
+    assert_eq!(entry.updated, Some(Utc.with_ymd_and_hms(2021, 3, 1, 11, 0, 0).unwrap()));
+    assert_eq!(entry.published, Some(Utc.with_ymd_and_hms(2021, 2, 1, 0, 0, 0).unwrap()));
+
+    // The other terms are recorded in the extensions
+    let terms: Vec<&str> = entry.extensions["http://purl.org/dc/terms/"]
+        .iter()
+        .map(|element| element.name.as_str())
+        .collect();
+    assert_eq!(terms, vec!["extent"]);
+
+    // "dc:date" takes precedence over "dcterms:issued" as it came first
+    let entry = &feed.entries[1];
+    assert_eq!(entry.updated, None);
+    assert_eq!(entry.published, Some(Utc.with_ymd_and_hms(2021, 1, 15, 8, 0, 0).unwrap()));
+
+    // Only the terms that are not handled are reported as unknown
+    let unknown = Arc::new(Mutex::new(Vec::new()));
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let (handler_unknown, handler_warnings) = (unknown.clone(), warnings.clone());
+    let parser = parser::Builder::new()
+        .unknown_element_handler(move |path, _| handler_unknown.lock().unwrap().push(path.to_vec()))
+        .warning_handler(move |warning| handler_warnings.lock().unwrap().push(warning.clone()))
+        .build();
+    parser.parse(test_data.as_bytes()).unwrap();
+    assert_eq!(*unknown.lock().unwrap(), vec![vec!["RDF".to_owned(), "item".to_owned(), "extent".to_owned()]]);
+    assert_eq!(
+        *warnings.lock().unwrap(),
+        vec![Warning::UnknownElement {
+            namespace: "http://purl.org/dc/terms/".into(),
+            element: "extent".into()
+        }]
+    );
+}
@@ -1,47 +1,66 @@
 use std::io::BufRead;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Utc, Weekday};
 use mime::Mime;
 
-use crate::model::{Category, Content, Entry, Feed, FeedType, Generator, Image, Link, MediaContent, MediaObject, Person, Text};
+use crate::model::{
+    shared_string, Category, Cloud, Content, Entry, Feed, FeedSource, FeedType, Generator, Image, Link, MediaContent, MediaObject, MediaRating, Person, Text,
+    TextInput, UpdateHints,
+};
+use crate::parser::atom;
+use crate::parser::dublincore::{
+    handle_dublin_core_channel_element, handle_dublin_core_item_element, handle_dublin_core_terms_channel_element, handle_dublin_core_terms_item_element,
+};
+use crate::parser::geo::GeoHandler;
 use crate::parser::itunes::{handle_itunes_channel_element, handle_itunes_item_element};
 use crate::parser::mediarss;
 use crate::parser::mediarss::handle_media_element;
-use crate::parser::util::{if_ok_then_some, if_some_then, timestamp_rfc2822_lenient};
-use crate::parser::{util, ParseErrorKind, ParseFeedError, ParseFeedResult};
-use crate::xml::{Element, NS};
+use crate::parser::podcast::{handle_podcast_channel_element, handle_podcast_item_element};
+use crate::parser::syndication::handle_syndication_element;
+use crate::parser::threading::{handle_entry_link, handle_threading_element};
+use crate::parser::util::{if_ok_then_some, if_some_then};
+use crate::parser::{util, ParseErrorKind, ParseFeedError, ParseFeedResult, Parser, PubDate};
+use crate::util::dates::TimestampFormat;
+use crate::xml::{Element, NS, RSS_NAMESPACES};
 
 #[cfg(test)]
 mod tests;
 
 /// Parses an RSS 2.0 feed into our model
-pub(crate) fn parse<R: BufRead>(root: Element<R>) -> ParseFeedResult<Feed> {
+pub(crate) fn parse<R: BufRead>(parser: &Parser, root: Element<R>) -> ParseFeedResult<Feed> {
     // Only expecting a channel element
     let found_channel = root.children().find(|result| match result {
-        Ok(element) => &element.name == "channel",
+        Ok(element) => element.ns_and_tag_in(RSS_NAMESPACES) == (&None, "channel"),
         Err(_) => true,
     });
     if let Some(channel) = found_channel {
-        handle_channel(channel?)
+        let mut feed = handle_channel(parser, channel?)?;
+        feed.version = root.attr_value("version").map(|version| version.trim().to_owned());
+        Ok(feed)
     } else {
         Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))
     }
 }
 
 // Handles the <channel> element
-fn handle_channel<R: BufRead>(channel: Element<R>) -> ParseFeedResult<Feed> {
+fn handle_channel<R: BufRead>(parser: &Parser, channel: Element<R>) -> ParseFeedResult<Feed> {
     let mut feed = Feed::new(FeedType::RSS2);
 
+    // The position of the next entry in the document, counting those that are filtered out or dropped
+    let mut document_index = 0;
     for child in channel.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "title") => feed.title = handle_text(child),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "title") => feed.title = parser.text_variant(&mut feed.title_variants, "title", handle_text(child)),
 
             (None, "link") => if_some_then(handle_link(child), |link| feed.links.push(link)),
 
-            (None, "description") => feed.description = handle_text(child),
+            // Atom links (e.g. <atom:link rel="self">) are commonly embedded in RSS feeds
+            (Some(NS::Atom), "link") => if_some_then(atom::handle_link(child), |link| util::add_link(link, &mut feed.links, &mut feed.license)),
 
-            (None, "language") => feed.language = child.child_as_text().map(|text| text.to_lowercase()),
+            (None, "description") => feed.description = parser.text_variant(&mut feed.description_variants, "description", handle_text(child)),
+
+            (None, "language") => feed.language = child.child_as_text(),
 
             (None, "copyright") => feed.rights = handle_text(child),
 
@@ -49,31 +68,84 @@ fn handle_channel<R: BufRead>(channel: Element<R>) -> ParseFeedResult<Feed> {
 
             (None, "webMaster") => if_some_then(handle_contact("webMaster", child), |person| feed.contributors.push(person)),
 
-            (None, "pubDate") => feed.published = handle_timestamp(child),
+            (None, "pubDate") => feed.published = handle_timestamp(parser, child)?,
 
             // Some feeds have "updated" instead of "lastBuildDate"
-            (None, "lastBuildDate") | (None, "updated") => feed.updated = handle_timestamp(child),
+            (None, "lastBuildDate") | (None, "updated") => feed.updated = handle_timestamp(parser, child)?,
 
             (None, "category") => if_some_then(handle_category(child), |category| feed.categories.push(category)),
 
             (None, "generator") => feed.generator = handle_generator(child),
 
+            (None, "docs") => feed.docs = child.child_as_text().map(|text| text.trim().to_owned()).filter(|docs| !docs.is_empty()),
+
+            (None, "cloud") => feed.cloud = handle_cloud(&child),
+
             (None, "ttl") => if_some_then(child.child_as_text(), |text| if_ok_then_some(text.parse::<u32>(), |ttl| feed.ttl = ttl)),
 
             (None, "image") => feed.logo = handle_image(child)?,
 
-            (None, "item") => if_some_then(handle_item(child)?, |item| feed.entries.push(item)),
+            // The PICS rating from RSS 0.91, which takes precedence over those from extensions as it has a defined scheme
+            (None, "rating") => if_some_then(child.child_as_text(), |text| {
+                feed.rating = Some(MediaRating::new(text.trim().to_owned()).urn("pics"))
+            }),
 
-            (Some(NS::Itunes), _) => handle_itunes_channel_element(child, &mut feed)?,
+            // RSS 0.91 has "textinput" rather than "textInput"
+            (None, "textInput") | (None, "textinput") => feed.text_input = handle_text_input(child)?,
 
-            // Nothing required for unknown elements
-            _ => {}
+            (None, "skipHours") => handle_skip_hours(child, feed.update_hints.get_or_insert_with(Default::default))?,
+
+            (None, "skipDays") => handle_skip_days(child, feed.update_hints.get_or_insert_with(Default::default))?,
+
+            (None, "item") => {
+                let parsed = handle_item(parser, child)?.map(|mut item| {
+                    item.document_index = document_index;
+                    item
+                });
+                document_index += 1;
+                // An entry cut short by the limit on the size of the source is left out
+                if channel.is_truncated() {
+                    break;
+                }
+                if_some_then(parsed.filter(|item| parser.entry_wanted(item)), |mut item| {
+                    parser.intern_entry_strings(&mut item);
+                    feed.entries.push(item)
+                });
+                parser.entry_parsed(channel.bytes_consumed(), feed.entries.len());
+                if parser.is_cancelled() {
+                    return Err(ParseFeedError::Cancelled(Box::new(feed)));
+                }
+                if parser.entry_limit_reached(feed.entries.len()) {
+                    break;
+                }
+            }
+
+            (Some(NS::Itunes), _) => handle_itunes_channel_element(parser, child, &mut feed)?,
+
+            (Some(NS::Podcast), _) => handle_podcast_channel_element(parser, child, &mut feed)?,
+
+            (Some(NS::DublinCore), _) => handle_dublin_core_channel_element(parser, child, &mut feed)?,
+
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_channel_element(parser, child, &mut feed)?,
+
+            (Some(NS::Syndication), _) => handle_syndication_element(parser, child, &mut feed)?,
+
+            (Some(NS::CreativeCommons), "license") => handle_license(child, &mut feed.license),
+
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut feed.extensions)?,
         }
     }
 
-    // RSS 2.0 defines <lastBuildDate> on an item as optional so for completeness we set them to the updated date of the feed
-    for entry in feed.entries.iter_mut() {
-        entry.updated = feed.updated;
+    // The spec requires a title, link and description, but feeds without them are still usable unless strict
+    if feed.title.is_none() {
+        parser.missing_content("channel.title")?;
+    }
+    if feed.links.is_empty() {
+        parser.missing_content("channel.link")?;
+    }
+    if feed.description.is_none() {
+        parser.missing_content("channel.description")?;
     }
 
     Ok(feed)
@@ -83,20 +155,34 @@ fn handle_channel<R: BufRead>(channel: Element<R>) -> ParseFeedResult<Feed> {
 fn handle_category<R: BufRead>(element: Element<R>) -> Option<Category> {
     element.child_as_text().map(|text| {
         let mut category = Category::new(&text);
-        category.scheme = element.attr_value("domain");
+        category.scheme = element.attr_value("domain").map(shared_string);
         category
     })
 }
 
-// Handles <managingEditor> and <webMaster>
-fn handle_contact<R: BufRead>(role: &str, element: Element<R>) -> Option<Person> {
-    element.child_as_text().map(|email| {
-        let mut person = Person::new(role);
-        person.email = Some(email);
-        person
+// Handles <source>, with the name and URL of the channel the item came from
+fn handle_source<R: BufRead>(element: Element<R>) -> Option<FeedSource> {
+    let title = element.child_as_text().map(|text| text.trim().to_owned()).filter(|title| !title.is_empty());
+    let link = element.attr_value("url").map(|url| Link {
+        rel: Some("self".into()),
+        ..Link::new(url.trim(), element.xml_base.as_ref())
+    });
+    if title.is_none() && link.is_none() {
+        return None;
+    }
+
+    Some(FeedSource {
+        title: title.map(Text::new),
+        links: link.into_iter().collect(),
+        ..Default::default()
     })
 }
 
+// Handles <managingEditor>, <webMaster> and <author>
+fn handle_contact<R: BufRead>(role: &str, element: Element<R>) -> Option<Person> {
+    element.child_as_text().and_then(|text| util::parse_contact(role, &text))
+}
+
 fn handle_generator<R: BufRead>(element: Element<R>) -> Option<Generator> {
     element.child_as_text().map(|c| {
         let mut generator = Generator::new(&c);
@@ -120,7 +206,7 @@ fn handle_enclosure<R: BufRead>(element: Element<R>, media_obj: &mut MediaObject
         let tag_name = attr.name.as_str();
         match tag_name {
             "url" => content.url = util::parse_uri(&attr.value, element.xml_base.as_ref()),
-            "length" => content.size = attr.value.parse::<u64>().ok(),
+            "length" => content.size = util::parse_length(&attr.value),
             "type" => if_ok_then_some(attr.value.parse::<Mime>(), |mime| content.content_type = mime),
 
             // Nothing required for unknown elements
@@ -140,28 +226,16 @@ fn handle_image<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Image
 
     for child in element.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "url") => if_some_then(child.child_as_text(), |url| image.uri = url),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "url") => if_some_then(child.child_as_text(), |url| image.uri = util::resolve_uri(url, child.xml_base.as_ref())),
 
             (None, "title") => image.title = child.child_as_text(),
 
             (None, "link") => if_some_then(child.child_as_text(), |uri| image.link = Some(Link::new(uri, element.xml_base.as_ref()))),
 
-            (None, "width") => if_some_then(child.child_as_text(), |width| {
-                if let Ok(width) = width.parse::<u32>() {
-                    if width > 0 && width <= 144 {
-                        image.width = Some(width)
-                    }
-                }
-            }),
+            (None, "width") => image.width = child.child_as_text().and_then(|width| handle_image_dimension(&width, 144)),
 
-            (None, "height") => if_some_then(child.child_as_text(), |height| {
-                if let Ok(height) = height.parse::<u32>() {
-                    if height > 0 && height <= 400 {
-                        image.height = Some(height)
-                    }
-                }
-            }),
+            (None, "height") => image.height = child.child_as_text().and_then(|height| handle_image_dimension(&height, 400)),
 
             (None, "description") => image.description = child.child_as_text(),
 
@@ -174,21 +248,23 @@ fn handle_image<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Image
     Ok(if !image.uri.is_empty() { Some(image) } else { None })
 }
 
-// Handles <content:encoded>
+// Handles <content:encoded>, which is always the HTML form of the content
 fn handle_content_encoded<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Content>> {
-    let src = element
-        .xml_base
-        .as_ref()
-        .map(|xml_base| Link::new(xml_base.to_string(), element.xml_base.as_ref()));
+    let src = element.xml_base.as_ref().map(|xml_base| Link::new(xml_base, element.xml_base.as_ref()));
 
     Ok(element.children_as_string()?.map(|string| Content {
         body: Some(string),
-        content_type: mime::TEXT_PLAIN,
+        content_type: mime::TEXT_HTML,
         src,
         ..Default::default()
     }))
 }
 
+// Handles the <width> or <height> of an <image>, clamping it to the maximum permitted by the spec
+fn handle_image_dimension(text: &str, max: u32) -> Option<u32> {
+    text.trim().parse::<u32>().ok().filter(|size| *size > 0).map(|size| size.min(max))
+}
+
 // Handles <item>
 //
 // There is some complexity around "enclosure", "content:encoded", MediaRSS and Itunes support
@@ -198,40 +274,73 @@ fn handle_content_encoded<R: BufRead>(element: Element<R>) -> ParseFeedResult<Op
 //
 // Handling is as follows:
 // * "enclosure" is treated as if it was a MediaRSS MediaContent element and wrapped in a MediaObject
-// * "content:encoded" is mapped to the content field of an Entry
+// * "content:encoded" is mapped to the content field of an Entry as HTML, with "description" remaining the summary
 // * MediaRSS elements without a parent group are added to a default MediaObject
-// * Itunes elements are added to the default MediaObject
-fn handle_item<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry>> {
+// * Itunes elements are recorded in the iTunes extension, and also added to the default MediaObject
+pub(crate) fn handle_item<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<Option<Entry>> {
     let mut entry = Entry::default();
+    let mut geo = GeoHandler::default();
 
     // Create a default media object e.g. MediaRSS elements that are not within a "<media:group>", enclosures etc
     let mut media_obj = MediaObject::default();
 
     for child in element.children() {
         let child = child?;
-        match child.ns_and_tag() {
-            (None, "title") => entry.title = handle_text(child),
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "title") => entry.title = parser.text_variant(&mut entry.title_variants, "title", handle_text(child)),
 
             (None, "link") => if_some_then(handle_link(child), |link| entry.links.push(link)),
 
-            (None, "description") => entry.summary = util::handle_encoded(child)?,
+            (Some(NS::Atom), "link") => handle_entry_link(parser, child, &mut entry)?,
+
+            (None, "description") => entry.summary = parser.text_variant(&mut entry.summary_variants, "description", util::handle_encoded(child)?),
 
             (None, "author") => if_some_then(handle_contact("author", child), |person| entry.authors.push(person)),
 
             (None, "category") => if_some_then(handle_category(child), |category| entry.categories.push(category)),
 
-            (None, "guid") => if_some_then(child.child_as_text(), |guid| entry.id = guid),
+            (None, "guid") => handle_guid(child, &mut entry),
 
             (None, "enclosure") => handle_enclosure(child, &mut media_obj),
 
-            (None, "pubDate") => entry.published = handle_timestamp(child),
+            (None, "pubDate") => {
+                let timestamp = handle_timestamp(parser, child)?;
+                match parser.pub_date {
+                    PubDate::Published => entry.published = timestamp,
+                    PubDate::Updated => entry.updated = timestamp,
+                    PubDate::Both => {
+                        entry.published = timestamp;
+                        entry.updated = timestamp;
+                    }
+                }
+            }
+
+            (None, "source") => entry.source = handle_source(child),
 
             (Some(NS::Content), "encoded") => entry.content = handle_content_encoded(child)?,
 
-            (Some(NS::DublinCore), "creator") => if_some_then(child.child_as_text(), |name| entry.authors.push(Person::new(&name))),
+            (Some(NS::DublinCore), _) => handle_dublin_core_item_element(parser, child, &mut entry)?,
+
+            (Some(NS::DublinCoreTerms), _) => handle_dublin_core_terms_item_element(parser, child, &mut entry)?,
+
+            (Some(NS::CreativeCommons), "license") => handle_license(child, &mut entry.license),
+
+            // MediaRSS titles and descriptions populate the default MediaObject, and are also variants of the entry's own
+            (Some(NS::MediaRSS), "title") => media_obj.title = parser.text_variant(&mut entry.title_variants, "media:title", mediarss::handle_text(child)?),
+            (Some(NS::MediaRSS), "description") => {
+                media_obj.description = parser.text_variant(&mut entry.summary_variants, "media:description", mediarss::handle_text(child)?)
+            }
+
+            // GeoRSS and W3C Basic Geo elements populate the location
+            (Some(NS::GeoRSS), _) | (Some(NS::W3CGeo), _) => geo.handle_element(parser, child, &mut entry.extensions)?,
+
+            // Threading, Slash and Well-Formed Web elements populate the threading extension
+            (Some(NS::Threading), _) | (Some(NS::Slash), _) | (Some(NS::WellFormedWeb), _) => handle_threading_element(parser, child, &mut entry)?,
+
+            // Itunes elements populate the iTunes extension and the default MediaObject
+            (Some(NS::Itunes), _) => handle_itunes_item_element(parser, child, &mut entry, &mut media_obj)?,
 
-            // Itunes elements populate the default MediaObject
-            (Some(NS::Itunes), _) => handle_itunes_item_element(child, &mut media_obj)?,
+            (Some(NS::Podcast), _) => handle_podcast_item_element(parser, child, &mut entry)?,
 
             // MediaRSS group creates a new object for this group of elements
             (Some(NS::MediaRSS), "group") => if_some_then(mediarss::handle_media_group(child)?, |obj| entry.media.push(obj)),
@@ -239,34 +348,157 @@ fn handle_item<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Entry>
             // MediaRSS tags that are not grouped are parsed into the default object
             (Some(NS::MediaRSS), _) => handle_media_element(child, &mut media_obj)?,
 
-            // Nothing required for unknown elements
-            _ => {}
+            // Unknown elements are passed to the caller's handler, if registered
+            _ => parser.handle_unknown_element(&child, &mut entry.extensions)?,
         }
     }
 
+    // A permalink guid doubles as the link of items that do not have one
+    if entry.links.is_empty() && entry.is_permalink == Some(true) {
+        let uri = util::parse_uri(&entry.id, element.xml_base.as_ref()).filter(|uri| matches!(uri.scheme(), "http" | "https"));
+        if_some_then(uri, |uri| entry.links.push(Link::new(uri, None)));
+    }
+
+    // The source of the entry is kept if enabled with `parser::Builder::raw_entries()`
+    entry.raw = element.raw();
+    entry.geo = geo.finish();
+
+    // The language is from "dc:language", or else the xml:lang declared on the item or inherited from the channel
+    if entry.language.is_none() {
+        entry.language = element.xml_lang.clone();
+    }
+
     // If a media:content item with content exists, then emit it
     if media_obj.has_content() {
         entry.media.push(media_obj);
     }
 
+    // The spec requires at least one of the title or description, which an item cut short by the limit on the source may have lost
+    if entry.title.is_none() && entry.summary.is_none() && !element.is_truncated() {
+        parser.missing_content("item.title")?;
+    }
+
     Ok(Some(entry))
 }
 
+// Handles <cloud>, which requires a domain, path and protocol
+fn handle_cloud<R: BufRead>(element: &Element<R>) -> Option<Cloud> {
+    Some(Cloud {
+        domain: element.attr_value("domain")?,
+        port: element.attr_value("port").and_then(|port| port.trim().parse().ok()),
+        path: element.attr_value("path")?,
+        register_procedure: element.attr_value("registerProcedure").filter(|procedure| !procedure.is_empty()),
+        protocol: element.attr_value("protocol")?,
+    })
+}
+
+// Handles <guid>, which is a permalink unless its isPermaLink attribute is "false"
+fn handle_guid<R: BufRead>(element: Element<R>, entry: &mut Entry) {
+    let is_permalink = !matches!(element.attr_value("isPermaLink"), Some(value) if value.trim().eq_ignore_ascii_case("false"));
+    if let Some(guid) = element.child_as_text() {
+        entry.id = guid;
+        entry.is_permalink = Some(is_permalink);
+    }
+}
+
 // Handles <link>
 fn handle_link<R: BufRead>(element: Element<R>) -> Option<Link> {
     element.child_as_text().map(|s| Link::new(s, element.xml_base.as_ref()))
 }
 
+// Handles <creativeCommons:license>, the URL of a license of the channel or item
+//
+// A channel or item may have several licenses, of which the first is kept.
+fn handle_license<R: BufRead>(element: Element<R>, license: &mut Option<Link>) {
+    if license.is_some() {
+        return;
+    }
+    *license = element
+        .child_as_text()
+        .map(|uri| uri.trim().to_owned())
+        .filter(|uri| !uri.is_empty())
+        .map(|uri| {
+            let mut link = Link::new(uri, element.xml_base.as_ref());
+            link.rel = Some("license".to_owned());
+            link
+        });
+}
+
+// Handles <textInput> (and <textinput> in RSS 0.91 and 1.0), which requires a name and link
+pub(crate) fn handle_text_input<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<TextInput>> {
+    let (mut title, mut description, mut name, mut link) = (None, None, None, None);
+    for child in element.children() {
+        let child = child?;
+        match child.ns_and_tag_in(RSS_NAMESPACES) {
+            (None, "title") => title = child.child_as_text().map(|text| text.trim().to_owned()),
+
+            (None, "description") => description = child.child_as_text().map(|text| text.trim().to_owned()),
+
+            (None, "name") => name = child.child_as_text().map(|text| text.trim().to_owned()),
+
+            (None, "link") => {
+                link = child
+                    .child_as_text()
+                    .map(|uri| util::resolve_uri(uri.trim().to_owned(), child.xml_base.as_ref()))
+            }
+
+            // Nothing required for unknown elements
+            _ => {}
+        }
+    }
+
+    Ok(match (name, link) {
+        (Some(name), Some(link)) if !name.is_empty() && !link.is_empty() => Some(TextInput {
+            title,
+            description,
+            name,
+            link,
+        }),
+        _ => None,
+    })
+}
+
+// Handles <skipHours>, where RSS 0.91 numbered the hours from 1 to 24 rather than 0 to 23
+fn handle_skip_hours<R: BufRead>(element: Element<R>, hints: &mut UpdateHints) -> ParseFeedResult<()> {
+    for child in element.children() {
+        let child = child?;
+        if let (None, "hour") = child.ns_and_tag_in(RSS_NAMESPACES) {
+            if_some_then(child.child_as_text().and_then(|text| text.trim().parse::<u32>().ok()), |hour| {
+                if hour <= 24 {
+                    hints.skip_hours.push(hour % 24);
+                }
+            });
+        }
+    }
+    Ok(())
+}
+
+// Handles <skipDays>
+fn handle_skip_days<R: BufRead>(element: Element<R>, hints: &mut UpdateHints) -> ParseFeedResult<()> {
+    for child in element.children() {
+        let child = child?;
+        if let (None, "day") = child.ns_and_tag_in(RSS_NAMESPACES) {
+            if_some_then(child.child_as_text().and_then(|text| text.trim().parse::<Weekday>().ok()), |day| {
+                hints.skip_days.push(day)
+            });
+        }
+    }
+    Ok(())
+}
+
 // Handles <title>, <description> etc
 fn handle_text<R: BufRead>(element: Element<R>) -> Option<Text> {
-    element.child_as_text().map(Text::new)
+    element.child_as_text().map(|content| Text {
+        lang: element.xml_lang.clone(),
+        ..Text::new(content)
+    })
 }
 
 // Handles date/time
-fn handle_timestamp<R: BufRead>(element: Element<R>) -> Option<DateTime<Utc>> {
+fn handle_timestamp<R: BufRead>(parser: &Parser, element: Element<R>) -> ParseFeedResult<Option<DateTime<Utc>>> {
     if let Some(text) = element.child_as_text() {
-        timestamp_rfc2822_lenient(&text)
+        parser.parse_timestamp(&text, TimestampFormat::Rfc2822)
     } else {
-        None
+        Ok(None)
     }
 }
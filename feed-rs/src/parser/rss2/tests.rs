@@ -1,7 +1,18 @@
+use chrono::{TimeZone, Utc};
 use std::time::Duration;
 
+use crate::extensions::dublincore::DublinCoreExtension;
+use crate::extensions::geo::{GeoBox, GeoExtension, GeoPoint};
+use crate::extensions::itunes::{ITunesCategory, ITunesFeedExtension, ITunesItemExtension, ITunesOwner};
+use crate::extensions::podcast::{
+    PodcastChapters, PodcastFeedExtension, PodcastFunding, PodcastItemExtension, PodcastPerson, PodcastSoundbite, PodcastTranscript, PodcastValue,
+    PodcastValueRecipient,
+};
+use crate::extensions::threading::ThreadingExtension;
+use crate::extensions::ExtensionElement;
 use crate::model::*;
 use crate::parser;
+use crate::parser::PubDate;
 use crate::util::test;
 use url::Url;
 
@@ -14,6 +25,7 @@ fn test_example_1() {
 
     // Expected feed
     let expected = Feed::new(FeedType::RSS2)
+        .version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
         .title(Text::new("RSS Title".into()))
         .description(Text::new("This is an example of an RSS feed".into()))
@@ -27,8 +39,8 @@ fn test_example_1() {
                 .summary(Text::new("Here is some text containing an interesting description.".into()))
                 .link(Link::new("http://www.example.com/blog/post/1", None))
                 .id("7bd204c6-1655-4c27-aeee-53f933c5395f")
-                .published_rfc2822("Sun, 06 Sep 2009 16:20:00 +0000")
-                .updated_rfc2822("Mon, 06 Sep 2010 00:01:00 +0000"),
+                .is_permalink(true)
+                .published_rfc2822("Sun, 06 Sep 2009 16:20:00 +0000"),
         ); // copy from feed
 
     // Check
@@ -43,32 +55,43 @@ fn test_example_2() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected: Feed = Feed::new(FeedType::RSS2)
+    let expected: Feed = Feed::new(FeedType::RSS2).version("2.0")
         .id(actual.id.as_ref())     // not present in the test data
         .updated(actual.updated)    // not present in the test data
-        .title(Text::new("NASA Breaking News".into()))
-        .description(Text::new("A RSS news feed containing the latest NASA news articles and press releases.".into()))
+        .title(Text::new("NASA Breaking News".into()).lang("en-us"))
+        .description(Text::new("A RSS news feed containing the latest NASA news articles and press releases.".into()).lang("en-us"))
+        .docs("http://blogs.harvard.edu/tech/rss")
         .link(Link::new("http://www.nasa.gov/", None))
+        .link(Link::new("http://www.nasa.gov/rss/dyn/breaking_news.rss", None).rel("self"))
         .language("en-us")
-        .contributor(Person::new("managingEditor".into())
-            .email("jim.wilson@nasa.gov"))
-        .contributor(Person::new("webMaster".into())
-            .email("brian.dunbar@nasa.gov"))
+        .contributor(Person::new("managingEditor")
+            .email("jim.wilson@nasa.gov")
+            .role("managingEditor"))
+        .contributor(Person::new("webMaster")
+            .email("brian.dunbar@nasa.gov")
+            .role("webMaster"))
         .entry(Entry::default()
-            .title(Text::new("NASA Television to Broadcast Space Station Departure of Cygnus Cargo Ship".into()))
+            .title(Text::new("NASA Television to Broadcast Space Station Departure of Cygnus Cargo Ship".into()).lang("en-us"))
             .link(Link::new("\n                http://www.nasa.gov/press-release/nasa-television-to-broadcast-space-station-departure-of-cygnus-cargo-ship\n            ", None))
             .summary(Text::new(r#"More than three months after delivering several tons of supplies and scientific experiments to
                 the International Space Station, Northrop Grumman’s Cygnus cargo spacecraft, the SS Roger Chaffee, will
                 depart the orbiting laboratory Tuesday, Aug. 6.
-            "#.to_owned()))
+            "#.to_owned()).lang("en-us"))
             .id("\n                http://www.nasa.gov/press-release/nasa-television-to-broadcast-space-station-departure-of-cygnus-cargo-ship\n            ")
+            .is_permalink(false)
             .published_rfc2822("Thu, 01 Aug 2019 16:15 EDT")
-            .updated(actual.updated)
+            .source(FeedSource::default()
+                .title(Text::new("NASA Breaking News".into()).lang("en-us"))
+                .link(Link::new("http://www.nasa.gov/rss/dyn/breaking_news.rss", None).rel("self")))
             .media(MediaObject::default()
                 .content(MediaContent::new()
                     .url("http://www.nasa.gov/sites/default/files/styles/1x1_cardfeed/public/thumbnails/image/47616261882_4bb534d293_k.jpg?itok=Djjjs81t")
                     .content_type("image/jpeg")
-                    .size(892854))));
+                    .size(892854)))
+            .dublin_core(DublinCoreExtension {
+                identifiers: vec!["449895".into()],
+                ..Default::default()
+            }));
 
     // Check
     assert_eq!(actual, expected);
@@ -82,26 +105,33 @@ fn test_example_3() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::RSS2)
+    let expected = Feed::new(FeedType::RSS2).version("2.0")
         .id(actual.id.as_ref())     // not present in the test data
-        .title(Text::new("News, Politics, Opinion, Commentary, and Analysis".into()))
-        .description(Text::new("In-depth reporting, commentary on breaking news, political analysis, and opinion from The New\n            Yorker.\n        ".into()))
+        .title(Text::new("News, Politics, Opinion, Commentary, and Analysis".into()).lang("en"))
+        .description(Text::new("In-depth reporting, commentary on breaking news, political analysis, and opinion from The New\n            Yorker.\n        ".into()).lang("en"))
         .link(Link::new("https://www.newyorker.com/news", None))
-        .rights(Text::new("© Condé Nast 2019".into()))
+        .link(Link::new("https://www.newyorker.com/feed/news/rss", None).rel("self").media_type("application/atom+xml"))
+        .rights(Text::new("© Condé Nast 2019".into()).lang("en"))
         .language("en")
         .updated_rfc2822("Tue, 06 Aug 2019 10:46:05 +0000")
         .entry(Entry::default()
-            .title(Text::new("How a Historian Uncovered Ronald Reagan’s Racist Remarks to Richard Nixon".into()))
+            .extension("http://purl.org/dc/elements/1.1/", ExtensionElement::new("modified").value("Fri, 02 Aug 2019 15:35:34 +0000"))
+            .title(Text::new("How a Historian Uncovered Ronald Reagan’s Racist Remarks to Richard Nixon".into()).lang("en"))
             .link(Link::new("\n                https://www.newyorker.com/news/q-and-a/how-a-historian-uncovered-ronald-reagans-racist-remarks-to-richard-nixon\n            ", None))
             .id("5d420f3abfe6c20008d5eaad")
-            .author(Person::new("Isaac Chotiner".into()))
-            .summary(Text::new("Isaac Chotiner talks with the historian Tim Naftali, who published the text and audio of a\n                taped call, from 1971, in which Reagan described the African delegates to the U.N. in luridly racist\n                terms.\n            ".into()))
+            .is_permalink(false)
+            .author(Person::new("Isaac Chotiner"))
+            .summary(Text::new("Isaac Chotiner talks with the historian Tim Naftali, who published the text and audio of a\n                taped call, from 1971, in which Reagan described the African delegates to the U.N. in luridly racist\n                terms.\n            ".into()).lang("en"))
             .category(Category::new("News / Q. & A."))
             .published_rfc2822("Fri, 02 Aug 2019 15:35:34 +0000")
-            .updated(actual.updated)
             .media(MediaObject::default()
                 .thumbnail(MediaThumbnail::new(Image::new("https://media.newyorker.com/photos/5d4211a4ba8a9c0009a57cfd/master/pass/Chotiner-ReaganRacismNaftali-3.jpg".into()).width(2560).height(1819)))
             )
+            .dublin_core(DublinCoreExtension {
+                creators: vec!["Isaac Chotiner".into()],
+                publishers: vec!["Condé Nast".into()],
+                ..Default::default()
+            })
         );
 
     // Check
@@ -116,27 +146,44 @@ fn test_example_4() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::RSS2)
+    let expected = Feed::new(FeedType::RSS2).version("2.0")
         .id(actual.id.as_ref())     // not present in the test data
-        .title(Text::new("Earthquakes today".into()))
+        .title(Text::new("Earthquakes today".into()).lang("en-US"))
+        .link(Link::new("http://www.earthquakenewstoday.com/feed/", None).rel("self").media_type("application/rss+xml"))
         .link(Link::new("http://www.earthquakenewstoday.com", None))
-        .description(Text::new("Current and latest world earthquakes breaking news, activity and articles today".into()))
+        .description(Text::new("Current and latest world earthquakes breaking news, activity and articles today".into()).lang("en-US"))
         .updated_rfc2822("Tue, 06 Aug 2019 05:01:15 +0000")
-        .language("en-us")
+        .language("en-US")
         .generator(Generator::new("https://wordpress.org/?v=5.1.1"))
+        .extension("com-wordpress:feed-additions:1", ExtensionElement::new("site").value("76661723"))
+        .update_hints(UpdateHints {
+            update_period: Some(UpdatePeriod::Hourly),
+            update_frequency: Some(1),
+            ..Default::default()
+        })
         .entry(Entry::default()
-            .title(Text::new("Minor earthquake, 3.5 mag was detected near Aris in Greece".into()))
-            .author(Person::new("admin".into()))
+            .extension("com-wordpress:feed-additions:1", ExtensionElement::new("post-id").value("149732"))
+            .threading(ThreadingExtension {
+                comments: Some(0),
+                comment_rss: Some("http://www.earthquakenewstoday.com/2019/08/06/minor-earthquake-3-5-mag-was-detected-near-aris-in-greece/feed/".into()),
+                ..Default::default()
+            })
+            .title(Text::new("Minor earthquake, 3.5 mag was detected near Aris in Greece".into()).lang("en-US"))
+            .author(Person::new("admin"))
+            .dublin_core(DublinCoreExtension {
+                creators: vec!["admin".into()],
+                ..Default::default()
+            })
             .link(Link::new("\n                http://www.earthquakenewstoday.com/2019/08/06/minor-earthquake-3-5-mag-was-detected-near-aris-in-greece/\n            ", None))
             .published_rfc2822("Tue, 06 Aug 2019 05:01:15 +0000")
             .category(Category::new("Earthquake breaking news"))
             .category(Category::new("Minor World Earthquakes Magnitude -3.9"))
             .category(Category::new("Spárti"))
             .id("\n                http://www.earthquakenewstoday.com/2019/08/06/minor-earthquake-3-5-mag-was-detected-near-aris-in-greece/\n            ")
-            .summary(Text::new("\n                A minor earthquake magnitude 3.5 (ml/mb) strikes near Kalamáta, Trípoli, Pýrgos, Spárti, Filiatrá, Messíni, Greece on Tuesday.".into()))
-            .content(Content::default()
-                .body("<p><img class='size-full alignleft' title='Earthquake location 37.102S, 21.9072W' alt='Earthquake location 37.102S, 21.9072W' src='http://www.earthquakenewstoday.com/wp-content/uploads/35_20.jpg' width='146' height='146' />A minor earthquake with magnitude 3.5 (ml/mb) was detected on Tuesday, 8 kilometers (5 miles) from Aris in Greece.Exact location of event, depth 10 km, 21.9072&deg; East, 37.102&deg; North. </p>"))
-            .updated(actual.updated));
+            .is_permalink(false)
+            .summary(Text::new("\n                A minor earthquake magnitude 3.5 (ml/mb) strikes near Kalamáta, Trípoli, Pýrgos, Spárti, Filiatrá, Messíni, Greece on Tuesday.".into()).lang("en-US"))
+            .content(Content::default().lang("en-US").content_type("text/html")
+                .body("<p><img class='size-full alignleft' title='Earthquake location 37.102S, 21.9072W' alt='Earthquake location 37.102S, 21.9072W' src='http://www.earthquakenewstoday.com/wp-content/uploads/35_20.jpg' width='146' height='146' />A minor earthquake with magnitude 3.5 (ml/mb) was detected on Tuesday, 8 kilometers (5 miles) from Aris in Greece.Exact location of event, depth 10 km, 21.9072&deg; East, 37.102&deg; North. </p>")));
 
     // Check
     assert_eq!(actual, expected);
@@ -151,14 +198,21 @@ fn test_example_5() {
 
     // Expected feed
     let expected = Feed::new(FeedType::RSS2)
+        .version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
-        .title(Text::new("Ars Technica".into()))
+        .title(Text::new("Ars Technica".into()).lang("en-US"))
         .link(Link::new("https://arstechnica.com", None))
-        .description(Text::new(
-            "Serving the Technologist for more than a decade. IT news, reviews, and analysis.".into(),
-        ))
+        .link(
+            Link::new("http://feeds.arstechnica.com/arstechnica/index", None)
+                .rel("self")
+                .media_type("application/rss+xml"),
+        )
+        .link(Link::new("http://pubsubhubbub.appspot.com/", None).rel("hub"))
+        .description(Text::new("Serving the Technologist for more than a decade. IT news, reviews, and analysis.".into()).lang("en-US"))
         .updated_rfc2822("Tue, 06 Aug 2019 00:03:56 +0000")
-        .language("en-us")
+        .stylesheet(Stylesheet::new("/~d/styles/rss2full.xsl".into()).media_type("text/xsl"))
+        .stylesheet(Stylesheet::new("http://feeds.arstechnica.com/~d/styles/itemcontent.css".into()).media_type("text/css"))
+        .language("en-US")
         .generator(Generator::new("https://wordpress.org/?v=4.8.3"))
         .logo(
             Image::new("https://cdn.arstechnica.net/wp-content/uploads/2016/10/cropped-ars-logo-512_480-32x32.png".into())
@@ -167,11 +221,18 @@ fn test_example_5() {
                 .width(32)
                 .height(32),
         )
+        .update_hints(UpdateHints {
+            update_period: Some(UpdatePeriod::Hourly),
+            update_frequency: Some(1),
+            ..Default::default()
+        })
+        .extension(
+            "http://rssnamespace.org/feedburner/ext/1.0",
+            ExtensionElement::new("info").attribute("uri", "arstechnica/index"),
+        )
         .entry(
             Entry::default()
-                .title(Text::new(
-                    "Apple isn’t the most cash-rich company in the world anymore, but it doesn’t matter".into(),
-                ))
+                .title(Text::new("Apple isn’t the most cash-rich company in the world anymore, but it doesn’t matter".into()).lang("en-US"))
                 .link(Link::new("https://arstechnica.com/?p=1546121", None))
                 .published_rfc2822("Mon, 05 Aug 2019 23:11:09 +0000")
                 .category(Category::new("Tech"))
@@ -179,10 +240,19 @@ fn test_example_5() {
                 .category(Category::new("apple"))
                 .category(Category::new("google"))
                 .id("https://arstechnica.com/?p=1546121")
-                .author(Person::new("Samuel Axon".into()))
-                .summary(Text::new("Alphabet has $117 billion in cash on hand.".into()))
-                .content(Content::default().body("Google co-founder Larry Page is now CEO of Alphabet."))
-                .updated(actual.updated),
+                .is_permalink(false)
+                .author(Person::new("Samuel Axon"))
+                .dublin_core(DublinCoreExtension {
+                    creators: vec!["Samuel Axon".into()],
+                    ..Default::default()
+                })
+                .summary(Text::new("Alphabet has $117 billion in cash on hand.".into()).lang("en-US"))
+                .content(
+                    Content::default()
+                        .lang("en-US")
+                        .content_type("text/html")
+                        .body("Google co-founder Larry Page is now CEO of Alphabet."),
+                ),
         );
 
     // Check
@@ -197,24 +267,23 @@ fn test_example_6() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::RSS2)
+    let expected = Feed::new(FeedType::RSS2).version("2.0")
         .id("b2ef47d837e6c0d9d757e14852e5bde")     // hash of the link
-        .title(Text::new("Latest Movie Trailers".into()))
+        .title(Text::new("Latest Movie Trailers".into()).lang("en-US"))
         .link(Link::new("https://trailers.apple.com/", None))
-        .description(Text::new("Recently added Movie Trailers.".into()))
-        .language("en-us")
+        .description(Text::new("Recently added Movie Trailers.".into()).lang("en-US"))
+        .language("en-US")
         .updated_rfc3339("2020-02-07T15:30:28Z")
         .generator(Generator::new("Custom"))
-        .rights(Text::new("2020 Apple Inc.".into()))
+        .rights(Text::new("2020 Apple Inc.".into()).lang("en-US"))
         .entry(Entry::default()
-            .title(Text::new("Vitalina Varela - Trailer".into()))
+            .title(Text::new("Vitalina Varela - Trailer".into()).lang("en-US"))
             .link(Link::new("https://trailers.apple.com/trailers/independent/vitalina-varela", None))
-            .summary(Text::new("A film of deeply concentrated beauty, acclaimed filmmaker Pedro Costa’s VITALINA VARELA stars nonprofessional actor Vitalina Varela in an extraordinary performance based on her own life. Vitalina plays a Cape Verdean woman who has travelled to Lisbon to reunite with her husband, after two decades of separation, only to arrive mere days after his funeral. Alone in a strange forbidding land, she perseveres and begins to establish a new life. Winner of the Golden Leopard for Best Film and Best Actress at the Locarno Film Festival, as well as an official selection of the Sundance Film Festival, VITALINA VARELA is a film of shadow and whisper, a profoundly moving and visually ravishing masterpiece.".into()))
-            .content(Content::default()
+            .summary(Text::new("A film of deeply concentrated beauty, acclaimed filmmaker Pedro Costa’s VITALINA VARELA stars nonprofessional actor Vitalina Varela in an extraordinary performance based on her own life. Vitalina plays a Cape Verdean woman who has travelled to Lisbon to reunite with her husband, after two decades of separation, only to arrive mere days after his funeral. Alone in a strange forbidding land, she perseveres and begins to establish a new life. Winner of the Golden Leopard for Best Film and Best Actress at the Locarno Film Festival, as well as an official selection of the Sundance Film Festival, VITALINA VARELA is a film of shadow and whisper, a profoundly moving and visually ravishing masterpiece.".into()).lang("en-US"))
+            .content(Content::default().lang("en-US").content_type("text/html")
                 .body(r#"<span style="font-size: 16px; font-weight: 900; text-decoration: underline;">Vitalina Varela - Trailer</span>"#))
             .published_rfc3339("2020-02-06T08:00:00Z")
-            .id("73226f21f249d758bd97a1fac90897d2")        // hash of the link
-            .updated(actual.updated));
+            .id("73226f21f249d758bd97a1fac90897d2")); // hash of the link
 
     // Check
     assert_eq!(actual, expected);
@@ -229,42 +298,64 @@ fn test_spec_1() {
 
     // Expected feed
     let expected = Feed::new(FeedType::RSS2)
+        .version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
-        .title(Text::new("Scripting News".into()))
+        .title(Text::new("Scripting News".into()).lang("en-us"))
         .link(Link::new("http://www.scripting.com/", None))
-        .description(Text::new("A weblog about scripting and stuff like that.".into()))
+        .description(Text::new("A weblog about scripting and stuff like that.".into()).lang("en-us"))
         .language("en-us")
-        .rights(Text::new("Copyright 1997-2002 Dave Winer".into()))
+        .rights(Text::new("Copyright 1997-2002 Dave Winer".into()).lang("en-us"))
         .updated_rfc2822("Mon, 30 Sep 2002 11:00:00 GMT")
         .generator(Generator::new("Radio UserLand v8.0.5"))
+        .docs("http://backend.userland.com/rss")
         .category(Category::new("1765").scheme("Syndic8"))
-        .contributor(Person::new("managingEditor".into()).email("dave@userland.com"))
-        .contributor(Person::new("webMaster".into()).email("dave@userland.com"))
+        .contributor(Person::new("managingEditor").email("dave@userland.com").role("managingEditor"))
+        .contributor(Person::new("webMaster").email("dave@userland.com").role("webMaster"))
         .ttl(40)
+        .extension(
+            "http://backend.userland.com/blogChannelModule",
+            ExtensionElement::new("blogRoll").value("http://radio.weblogs.com/0001015/userland/scriptingNewsLeftLinks.opml"),
+        )
+        .extension(
+            "http://backend.userland.com/blogChannelModule",
+            ExtensionElement::new("mySubscriptions").value("http://radio.weblogs.com/0001015/gems/mySubscriptions.opml"),
+        )
+        .extension(
+            "http://backend.userland.com/blogChannelModule",
+            ExtensionElement::new("blink").value("http://diveintomark.org/"),
+        )
         .entry(
             Entry::default()
-                .summary(Text::new(
-                    r#"Joshua Allen: <a href="http://www.netcrucible.com/blog/2002/09/29.html#a243">Who
+                .summary(
+                    Text::new(
+                        r#"Joshua Allen: <a href="http://www.netcrucible.com/blog/2002/09/29.html#a243">Who
                 loves namespaces?</a>
             "#
-                    .to_owned(),
-                ))
+                        .to_owned(),
+                    )
+                    .lang("en-us"),
+                )
                 .published_rfc2822("Sun, 29 Sep 2002 19:59:01 GMT")
                 .id("http://scriptingnews.userland.com/backissues/2002/09/29#When:12:59:01PM")
-                .updated_rfc2822("Mon, 30 Sep 2002 11:00:00 GMT"),
+                .is_permalink(true)
+                .link(Link::new("http://scriptingnews.userland.com/backissues/2002/09/29#When:12:59:01PM", None)), // from the guid
         ) // copy from feed
         .entry(
             Entry::default()
-                .summary(Text::new(
-                    r#"<a href="http://www.docuverse.com/blog/donpark/2002/09/29.html#a68">Don Park</a>:
+                .summary(
+                    Text::new(
+                        r#"<a href="http://www.docuverse.com/blog/donpark/2002/09/29.html#a68">Don Park</a>:
                 "It is too easy for engineer to anticipate too much and XML Namespace is a frequent host of
                 over-anticipation."
             "#
-                    .to_owned(),
-                ))
+                        .to_owned(),
+                    )
+                    .lang("en-us"),
+                )
                 .published_rfc2822("Mon, 30 Sep 2002 01:52:02 GMT")
                 .id("http://scriptingnews.userland.com/backissues/2002/09/29#When:6:52:02PM")
-                .updated_rfc2822("Mon, 30 Sep 2002 11:00:00 GMT"),
+                .is_permalink(true)
+                .link(Link::new("http://scriptingnews.userland.com/backissues/2002/09/29#When:6:52:02PM", None)), // from the guid
         ); // copy from feed
 
     // Check
@@ -305,49 +396,79 @@ fn test_spiegel() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::RSS2)
+    let expected = Feed::new(FeedType::RSS2).version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
         .language("de")
-        .title(Text::new("SPIEGEL Update – Die Nachrichten".into()))
+        .title(Text::new("SPIEGEL Update – Die Nachrichten".into()).lang("de"))
         .author(Person::new("DER SPIEGEL"))
+        .link(Link::new("https://www.omnycontent.com/d/playlist/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/4c18e072-24d2-4d60-9a42-abc00102c97e/podcast.rss", None).rel("self").media_type("application/rss+xml"))
+        .link(Link::new("https://www.omnycontent.com/d/playlist/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/4c18e072-24d2-4d60-9a42-abc00102c97e/podcast.rss?page=2", None).rel("next").media_type("application/rss+xml"))
+        .link(Link::new("https://www.omnycontent.com/d/playlist/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/4c18e072-24d2-4d60-9a42-abc00102c97e/podcast.rss?page=1", None).rel("first").media_type("application/rss+xml"))
+        .link(Link::new("https://www.omnycontent.com/d/playlist/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/4c18e072-24d2-4d60-9a42-abc00102c97e/podcast.rss?page=7", None).rel("last").media_type("application/rss+xml"))
         .link(Link::new("https://www.spiegel.de/thema/spiegel-update/", None))
         .category(Category::new("News"))
         .contributor(Person::new("SPIEGEL Update – Die Nachrichten").email("charlotte.meyer-hamme@spiegel.de"))
-        .description(Text::new("<p>Die wichtigsten Nachrichten des Tages &ndash; erg&auml;nzt um Meinungen und Empfehlungen aus der SPIEGEL-Redaktion. Wochentags aktualisieren wir morgens, mittags und abends unsere Meldungen. Am Wochenende blicken wir zur&uuml;ck auf die vergangene Woche &ndash; und erkl&auml;ren, was in der n&auml;chsten Woche wichtig wird.</p>".into()))
-        .rights(Text::new("2021 DER SPIEGEL GmbH & Co. KG".into()))
+        .description(Text::new("<p>Die wichtigsten Nachrichten des Tages &ndash; erg&auml;nzt um Meinungen und Empfehlungen aus der SPIEGEL-Redaktion. Wochentags aktualisieren wir morgens, mittags und abends unsere Meldungen. Am Wochenende blicken wir zur&uuml;ck auf die vergangene Woche &ndash; und erkl&auml;ren, was in der n&auml;chsten Woche wichtig wird.</p>".into()).lang("de"))
+        .rights(Text::new("2021 DER SPIEGEL GmbH & Co. KG".into()).lang("de"))
         .logo(Image::new("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&size=Large".into())
             .title("SPIEGEL Update – Die Nachrichten")
             .link("https://www.spiegel.de/thema/spiegel-update/")
         )
+        .itunes(ITunesFeedExtension {
+            author: Some("DER SPIEGEL".into()),
+            categories: vec![ITunesCategory::new("News")],
+            explicit: Some(false),
+            image: Some("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&amp;size=Large".into()),
+            new_feed_url: Some("https://www.omnycontent.com/d/playlist/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/4c18e072-24d2-4d60-9a42-abc00102c97e/podcast.rss".into()),
+            owner: Some(ITunesOwner {
+                name: Some("SPIEGEL Update – Die Nachrichten".into()),
+                email: Some("charlotte.meyer-hamme@spiegel.de".into()),
+            }),
+            subtitle: Some("Die wichtigsten Nachrichten des Tages".into()),
+            summary: Some("Summary".into()),
+            show_type: Some("episodic".into()),
+            ..Default::default()
+        })
         .entry(
             Entry::default()
-                .title(Text::new("07.02. – die Wochenvorschau: Lockdown-Verlängerung, Kriegsverbrecher vor Gericht, Super Bowl, Karneval ".into()))
-                .content(Content::default().body(r#"Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. <br><br><p>See <a href="https://omnystudio.com/listener">omnystudio.com/listener</a> for privacy information.</p>"#))
-                .summary(Text::new("Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. \r\nSee omnystudio.com/listener for privacy information.".into()))
+                .extension("https://omny.fm/rss-extensions", ExtensionElement::new("clipId").value("c7e3cca2-665e-4bc4-bcac-acc6011b9fa2"))
+                .title(Text::new("07.02. – die Wochenvorschau: Lockdown-Verlängerung, Kriegsverbrecher vor Gericht, Super Bowl, Karneval ".into()).lang("de"))
+                .content(Content::default().lang("de").content_type("text/html").body(r#"Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. <br><br><p>See <a href="https://omnystudio.com/listener">omnystudio.com/listener</a> for privacy information.</p>"#))
+                .summary(Text::new("Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. \r\nSee omnystudio.com/listener for privacy information.".into()).lang("de"))
                 .link(Link::new("https://omny.fm/shows/spiegel-update-die-nachrichten/07-02-die-wochenvorschau-lockdown-verl-ngerung-kri", None))
                 .published_rfc3339("2021-02-06T23:01:00Z")
                 .id("c7e3cca2-665e-4bc4-bcac-acc6011b9fa2")
+                .is_permalink(false)
                 // <enclosure>, media: and itunes: tags
                 .media(MediaObject::default()
-                    .title("07.02. – die Wochenvorschau: Lockdown-Verlängerung, Kriegsverbrecher vor Gericht, Super Bowl, Karneval ".into())
-                    .description("Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. \r\nSee omnystudio.com/listener for privacy information.".into())
+                    .title("07.02. – die Wochenvorschau: Lockdown-Verlängerung, Kriegsverbrecher vor Gericht, Super Bowl, Karneval ")
+                    .description("Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. \r\nSee omnystudio.com/listener for privacy information.")
                     .credit("DER SPIEGEL")
                     .thumbnail(MediaThumbnail::new(Image::new("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&amp;size=Large".into())))
                     .content(MediaContent::new()
-                        .url("https://traffic.omny.fm/d/clips/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/c7e3cca2-665e-4bc4-bcac-acc6011b9fa2/audio.mp3?utm_source=Podcast&amp;in_playlist=4c18e072-24d2-4d60-9a42-abc00102c97e&amp;t=1612652510".into())
+                        .url("https://traffic.omny.fm/d/clips/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/c7e3cca2-665e-4bc4-bcac-acc6011b9fa2/audio.mp3?utm_source=Podcast&amp;in_playlist=4c18e072-24d2-4d60-9a42-abc00102c97e&amp;t=1612652510")
                         .content_type("audio/mpeg")
                     )
                     .content(MediaContent::new()
-                        .url("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&amp;size=Large".into())
+                        .url("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&amp;size=Large")
                         .content_type("image/jpeg")
                     )
                     .content(MediaContent::new()
-                        .url("https://traffic.omny.fm/d/clips/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/c7e3cca2-665e-4bc4-bcac-acc6011b9fa2/audio.mp3?utm_source=Podcast&amp;in_playlist=4c18e072-24d2-4d60-9a42-abc00102c97e&amp;t=1612652510".into())
+                        .url("https://traffic.omny.fm/d/clips/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/c7e3cca2-665e-4bc4-bcac-acc6011b9fa2/audio.mp3?utm_source=Podcast&amp;in_playlist=4c18e072-24d2-4d60-9a42-abc00102c97e&amp;t=1612652510")
                         .size(2519606)
                         .content_type("audio/mpeg")
                     )
                     .duration(Duration::from_secs(312))
                 )
+                .itunes(ITunesItemExtension {
+                    author: Some("DER SPIEGEL".into()),
+                    duration: Some(Duration::from_secs(312)),
+                    episode_type: Some("full".into()),
+                    image: Some("https://www.omnycontent.com/d/programs/5ac1e950-45c7-4eb7-87c0-aa0f018441b8/bb17ca27-51f4-4349-bc1e-abc00102c975/image.jpg?t=1589902935&amp;size=Large".into()),
+                    summary: Some("Die wichtigsten Nachrichten aus der SPIEGEL-Redaktion. \r\nSee omnystudio.com/listener for privacy information.".into()),
+                    title: Some("07.02. – die Wochenvorschau: Lockdown-Verlängerung, Kriegsverbrecher vor Gericht, Super Bowl, Karneval ".into()),
+                    ..Default::default()
+                })
         );
 
     // Check
@@ -363,11 +484,17 @@ fn test_bbc() {
 
     // Expected feed
     let expected = Feed::new(FeedType::RSS2)
+        .version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
-        .title(Text::new("In Our Time".into()))
+        .title(Text::new("In Our Time".into()).lang("en"))
         .link(Link::new("http://www.bbc.co.uk/programmes/b006qykl", None))
+        .link(
+            Link::new("http://www.bbc.co.uk/programmes/b006qykl/episodes/downloads.rss", None)
+                .rel("self")
+                .media_type("application/rss+xml"),
+        )
         .category(Category::new("History"))
-        .description(Text::new("Melvyn Bragg and guests discuss the history of ideas".into()))
+        .description(Text::new("Melvyn Bragg and guests discuss the history of ideas".into()).lang("en"))
         .author(Person::new("BBC Radio 4"))
         .contributor(Person::new("BBC").email("RadioMusic.Support@bbc.co.uk"))
         .language("en")
@@ -376,41 +503,110 @@ fn test_bbc() {
                 .title("In Our Time")
                 .link("http://www.bbc.co.uk/programmes/b006qykl"),
         )
-        .rights(Text::new("(C) BBC 2021".into()))
+        .rights(Text::new("(C) BBC 2021".into()).lang("en"))
         .published_rfc2822("Thu, 25 Feb 2021 10:15:00 +0000")
+        .itunes(ITunesFeedExtension {
+            author: Some("BBC Radio 4".into()),
+            categories: vec![ITunesCategory::new("History")],
+            explicit: Some(false),
+            image: Some("http://ichef.bbci.co.uk/images/ic/3000x3000/p087hyhs.jpg".into()),
+            new_feed_url: Some("https://podcasts.files.bbci.co.uk/b006qykl.rss".into()),
+            owner: Some(ITunesOwner {
+                name: Some("BBC".into()),
+                email: Some("RadioMusic.Support@bbc.co.uk".into()),
+            }),
+            summary: Some("Melvyn Bragg and guests discuss the history of ideas".into()),
+            ..Default::default()
+        })
+        .extension(
+            "http://bbc.co.uk/2009/01/ppgRss",
+            ExtensionElement::new("seriesDetails")
+                .attribute("daysLive", "-1")
+                .attribute("frequency", "weekly"),
+        )
+        .extension(
+            "http://bbc.co.uk/2009/01/ppgRss",
+            ExtensionElement::new("systemRef")
+                .attribute("key", "b006qykl")
+                .attribute("systemId", "pid.brand"),
+        )
+        .extension(
+            "http://bbc.co.uk/2009/01/ppgRss",
+            ExtensionElement::new("systemRef").attribute("key", "C00060").attribute("systemId", "pid.genre"),
+        )
+        .extension(
+            "http://bbc.co.uk/2009/01/ppgRss",
+            ExtensionElement::new("systemRef").attribute("key", "PT004").attribute("systemId", "pid.format"),
+        )
+        .extension(
+            "http://bbc.co.uk/2009/01/ppgRss",
+            ExtensionElement::new("network").attribute("id", "radio4").attribute("name", "BBC Radio 4"),
+        )
+        .extension(
+            "http://search.yahoo.com/mrss/",
+            ExtensionElement::new("rating").attribute("scheme", "urn:simple").value("nonadult"),
+        )
         .entry(
             Entry::default()
-                .title(Text::new("Marcus Aurelius".into()))
-                .summary(Text::new("Melvyn Bragg and guests discuss...".into()))
+                .extension(
+                    "http://bbc.co.uk/2009/01/ppgRss",
+                    ExtensionElement::new("enclosureLegacy")
+                        .attribute("length", "50496000")
+                        .attribute("type", "audio/mpeg")
+                        .attribute(
+                            "url",
+                            "http://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/http/vpid/p097wt5b.mp3",
+                        ),
+                )
+                .extension(
+                    "http://bbc.co.uk/2009/01/ppgRss",
+                    ExtensionElement::new("enclosureSecure")
+                        .attribute("length", "50496000")
+                        .attribute("type", "audio/mpeg")
+                        .attribute(
+                            "url",
+                            "https://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/https/vpid/p097wt5b.mp3",
+                        ),
+                )
+                .extension(
+                    "http://bbc.co.uk/2009/01/ppgRss",
+                    ExtensionElement::new("canonical").value("/programmes/m000sjxt"),
+                )
+                .title(Text::new("Marcus Aurelius".into()).lang("en"))
+                .summary(Text::new("Melvyn Bragg and guests discuss...".into()).lang("en"))
                 .published_rfc2822("Thu, 25 Feb 2021 10:15:00 +0000")
                 .id("urn:bbc:podcast:m000sjxt")
+                .is_permalink(false)
                 .link(Link::new("http://www.bbc.co.uk/programmes/m000sjxt", None))
                 // <enclosure>,  media: and itunes: tags
                 .media(
                     MediaObject::default()
-                        .description("Melvyn Bragg and guests discuss the man who, according to Machiavelli...".into())
+                        .description("Melvyn Bragg and guests discuss the man who, according to Machiavelli...")
                         .duration(Duration::from_secs(3156))
                         .content(
                             MediaContent::new()
-                                .url(
-                                    "http://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/http/vpid/p097wt5b.mp3"
-                                        .into(),
-                                )
+                                .url("http://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/http/vpid/p097wt5b.mp3")
                                 .size(50496000)
                                 .content_type("audio/mpeg"),
                         )
                         .content(
                             MediaContent::new()
-                                .url(
-                                    "http://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/http/vpid/p097wt5b.mp3"
-                                        .into(),
-                                )
+                                .url("http://open.live.bbc.co.uk/mediaselector/6/redir/version/2.0/mediaset/audio-nondrm-download/proto/http/vpid/p097wt5b.mp3")
                                 .size(50496000)
                                 .content_type("audio/mpeg")
+                                .medium("audio")
                                 .duration(Duration::from_secs(3156)),
                         )
                         .credit("BBC Radio 4"),
-                ),
+                )
+                .itunes(ITunesItemExtension {
+                    author: Some("BBC Radio 4".into()),
+                    duration: Some(Duration::from_secs(3156)),
+                    explicit: Some(false),
+                    subtitle: Some("Melvyn Bragg and guests discuss the life and meditations of 'the last good Roman emperor'.".into()),
+                    summary: Some("Melvyn Bragg and guests discuss the man who, according to Machiavelli...".into()),
+                    ..Default::default()
+                }),
         );
 
     // Check
@@ -425,9 +621,10 @@ fn test_ch9() {
     let actual = parser::parse(test_data.as_bytes()).unwrap();
 
     // Expected feed
-    let expected = Feed::new(FeedType::RSS2)
+    let expected = Feed::new(FeedType::RSS2).version("2.0")
         .id(actual.id.as_ref()) // not present in the test data
-        .title(Text::new("Azure Friday (HD) - Channel 9".into()))
+        .title(Text::new("Azure Friday (HD) - Channel 9".into()).lang("en"))
+        .stylesheet(Stylesheet::new("/styles/xslt/rss.xslt".into()).media_type("text/xsl"))
         .logo(
             Image::new("https://f.ch9.ms/thumbnail/4761e196-da48-4b41-abfe-e56e0509f04d.png".into())
                 .title("Azure Friday (HD) - Channel 9")
@@ -436,7 +633,8 @@ fn test_ch9() {
         .description(Text::new(
             "Join Scott Hanselman, Donovan Brown, or Lara Rubbelke as they host the engineers who build Azure, demo it, answer questions, and share insights. "
                 .into(),
-        ))
+        ).lang("en"))
+        .link(Link::new("https://s.ch9.ms/Shows/Azure-Friday/feed/mp4high", None).rel("self").media_type("application/rss+xml"))
         .link(Link::new("https://s.ch9.ms/Shows/Azure-Friday", None))
         .category(Category::new("Technology"))
         .language("en")
@@ -444,18 +642,40 @@ fn test_ch9() {
         .updated_rfc2822("Sat, 27 Feb 2021 06:55:01 GMT")
         .author(Person::new("Microsoft"))
         .generator(Generator::new("Rev9"))
+        .itunes(ITunesFeedExtension {
+            author: Some("Microsoft".into()),
+            categories: vec![ITunesCategory::new("Technology")],
+            explicit: Some(false),
+            image: Some("https://f.ch9.ms/thumbnail/2b039019-ee4b-4838-a5c3-4436541806df.png".into()),
+            summary: Some("Join Scott Hanselman every Friday as he engages one-on-one with the engineers who build the services that power Microsoft Azure as they demo capabilities, answer Scott's questions, and share their insights. Follow us at: friday.azure.com. ".into()),
+            ..Default::default()
+        })
+        .extension("http://channel9.msdn.com", ExtensionElement::new("totalResults").value("374"))
+        .extension("http://channel9.msdn.com", ExtensionElement::new("pageCount").value("15"))
+        .extension("http://channel9.msdn.com", ExtensionElement::new("pageSize").value("25"))
+        .extension("http://www.google.com/schemas/play-podcasts/1.0", ExtensionElement::new("author").value("Microsoft"))
+        .extension("http://www.google.com/schemas/play-podcasts/1.0", ExtensionElement::new("email").value("ch9@microsoft.com"))
         .entry(
             Entry::default()
-                .title(Text::new("Troubleshoot AKS cluster issues with AKS Diagnostics and AKS Periscope".into()))
-                .summary(Text::new("<p>Yun Jung Choi shows Scott Hanselman...".into()))
+                .threading(ThreadingExtension {
+                    comments: Some(0),
+                    comment_rss: Some("https://channel9.msdn.com/Shows/Azure-Friday/Troubleshoot-AKS-cluster-issues-with-AKS-Diagnostics-and-AKS-Periscope/RSS".into()),
+                    ..Default::default()
+                })
+                .title(Text::new("Troubleshoot AKS cluster issues with AKS Diagnostics and AKS Periscope".into()).lang("en"))
+                .summary(Text::new("<p>Yun Jung Choi shows Scott Hanselman...".into()).lang("en"))
                 .link(Link::new(
                     "https://channel9.msdn.com/Shows/Azure-Friday/Troubleshoot-AKS-cluster-issues-with-AKS-Diagnostics-and-AKS-Periscope",
                     None,
                 ))
                 .published_rfc2822("Fri, 26 Feb 2021 20:00:00 GMT")
-                .updated_rfc2822("Sat, 27 Feb 2021 06:55:01 GMT")
                 .id("https://channel9.msdn.com/Shows/Azure-Friday/Troubleshoot-AKS-cluster-issues-with-AKS-Diagnostics-and-AKS-Periscope")
+                .is_permalink(false)
                 .author(Person::new("Scott Hanselman, Rob Caron"))
+                .dublin_core(DublinCoreExtension {
+                    creators: vec!["Scott Hanselman, Rob Caron".into()],
+                    ..Default::default()
+                })
                 .category(Category::new("Azure"))
                 .category(Category::new("Kubernetes"))
                 .category(Category::new("aft"))
@@ -464,42 +684,48 @@ fn test_ch9() {
                     MediaObject::default()
                         .content(
                             MediaContent::new()
-                                .url("https://rev9.blob.core.windows.net/mfupload/04b236b5-e824-4091-85d8-acd90155d4b0_20210124205102.mp4".into())
+                                .url("https://rev9.blob.core.windows.net/mfupload/04b236b5-e824-4091-85d8-acd90155d4b0_20210124205102.mp4")
+                                .medium("video")
                                 .duration(Duration::from_secs(867))
                                 .size(1)
                                 .content_type("video/mp4"),
                         )
                         .content(
                             MediaContent::new()
-                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663.mp3".into())
+                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663.mp3")
+                                .medium("audio")
                                 .duration(Duration::from_secs(867))
                                 .size(13878646)
                                 .content_type("audio/mp3"),
                         )
                         .content(
                             MediaContent::new()
-                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663.mp4".into())
+                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663.mp4")
+                                .medium("video")
                                 .duration(Duration::from_secs(867))
                                 .size(20450133)
                                 .content_type("video/mp4"),
                         )
                         .content(
                             MediaContent::new()
-                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_high.mp4".into())
+                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_high.mp4")
+                                .medium("video")
                                 .duration(Duration::from_secs(867))
                                 .size(126659374)
                                 .content_type("video/mp4"),
                         )
                         .content(
                             MediaContent::new()
-                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_mid.mp4".into())
+                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_mid.mp4")
+                                .medium("video")
                                 .duration(Duration::from_secs(867))
                                 .size(49241848)
                                 .content_type("video/mp4"),
                         )
                         .content(
                             MediaContent::new()
-                                .url("https://www.youtube-nocookie.com/embed/E-XqYb88hUY?enablejsapi=1".into())
+                                .url("https://www.youtube-nocookie.com/embed/E-XqYb88hUY?enablejsapi=1")
+                                .medium("video")
                                 .duration(Duration::from_secs(867))
                                 .size(1),
                         ),
@@ -531,12 +757,18 @@ fn test_ch9() {
                         ))
                         .content(
                             MediaContent::new()
-                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_high.mp4".into())
+                                .url("https://sec.ch9.ms/ch9/075d/6e61e6c6-3890-4172-a617-fa0c4b38075d/azfr663_high.mp4")
                                 .size(126659374)
                                 .content_type("video/mp4"),
                         )
                         .credit("Scott Hanselman, Rob Caron"),
-                ),
+                )
+                .itunes(ITunesItemExtension {
+                    author: Some("Scott Hanselman, Rob Caron".into()),
+                    duration: Some(Duration::from_secs(867)),
+                    summary: Some("Yun Jung Choi shows Scott Hanselman how to use AKS Diagnostics...".into()),
+                    ..Default::default()
+                }),
         );
 
     // Check
@@ -561,6 +793,12 @@ fn test_relurl_1() {
         content.src,
         Some(Link::new("https://insanity.industries/post/pacman-tracking-leftover-packages/", None))
     );
+
+    // The author is given in the "email (Name)" form
+    assert_eq!(
+        actual.entries[0].authors,
+        vec![Person::new("Jonas Große Sundrup").email("jonas@insanity.industries").role("author")]
+    );
 }
 
 // Verifies that we handle relative URLs for links on the enclosure element
@@ -574,3 +812,616 @@ fn test_relurl_2() {
     let content = &actual.entries[0].media[0].content[0];
     assert_eq!(content.url, Url::parse("http://example.com/images/me/hackergotchi-simpler.png").ok());
 }
+
+// Verifies the heap size estimate accounts for the entries held by the feed
+#[test]
+fn test_heap_size() {
+    let test_data = test::fixture_as_string("rss_2.0_bbc.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    // The feed holds all the entries plus its own fields
+    let entries_size: usize = feed.entries.iter().map(|e| e.heap_size()).sum();
+    assert!(entries_size > 0);
+    assert!(feed.heap_size() > entries_size);
+
+    // Each entry owns at least its title
+    for entry in &feed.entries {
+        assert!(entry.heap_size() >= entry.title.as_ref().unwrap().content.len());
+    }
+}
+
+// Verify effective_updated() prefers the declared update time, then falls back to the newest entry
+#[test]
+fn test_effective_updated() {
+    // lastBuildDate is present
+    let feed = parser::parse(test::fixture_as_string("rss_2.0_example_3.xml").as_bytes()).unwrap();
+    assert_eq!(feed.effective_updated(), feed.updated);
+
+    // No lastBuildDate so we use the publication date of the item
+    let feed = parser::parse(test::fixture_as_string("rss_2.0_example_2.xml").as_bytes()).unwrap();
+    assert!(feed.updated.is_none());
+    assert_eq!(feed.effective_updated(), feed.entries[0].published);
+    assert!(feed.effective_updated().is_some());
+}
+
+// Verify the pubDate of an item is recorded where the parser is configured to put it
+#[test]
+fn test_pub_date() {
+    let xml = r#"<rss version="2.0"><channel><item><pubDate>Mon, 01 Mar 2021 10:00:00 GMT</pubDate></item></channel></rss>"#;
+    let entry = |pub_date| {
+        let entry = parser::Builder::new()
+            .pub_date(pub_date)
+            .build()
+            .parse(xml.as_bytes())
+            .unwrap()
+            .entries
+            .remove(0);
+        (entry.updated, entry.published, entry.timestamp())
+    };
+    let date = Some(Utc.with_ymd_and_hms(2021, 3, 1, 10, 0, 0).unwrap());
+
+    assert_eq!(entry(PubDate::Published), (None, date, date));
+    assert_eq!(entry(PubDate::Updated), (date, None, date));
+    assert_eq!(entry(PubDate::Both), (date, date, date));
+}
+
+// Verify a dc:creator listing several people adds an author for each when enabled, with people only added once, and is kept whole by default
+#[test]
+fn test_dublin_core_creators() {
+    let xml = r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel><item>
+            <dc:creator>Alice Smith, Bob Jones and Carol White</dc:creator>
+            <dc:creator>Bob Jones</dc:creator>
+            <dc:creator>alice smith</dc:creator>
+        </item></channel></rss>"#;
+    let authors = |split| {
+        let feed = parser::Builder::new().split_author_names(split).build().parse(xml.as_bytes()).unwrap();
+        feed.entries[0].authors.iter().map(|author| author.name.to_string()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(authors(true), vec!["Alice Smith", "Bob Jones", "Carol White"]);
+    assert_eq!(authors(false), vec!["Alice Smith, Bob Jones and Carol White", "Bob Jones", "alice smith"]);
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].authors.len(), 3);
+}
+
+// Verify the podcast description variants are all retained, and the best one is chosen for display
+#[test]
+fn test_best_description() {
+    let item = |elements: &str| {
+        let xml = format!(
+            r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+                <channel><item>{}</item></channel>
+            </rss>"#,
+            elements
+        );
+        parser::parse(xml.as_bytes()).unwrap().entries.remove(0)
+    };
+
+    let entry = item("<description>description</description><itunes:summary>summary</itunes:summary><content:encoded>content</content:encoded>");
+    assert_eq!(entry.best_description(), Some("content"));
+    assert_eq!(entry.summary.as_ref().unwrap().content, "description");
+    assert_eq!(entry.media[0].description.as_ref().unwrap().content, "summary");
+
+    let entry = item("<description>description</description><itunes:summary>summary</itunes:summary>");
+    assert_eq!(entry.best_description(), Some("description"));
+
+    let entry = item("<itunes:summary>summary</itunes:summary>");
+    assert_eq!(entry.best_description(), Some("summary"));
+
+    let entry = item("<title>title only</title>");
+    assert_eq!(entry.best_description(), None);
+}
+
+// Verify the body of an entry falls back from the content to the summary, and the summary text is plain and truncated
+#[test]
+fn test_body_and_summary_text() {
+    let item = |elements: &str| {
+        let xml = format!(
+            r#"<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/"><channel><item>{}</item></channel></rss>"#,
+            elements
+        );
+        parser::parse(xml.as_bytes()).unwrap().entries.remove(0)
+    };
+
+    let entry = item("<description>&lt;p&gt;Fish &amp;amp; &lt;b&gt;chips&lt;/b&gt;&lt;/p&gt;</description><content:encoded>Full text</content:encoded>");
+    assert!(matches!(entry.body(), Some(Body::Inline(content)) if content.body.as_deref() == Some("Full text")));
+    assert_eq!(entry.summary_text(100).as_deref(), Some("Fish & chips"));
+    assert_eq!(entry.summary_text(9).as_deref(), Some("Fish &"));
+    assert_eq!(entry.summary_text(6).as_deref(), Some("Fish &"));
+    assert_eq!(entry.summary_text(3).as_deref(), Some("Fis"));
+
+    // Truncation respects character boundaries
+    let entry = item("<description>Crème brûlée</description>");
+    assert!(matches!(entry.body(), Some(Body::Summary(summary)) if summary.content == "Crème brûlée"));
+    assert_eq!(entry.summary_text(4).as_deref(), Some("Crè"));
+    assert_eq!(entry.summary_text(3).as_deref(), Some("Cr"));
+
+    // Content is used when there is no summary
+    let entry = item("<content:encoded>&lt;p&gt;Only content&lt;/p&gt;</content:encoded>");
+    assert_eq!(entry.summary_text(100).as_deref(), Some("Only content"));
+
+    let entry = item("<title>Title only</title>");
+    assert_eq!(entry.body(), None);
+    assert_eq!(entry.summary_text(100), None);
+}
+
+// Verifies the iTunes extension captures the podcast specific elements
+#[test]
+fn test_itunes_extension() {
+    let test_data = test::fixture_as_string("rss_2.0_itunes.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    // Nested categories are retained in the extension, with only the top level mapped to the feed categories
+    let expected = ITunesFeedExtension {
+        categories: vec![ITunesCategory::new("Sports").subcategory(ITunesCategory::new("Wilderness"))],
+        complete: Some(true),
+        explicit: Some(false),
+        keywords: vec!["hiking".into(), "outdoors".into(), "nature".into()],
+        show_type: Some("serial".into()),
+        ..Default::default()
+    };
+    assert_eq!(feed.itunes, Some(expected));
+    assert_eq!(feed.categories, vec![Category::new("Sports")]);
+
+    let expected = ITunesItemExtension {
+        block: Some(true),
+        duration: Some(Duration::from_secs(1079)),
+        episode: Some(1),
+        episode_type: Some("trailer".into()),
+        explicit: Some(true),
+        season: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(feed.entries[0].itunes, Some(expected));
+
+    // Feeds without any iTunes elements have no extension
+    let feed = parser::parse(test::fixture_as_string("rss_2.0_example_1.xml").as_bytes()).unwrap();
+    assert!(feed.itunes.is_none());
+    assert!(feed.entries.iter().all(|entry| entry.itunes.is_none()));
+}
+
+// Verify each enclosure on an item is kept, along with its length and MIME type
+#[test]
+fn test_multiple_enclosures() {
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <item>
+                    <title>Episode 1</title>
+                    <enclosure url="http://example.com/episode1.mp3" length="12216320" type="audio/mpeg"/>
+                    <enclosure url="http://example.com/episode1.ogg" length="9216320" type="audio/ogg"/>
+                </item>
+            </channel>
+        </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    let expected = vec![MediaObject::default()
+        .content(
+            MediaContent::new()
+                .url("http://example.com/episode1.mp3")
+                .content_type("audio/mpeg")
+                .size(12216320),
+        )
+        .content(
+            MediaContent::new()
+                .url("http://example.com/episode1.ogg")
+                .content_type("audio/ogg")
+                .size(9216320),
+        )];
+    assert_eq!(feed.entries[0].media, expected);
+}
+
+// Verify the GeoRSS (Simple and GML) and W3C Basic Geo locations are recorded in the geo extension
+#[test]
+fn test_geo() {
+    let test_data = test::fixture_as_string("rss_2.0_geo.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    let polygon = vec![
+        GeoPoint::new(45.256, -110.45),
+        GeoPoint::new(46.46, -109.48),
+        GeoPoint::new(43.84, -109.86),
+        GeoPoint::new(45.256, -110.45),
+    ];
+    let bounding_box = GeoBox {
+        lower: GeoPoint::new(42.943, -71.032),
+        upper: GeoPoint::new(43.039, -69.856),
+    };
+    let expected = vec![
+        Some(GeoExtension {
+            point: Some(GeoPoint::new(45.256, -71.92)),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            point: Some(GeoPoint::new(55.701, 12.552)),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            line: Some(polygon[..3].to_vec()),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            polygon: Some(polygon.clone()),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            bounding_box: Some(bounding_box),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            polygon: Some(polygon),
+            ..Default::default()
+        }),
+        Some(GeoExtension {
+            bounding_box: Some(bounding_box),
+            ..Default::default()
+        }),
+        None,
+    ];
+    assert_eq!(feed.entries.iter().map(|entry| entry.geo.clone()).collect::<Vec<_>>(), expected);
+
+    // Elements without an equivalent in the extension are kept with the other unknown elements
+    let elevation = &feed.entries[0].extensions["http://www.georss.org/georss"][0];
+    assert_eq!((elevation.name.as_str(), elevation.value.as_deref()), ("elev", Some("313")));
+}
+
+// Verify references are decoded once (including the HTML entities XML does not define), and text wrapped in CDATA is left as written
+#[test]
+fn test_entities() {
+    let test_data = test::fixture_as_string("rss_2.0_entities.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    // Whitespace laying out a CDATA section is dropped, and HTML entities are decoded
+    assert_eq!(feed.title.unwrap().content, "Ben & Jerry’s Blog");
+    assert_eq!(feed.description.unwrap().content, "Ice cream\u{a0}& other things");
+
+    // References escaped twice are only decoded once, as the publisher may intend them to be shown
+    let entry = &feed.entries[0];
+    assert_eq!(
+        entry.title.as_ref().unwrap().content,
+        "What’s new in the &#8216;Cookie Dough&#8217; range&hellip;"
+    );
+    assert_eq!(&*entry.authors[0].name, "Ben");
+    assert_eq!(&*entry.categories[0].term, "Flavours &amp; Toppings");
+
+    // Text and CDATA sections are joined
+    assert_eq!(entry.summary.as_ref().unwrap().content, "Half\u{a0}baked <b>news</b> from Ben & Jerry");
+
+    // The content of CDATA sections is otherwise left as it is
+    assert_eq!(
+        entry.content.as_ref().unwrap().body.as_deref(),
+        Some("<p>We&#8217;re <b>delighted</b> to announce &lt;three&gt; new flavours</p>")
+    );
+    assert_eq!(entry.content.as_ref().unwrap().content_type, mime::TEXT_HTML);
+
+    // Escaped markup in a CDATA section is not turned into markup, references to characters XML does not allow are left alone, and
+    // the text between CDATA sections is kept
+    let xml = r#"<rss version="2.0"><channel><item>
+            <title>Nothing&#0; here&#x1;</title>
+            <description><![CDATA[Write &lt;script&gt;]]></description>
+            <author><![CDATA[Ben]]> <![CDATA[Jerry]]></author>
+        </item></channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let entry = &feed.entries[0];
+    assert_eq!(entry.title.as_ref().unwrap().content, "Nothing&#0; here&#x1;");
+    assert_eq!(entry.summary.as_ref().unwrap().content, "Write &lt;script&gt;");
+    assert_eq!(&*entry.authors[0].name, "Ben Jerry");
+}
+
+// Verify the comment metadata from the Slash and Well-Formed Web modules
+#[test]
+fn test_comments() {
+    let xml = r#"<rss version="2.0" xmlns:slash="http://purl.org/rss/1.0/modules/slash/" xmlns:wfw="http://wellformedweb.org/CommentAPI/">
+            <channel>
+                <item>
+                    <link>http://example.com/post/1</link>
+                    <slash:comments>12</slash:comments>
+                    <slash:department>nothing-to-see-here</slash:department>
+                    <wfw:comment>http://example.com/comments/1</wfw:comment>
+                    <wfw:commentRss>http://example.com/post/1/feed</wfw:commentRss>
+                </item>
+            </channel>
+        </rss>"#;
+    let actual = parser::parse(xml.as_bytes()).unwrap();
+
+    let entry = &actual.entries[0];
+    assert_eq!(
+        entry.threading,
+        Some(ThreadingExtension {
+            comments: Some(12),
+            comment_rss: Some("http://example.com/post/1/feed".into()),
+            comment_api: Some("http://example.com/comments/1".into()),
+            ..Default::default()
+        })
+    );
+    // Other Slash elements are kept as extensions
+    assert_eq!(
+        entry.extensions["http://purl.org/rss/1.0/modules/slash/"],
+        vec![ExtensionElement::new("department").value("nothing-to-see-here")]
+    );
+}
+
+// Verify the Podcasting 2.0 elements are parsed at the channel and item level
+#[test]
+fn test_podcast() {
+    let test_data = test::fixture_as_string("rss_2.0_podcast.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    let expected = PodcastFeedExtension {
+        funding: vec![PodcastFunding {
+            url: "https://www.example.com/donations".into(),
+            text: Some("Support the show!".into()),
+        }],
+        persons: vec![PodcastPerson {
+            name: "John Smith".into(),
+            role: None,
+            group: None,
+            img: Some("http://example.com/images/johnsmith.jpg".into()),
+            href: Some("https://example.com/johnsmith/blog".into()),
+        }],
+        value: Some(PodcastValue {
+            value_type: "lightning".into(),
+            method: "keysend".into(),
+            suggested: Some("0.00000005000".into()),
+            recipients: vec![
+                PodcastValueRecipient {
+                    name: Some("Host".into()),
+                    recipient_type: "node".into(),
+                    address: "02d5c1bf8b940dc9cadca86d1b0a3c37fbe39cee4c7e839e33bef9174531d27f52".into(),
+                    split: 90,
+                    custom_key: None,
+                    custom_value: None,
+                    fee: false,
+                },
+                PodcastValueRecipient {
+                    name: Some("Podcastindex.org".into()),
+                    recipient_type: "node".into(),
+                    address: "03ae9f91a0cb8ff43840e3c322c4c61f019d8c1c3cea15a25cfc425ac605e61a4a".into(),
+                    split: 10,
+                    custom_key: None,
+                    custom_value: None,
+                    fee: true,
+                },
+            ],
+        }),
+    };
+    assert_eq!(feed.podcast, Some(expected));
+    assert_eq!(feed.contributors, vec![Person::new("John Smith").uri("https://example.com/johnsmith/blog")]);
+    // Elements that are not modelled are kept as extensions
+    assert_eq!(
+        feed.extensions["https://podcastindex.org/namespace/1.0"],
+        vec![ExtensionElement::new("locked").attribute("owner", "podcastowner@example.com").value("yes")]
+    );
+
+    let expected = PodcastItemExtension {
+        transcripts: vec![
+            PodcastTranscript {
+                url: "https://example.com/ep3/transcript.txt".into(),
+                media_type: "text/plain".into(),
+                language: None,
+                rel: None,
+            },
+            PodcastTranscript {
+                url: "https://example.com/ep3/transcript.vtt".into(),
+                media_type: "text/vtt".into(),
+                language: Some("es".into()),
+                rel: Some("captions".into()),
+            },
+        ],
+        chapters: Some(PodcastChapters {
+            url: "https://example.com/ep3_chapters.json".into(),
+            media_type: "application/json+chapters".into(),
+        }),
+        persons: vec![PodcastPerson {
+            name: "Jane Doe".into(),
+            role: Some("guest".into()),
+            group: None,
+            img: None,
+            href: Some("https://www.imdb.com/name/nm0427852888/".into()),
+        }],
+        soundbites: vec![PodcastSoundbite {
+            start_time: Duration::from_millis(33833),
+            duration: Duration::from_secs(60),
+            title: Some("Why the Podcast Namespace Matters".into()),
+        }],
+        value: None,
+    };
+    assert_eq!(feed.entries[0].podcast, Some(expected));
+    assert_eq!(
+        feed.entries[0].contributors,
+        vec![Person::new("Jane Doe").uri("https://www.imdb.com/name/nm0427852888/")]
+    );
+}
+
+// Verify soundbites with times that cannot be a duration are ignored rather than panicking
+#[test]
+fn test_podcast_invalid_soundbites() {
+    let xml = r#"<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0"><channel><item>
+            <podcast:soundbite startTime="1e30" duration="60"/>
+            <podcast:soundbite startTime="-1" duration="60"/>
+            <podcast:soundbite startTime="NaN" duration="60"/>
+            <podcast:soundbite startTime="10" duration="inf"/>
+            <podcast:soundbite startTime="10.5" duration="60"/>
+        </item></channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    let soundbites = &feed.entries[0].podcast.as_ref().unwrap().soundbites;
+    assert_eq!(
+        soundbites,
+        &vec![PodcastSoundbite {
+            start_time: Duration::from_millis(10500),
+            duration: Duration::from_secs(60),
+            title: None,
+        }]
+    );
+}
+
+// Verifies the rssCloud details, where "http-post" subscriptions have no procedure to call
+#[test]
+fn test_cloud() {
+    let test_data = test::fixture_as_string("rss_2.0_cloud.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    assert_eq!(feed.docs.as_deref(), Some("https://www.rssboard.org/rss-specification"));
+    assert_eq!(
+        feed.cloud,
+        Some(Cloud {
+            domain: "rpc.rsscloud.example".into(),
+            port: Some(5337),
+            path: "/pleaseNotify".into(),
+            register_procedure: None,
+            protocol: "http-post".into(),
+        })
+    );
+}
+
+// Verifies the text input of the channel, whose link is resolved against the base URI
+#[test]
+fn test_text_input() {
+    let test_data = test::fixture_as_string("rss_2.0_text_input.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    assert_eq!(
+        feed.text_input,
+        Some(TextInput {
+            title: Some("Search".into()),
+            description: Some("Search the archive".into()),
+            name: "q".into(),
+            link: "http://example.com/search".into(),
+        })
+    );
+
+    // The source of an item is resolved against the base URI too
+    assert_eq!(
+        feed.entries[0].source,
+        Some(
+            FeedSource::default()
+                .title(Text::new("The Original".into()))
+                .link(Link::new("http://example.com/original.xml", None).rel("self"))
+        )
+    );
+}
+
+// Verifies categories are tidied up, so the same term only appears once in each scheme
+#[test]
+fn test_categories() {
+    let test_data = test::fixture_as_string("rss_2.0_categories.xml");
+    let feed = parser::parse(test_data.as_bytes()).unwrap();
+
+    assert_eq!(
+        feed.categories,
+        vec![Category::new("Computers/Software/Internet/Site Management/Content Management").scheme("http://www.dmoz.org")]
+    );
+    assert_eq!(
+        feed.categories[0].path(),
+        vec!["Computers", "Software", "Internet", "Site Management", "Content Management"]
+    );
+    assert_eq!(
+        Category::new("/Business/ Industries //Publishing/").path(),
+        vec!["Business", "Industries", "Publishing"]
+    );
+    assert_eq!(Category::new("News").path(), vec!["News"]);
+    assert_eq!(
+        feed.entries[0].categories,
+        vec![
+            Category::new("News"),
+            Category::new("News").scheme("http://example.com/tags"),
+            Category::new("Fish & Chips"),
+        ]
+    );
+}
+
+// Verify a permalink guid is used as the link of an item without one, but only if it is a web URL
+#[test]
+fn test_guid_permalink() {
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <item><guid>https://example.com/1</guid></item>
+                <item><guid isPermaLink="true">/2</guid><link>https://example.com/two</link></item>
+                <item><guid isPermaLink="false">https://example.com/3</guid></item>
+                <item><guid>urn:4</guid></item>
+                <item xml:base="https://example.com/"><guid isPermaLink="true">5</guid></item>
+                <item><title>No guid</title><link>https://example.com/6</link></item>
+            </channel>
+        </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    let permalinks = feed.entries.iter().map(|entry| entry.is_permalink).collect::<Vec<_>>();
+    assert_eq!(permalinks, vec![Some(true), Some(true), Some(false), Some(true), Some(true), None]);
+    let links = feed
+        .entries
+        .iter()
+        .map(|entry| entry.links.iter().map(|link| link.href.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        links,
+        vec![
+            vec!["https://example.com/1"],
+            vec!["https://example.com/two"],
+            vec![],
+            vec![],
+            vec!["https://example.com/5"],
+            vec!["https://example.com/6"],
+        ]
+    );
+}
+
+// Verify image dimensions are only set if present, and are clamped to the maximum permitted by the spec
+#[test]
+fn test_image_dimensions() {
+    let image = |elements: &str| {
+        let xml = format!(
+            r#"<rss version="2.0"><channel><title>Title</title><image><url>http://example.com/logo.png</url>{}</image></channel></rss>"#,
+            elements
+        );
+        let logo = parser::parse(xml.as_bytes()).unwrap().logo.unwrap();
+        (logo.width, logo.height)
+    };
+
+    assert_eq!(image(""), (None, None));
+    assert_eq!(image("<width> 100 </width><height>50</height>"), (Some(100), Some(50)));
+    assert_eq!(image("<width>1024</width><height>768</height>"), (Some(144), Some(400)));
+    assert_eq!(image("<width>0</width><height>big</height>"), (None, None));
+}
+
+// Verify Creative Commons licenses are recorded as the license of the channel and items, keeping the first of several
+#[test]
+fn test_creative_commons_license() {
+    let xml = r#"<rss version="2.0" xmlns:creativeCommons="http://backend.userland.com/creativeCommonsRssModule"><channel>
+            <creativeCommons:license> http://creativecommons.org/licenses/by/4.0/ </creativeCommons:license>
+            <item>
+                <guid>urn:1</guid>
+                <creativeCommons:license>http://creativecommons.org/licenses/by-nc/4.0/</creativeCommons:license>
+                <creativeCommons:license>http://creativecommons.org/licenses/by-sa/4.0/</creativeCommons:license>
+            </item>
+            <item><guid>urn:2</guid><creativeCommons:license></creativeCommons:license></item>
+        </channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    assert_eq!(
+        feed.license,
+        Some(Link::new("http://creativecommons.org/licenses/by/4.0/", None).rel("license"))
+    );
+    assert_eq!(
+        feed.entries[0].license,
+        Some(Link::new("http://creativecommons.org/licenses/by-nc/4.0/", None).rel("license"))
+    );
+    assert_eq!(feed.entries[1].license, None);
+    assert!(feed.extensions.is_empty() && feed.entries[0].extensions.is_empty());
+}
+
+// Verify the language of an item is from "dc:language", or else its xml:lang, and the channel's language is not copied to the items
+#[test]
+fn test_item_language() {
+    let xml = r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>
+            <language>en</language>
+            <item><guid>urn:1</guid><dc:language>fr-CA</dc:language></item>
+            <item xml:lang="de"><guid>urn:2</guid><dc:language>pt_br</dc:language></item>
+            <item xml:lang="de"><guid>urn:3</guid></item>
+            <item><guid>urn:4</guid></item>
+        </channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    let languages = feed.entries.iter().map(|entry| entry.language.as_deref()).collect::<Vec<_>>();
+    assert_eq!(languages, vec![Some("fr-CA"), Some("pt_br"), Some("de"), None]);
+    assert_eq!(feed.entries[1].language_tag().as_deref(), Some("pt-BR"));
+}
@@ -0,0 +1,36 @@
+use crate::model::{Feed, UpdateHints, UpdatePeriod};
+use crate::parser::util::if_some_then;
+use crate::parser::{ParseFeedResult, Parser};
+use crate::util::dates::TimestampFormat;
+use crate::xml::Element;
+use std::io::BufRead;
+
+// Process <sy> elements at channel level, recording the update schedule in the hints
+pub(crate) fn handle_syndication_element<R: BufRead>(parser: &Parser, element: Element<R>, feed: &mut Feed) -> ParseFeedResult<()> {
+    fn hints(feed: &mut Feed) -> &mut UpdateHints {
+        feed.update_hints.get_or_insert_with(Default::default)
+    }
+
+    match element.name.as_str() {
+        "updatePeriod" => if_some_then(element.child_as_text().and_then(|text| UpdatePeriod::parse(&text)), |period| {
+            hints(feed).update_period = Some(period)
+        }),
+
+        "updateFrequency" => if_some_then(element.child_as_text().and_then(|text| text.trim().parse::<u32>().ok()), |frequency| {
+            hints(feed).update_frequency = Some(frequency)
+        }),
+
+        "updateBase" => {
+            if let Some(text) = element.child_as_text() {
+                if let Some(base) = parser.parse_timestamp(&text, TimestampFormat::W3cDtf)? {
+                    hints(feed).update_base = Some(base);
+                }
+            }
+        }
+
+        // Unknown elements are passed to the caller's handler, if registered
+        _ => parser.handle_unknown_element(&element, &mut feed.extensions)?,
+    }
+
+    Ok(())
+}
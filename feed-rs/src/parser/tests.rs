@@ -0,0 +1,1655 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc, Weekday};
+
+use crate::extensions::ExtensionElement;
+use crate::model::{Entry, Feed, FeedType, MediaRating, Stylesheet, Text, TextVariant, UpdatePeriod};
+use crate::opml::{Opml, Outline};
+use crate::parser;
+use crate::parser::{Builder, CancellationToken, DuplicateIds, NamespaceHandler, ParseErrorKind, ParseFeedError, Position, Progress, Warning, XmlEvent};
+use crate::util::test;
+
+// Verify the progress handler is called for each entry, for both XML and JSON sources
+#[test]
+fn test_progress_handler() {
+    for fixture in &["rss_2.0_bbc.xml", "atom_example_1.xml", "rss_1.0_example_1.xml", "jsonfeed_example_1.json"] {
+        let reported = Arc::new(Mutex::new(Vec::<Progress>::new()));
+        let handler_reported = reported.clone();
+        let parser = Builder::new()
+            .progress_handler(move |progress| handler_reported.lock().unwrap().push(*progress))
+            .build();
+
+        let test_data = test::fixture_as_raw(fixture);
+        let feed = parser.parse(test_data.as_slice()).unwrap();
+
+        // We should see one call per entry, in order, with the consumed bytes increasing as we go
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), feed.entries.len(), "{}", fixture);
+        for (i, progress) in reported.iter().enumerate() {
+            assert_eq!(progress.entries_emitted, i + 1);
+            assert!(progress.bytes_consumed <= test_data.len() as u64);
+            if i > 0 {
+                assert!(progress.bytes_consumed >= reported[i - 1].bytes_consumed);
+            }
+        }
+    }
+}
+
+// Verify a parser with handlers can be shared between threads, and entries streamed on another thread
+#[test]
+fn test_parser_threads() {
+    let reported = Arc::new(Mutex::new(0));
+    let handler_reported = reported.clone();
+    let parser = Arc::new(Builder::new().progress_handler(move |_| *handler_reported.lock().unwrap() += 1).build());
+
+    let threads = (0..2)
+        .map(|_| {
+            let parser = parser.clone();
+            std::thread::spawn(move || parser.parse(test::fixture_as_raw("rss_2.0_bbc.xml").as_slice()).unwrap().entries.len())
+        })
+        .collect::<Vec<_>>();
+    let parsed = threads.into_iter().map(|thread| thread.join().unwrap()).sum::<usize>();
+    assert_eq!(*reported.lock().unwrap(), parsed);
+
+    let entries = Builder::new()
+        .build()
+        .parse_entries(&b"<rss version=\"2.0\"><channel><item><title>One</title></item></channel></rss>"[..]);
+    assert_eq!(std::thread::spawn(move || entries.count()).join().unwrap(), 1);
+}
+
+// Verify a parse can be cancelled part way through, returning the entries parsed so far
+#[test]
+fn test_cancellation() {
+    for fixture in &["rss_2.0_spec_1.xml", "atom_example_2.xml", "rss_1.0_example_1.xml", "jsonfeed_example_1.json"] {
+        // Cancel once the first entry has been parsed (each fixture has two)
+        let token = CancellationToken::new();
+        let handler_token = token.clone();
+        let parser = Builder::new()
+            .cancellation_token(token.clone())
+            .progress_handler(move |progress| {
+                if progress.entries_emitted == 1 {
+                    handler_token.cancel();
+                }
+            })
+            .build();
+
+        let test_data = test::fixture_as_raw(fixture);
+        match parser.parse(test_data.as_slice()) {
+            Err(ParseFeedError::Cancelled(feed)) => {
+                assert_eq!(feed.entries.len(), 1, "{}", fixture);
+                assert!(!feed.entries[0].id.is_empty());
+            }
+            other => panic!("expected {} to be cancelled, got {:?}", fixture, other),
+        }
+        assert!(token.is_cancelled());
+    }
+}
+
+// Verify a stream of entries can be cancelled part way through, ending with a single error
+#[test]
+fn test_cancellation_streaming() {
+    for fixture in &["rss_2.0_spec_1.xml", "atom_example_2.xml", "rss_1.0_example_1.xml", "jsonfeed_example_1.json"] {
+        let token = CancellationToken::new();
+        let test_data = test::fixture_as_raw(fixture);
+        let mut entries = Builder::new().cancellation_token(token.clone()).build().parse_entries(test_data.as_slice());
+
+        assert!(entries.next().unwrap().is_ok(), "{}", fixture);
+        token.cancel();
+        match entries.next() {
+            Some(Err(ParseFeedError::Cancelled(feed))) => assert!(feed.entries.is_empty(), "{}", fixture),
+            other => panic!("expected {} to be cancelled, got {:?}", fixture, other),
+        }
+        assert!(entries.next().is_none(), "{}", fixture);
+    }
+}
+
+// Verify that tiny buffers still produce the same result as the defaults
+#[test]
+fn test_buffer_capacity() {
+    for fixture in &["rss_2.0_bbc.xml", "atom_example_6.xml", "jsonfeed_example_1.json"] {
+        let test_data = test::fixture_as_raw(fixture);
+        let expected = parser::parse(test_data.as_slice()).unwrap();
+
+        let parser = Builder::new().read_buffer_capacity(16).text_buffer_capacity(1).build();
+        let actual = parser.parse(test_data.as_slice()).unwrap();
+
+        assert_eq!(actual, expected, "{}", fixture);
+
+        // A capacity too small to detect the format is raised to the minimum
+        let parser = Builder::new().read_buffer_capacity(0).build();
+        assert_eq!(parser.parse(test_data.as_slice()).unwrap(), expected, "{}", fixture);
+    }
+}
+
+// Verify elements the parser does not understand are passed to the handler with their path and events
+#[test]
+fn test_unknown_element_handler() {
+    let xml = r#"
+        <rss version="2.0" xmlns:ext="http://example.com/ext">
+            <channel>
+                <title>Channel</title>
+                <ext:rating scheme="stars">4<ext:note>good</ext:note></ext:rating>
+                <item>
+                    <title>Item</title>
+                    <ext:flag/>
+                </item>
+            </channel>
+        </rss>"#;
+
+    let captured = Arc::new(Mutex::new(Vec::<(Vec<String>, Vec<XmlEvent>)>::new()));
+    let handler_captured = captured.clone();
+    let parser = Builder::new()
+        .unknown_element_handler(move |path, events| handler_captured.lock().unwrap().push((path.to_vec(), events.to_vec())))
+        .build();
+    let feed = parser.parse(xml.as_bytes()).unwrap();
+
+    // The known elements are still parsed as normal
+    assert_eq!(feed.title.unwrap().content, "Channel");
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().content, "Item");
+
+    let path = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<String>>();
+    let start = |name: &str, attributes: &[(&str, &str)]| XmlEvent::Start {
+        name: name.to_owned(),
+        attributes: attributes.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+    };
+    let end = |name: &str| XmlEvent::End { name: name.to_owned() };
+    let captured = captured.lock().unwrap();
+    assert_eq!(
+        *captured,
+        vec![
+            (
+                path(&["rss", "channel", "rating"]),
+                vec![
+                    start("rating", &[("scheme", "stars")]),
+                    XmlEvent::Text("4".to_owned()),
+                    start("note", &[]),
+                    XmlEvent::Text("good".to_owned()),
+                    end("note"),
+                    end("rating"),
+                ]
+            ),
+            (path(&["rss", "channel", "item", "flag"]), vec![start("flag", &[]), end("flag")]),
+        ]
+    );
+}
+
+// Verify elements from unknown namespaces are recorded as extensions on the feed and entry, but unknown elements of the format are not
+#[test]
+fn test_extensions() {
+    let xml = r#"
+        <rss version="2.0" xmlns:ext="http://example.com/ext" xmlns:blogChannel="http://backend.userland.com/blogChannelModule">
+            <channel>
+                <title>Channel</title>
+                <blogChannel:blogRoll>http://example.com/blogroll.opml</blogChannel:blogRoll>
+                <ext:rating scheme="stars">4<ext:note lang="en">good</ext:note></ext:rating>
+                <undefined>Not namespaced</undefined>
+                <item>
+                    <title>Item</title>
+                    <ext:flag/>
+                    <ext:title> Kept <ext:em>as</ext:em> written, <![CDATA[with]]> its spaces </ext:title>
+                </item>
+            </channel>
+        </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    assert_eq!(
+        feed.extensions.get("http://example.com/ext").unwrap(),
+        &vec![ExtensionElement::new("rating")
+            .attribute("scheme", "stars")
+            .value("4")
+            .child(ExtensionElement::new("note").attribute("lang", "en").value("good"))]
+    );
+    assert_eq!(
+        feed.extensions.get("http://backend.userland.com/blogChannelModule").unwrap(),
+        &vec![ExtensionElement::new("blogRoll").value("http://example.com/blogroll.opml")]
+    );
+    assert_eq!(feed.extensions.len(), 2);
+
+    // Elements with the same name as those of the format are only extensions, with the text around their children as found
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().content, "Item");
+    assert_eq!(feed.entries[0].extensions.len(), 1);
+    assert_eq!(
+        feed.entries[0].extensions.get("http://example.com/ext").unwrap(),
+        &vec![
+            ExtensionElement::new("flag"),
+            ExtensionElement::new("title")
+                .value("Kept  written, with its spaces")
+                .child(ExtensionElement::new("em").value("as"))
+        ]
+    );
+}
+
+// Verify elements from unknown namespaces are not mistaken for those of the format with the same name
+#[test]
+fn test_unknown_namespace_core_names() {
+    let xml = r#"
+        <rss version="2.0" xmlns:ext="http://example.com/ext">
+            <channel>
+                <title>Channel</title>
+                <ext:title>Not the title</ext:title>
+                <link>http://example.com/</link>
+                <ext:link>http://example.com/ext</ext:link>
+                <item>
+                    <title>Item</title>
+                    <ext:title>Not the title</ext:title>
+                    <ext:link>http://example.com/ext</ext:link>
+                </item>
+            </channel>
+        </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.as_ref().unwrap().content, "Channel");
+    assert_eq!(feed.links.len(), 1);
+    assert_eq!(feed.extensions.get("http://example.com/ext").unwrap().len(), 2);
+    let entry = &feed.entries[0];
+    assert_eq!(entry.title.as_ref().unwrap().content, "Item");
+    assert!(entry.links.is_empty());
+    assert_eq!(entry.extensions.get("http://example.com/ext").unwrap().len(), 2);
+
+    // The same applies to Atom, whose own namespace may be the default, and to RSS 2.0 feeds that declare a namespace
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+            <title>Feed</title>
+            <entry><id>urn:1</id><ext:id>urn:ext</ext:id><title>Entry</title><ext:title>Not the title</ext:title></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].id, "urn:1");
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().content, "Entry");
+
+    let xml = r#"<rss version="2.0" xmlns="http://backend.userland.com/rss2"><channel><title>Channel</title></channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "Channel");
+}
+
+// Verify stylesheet processing instructions are tolerated, and reported on the feed
+#[test]
+fn test_stylesheets() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <?xml-stylesheet type="text/xsl" href="https://example.com/feed.xsl"?>
+        <?xml-stylesheet href='feed.css'?>
+        <?xml-stylesheet type="text/css"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Styled</title>
+            <entry><title>Entry</title></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries.len(), 1);
+
+    // Stylesheets without a location are dropped
+    let expected = vec![
+        Stylesheet::new("https://example.com/feed.xsl".into()).media_type("text/xsl"),
+        Stylesheet::new("feed.css".into()),
+    ];
+    assert_eq!(feed.stylesheets, expected);
+}
+
+// Verify all variants of the title and description are retained when requested, with the model fields unchanged
+#[test]
+fn test_text_variants() {
+    let xml = r#"
+        <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <channel>
+                <title>Channel</title>
+                <itunes:summary>Channel summary</itunes:summary>
+                <description>Channel description</description>
+                <item>
+                    <dc:title>DC title</dc:title>
+                    <title>Title</title>
+                    <itunes:title>iTunes title</itunes:title>
+                    <description>Description</description>
+                    <itunes:summary>iTunes summary</itunes:summary>
+                </item>
+            </channel>
+        </rss>"#;
+    let elements = |variants: &[TextVariant]| variants.iter().map(|v| (v.element.clone(), v.text.content.clone())).collect::<Vec<_>>();
+    let expected = |pairs: &[(&str, &str)]| pairs.iter().map(|(e, t)| (e.to_string(), t.to_string())).collect::<Vec<_>>();
+
+    let feed = Builder::new().text_variants(true).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.as_ref().unwrap().content, "Channel");
+    assert_eq!(feed.description.as_ref().unwrap().content, "Channel description");
+    assert_eq!(elements(&feed.title_variants), expected(&[("title", "Channel")]));
+    assert_eq!(
+        elements(&feed.description_variants),
+        expected(&[("itunes:summary", "Channel summary"), ("description", "Channel description")])
+    );
+
+    let entry = &feed.entries[0];
+    assert_eq!(entry.title.as_ref().unwrap().content, "Title");
+    assert_eq!(entry.summary.as_ref().unwrap().content, "Description");
+    assert_eq!(
+        elements(&entry.title_variants),
+        expected(&[("dc:title", "DC title"), ("title", "Title"), ("itunes:title", "iTunes title")])
+    );
+    assert_eq!(
+        elements(&entry.summary_variants),
+        expected(&[("description", "Description"), ("itunes:summary", "iTunes summary")])
+    );
+
+    // Variants are not retained by default
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert!(feed.title_variants.is_empty());
+    assert!(feed.entries[0].title_variants.is_empty());
+}
+
+// Verify feeds that differ only in entry order, whitespace and date formatting are semantically equal
+#[test]
+fn test_semantically_eq() {
+    let first = r#"
+        <rss version="2.0">
+            <channel>
+                <title>Channel</title>
+                <link>http://example.com/</link>
+                <item><guid>1</guid><title>First</title><pubDate>Mon, 06 Sep 2010 00:01:00 +0000</pubDate></item>
+                <item><guid>2</guid><title>Second</title></item>
+            </channel>
+        </rss>"#;
+    let second = r#"
+        <rss version="2.0" xmlns:x="http://example.com/x">
+            <channel>
+                <link>http://example.com/</link>
+                <title>Channel</title>
+                <item><guid>2</guid><title>
+                    Second
+                </title></item>
+                <item><guid>1</guid><title>First</title><pubDate>Mon, 6 Sep 2010 01:01:00 +0100</pubDate></item>
+            </channel>
+        </rss>"#;
+    let first = parser::parse(first.as_bytes()).unwrap();
+    let second = parser::parse(second.as_bytes()).unwrap();
+    assert_ne!(first, second);
+    assert!(first.semantically_eq(&second), "{:?}", first.semantic_differences(&second));
+
+    // Real differences are reported by field
+    let mut third = second.clone();
+    third.title = None;
+    third.entries[0].summary = Some(Text::new("Summary".into()));
+    third.entries.pop();
+    assert!(!first.semantically_eq(&third));
+    assert_eq!(
+        first.semantic_differences(&third),
+        vec!["title", "entries[1]: only in this feed", "entries[2].summary"]
+    );
+}
+
+// Verify streamed entries match those of the parsed feed, and progress is reported as the iterator is advanced
+#[test]
+fn test_parse_entries() {
+    for fixture in &[
+        "atom_example_1.xml",
+        "atom_spec_1.xml",
+        "rss_0.91_spec_1.xml",
+        "rss_1.0_example_1.xml",
+        "rss_2.0_bbc.xml",
+        "rss_2.0_example_5.xml",
+        "jsonfeed_example_1.json",
+    ] {
+        let test_data = test::fixture_as_raw(fixture);
+        let feed = parser::parse(test_data.as_slice()).unwrap();
+
+        let reported = Arc::new(Mutex::new(0));
+        let handler_reported = reported.clone();
+        let parser = Builder::new()
+            .progress_handler(move |progress| *handler_reported.lock().unwrap() = progress.entries_emitted)
+            .build();
+        let entries = parser.parse_entries(test_data.as_slice()).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, feed.entries, "{}", fixture);
+        if !fixture.ends_with(".json") {
+            assert_eq!(*reported.lock().unwrap(), entries.len(), "{}", fixture);
+        }
+    }
+
+    // Errors end the iteration
+    let mut entries = parser::parse_entries(r#"<rss version="2.0"><channel><item><title>One</title></item><item>"#.as_bytes());
+    assert!(entries.next().unwrap().is_ok());
+    assert!(entries.next().unwrap().is_err());
+    assert!(entries.next().is_none());
+
+    // As do documents that are not feeds
+    let mut entries = parser::parse_entries("<html/>".as_bytes());
+    assert!(matches!(entries.next(), Some(Err(ParseFeedError::ParseError(_)))));
+    assert!(entries.next().is_none());
+
+    // Feeds are recognised as they are by parse(), including the later versions of RSS 0.9x and UTF-16 sources
+    let xml = "\u{feff}<rss version=\" 0.94 \"><channel><language>fr</language><item><title>Café</title></item></channel></rss>";
+    let utf16: Vec<u8> = xml.encode_utf16().flat_map(|unit| unit.to_le_bytes().to_vec()).collect();
+    for source in &[xml.as_bytes(), utf16.as_slice()] {
+        let feed = parser::parse(*source).unwrap();
+        let entries = parser::parse_entries(*source).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, feed.entries);
+        let title = entries[0].title.as_ref().unwrap();
+        assert_eq!((title.content.as_str(), title.lang.as_deref()), ("Café", Some("fr")));
+    }
+}
+
+// Verify malformed sources report where the problem was found
+#[test]
+fn test_error_position() {
+    let xml = "<rss version=\"2.0\">\n  <channel>\n    <title>Café</title>\n    <item></itme>\n";
+    let error = parser::parse(xml.as_bytes()).unwrap_err();
+    assert_eq!(error.position(), Some(Position { line: 4, column: 18 }));
+    assert!(error.to_string().contains("line 4, column 18"), "{}", error);
+
+    let json = "{\n  \"version\": \"https://jsonfeed.org/version/1\",\n  \"title\": }";
+    let error = parser::parse(json.as_bytes()).unwrap_err();
+    assert_eq!(error.position(), Some(Position { line: 3, column: 12 }));
+
+    // Errors in the structure of a well-formed document have no position
+    let error = parser::parse("<html/>".as_bytes()).unwrap_err();
+    assert_eq!(error.position(), None);
+}
+
+// Verify the model survives a round trip through serde unchanged
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    for fixture in &[
+        "atom_mediarss_youtube_1.xml",
+        "jsonfeed_spec_1.json",
+        "rss_2.0_itunes.xml",
+        "rss_1.0_spec_2.xml",
+    ] {
+        let feed = parser::parse(test::fixture_as_raw(fixture).as_slice()).unwrap();
+        let json = serde_json::to_string(&feed).unwrap();
+        let actual: crate::model::Feed = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, feed, "{}", fixture);
+    }
+}
+
+// Verify the format of each fixture is detected without parsing it
+#[test]
+fn test_detect() {
+    for (fixture, expected) in &[
+        ("atom_example_1.xml", FeedType::Atom),
+        ("atom_entry_1.xml", FeedType::Atom),
+        ("jsonfeed_spec_1.json", FeedType::JSON),
+        ("rss_0.91_spec_1.xml", FeedType::RSS0),
+        ("rss_0.92_spec_1.xml", FeedType::RSS0),
+        ("rss_1.0_spec_1.xml", FeedType::RSS1),
+        ("rss_2.0_spec_1.xml", FeedType::RSS2),
+        ("rss_2.0_encoding_1.xml", FeedType::RSS2),
+    ] {
+        let source = test::fixture_as_raw(fixture);
+        assert_eq!(parser::detect(&source), Some(*expected), "{}", fixture);
+        assert_eq!(parser::parse(source.as_slice()).unwrap().feed_type, *expected, "{}", fixture);
+    }
+
+    // Documents that are not feeds
+    for source in &["", "<html><body/></html>", "<rss version=\"3.0\"/>", r#"{"title": "not a feed"}"#, "plain text"] {
+        assert_eq!(parser::detect(source.as_bytes()), None, "{}", source);
+    }
+}
+
+// Verify the charset from an external source (e.g. an HTTP header) overrides the declared encoding
+#[test]
+fn test_parse_with_charset() {
+    let xml = "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><rss version=\"2.0\"><channel><title>Новости</title></channel></rss>";
+    let (source, _, _) = encoding_rs::WINDOWS_1251.encode(xml);
+
+    let feed = parser::parse_with_charset(source.as_ref(), None, "application/rss+xml; charset=windows-1251").unwrap();
+    assert_eq!(feed.title.unwrap().content, "Новости");
+
+    // An unknown charset falls back to the declared encoding
+    let feed = parser::parse_with_charset(source.as_ref(), None, "bogus").unwrap();
+    assert_ne!(feed.title.unwrap().content, "Новости");
+}
+
+// Verify the headers of an HTTP response guide the parser, and unsuccessful responses are not parsed
+#[test]
+fn test_parse_response() {
+    let xml = r#"<rss version="2.0"><channel><title>News</title><item><title>Item</title></item></channel></rss>"#;
+
+    // The language of the response applies to a feed without one of its own
+    let feed = parser::parse_response(200, vec![("Content-Language", "de-DE, en")], xml.as_bytes()).unwrap();
+    assert_eq!(feed.language.as_deref(), Some("de-DE"));
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().lang.as_deref(), Some("de-DE"));
+
+    let xml_with_language = r#"<rss version="2.0"><channel><title>News</title><language>en</language></channel></rss>"#;
+    let feed = parser::parse_response(200, vec![("content-language", "de")], xml_with_language.as_bytes()).unwrap();
+    assert_eq!(feed.language.as_deref(), Some("en"));
+
+    // The language of the response is checked like one the feed declares
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let reported = warnings.clone();
+    let parser = Builder::new()
+        .warning_handler(move |warning| reported.lock().unwrap().push(warning.clone()))
+        .build();
+    parser
+        .parse_response(200, vec![("Content-Language", "not a language")], xml.as_bytes())
+        .unwrap();
+    assert!(warnings.lock().unwrap().iter().any(|warning| matches!(warning, Warning::InvalidLanguage(_))));
+
+    // The declared format is looked for first, so markup printed before a JSON Feed is skipped
+    let json = r#"<b>Notice</b>: undefined index
+        {"version": "https://jsonfeed.org/version/1", "title": "News", "items": []}"#;
+    let feed = parser::parse_response(200, vec![("Content-Type", "application/feed+json")], json.as_bytes()).unwrap();
+    assert_eq!(feed.feed_type, FeedType::JSON);
+    let feed = parser::parse_response(200, vec![("Content-Type", "application/rss+xml")], format!("{{}}{}", xml).as_bytes()).unwrap();
+    assert_eq!(feed.feed_type, FeedType::RSS2);
+
+    // Links are resolved against the location of the response, which may itself be relative to the parser's base URI
+    let xml_with_link = r#"<rss version="2.0"><channel><item><link>item/1</link></item></channel></rss>"#;
+    let feed = parser::parse_response(200, vec![("Content-Location", "http://example.com/feed/")], xml_with_link.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].links[0].href, "http://example.com/feed/item/1");
+    let parser = Builder::new().base_uri("http://example.com/news/").build();
+    let feed = parser
+        .parse_response(200, vec![("Content-Location", "rss/")], xml_with_link.as_bytes())
+        .unwrap();
+    assert_eq!(feed.entries[0].links[0].href, "http://example.com/news/rss/item/1");
+
+    // Media types which cannot be a feed are rejected without parsing the body
+    let error = parser::parse_response(200, vec![("Content-Type", "image/png")], xml.as_bytes()).unwrap_err();
+    assert!(matches!(error, ParseFeedError::ParseError(ParseErrorKind::UnknownMimeType(ref media_type)) if media_type == "image/png"));
+
+    // The body of an error page is not the feed
+    let error = parser::parse_response(404, vec![("Content-Type", "text/html")], "<html></html>".as_bytes()).unwrap_err();
+    assert!(matches!(error, ParseFeedError::ParseError(ParseErrorKind::UnsuccessfulStatus(404))));
+}
+
+// Verify UTF-16 sources are detected from their byte order mark
+#[test]
+fn test_parse_utf16() {
+    let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"UTF-16\"?><rss version=\"2.0\"><channel><title>Café</title></channel></rss>";
+    let source: Vec<u8> = xml.encode_utf16().flat_map(|unit| unit.to_le_bytes().to_vec()).collect();
+
+    let feed = parser::parse(source.as_slice()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "Café");
+}
+
+// Verify byte order marks and anything emitted before the XML declaration are skipped
+#[test]
+fn test_leading_garbage() {
+    let xml = r#"<?xml version="1.0" encoding="utf-8"?><rss version="2.0"><channel><title>Title</title><item><title>Item</title></item></channel></rss>"#;
+    for prefix in &[
+        "\u{feff}",
+        "\n\n",
+        "\u{feff}\r\n",
+        "<br />\n<b>Warning</b>: Cannot modify header information in <b>feed.php</b><br />\n",
+        "Deprecated: Function create_function() is deprecated in feed.php on line 7\n",
+    ] {
+        let source = format!("{}{}", prefix, xml);
+        let feed = parser::parse(source.as_bytes()).unwrap();
+        assert_eq!(feed.title.unwrap().content, "Title", "{:?}", prefix);
+        assert_eq!(parser::parse_entries(source.as_bytes()).count(), 1, "{:?}", prefix);
+        assert_eq!(parser::detect(source.as_bytes()), Some(FeedType::RSS2), "{:?}", prefix);
+    }
+
+    // A JSON Feed whose content includes a declaration is not skipped to it
+    let json = r#"{"version": "https://jsonfeed.org/version/1", "title": "Title", "items": [{"id": "1", "content_text": "<?xml version=\"1.0\"?><rss/>"}]}"#;
+    let feed = parser::parse(json.as_bytes()).unwrap();
+    assert_eq!(feed.feed_type, FeedType::JSON);
+    assert_eq!(
+        feed.entries[0].content.as_ref().unwrap().body.as_deref(),
+        Some(r#"<?xml version="1.0"?><rss/>"#)
+    );
+    assert_eq!(parser::detect(json.as_bytes()), Some(FeedType::JSON));
+}
+
+// Verify feeds wrapped in HTML or following server notices are salvaged if enabled, with the skipped content reported
+#[test]
+fn test_salvage() {
+    let feed = r#"<rss version="2.0"><channel><title>Title</title><item><title>Item</title></item></channel></rss>"#;
+    for (prefix, suffix) in &[
+        ("<html><head><title>502</title></head><body><pre>", "</pre></body></html>"),
+        (
+            "<br />\n<b>Notice</b>: Undefined variable: limit in <b>/var/www/feed.php</b> on line <b>12</b><br />\n",
+            "",
+        ),
+    ] {
+        let source = format!("{}{}{}", prefix, feed, suffix);
+        assert!(parser::parse(source.as_bytes()).is_err(), "{:?}", prefix);
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let handler_reported = reported.clone();
+        let parser = Builder::new()
+            .salvage(true)
+            .warning_handler(move |warning| handler_reported.lock().unwrap().push(warning.clone()))
+            .build();
+        let actual = parser.parse(source.as_bytes()).unwrap();
+        assert_eq!(actual.title.unwrap().content, "Title", "{:?}", prefix);
+        assert_eq!(actual.entries.len(), 1, "{:?}", prefix);
+        assert_eq!(reported.lock().unwrap()[0], Warning::SkippedContent(prefix.len()));
+
+        let entries = Builder::new()
+            .salvage(true)
+            .build()
+            .parse_entries(source.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1, "{:?}", prefix);
+    }
+
+    // Well formed feeds are not affected, including JSON Feeds with the markup of a feed in their content
+    let source = test::fixture_as_raw("atom_spec_1.xml");
+    assert_eq!(
+        Builder::new().salvage(true).build().parse(source.as_slice()).unwrap(),
+        parser::parse(source.as_slice()).unwrap()
+    );
+    let source = r#"{"version": "https://jsonfeed.org/version/1", "title": "Title", "items": [{"id": "1", "content_html": "<feed xmlns='x'>"}]}"#;
+    let actual = Builder::new().salvage(true).build().parse(source.as_bytes()).unwrap();
+    assert_eq!(actual.feed_type, FeedType::JSON);
+    assert_eq!(actual, parser::parse(source.as_bytes()).unwrap());
+}
+
+// Verify feeds are parsed from an asynchronous source, including those larger than the chunks passed to the parser
+#[cfg(feature = "tokio")]
+#[test]
+fn test_parse_async() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    // Fails after returning a truncated document
+    struct FailingReader(bool);
+    impl tokio::io::AsyncRead for FailingReader {
+        fn poll_read(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+            if self.0 {
+                return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset")));
+            }
+            self.0 = true;
+            let start = b"<rss version=\"2.0\"><channel><title>";
+            buf[..start.len()].copy_from_slice(start);
+            Poll::Ready(Ok(start.len()))
+        }
+    }
+
+    let mut runtime = tokio::runtime::Builder::new().basic_scheduler().enable_all().build().unwrap();
+    runtime.block_on(async {
+        let fixture = test::fixture_as_raw("rss_2.0_bbc.xml");
+        assert_eq!(
+            parser::parse_async(fixture.as_slice()).await.unwrap(),
+            parser::parse(fixture.as_slice()).unwrap()
+        );
+
+        let mut xml = String::from(r#"<rss version="2.0"><channel><title>Large</title>"#);
+        for i in 0..5000 {
+            xml.push_str(&format!("<item><guid>{}</guid><title>Item {}</title></item>", i, i));
+        }
+        xml.push_str("</channel></rss>");
+        let feed = parser::parse_async(xml.as_bytes()).await.unwrap();
+        assert_eq!(feed.entries.len(), 5000);
+        assert_eq!(feed.entries[4999].id, "4999");
+
+        // Errors reading the source are returned, as are those from the parser
+        let error = parser::parse_async(FailingReader(false)).await.unwrap_err();
+        assert!(format!("{:?}", error).contains("ConnectionReset"), "{:?}", error);
+        assert!(parser::parse_async(&b"<html></html>"[..]).await.is_err());
+    });
+}
+
+// Verify OPML subscription lists are parsed, including nested folders and attributes in any case
+#[test]
+fn test_opml() {
+    let test_data = test::fixture_as_raw("opml_example_1.xml");
+    let opml = Opml::parse(test_data.as_slice()).unwrap();
+
+    let expected = Opml {
+        version: "2.0".into(),
+        title: Some("mySubscriptions.opml".into()),
+        date_created: Some(Utc.with_ymd_and_hms(2005, 6, 18, 12, 11, 52).unwrap()),
+        owner_name: Some("Dave Winer".into()),
+        owner_email: Some("dave@scripting.com".into()),
+        outlines: vec![
+            Outline {
+                title: Some("CNET News.com".into()),
+                html_url: Some("http://news.com.com/".into()),
+                ..Outline::feed("CNET News.com", "http://news.com.com/2547-1_3-0-5.xml")
+            },
+            Outline::folder(
+                "Science",
+                vec![
+                    Outline::feed("Nature", "http://feeds.nature.com/nature/rss/current"),
+                    Outline::folder(
+                        "Space",
+                        vec![Outline {
+                            title: Some("NASA Breaking News".into()),
+                            html_url: Some("https://www.nasa.gov/".into()),
+                            ..Outline::feed("NASA Breaking News", "https://www.nasa.gov/rss/dyn/breaking_news.rss")
+                        }],
+                    ),
+                ],
+            ),
+            Outline::feed("Fish & Chips", "http://example.com/feed?a=1&b=2"),
+        ],
+    };
+    assert_eq!(opml, expected);
+    assert_eq!(
+        opml.feeds().map(|outline| outline.text.as_str()).collect::<Vec<_>>(),
+        vec!["CNET News.com", "Nature", "NASA Breaking News", "Fish & Chips"]
+    );
+
+    // Feeds are not OPML
+    assert!(matches!(
+        Opml::parse(test::fixture_as_raw("rss_2.0_example_1.xml").as_slice()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot))
+    ));
+}
+
+// Verify the WebSub hub and self links are found in each format
+#[test]
+fn test_websub_links() {
+    let links = |feed: &crate::model::Feed| {
+        (
+            feed.hub_links().map(|link| link.href.clone()).collect::<Vec<_>>(),
+            feed.self_link().map(|link| link.href.clone()),
+        )
+    };
+
+    let feed = parser::parse(test::fixture_as_raw("rss_2.0_example_5.xml").as_slice()).unwrap();
+    assert_eq!(
+        links(&feed),
+        (
+            vec!["http://pubsubhubbub.appspot.com/".to_owned()],
+            Some("http://feeds.arstechnica.com/arstechnica/index".to_owned())
+        )
+    );
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="alternate" href="http://example.com/"/>
+            <link rel="SELF" href="http://example.com/feed"/>
+            <link rel="hub" href="https://hub-1.example.com/"/>
+            <link rel="hub" href="https://hub-2.example.com/"/>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(
+        links(&feed),
+        (
+            vec!["https://hub-1.example.com/".to_owned(), "https://hub-2.example.com/".to_owned()],
+            Some("http://example.com/feed".to_owned())
+        )
+    );
+
+    let json = r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "feed_url": "https://example.com/feed.json",
+            "hubs": [{"type": "WebSub", "url": "https://hub.example.com/"}],
+            "items": []
+        }"#;
+    let feed = parser::parse(json.as_bytes()).unwrap();
+    assert_eq!(
+        links(&feed),
+        (vec!["https://hub.example.com/".to_owned()], Some("https://example.com/feed.json".to_owned()))
+    );
+
+    // Feeds without them have neither
+    let feed = parser::parse(test::fixture_as_raw("rss_2.0_example_1.xml").as_slice()).unwrap();
+    assert_eq!(links(&feed), (vec![], None));
+}
+
+// Verify relative URLs are resolved against the base URI of the parser, and any xml:base within the document
+#[test]
+fn test_base_uri() {
+    let parser = Builder::new().base_uri("https://example.com/blog/feed.xml").build();
+
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <link>/blog/</link>
+                <image><url>images/logo.png</url><title>Logo</title><link>/</link></image>
+                <item>
+                    <link>posts/123</link>
+                    <enclosure url="/media/123.mp3" length="1000" type="audio/mpeg"/>
+                </item>
+            </channel>
+        </rss>"#;
+    let feed = parser.parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.links[0].href, "https://example.com/blog/");
+    let logo = feed.logo.unwrap();
+    assert_eq!(logo.uri, "https://example.com/blog/images/logo.png");
+    assert_eq!(logo.link.unwrap().href, "https://example.com/");
+    assert_eq!(feed.entries[0].links[0].href, "https://example.com/blog/posts/123");
+    assert_eq!(
+        feed.entries[0].media[0].content[0].url.as_ref().unwrap().as_str(),
+        "https://example.com/media/123.mp3"
+    );
+
+    // xml:base applies on top of the base URI, and the URI of the source takes precedence over it
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:base="/archive/">
+            <logo>logo.png</logo>
+            <entry xml:base="2021/">
+                <link href="first.html"/>
+                <content type="text/html" src="first/content.html"/>
+            </entry>
+        </feed>"#;
+    let feed = parser.parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.logo.unwrap().uri, "https://example.com/archive/logo.png");
+    assert_eq!(feed.entries[0].links[0].href, "https://example.com/archive/2021/first.html");
+    let content = feed.entries[0].content.as_ref().unwrap();
+    assert_eq!(content.src.as_ref().unwrap().href, "https://example.com/archive/2021/first/content.html");
+    assert_eq!(content.content_type, mime::TEXT_HTML);
+    assert!(content.body.is_none());
+    let feed = parser.parse_with_uri(xml.as_bytes(), Some("http://mirror.example.org/")).unwrap();
+    assert_eq!(feed.entries[0].links[0].href, "http://mirror.example.org/archive/2021/first.html");
+
+    let json = r#"{
+            "version": "https://jsonfeed.org/version/1",
+            "title": "Example",
+            "home_page_url": "/",
+            "icon": "icon.png",
+            "items": [{"id": "1", "url": "posts/1", "attachments": [{"url": "1.mp3", "mime_type": "audio/mpeg"}]}]
+        }"#;
+    let feed = parser.parse(json.as_bytes()).unwrap();
+    assert_eq!(feed.links[0].href, "https://example.com/");
+    assert_eq!(feed.logo.unwrap().uri, "https://example.com/blog/icon.png");
+    let links = feed.entries[0].links.iter().map(|link| link.href.as_str()).collect::<Vec<_>>();
+    assert_eq!(links, vec!["https://example.com/blog/posts/1", "https://example.com/blog/1.mp3"]);
+
+    // Streamed entries are resolved in the same way
+    let parser = Builder::new().base_uri("https://example.com/blog/feed.xml").build();
+    let xml = r#"<rss version="2.0"><channel><item><link>posts/123</link></item></channel></rss>"#;
+    let entry = parser.parse_entries(xml.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(entry.links[0].href, "https://example.com/blog/posts/123");
+}
+
+// Verify a strict parser fails on spec violations that a lenient parser works around
+#[test]
+fn test_strict() {
+    let xml = r#"<rss version="2.0"><channel><item><title>Entry</title><pubDate>Thurs, 13 Jul 2011 07:38:00 GMT</pubDate></item></channel></rss>"#;
+    let feed = Builder::new().lenient().build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].published, Some(Utc.with_ymd_and_hms(2011, 7, 13, 7, 38, 0).unwrap()));
+    assert!(matches!(
+        Builder::new().strict().build().parse(xml.as_bytes()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::InvalidTimestamp(text))) if text == "Thurs, 13 Jul 2011 07:38:00 GMT"
+    ));
+
+    // Timestamps that follow the spec are accepted, including dates on their own for Dublin Core
+    let xml = r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+            <channel>
+                <title>Channel</title>
+                <link>http://example.com/</link>
+                <description>Text</description>
+                <pubDate>Wed, 13 Jul 2011 07:38:00 GMT</pubDate>
+                <item><title>Entry</title><dc:date>2011-07-12</dc:date></item>
+            </channel>
+        </rss>"#;
+    let feed = Builder::new().strict().build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.published, Some(Utc.with_ymd_and_hms(2011, 7, 13, 7, 38, 0).unwrap()));
+    assert_eq!(feed.entries[0].published, Some(Utc.with_ymd_and_hms(2011, 7, 12, 0, 0, 0).unwrap()));
+
+    // Atom feeds and entries require an ID
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><entry><title>Entry</title></entry></feed>"#;
+    assert!(Builder::new().build().parse(xml.as_bytes()).is_ok());
+    assert!(matches!(
+        Builder::new().strict().build().parse(xml.as_bytes()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::MissingContent("entry.id")))
+    ));
+
+    // RSS channels require a title, link and description, and items a title or description
+    let xml = r#"<rss version="2.0"><channel><title>Channel</title><link>http://example.com/</link></channel></rss>"#;
+    assert!(Builder::new().build().parse(xml.as_bytes()).is_ok());
+    assert!(matches!(
+        Builder::new().strict().build().parse(xml.as_bytes()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::MissingContent("channel.description")))
+    ));
+    let xml = r#"<rss version="2.0"><channel><title>Channel</title><link>http://example.com/</link><description>Text</description>
+            <item><description>Untitled</description></item>
+            <item><guid>urn:1</guid></item>
+        </channel></rss>"#;
+    assert!(Builder::new().build().parse(xml.as_bytes()).is_ok());
+    assert!(matches!(
+        Builder::new().strict().build().parse(xml.as_bytes()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::MissingContent("item.title")))
+    ));
+    for fixture in &["atom_example_1.xml", "atom_spec_1.xml", "rss_2.0_spec_1.xml", "jsonfeed_spec_1.json"] {
+        assert!(
+            Builder::new().strict().build().parse(test::fixture_as_raw(fixture).as_slice()).is_ok(),
+            "{}",
+            fixture
+        );
+    }
+}
+
+// Verify parsing stops once the maximum number of entries have been parsed
+#[test]
+fn test_max_entries() {
+    for fixture in &["rss_2.0_spec_1.xml", "atom_example_6.xml", "rss_1.0_example_1.xml", "jsonfeed_example_1.json"] {
+        let test_data = test::fixture_as_raw(fixture);
+        assert!(parser::parse(test_data.as_slice()).unwrap().entries.len() > 1, "{}", fixture);
+
+        let feed = Builder::new().max_entries(1).build().parse(test_data.as_slice()).unwrap();
+        assert_eq!(feed.entries.len(), 1, "{}", fixture);
+        assert!(feed.title.is_some(), "{}", fixture);
+
+        let entries = Builder::new().max_entries(1).build().parse_entries(test_data.as_slice());
+        assert_eq!(entries.count(), 1, "{}", fixture);
+    }
+}
+
+// Verify entries are left out if they fail the filter or are older than the given time
+#[test]
+fn test_entry_filter() {
+    let xml = r#"<rss version="2.0"><channel><title>Channel</title>
+            <item><title>Old</title><pubDate>Mon, 01 Jan 2018 00:00:00 GMT</pubDate></item>
+            <item><title>New</title><pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate></item>
+            <item><title>Skipped</title><pubDate>Tue, 02 Jan 2024 00:00:00 GMT</pubDate></item>
+            <item><title>Undated</title></item>
+            <item><title>Beyond the maximum</title></item>
+        </channel></rss>"#;
+    let titles = |entries: &[Entry]| entries.iter().map(|entry| entry.title.as_ref().unwrap().content.clone()).collect::<Vec<_>>();
+    let build = || {
+        Builder::new()
+            .since(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap())
+            .entry_filter(|entry| entry.title.as_ref().map(|title| title.content != "Skipped").unwrap_or(true))
+            .max_entries(2)
+    };
+
+    // Entries that are left out do not count towards the maximum
+    let feed = build().build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(titles(&feed.entries), vec!["New", "Undated"]);
+    assert_eq!(feed.title.unwrap().content, "Channel");
+
+    let entries = build().build().parse_entries(xml.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(titles(&entries), vec!["New", "Undated"]);
+
+    // The entries kept have their position in the document, counting those left out
+    let indexes = |entries: &[Entry]| entries.iter().map(|entry| entry.document_index).collect::<Vec<_>>();
+    assert_eq!(indexes(&feed.entries), vec![1, 3]);
+    assert_eq!(indexes(&entries), vec![1, 3]);
+
+    // The filters apply to the other formats in the same way
+    for fixture in &["atom_example_6.xml", "rss_1.0_example_1.xml", "jsonfeed_example_1.json"] {
+        let test_data = test::fixture_as_raw(fixture);
+        let feed = Builder::new().entry_filter(|_| false).build().parse(test_data.as_slice()).unwrap();
+        assert!(feed.entries.is_empty(), "{}", fixture);
+        assert!(feed.title.is_some(), "{}", fixture);
+
+        let feed = Builder::new()
+            .entry_filter(|entry| entry.document_index > 0)
+            .build()
+            .parse(test_data.as_slice())
+            .unwrap();
+        assert!(feed.entries.iter().all(|entry| entry.document_index > 0), "{}", fixture);
+    }
+}
+
+// Verify the source of each entry is kept as it was found, if enabled
+#[test]
+fn test_raw_entries() {
+    let items = [
+        r#"<item><title>Fish &amp; Chips</title><guid>urn:1</guid></item>"#,
+        r#"<item a="1"><!-- comment --><description><![CDATA[<p>Para</p>]]></description><guid>urn:2</guid></item>"#,
+        r#"<item><guid>urn:3</guid><dc:creator xmlns:dc="http://purl.org/dc/elements/1.1/">Chef</dc:creator></item>"#,
+    ];
+    let xml = format!(
+        "<rss version=\"2.0\"><channel><title>Channel</title>\n{}\n<!-- between -->\n{}{}</channel></rss>",
+        items[0], items[1], items[2]
+    );
+
+    let feed = Builder::new().raw_entries(true).build().parse(xml.as_bytes()).unwrap();
+    let raw: Vec<&str> = feed.entries.iter().map(|entry| entry.raw.as_deref().unwrap()).collect();
+    assert_eq!(raw, items);
+
+    let entries = Builder::new()
+        .raw_entries(true)
+        .build()
+        .parse_entries(xml.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let raw: Vec<&str> = entries.iter().map(|entry| entry.raw.as_deref().unwrap()).collect();
+    assert_eq!(raw, items);
+
+    // Atom entries are kept in the same way, and nothing is kept by default
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Feed</title><entry><id>urn:1</id><title>Café</title></entry><entry/></feed>"#;
+    let feed = Builder::new().raw_entries(true).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].raw.as_deref(), Some("<entry><id>urn:1</id><title>Café</title></entry>"));
+    assert_eq!(feed.entries[1].raw.as_deref(), Some("<entry/>"));
+    assert_eq!(parser::parse(xml.as_bytes()).unwrap().entries[0].raw, None);
+}
+
+// Verify custom timestamp parsing and ID generation are used in place of the built in behaviour
+#[test]
+fn test_custom_parsing() {
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <title>Channel</title>
+                <link>http://example.com/</link>
+                <description>Dates in a custom format</description>
+                <item><title>Custom</title><link>http://example.com/1</link><pubDate>13/07/2011</pubDate></item>
+                <item><title>Standard</title><guid>urn:2</guid><pubDate>Wed, 13 Jul 2011 07:38:00 GMT</pubDate></item>
+            </channel>
+        </rss>"#;
+    let parser = Builder::new()
+        .strict()
+        .timestamp_parser(|text| {
+            chrono::NaiveDate::parse_from_str(text, "%d/%m/%Y")
+                .ok()
+                .map(|date| Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()))
+        })
+        .id_generator(|links, title, _uri| match (links.first(), title) {
+            (Some(link), Some(title)) => format!("{}:{}", link.href, title.content),
+            _ => "generated".into(),
+        })
+        .build();
+    let feed = parser.parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].published, Some(Utc.with_ymd_and_hms(2011, 7, 13, 12, 0, 0).unwrap()));
+    assert_eq!(feed.entries[0].id, "http://example.com/1:Custom");
+    assert_eq!(feed.entries[1].published, Some(Utc.with_ymd_and_hms(2011, 7, 13, 7, 38, 0).unwrap()));
+    assert_eq!(feed.entries[1].id, "urn:2");
+    assert_eq!(feed.id, "http://example.com/:Channel");
+}
+
+// Verify feeds and entries without an ID or link are given the same ID each time the document is parsed
+#[test]
+fn test_stable_ids() {
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <title>No links</title>
+                <item><title>First</title><description>One</description></item>
+                <item><title>First</title><description>Two</description></item>
+                <item><description>Three</description></item>
+                <item><description>Three</description></item>
+            </channel>
+        </rss>"#;
+    let ids = || {
+        let feed = parser::parse(xml.as_bytes()).unwrap();
+        let mut ids = vec![feed.id];
+        ids.extend(feed.entries.into_iter().map(|entry| entry.id));
+        ids
+    };
+
+    let first = ids();
+    assert_eq!(first, ids());
+    let streamed = parser::parse_entries(xml.as_bytes()).map(|entry| entry.unwrap().id).collect::<Vec<_>>();
+    assert_eq!(streamed, first[1..]);
+
+    // The base URI is used by both ways of parsing
+    let parser = || Builder::new().base_uri("http://example.com/feed").build();
+    let feed = parser().parse(xml.as_bytes()).unwrap();
+    let streamed = parser().parse_entries(xml.as_bytes()).map(|entry| entry.unwrap().id).collect::<Vec<_>>();
+    assert_eq!(streamed, feed.entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+    assert_ne!(streamed, first[1..]);
+
+    // Entries with different content have different IDs, while identical entries are left to DuplicateIds
+    let mut unique = first.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), first.len() - 1);
+    let feed = Builder::new().duplicate_ids(DuplicateIds::Rename).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[3].id, format!("{}-2", first[3]));
+
+    // The IDs do not depend on where the entries are in the document, so prepending an item leaves those of the others unchanged
+    let prepended = xml.replace("<title>No links</title>", "<title>No links</title><item><title>Newest</title></item>");
+    let feed = parser::parse(prepended.as_bytes()).unwrap();
+    assert_eq!(feed.entries.len(), 5);
+    assert_eq!(feed.entries[1..].iter().map(|entry| entry.id.clone()).collect::<Vec<_>>(), first[1..]);
+}
+
+// Verify a newer fetch of a feed is merged in, with entries matched by ID or link and the newest version of each kept
+#[test]
+fn test_merge() {
+    let older = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>Old title</title>
+            <updated>2021-03-02T10:00:00Z</updated>
+            <entry><id>urn:1</id><title>First</title><updated>2021-03-01T10:00:00Z</updated></entry>
+            <entry><id>urn:2</id><title>Second, edited</title><updated>2021-03-02T10:00:00Z</updated></entry>
+            <entry><id>urn:3</id><title>Third</title><link href="http://example.com/3"/></entry>
+        </feed>"#;
+    let newer = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>New title</title>
+            <updated>2021-03-01T12:00:00Z</updated>
+            <entry><id>urn:4</id><title>Fourth</title><updated>2021-03-03T10:00:00Z</updated></entry>
+            <entry><id>urn:1</id><title>First, edited</title><updated>2021-03-03T09:00:00Z</updated></entry>
+            <entry><id>urn:2</id><title>Second</title><updated>2021-03-01T10:00:00Z</updated></entry>
+            <entry><id>urn:3-renamed</id><title>Third, edited</title><link href="http://example.com/3"/></entry>
+        </feed>"#;
+
+    let mut feed = parser::parse(older.as_bytes()).unwrap();
+    feed.merge(parser::parse(newer.as_bytes()).unwrap());
+
+    assert_eq!(feed.title.unwrap().content, "New title");
+    assert_eq!(feed.updated, Some(Utc.with_ymd_and_hms(2021, 3, 2, 10, 0, 0).unwrap()));
+    let entries = feed
+        .entries
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry.title.as_ref().unwrap().content.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            ("urn:4", "Fourth"),
+            ("urn:1", "First, edited"),
+            ("urn:2", "Second, edited"),
+            ("urn:3-renamed", "Third, edited")
+        ]
+    );
+}
+
+// Verify skipHours, skipDays and the syndication module are parsed into the update hints, and used to schedule the next fetch
+#[test]
+fn test_update_hints() {
+    let xml = r#"<rss version="2.0" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+        <channel>
+            <title>Office hours</title>
+            <ttl>30</ttl>
+            <sy:updatePeriod>daily</sy:updatePeriod>
+            <sy:updateFrequency>4</sy:updateFrequency>
+            <sy:updateBase>2021-03-01T00:00:00Z</sy:updateBase>
+            <skipHours><hour>0</hour><hour>1</hour><hour>24</hour><hour>25</hour></skipHours>
+            <skipDays><day>Saturday</day><day>Sunday</day><day>Someday</day></skipDays>
+        </channel>
+    </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let hints = feed.update_hints.as_ref().unwrap();
+    assert_eq!(hints.skip_hours, vec![0, 1, 0]);
+    assert_eq!(hints.skip_days, vec![Weekday::Sat, Weekday::Sun]);
+    assert_eq!(hints.update_period, Some(UpdatePeriod::Daily));
+    assert_eq!(hints.update_interval(), Some(chrono::Duration::hours(6)));
+
+    // Updates are every six hours from midnight on Monday 1st March
+    let at = |day, hour, minute| Utc.with_ymd_and_hms(2021, 3, day, hour, minute, 0).unwrap();
+    assert_eq!(feed.next_fetch(at(1, 7, 15)), at(1, 12, 0));
+
+    // The ttl is longer than the wait for the next update
+    assert_eq!(feed.next_fetch(at(1, 11, 50)), at(1, 12, 20));
+
+    // The next update would be at midnight, which is skipped
+    assert_eq!(feed.next_fetch(at(1, 20, 0)), at(2, 2, 0));
+
+    // Friday night runs in to the weekend, which is skipped
+    assert_eq!(feed.next_fetch(at(5, 20, 0)), at(8, 2, 0));
+
+    // A frequency too large for the interval to be divided by does not make the interval negative
+    let xml = r#"<rss version="2.0" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+        <channel>
+            <sy:updatePeriod>hourly</sy:updatePeriod>
+            <sy:updateFrequency>4294967295</sy:updateFrequency>
+        </channel>
+    </rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let hints = feed.update_hints.as_ref().unwrap();
+    assert_eq!(hints.update_frequency, Some(u32::MAX));
+    assert!(hints.update_interval().unwrap() >= chrono::Duration::zero());
+    assert!(feed.next_fetch(at(1, 7, 15)) >= at(1, 7, 15));
+}
+
+// Verify documents that attempt to read external resources or exhaust the parser are rejected
+#[test]
+fn test_untrusted_xml() {
+    // External entities are rejected
+    for doctype in &[
+        r#"<!DOCTYPE rss [<!ENTITY xxe SYSTEM "file:///etc/passwd">]>"#,
+        r#"<!DOCTYPE rss [<!ENTITY % remote PUBLIC "-//X//Y" "http://example.com/evil.dtd"> %remote;]>"#,
+    ] {
+        let xml = format!(r#"{}<rss version="2.0"><channel><title>&xxe;</title></channel></rss>"#, doctype);
+        match parser::parse(xml.as_bytes()) {
+            Err(ParseFeedError::XmlReader(e)) => assert!(e.to_string().starts_with("External entity declared")),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    // Internal entities are not expanded, so cannot be used to exhaust memory
+    let xml = r#"<!DOCTYPE rss [
+            <!ENTITY lol "lol">
+            <!ENTITY lol1 "&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;">
+            <!ENTITY lol2 "&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;">
+        ]>
+        <rss version="2.0"><channel><title>&lol2;</title></channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "&lol2;");
+
+    // A reference to an external DTD is harmless, as it is never fetched
+    let xml = r#"<!DOCTYPE rss PUBLIC "-//Netscape Communications//DTD RSS 0.91//EN" "http://my.netscape.com/publish/formats/rss-0.91.dtd">
+        <rss version="0.91"><channel><title>Netscape</title></channel></rss>"#;
+    assert_eq!(parser::parse(xml.as_bytes()).unwrap().title.unwrap().content, "Netscape");
+
+    // Deeply nested elements are rejected, unless the limit is raised
+    let xml = format!(
+        r#"<rss version="2.0"><channel><title>Deep</title><item><description>{}{}</description></item></channel></rss>"#,
+        "<div>".repeat(300),
+        "</div>".repeat(300)
+    );
+    match parser::parse(xml.as_bytes()) {
+        Err(ParseFeedError::XmlReader(e)) => assert!(e.to_string().starts_with("Limit on depth exceeded")),
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert!(Builder::new().max_depth(400).build().parse(xml.as_bytes()).is_ok());
+
+    // The number of elements can be limited
+    let xml = test::fixture_as_raw("rss_2.0_spec_1.xml");
+    assert!(Builder::new().max_elements(1000).build().parse(xml.as_slice()).is_ok());
+    match Builder::new().max_elements(10).build().parse(xml.as_slice()) {
+        Err(ParseFeedError::XmlReader(e)) => assert!(e.to_string().starts_with("Limit on elements exceeded")),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+// Verify malformed XML is recovered from if enabled, and fails the parse otherwise
+#[test]
+fn test_recover() {
+    let titles = |entries: &[Entry]| entries.iter().map(|entry| entry.title.as_ref().unwrap().content.clone()).collect::<Vec<_>>();
+    let recovering = || Builder::new().recover(true).build();
+
+    // Control characters are removed
+    let xml = "<rss version=\"2.0\"><channel><title>Fish\u{b} &amp; Chips\u{1}</title><item><title a=\"\u{8}\">Battered</title></item></channel></rss>";
+    let feed = recovering().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "Fish & Chips");
+    assert_eq!(parser::parse(xml.as_bytes()).unwrap().title.unwrap().content, "Fish\u{b} & Chips\u{1}");
+
+    // Elements left open are closed by the end tag of their parent, and stray end tags are ignored
+    let xml = r#"<rss version="2.0"><channel><title>Channel</title>
+            <item><title>Unclosed</title><comments>http://example.com/</item>
+            <item><title>Stray</title></i></item>
+            <item><title>Last</title></item>
+        </channel></rss>"#;
+    assert!(parser::parse(xml.as_bytes()).is_err());
+    let feed = recovering().parse(xml.as_bytes()).unwrap();
+    assert_eq!(titles(&feed.entries), vec!["Unclosed", "Stray", "Last"]);
+    assert_eq!(feed.title.unwrap().content, "Channel");
+
+    // The entries completed before a truncated source ends are kept, whether it ends within a tag or between them
+    for xml in &[
+        r#"<rss version="2.0"><channel><title>Channel</title><item><title>First</title></item><item><title>Second</ti"#,
+        r#"<rss version="2.0"><channel><title>Channel</title><item><title>First</title></item><item><title>Second</title>"#,
+    ] {
+        assert!(parser::parse(xml.as_bytes()).is_err());
+        let feed = recovering().parse(xml.as_bytes()).unwrap();
+        assert_eq!(titles(&feed.entries), vec!["First"]);
+
+        let entries = recovering().parse_entries(xml.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(titles(&entries), vec!["First"]);
+    }
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><entry><id>urn:1</id><title>First</title></entry><entry><id>urn:2</id>"#;
+    let feed = recovering().parse(xml.as_bytes()).unwrap();
+    assert_eq!(titles(&feed.entries), vec!["First"]);
+}
+
+// Verify the size of the input and its text can be limited, with a lenient parser truncating and a strict parser failing
+#[test]
+fn test_size_limits() {
+    let xml = r#"<rss version="2.0"><channel><title>Title</title><link>http://example.com/</link><description>Text</description>
+            <item><title>First</title></item>
+            <item><title>Second</title></item>
+        </channel></rss>"#;
+    let limit = xml.find("<item><title>Second").unwrap() as u64 + 10;
+
+    // The entries completed before the limit are returned
+    let feed = Builder::new().max_input_bytes(limit).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries.len(), 1);
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().content, "First");
+    assert_eq!(Builder::new().max_input_bytes(limit).build().parse_entries(xml.as_bytes()).count(), 1);
+
+    match Builder::new().strict().max_input_bytes(limit).build().parse(xml.as_bytes()) {
+        Err(ParseFeedError::ParseError(ParseErrorKind::InputTooLarge)) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    let results = Builder::new()
+        .strict()
+        .max_input_bytes(limit)
+        .build()
+        .parse_entries(xml.as_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 2, "{:?}", results);
+    assert!(matches!(results[1], Err(ParseFeedError::ParseError(ParseErrorKind::InputTooLarge))));
+
+    // A source that is exactly the limit is not too large
+    assert!(Builder::new().strict().max_input_bytes(xml.len() as u64).build().parse(xml.as_bytes()).is_ok());
+
+    // Text is truncated on a character boundary
+    let xml = r#"<rss version="2.0"><channel><title>Café society</title><description><![CDATA[A long description]]></description></channel></rss>"#;
+    let feed = Builder::new().max_text_length(4).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "Caf");
+    assert_eq!(feed.description.unwrap().content, "A lo");
+    match Builder::new().strict().max_text_length(4).build().parse(xml.as_bytes()) {
+        Err(ParseFeedError::XmlReader(e)) => assert!(e.to_string().starts_with("Limit on text exceeded")),
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    // The limit applies to all the text of an element, however it is split
+    let xml = r#"<rss version="2.0"><channel><title>aaaa<![CDATA[bbbb]]>cccc</title><description>dd<![CDATA[eeee]]></description></channel></rss>"#;
+    let feed = Builder::new().max_text_length(4).build().parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "aaaa");
+    assert_eq!(feed.description.unwrap().content, "ddee");
+
+    // As it does to the text of a JSON Feed
+    let json =
+        r#"{"version": "https://jsonfeed.org/version/1", "title": "Café society", "items": [{"id": "1", "content_text": "A long text", "tags": ["Fishing"]}]}"#;
+    let feed = Builder::new().max_text_length(4).build().parse(json.as_bytes()).unwrap();
+    assert_eq!(feed.title.unwrap().content, "Caf");
+    assert_eq!(feed.entries[0].content.as_ref().unwrap().body.as_deref(), Some("A lo"));
+    assert_eq!(&*feed.entries[0].categories[0].term, "Fish");
+    assert!(matches!(
+        Builder::new().strict().max_text_length(4).build().parse(json.as_bytes()),
+        Err(ParseFeedError::ParseError(ParseErrorKind::TextTooLong))
+    ));
+}
+
+// Verify the smaller model types can be used as keys, e.g. to deduplicate them across feeds
+#[test]
+fn test_model_hash() {
+    use std::collections::HashSet;
+
+    let feeds: Vec<_> = ["atom_example_1.xml", "atom_example_1.xml", "rss_2.0_example_1.xml"]
+        .iter()
+        .map(|fixture| parser::parse(test::fixture_as_raw(fixture).as_slice()).unwrap())
+        .collect();
+
+    let links: HashSet<_> = feeds.iter().flat_map(|feed| feed.links.iter().cloned()).collect();
+    assert_eq!(links.len(), feeds[0].links.len() + feeds[2].links.len());
+
+    let feed_types: HashSet<FeedType> = feeds.iter().map(|feed| feed.feed_type).collect();
+    assert_eq!(feed_types, vec![FeedType::Atom, FeedType::RSS2].into_iter().collect());
+}
+
+// Verify text records the xml:lang in effect, falling back to the language of the feed
+#[test]
+fn test_text_language() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en">
+            <title>Colours</title>
+            <entry xml:lang="fr">
+                <id>urn:entry:1</id>
+                <title>Couleurs</title>
+                <summary xml:lang="de">Farben</summary>
+                <content xml:lang="">Unknown</content>
+            </entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.language.as_deref(), Some("en"));
+    assert_eq!(feed.title.unwrap().lang.as_deref(), Some("en"));
+    let entry = &feed.entries[0];
+    assert_eq!(entry.title.as_ref().unwrap().lang.as_deref(), Some("fr"));
+    assert_eq!(entry.summary.as_ref().unwrap().lang.as_deref(), Some("de"));
+    // An empty xml:lang means the language is unknown, so the feed language is used
+    assert_eq!(entry.content.as_ref().unwrap().lang.as_deref(), Some("en"));
+
+    // RSS has no xml:lang, so the channel language applies, including to streamed entries
+    let xml = r#"<rss version="2.0"><channel>
+            <language>en-GB</language>
+            <item><title>Colours</title></item>
+        </channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries[0].title.as_ref().unwrap().lang.as_deref(), Some("en-GB"));
+    let entry = parser::parse_entries(xml.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(entry.title.unwrap().lang.as_deref(), Some("en-GB"));
+}
+
+// Verify the warning handler is told about the problems a lenient parser works around
+#[test]
+fn test_warnings() {
+    fn warnings(xml: &str) -> Vec<Warning> {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let handler_reported = reported.clone();
+        let parser = Builder::new()
+            .warning_handler(move |warning| handler_reported.lock().unwrap().push(warning.clone()))
+            .build();
+        parser.parse(xml.as_bytes()).unwrap();
+        let warnings = reported.lock().unwrap().clone();
+        warnings
+    }
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:x="urn:x">
+            <entry>
+                <id>urn:entry:1</id>
+                <updated>yesterday</updated>
+                <x:rating>5</x:rating>
+            </entry>
+            <entry><id>urn:entry:1</id></entry>
+            <entry><id>urn:entry:1</id></entry>
+            <entry><title>No ID</title></entry>
+        </feed>"#;
+    assert_eq!(
+        warnings(xml),
+        vec![
+            Warning::InvalidTimestamp("yesterday".into()),
+            Warning::UnknownElement {
+                namespace: "urn:x".into(),
+                element: "rating".into()
+            },
+            Warning::MissingContent("entry.id"),
+            Warning::MissingContent("feed.id"),
+            Warning::DuplicateId("urn:entry:1".into()),
+        ]
+    );
+
+    let xml = r#"<rss version="2.0"><channel><title>Title</title></channel></rss>"#;
+    assert_eq!(
+        warnings(xml),
+        vec![Warning::MissingContent("channel.link"), Warning::MissingContent("channel.description")]
+    );
+
+    let xml = r#"<rss version="2.0"><channel><title>Title</title><link>http://example.com/</link><description>Text</description><language>en, fr</language></channel></rss>"#;
+    assert_eq!(warnings(xml), vec![Warning::InvalidLanguage("en, fr".into())]);
+
+    // Well formed feeds have nothing to report
+    for fixture in &["atom_spec_1.xml", "rss_2.0_example_1.xml"] {
+        assert_eq!(warnings(&test::fixture_as_string(fixture)), vec![], "{}", fixture);
+    }
+}
+
+// Verify entries which share an ID are kept, dropped or renamed according to the policy
+#[test]
+fn test_duplicate_ids() {
+    let xml = r#"<rss version="2.0"><channel><title>Title</title>
+            <item><guid>urn:1</guid><title>First</title></item>
+            <item><guid>urn:2</guid><title>Second</title></item>
+            <item><guid>urn:1</guid><title>Third</title></item>
+            <item><guid>urn:1-2</guid><title>Fourth</title></item>
+            <item><guid>urn:1</guid><title>Fifth</title></item>
+        </channel></rss>"#;
+    let entries = |policy| {
+        let feed = Builder::new().duplicate_ids(policy).build().parse(xml.as_bytes()).unwrap();
+        feed.entries
+            .into_iter()
+            .map(|entry| (entry.id, entry.title.unwrap().content))
+            .collect::<Vec<_>>()
+    };
+    let pairs = |expected: &[(&str, &str)]| expected.iter().map(|(id, title)| (id.to_string(), title.to_string())).collect::<Vec<_>>();
+
+    assert_eq!(
+        entries(DuplicateIds::KeepAll),
+        pairs(&[
+            ("urn:1", "First"),
+            ("urn:2", "Second"),
+            ("urn:1", "Third"),
+            ("urn:1-2", "Fourth"),
+            ("urn:1", "Fifth")
+        ])
+    );
+    assert_eq!(
+        entries(DuplicateIds::KeepFirst),
+        pairs(&[("urn:1", "First"), ("urn:2", "Second"), ("urn:1-2", "Fourth")])
+    );
+    assert_eq!(
+        entries(DuplicateIds::KeepLast),
+        pairs(&[("urn:2", "Second"), ("urn:1-2", "Fourth"), ("urn:1", "Fifth")])
+    );
+
+    // Suffixes already used by another entry are skipped
+    assert_eq!(
+        entries(DuplicateIds::Rename),
+        pairs(&[
+            ("urn:1", "First"),
+            ("urn:2", "Second"),
+            ("urn:1-3", "Third"),
+            ("urn:1-2", "Fourth"),
+            ("urn:1-4", "Fifth")
+        ])
+    );
+}
+
+// Verify namespace handlers are given the extensions of their namespace, with those they handle removed
+#[test]
+fn test_namespace_handler() {
+    // Records the stars of a rating on the feed, and counts the flags on entries
+    struct RatingHandler {
+        flags: Arc<Mutex<usize>>,
+    }
+
+    impl NamespaceHandler for RatingHandler {
+        fn namespace(&self) -> &str {
+            "http://example.com/ext"
+        }
+
+        fn handle_feed_element(&self, element: &ExtensionElement, feed: &mut Feed) -> bool {
+            if element.name != "rating" {
+                return false;
+            }
+            feed.rating = element.attributes.get("stars").map(|stars| MediaRating::new(stars.clone()));
+            true
+        }
+
+        fn handle_entry_element(&self, element: &ExtensionElement, _entry: &mut Entry) -> bool {
+            if element.name != "flag" {
+                return false;
+            }
+            *self.flags.lock().unwrap() += 1;
+            true
+        }
+    }
+
+    let xml = r#"
+        <rss version="2.0" xmlns:ext="http://example.com/ext" xmlns:other="http://example.com/other">
+            <channel>
+                <title>Channel</title>
+                <ext:rating stars="4"/>
+                <ext:note>kept</ext:note>
+                <item>
+                    <title>Item</title>
+                    <ext:flag/>
+                    <other:flag/>
+                </item>
+                <item>
+                    <title>Another item</title>
+                    <ext:flag/>
+                </item>
+            </channel>
+        </rss>"#;
+
+    let flags = Arc::new(Mutex::new(0));
+    let parser = Builder::new().namespace_handler(RatingHandler { flags: flags.clone() }).build();
+    let feed = parser.parse(xml.as_bytes()).unwrap();
+    assert_eq!(feed.rating, Some(MediaRating::new("4".into())));
+    assert_eq!(feed.extensions["http://example.com/ext"], vec![ExtensionElement::new("note").value("kept")]);
+    assert_eq!(*flags.lock().unwrap(), 2);
+    assert!(!feed.entries[0].extensions.contains_key("http://example.com/ext"));
+    assert_eq!(feed.entries[0].extensions["http://example.com/other"], vec![ExtensionElement::new("flag")]);
+    assert!(feed.entries[1].extensions.is_empty());
+
+    // Streamed entries are passed to the handlers as they are parsed
+    *flags.lock().unwrap() = 0;
+    let parser = Builder::new().namespace_handler(RatingHandler { flags: flags.clone() }).build();
+    let entries = parser.parse_entries(xml.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(*flags.lock().unwrap(), 2);
+    assert!(entries.iter().all(|entry| !entry.extensions.contains_key("http://example.com/ext")));
+}
+
+// Verify a batch of feeds is parsed across threads, with the results in the order of the feeds
+#[cfg(feature = "rayon")]
+#[test]
+fn test_parse_many() {
+    let feeds = (0..50)
+        .map(|i| {
+            if i % 7 == 3 {
+                b"<html><body>Not found</body></html>".to_vec()
+            } else {
+                format!(
+                    r#"<rss version="2.0"><channel><title>Feed {}</title><item><title>Item {}</title></item></channel></rss>"#,
+                    i, i
+                )
+                .into_bytes()
+            }
+        })
+        .collect::<Vec<_>>();
+    let expected = feeds.iter().map(|feed| parser::parse(feed.as_slice())).collect::<Vec<_>>();
+
+    let actual = parser::parse_many(feeds.clone());
+    assert_eq!(actual.len(), 50);
+    for (i, (actual, expected)) in actual.into_iter().zip(expected).enumerate() {
+        match (actual, expected) {
+            (Ok(actual), Ok(expected)) => {
+                assert_eq!(actual.title.as_ref().unwrap().content, format!("Feed {}", i));
+                assert_eq!(actual, expected);
+            }
+            (Err(_), Err(_)) => assert_eq!(i % 7, 3),
+            (actual, _) => panic!("feed {} was parsed differently: {:?}", i, actual),
+        }
+    }
+
+    assert!(parser::parse_many(Vec::new()).is_empty());
+
+    // A configured parser is used for each feed of the batch
+    let parsed = Arc::new(Mutex::new(0));
+    let counter = parsed.clone();
+    let parser = Builder::new().progress_handler(move |_| *counter.lock().unwrap() += 1).build();
+    let actual = parser.parse_many(feeds);
+    assert_eq!(*parsed.lock().unwrap(), actual.iter().filter(|result| result.is_ok()).count());
+}
+
+// Verify the names of people and the terms and schemes of categories are shared by the entries and feeds parsed with an interner
+#[cfg(feature = "intern")]
+#[test]
+fn test_interner() {
+    use crate::parser::Interner;
+
+    let xml = |title: &str| {
+        format!(
+            r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel><title>{}</title>
+                <item><guid>1</guid><dc:creator>Alice</dc:creator><category domain="tags">Rust</category></item>
+                <item><guid>2</guid><dc:creator>Alice</dc:creator><category domain="tags">Rust</category></item>
+            </channel></rss>"#,
+            title
+        )
+    };
+    let interner = Interner::new();
+    let parser = Builder::new().interner(interner.clone()).build();
+
+    let first = parser.parse(xml("First").as_bytes()).unwrap();
+    let first_size = first.heap_size();
+    let second = parser.parse(xml("Second").as_bytes()).unwrap();
+    let streamed = parser.parse_entries(xml("Third").as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+    for entry in first.entries.iter().chain(&second.entries).chain(&streamed) {
+        assert!(Arc::ptr_eq(&entry.authors[0].name, &first.entries[0].authors[0].name));
+        assert!(Arc::ptr_eq(&entry.categories[0].term, &first.entries[0].categories[0].term));
+        assert!(Arc::ptr_eq(
+            entry.categories[0].scheme.as_ref().unwrap(),
+            first.entries[0].categories[0].scheme.as_ref().unwrap()
+        ));
+    }
+    assert_eq!(interner.len(), 3);
+
+    // The shared strings are charged evenly to their holders, so a feed's share shrinks as more feeds hold the same strings
+    let unshared = parser::parse(xml("First").as_bytes()).unwrap();
+    assert!(first.heap_size() < unshared.heap_size());
+    assert!(first.heap_size() < first_size);
+    assert!(interner.heap_size() > 0);
+    assert_eq!(first, unshared);
+
+    // Strings are kept until no feed refers to them
+    interner.purge();
+    assert_eq!(interner.len(), 3);
+    drop((second, streamed));
+    assert_eq!(first.heap_size(), first_size);
+    drop(first);
+    interner.purge();
+    assert!(interner.is_empty());
+
+    // Without an interner, each entry has its own copy
+    assert!(!Arc::ptr_eq(&unshared.entries[0].authors[0].name, &unshared.entries[1].authors[0].name));
+}
+
+// Verify the links to the pages of a paged or archived feed (RFC 5005) are found in each format
+#[test]
+fn test_paging_links() {
+    let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="next" href="http://example.com/feed?page=2"/>
+            <link rel="current" href="http://example.com/feed"/>
+            <link rel="prev-archive" href="http://example.com/2020"/>
+            <link rel="alternate" href="http://example.com/"/>
+        </feed>"#;
+    let feed = parser::parse(atom.as_bytes()).unwrap();
+    assert_eq!(feed.next_page().map(|link| link.href.as_str()), Some("http://example.com/feed?page=2"));
+    assert_eq!(
+        feed.archive_links().map(|link| link.href.as_str()).collect::<Vec<_>>(),
+        vec!["http://example.com/feed", "http://example.com/2020"]
+    );
+
+    let rss = r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>
+            <link>http://example.com/</link>
+            <atom:link rel="next" href="http://example.com/feed?page=2"/>
+        </channel></rss>"#;
+    let feed = parser::parse(rss.as_bytes()).unwrap();
+    assert_eq!(feed.next_page().map(|link| link.href.as_str()), Some("http://example.com/feed?page=2"));
+    assert_eq!(feed.archive_links().count(), 0);
+
+    let json = r#"{"version": "https://jsonfeed.org/version/1.1", "title": "Example", "next_url": "http://example.com/feed.json?page=2", "items": []}"#;
+    let feed = parser::parse(json.as_bytes()).unwrap();
+    assert_eq!(feed.next_page().map(|link| link.href.as_str()), Some("http://example.com/feed.json?page=2"));
+}
@@ -0,0 +1,94 @@
+use crate::extensions::threading::{InReplyTo, Replies, ThreadingExtension};
+use crate::model::Entry;
+use crate::parser::atom;
+use crate::parser::util::{if_some_then, resolve_uri};
+use crate::parser::{util, ParseFeedResult, Parser};
+use crate::util::dates::TimestampFormat;
+use crate::xml::{Element, NS};
+use std::io::BufRead;
+
+fn threading(entry: &mut Entry) -> &mut ThreadingExtension {
+    entry.threading.get_or_insert_with(Default::default)
+}
+
+// Process <thr>, <slash> and <wfw> elements at entry level, recording them in the threading extension
+pub(crate) fn handle_threading_element<R: BufRead>(parser: &Parser, element: Element<R>, entry: &mut Entry) -> ParseFeedResult<()> {
+    match element.ns_and_tag() {
+        (Some(NS::Threading), "in-reply-to") => if_some_then(handle_in_reply_to(&element), |in_reply_to| threading(entry).in_reply_to.push(in_reply_to)),
+
+        (Some(NS::Threading), "total") => if_some_then(handle_count(&element), |total| threading(entry).total = Some(total)),
+
+        (Some(NS::Slash), "comments") => if_some_then(handle_count(&element), |comments| threading(entry).comments = Some(comments)),
+
+        (Some(NS::WellFormedWeb), "commentRss") | (Some(NS::WellFormedWeb), "commentRSS") => {
+            if_some_then(handle_uri(&element), |uri| threading(entry).comment_rss = Some(uri))
+        }
+
+        (Some(NS::WellFormedWeb), "comment") => if_some_then(handle_uri(&element), |uri| threading(entry).comment_api = Some(uri)),
+
+        // Unknown elements are passed to the caller's handler, if registered
+        _ => parser.handle_unknown_element(&element, &mut entry.extensions)?,
+    }
+
+    Ok(())
+}
+
+// Handles an Atom <link> within an entry, also recording links to replies in the threading extension
+//
+// The "thr:count" and "thr:updated" attributes are matched on their local name, as attribute prefixes are not resolved to a namespace.
+pub(crate) fn handle_entry_link<R: BufRead>(parser: &Parser, element: Element<R>, entry: &mut Entry) -> ParseFeedResult<()> {
+    let mut count = None;
+    let mut updated = None;
+    for attr in &element.attributes {
+        match attr.name.split_once(':') {
+            Some((prefix, "count")) if prefix != "xmlns" => count = attr.value.trim().parse::<u64>().ok(),
+            Some((prefix, "updated")) if prefix != "xmlns" => updated = parser.parse_timestamp(&attr.value, TimestampFormat::Rfc3339)?,
+            _ => {}
+        }
+    }
+
+    if let Some(link) = atom::handle_link(element) {
+        if link.rel.as_deref() == Some("replies") {
+            threading(entry).replies.push(Replies {
+                href: link.href.clone(),
+                media_type: link.media_type.as_ref().map(|media_type| media_type.to_string()),
+                count,
+                updated,
+            });
+        }
+        util::add_link(link, &mut entry.links, &mut entry.license);
+    }
+
+    Ok(())
+}
+
+// Handles an element containing a count, such as <thr:total>
+fn handle_count<R: BufRead>(element: &Element<R>) -> Option<u64> {
+    element.child_as_text().and_then(|text| text.trim().parse::<u64>().ok())
+}
+
+// Handles an element containing a URI, such as <wfw:commentRss>
+fn handle_uri<R: BufRead>(element: &Element<R>) -> Option<String> {
+    element
+        .child_as_text()
+        .map(|text| text.trim().to_owned())
+        .filter(|uri| !uri.is_empty())
+        .map(|uri| resolve_uri(uri, element.xml_base.as_ref()))
+}
+
+// Handles <thr:in-reply-to>, which must identify the resource being responded to
+fn handle_in_reply_to<R: BufRead>(element: &Element<R>) -> Option<InReplyTo> {
+    let mut in_reply_to = InReplyTo {
+        reference: element.attr_value("ref")?,
+        ..Default::default()
+    };
+    for attr in &element.attributes {
+        match attr.name.as_str() {
+            "href" => in_reply_to.href = Some(resolve_uri(attr.value.clone(), element.xml_base.as_ref())),
+            "type" => in_reply_to.media_type = Some(attr.value.clone()),
+            "source" => in_reply_to.source = Some(resolve_uri(attr.value.clone(), element.xml_base.as_ref())),
+            _ => {}
+        }
+    }
+    Some(in_reply_to)
+}
@@ -1,54 +1,112 @@
-use crate::model::Text;
+use crate::model::{Category, Entry, Feed, Link, Person, Stylesheet, Text, TextVariant};
 use crate::parser::ParseFeedResult;
 use crate::xml::Element;
-use chrono::{DateTime, Utc};
+use mime::Mime;
 use regex::{Captures, Regex};
+use std::collections::HashSet;
 use std::error::Error;
 use std::io::BufRead;
 use std::ops::Add;
 use std::time::Duration;
 use url::Url;
-use uuid::Uuid;
 
-lazy_static! {
-    // Initialise the set of regular expressions we use to clean up broken dates
-
-    // Feeds may not comply with the specification in various ways (https://tools.ietf.org/html/rfc2822#page-14)
-    static ref RFC2822_FIXES: Vec<(Regex, &'static str)> = {
-        vec!(
-            // RFC 2822 mandates a +/- 4 digit offset, or UT/GMT (obsolete) but feeds have "UTC" or "-0000"
-            // Suffixes that are not handled by the parser are trimmed and replaced with the corresponding value timezone.
-            (Regex::new("(UTC|-0000$)").unwrap(), "+0000"),
-
-            // The short weekday can be wrong e.g. "Wed, 25 Aug 2012" was actually a Saturday - https://www.timeanddate.com/calendar/monthly.html?year=2012&month=8
-            // or it can be something other than a short weekday name e.g. "Thurs, 13 Jul 2011 07:38:00 GMT"
-            // As its extraneous, we just remove it
-            (Regex::new("(Sun|Mon|Tue|Wed|Thu|Fri|Sat)[a-z]*, ").unwrap(), ""),
-
-            // Long month names are not allowed, so replace them with short
-            (Regex::new("(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*").unwrap(), "$1"),
-
-            // Some timestamps have an hours component adjusted by 24h, while not adjusting the day so we just reset to start of day
-            #[allow(clippy::trivial_regex)]
-            (Regex::new(" 24:").unwrap(), " 00:"),
-
-            // Single digit hours are padded
-            (Regex::new(" ([0-9]):").unwrap(), " 0${1}:"),
-        )
-    };
+/// Handles <content:encoded>
+pub(crate) fn handle_encoded<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Text>> {
+    Ok(element.children_as_string()?.map(|content| Text {
+        lang: element.xml_lang.clone(),
+        ..Text::new(content)
+    }))
+}
+
+/// Adds the link to those of a feed or entry, which is also its license if it is the first with the "license" relation (RFC 4946)
+pub(crate) fn add_link(link: Link, links: &mut Vec<Link>, license: &mut Option<Link>) {
+    if license.is_none() && link.has_rel("license") {
+        *license = Some(link.clone());
+    }
+    links.push(link);
+}
+
+/// Tidies the categories of the feed and its entries (see `tidy_entry_categories()`)
+pub(crate) fn tidy_categories(feed: &mut Feed) {
+    tidy(&mut feed.categories);
+    for entry in &mut feed.entries {
+        tidy_entry_categories(entry);
+    }
+}
+
+/// Trims the terms and schemes of the entry's categories, and removes exact duplicates (keeping the first)
+///
+/// Categories with the same term in different schemes are distinct, so are all kept.
+pub(crate) fn tidy_entry_categories(entry: &mut Entry) {
+    tidy(&mut entry.categories);
+}
+
+fn tidy(categories: &mut Vec<Category>) {
+    for category in categories.iter_mut() {
+        trim_string(&mut category.term);
+        if let Some(scheme) = &mut category.scheme {
+            trim_string(scheme);
+        }
+        category.scheme = category.scheme.take().filter(|scheme| !scheme.is_empty());
+    }
+
+    let mut seen = HashSet::new();
+    categories.retain(|category| !category.term.is_empty() && seen.insert(category.clone()));
+}
+
+// Strings are either owned or shared (see `Interner`), and replaced by a copy if they change
+fn trim_string<S: AsRef<str> + From<String>>(text: &mut S) {
+    let trimmed = text.as_ref().trim();
+    if trimmed.len() != text.as_ref().len() {
+        *text = trimmed.to_owned().into();
+    }
+}
 
-    // Feeds may not comply with the specification (https://tools.ietf.org/html/rfc3339)
-    static ref RFC3339_FIXES: Vec<(Regex, &'static str)> = {
-        vec!(
-            // inserts missing colon in timezone
-            (Regex::new(r#"(\+|-)(\d{2})(\d{2})"#).unwrap(), "${1}${2}:${3}"),
-        )
+/// Gives the text of the feed without an xml:lang of its own the language of the feed (e.g. from RSS "language")
+pub(crate) fn inherit_language(feed: &mut Feed) {
+    let language = match &feed.language {
+        Some(language) => language.clone(),
+        None => return,
     };
+
+    for text in texts(&mut feed.title, &mut feed.title_variants)
+        .chain(texts(&mut feed.description, &mut feed.description_variants))
+        .chain(feed.rights.iter_mut())
+    {
+        inherit(&mut text.lang, &language);
+    }
+    for entry in &mut feed.entries {
+        inherit_entry_language(entry, &language);
+    }
 }
 
-/// Handles <content:encoded>
-pub(crate) fn handle_encoded<R: BufRead>(element: Element<R>) -> ParseFeedResult<Option<Text>> {
-    Ok(element.children_as_string()?.map(Text::new))
+/// Gives the text of the entry without an xml:lang of its own the language of the feed
+pub(crate) fn inherit_entry_language(entry: &mut Entry, language: &str) {
+    for text in texts(&mut entry.title, &mut entry.title_variants)
+        .chain(texts(&mut entry.summary, &mut entry.summary_variants))
+        .chain(entry.rights.iter_mut())
+    {
+        inherit(&mut text.lang, language);
+    }
+    if let Some(content) = &mut entry.content {
+        inherit(&mut content.lang, language);
+    }
+    if let Some(source) = &mut entry.source {
+        for text in source.title.iter_mut().chain(source.rights.iter_mut()) {
+            inherit(&mut text.lang, language);
+        }
+    }
+}
+
+fn inherit(lang: &mut Option<String>, language: &str) {
+    if lang.is_none() {
+        *lang = Some(language.to_owned());
+    }
+}
+
+// Iterates over the text and each of its variants
+fn texts<'a>(text: &'a mut Option<Text>, variants: &'a mut [TextVariant]) -> impl Iterator<Item = &'a mut Text> {
+    text.iter_mut().chain(variants.iter_mut().map(|variant| &mut variant.text))
 }
 
 /// Simplifies the "if let ... = parse ... assign" block
@@ -87,38 +145,118 @@ pub(crate) fn parse_uri(uri: &str, base: Option<&Url>) -> Option<Url> {
     }
 }
 
-/// Parses a timestamp from an RSS2 feed.
-/// This should be an RFC-2822 formatted timestamp but we need a bunch of fixes / workarounds for the generally broken stuff we find on the internet
-pub(crate) fn timestamp_rfc2822_lenient(text: &str) -> Option<DateTime<Utc>> {
-    // Curiously, we see RFC-3339 dates in RSS 2 feeds so try that first
-    if let Some(ts) = timestamp_rfc3339_lenient(text) {
-        return Some(ts);
+// Splits a list of names separated by commas, semicolons, "and" or "&" (e.g. "Alice Smith, Bob Jones and Carol White")
+//
+// A single comma between two words (e.g. "Smith, Alice") is taken as a name written surname first rather than a list.
+pub(crate) fn split_names(text: &str) -> Vec<String> {
+    let text = text.trim();
+    let words = |part: &str| part.split_whitespace().count();
+    if let [surname, given] = text.split(',').collect::<Vec<_>>().as_slice() {
+        if words(surname) == 1 && words(given) == 1 && !text.contains(';') {
+            return vec![text.to_owned()];
+        }
+    }
+
+    let mut names = Vec::new();
+    for part in text.split([',', ';']) {
+        let mut name = Vec::new();
+        for word in part.split_whitespace() {
+            if word == "&" || word.eq_ignore_ascii_case("and") {
+                names.push(name.join(" "));
+                name.clear();
+            } else {
+                name.push(word);
+            }
+        }
+        names.push(name.join(" "));
     }
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+// Parses a media type (e.g. "text/html; charset=utf-8"), ignoring surrounding whitespace and values that are not well-formed
+pub(crate) fn parse_media_type(text: &str) -> Option<Mime> {
+    text.trim().parse().ok()
+}
 
-    // Clean the input string by applying each of the regex fixes
-    let mut text = text.trim().to_string();
-    for (regex, replacement) in RFC2822_FIXES.iter() {
-        text = regex.replace(&text, *replacement).to_string();
+/// Resolves a relative URI against the base if provided, otherwise leaving it as found in the source
+pub(crate) fn resolve_uri(uri: String, base: Option<&Url>) -> String {
+    match (Url::parse(&uri), base) {
+        (Err(url::ParseError::RelativeUrlWithoutBase), Some(base)) => base.join(&uri).map_or(uri, |with_base| with_base.to_string()),
+        _ => uri,
     }
+}
+
+lazy_static! {
+    // Matches a pseudo-attribute within a processing instruction e.g. href="style.xsl" or type='text/xsl'
+    static ref PSEUDO_ATTRIBUTE: Regex = Regex::new(r#"([\w-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+}
 
-    DateTime::parse_from_rfc2822(&text).map(|t| t.with_timezone(&Utc)).ok()
+lazy_static! {
+    // Matches the "email (Name)" form of an RSS contact, as used in the examples of the spec
+    static ref EMAIL_AND_NAME: Regex = Regex::new(r#"^(\S+@\S+)\s*\((.*)\)$"#).unwrap();
+    // Matches the "Name <email>" form of a contact, as used in mail headers
+    static ref NAME_AND_EMAIL: Regex = Regex::new(r#"^(.*?)\s*<(\S+@\S+)>$"#).unwrap();
 }
 
-/// Parses a timestamp from an Atom or JSON feed.
-/// This should be an RFC-3339 formatted timestamp but we need fixes for feeds that don't comply
-pub(crate) fn timestamp_rfc3339_lenient(text: &str) -> Option<DateTime<Utc>> {
-    // Clean the input string by applying each of the regex fixes
-    let mut text = text.trim().to_string();
-    for (regex, replacement) in RFC3339_FIXES.iter() {
-        text = regex.replace(&text, *replacement).to_string();
+/// Parses an RSS contact (e.g. "author" or "managingEditor"), which is the email address of the person optionally followed by their name
+///
+/// The "email (Name)" form from the spec is the most common, though "Name <email>" and a name alone are also found. The role is
+/// recorded with the person, who is also named after it if only known by their email address.
+pub(crate) fn parse_contact(role: &str, text: &str) -> Option<Person> {
+    let text = text.trim();
+    fn group<'t>(captures: &Captures<'t>, i: usize) -> &'t str {
+        captures.get(i).map_or("", |m| m.as_str())
     }
+    let (name, email) = if let Some(captures) = EMAIL_AND_NAME.captures(text) {
+        (group(&captures, 2).trim(), Some(group(&captures, 1)))
+    } else if let Some(captures) = NAME_AND_EMAIL.captures(text) {
+        (group(&captures, 1).trim(), Some(group(&captures, 2)))
+    } else if text.contains('@') && !text.contains(char::is_whitespace) {
+        ("", Some(text))
+    } else {
+        (text, None)
+    };
 
-    DateTime::parse_from_rfc3339(text.trim()).map(|t| t.with_timezone(&Utc)).ok()
+    let person = match (name, email) {
+        ("", None) => return None,
+        ("", Some(email)) => Person::new(role).email(email),
+        (name, Some(email)) => Person::new(name).email(email),
+        (name, None) => Person::new(name),
+    };
+    Some(person.role(role))
 }
 
-/// Generates a new UUID.
-pub(crate) fn uuid_gen() -> String {
-    Uuid::new_v4().to_string()
+/// Parses a length in bytes (e.g. of an enclosure) leniently
+///
+/// Real feeds contain empty values, placeholders such as "None", negative numbers and thousands separators, so
+/// separators are stripped and anything that is still not a positive integer is treated as unknown.
+pub(crate) fn parse_length(text: &str) -> Option<u64> {
+    let digits = text.trim().replace(|c: char| c == ',' || c == '_' || c.is_whitespace(), "");
+    match digits.parse::<u64>() {
+        Ok(length) if length > 0 => Some(length),
+        _ => None,
+    }
+}
+
+/// Parses the pseudo-attributes of an `<?xml-stylesheet?>` processing instruction, which must include a "href"
+pub(crate) fn parse_stylesheet(pseudo_attributes: &str) -> Option<Stylesheet> {
+    let mut href = None;
+    let mut media_type = None;
+    for captures in PSEUDO_ATTRIBUTE.captures_iter(pseudo_attributes) {
+        let value = captures.get(2).or_else(|| captures.get(3)).map(|m| m.as_str().to_owned());
+        match &captures[1] {
+            "href" => href = value,
+            "type" => media_type = value,
+            _ => {}
+        }
+    }
+
+    href.map(|href| {
+        let mut stylesheet = Stylesheet::new(href);
+        stylesheet.media_type = media_type;
+        stylesheet
+    })
 }
 
 lazy_static! {
@@ -190,54 +328,8 @@ fn parse_npt_add_frac_sec(duration: Duration, captures: Captures) -> Duration {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{TimeZone, Utc};
-
     use super::*;
 
-    // Verify we can parse non-spec compliant date strings
-    // Regression tests for https://github.com/feed-rs/feed-rs/issues/7
-    #[test]
-    fn test_timestamp_rss2() {
-        let tests = vec![
-            //
-            ("26 August 2019 10:00:00 +0000", Utc.ymd(2019, 8, 26).and_hms_milli(10, 0, 0, 0)),
-            // UTC is not a valid timezone in RFC-2822
-            ("Mon, 01 Jan 0001 00:00:00 UTC", Utc.ymd(1, 1, 1).and_hms_milli(0, 0, 0, 0)),
-            // -0000 is not considered a timezone in the parser
-            ("Wed, 22 Jan 2020 10:58:02 -0000", Utc.ymd(2020, 1, 22).and_hms_milli(10, 58, 02, 0)),
-            // The 25th of August 2012 was a Saturday, not a Wednesday
-            ("Wed, 25 Aug 2012 03:25:42 GMT", Utc.ymd(2012, 8, 25).and_hms_milli(3, 25, 42, 0)),
-            // Long month names are not allowed
-            ("2 September 2019 20:00:00 +0000", Utc.ymd(2019, 9, 2).and_hms_milli(20, 0, 0, 0)),
-            // RSS2 should be RFC-2822 but we get Atom/RFC-3339 formats
-            ("2016-10-01T00:00:00+10:00", Utc.ymd(2016, 9, 30).and_hms_milli(14, 0, 0, 0)),
-            // Single digit hours should be padded
-            ("24 Sep 2013 1:27 PDT", Utc.ymd(2013, 9, 24).and_hms_milli(8, 27, 0, 0)),
-            // Consider an invalid hour specification as start-of-day
-            ("5 Jun 2017 24:05 PDT", Utc.ymd(2017, 6, 5).and_hms_milli(7, 5, 0, 0)),
-        ];
-
-        for (source, expected) in tests {
-            let parsed = timestamp_rfc2822_lenient(source).expect(&format!("failed to parse {}", source));
-            assert_eq!(parsed, expected);
-        }
-    }
-
-    #[test]
-    fn test_timestamp_atom() {
-        let tests = vec![
-            // properly formated rfc3339 string
-            ("2014-12-29T14:53:35+02:00", Utc.ymd(2014, 12, 29).and_hms_milli(12, 53, 35, 0)),
-            // missing colon in timezone
-            ("2014-12-29T14:53:35+0200", Utc.ymd(2014, 12, 29).and_hms_milli(12, 53, 35, 0)),
-        ];
-
-        for (source, expected) in tests {
-            let parsed = timestamp_rfc3339_lenient(source).expect(&format!("failed to parse {}", source));
-            assert_eq!(parsed, expected);
-        }
-    }
-
     // Verify we can parse NPT times
     #[test]
     fn test_parse_npt() {
@@ -248,4 +340,55 @@ mod tests {
         );
         assert_eq!(parse_npt("123.45").unwrap(), Duration::from_millis(123450));
     }
+
+    // Verify the name and email are separated out of RSS contacts
+    #[test]
+    fn test_parse_contact() {
+        let person = |name: &str, email: Option<&str>| Person {
+            email: email.map(|email| email.to_owned()),
+            ..Person::new(name).role("author")
+        };
+        assert_eq!(
+            parse_contact("author", "lawyer@boyer.net (Lawyer Boyer)"),
+            Some(person("Lawyer Boyer", Some("lawyer@boyer.net")))
+        );
+        assert_eq!(
+            parse_contact("author", " lawyer@boyer.net(Lawyer Boyer) "),
+            Some(person("Lawyer Boyer", Some("lawyer@boyer.net")))
+        );
+        assert_eq!(
+            parse_contact("author", "Lawyer Boyer <lawyer@boyer.net>"),
+            Some(person("Lawyer Boyer", Some("lawyer@boyer.net")))
+        );
+        assert_eq!(parse_contact("author", "lawyer@boyer.net"), Some(person("author", Some("lawyer@boyer.net"))));
+        assert_eq!(parse_contact("author", "lawyer@boyer.net ()"), Some(person("author", Some("lawyer@boyer.net"))));
+        assert_eq!(parse_contact("author", "Lawyer Boyer"), Some(person("Lawyer Boyer", None)));
+        assert_eq!(parse_contact("author", "  "), None);
+    }
+
+    // Verify lists of names are split, but not a single name written surname first
+    #[test]
+    fn test_split_names() {
+        assert_eq!(split_names("Alice Smith"), vec!["Alice Smith"]);
+        assert_eq!(split_names("Alice Smith, Bob Jones"), vec!["Alice Smith", "Bob Jones"]);
+        assert_eq!(
+            split_names("Alice Smith, Bob Jones, and Carol White"),
+            vec!["Alice Smith", "Bob Jones", "Carol White"]
+        );
+        assert_eq!(split_names(" Alice AND Bob & Carol; Dave "), vec!["Alice", "Bob", "Carol", "Dave"]);
+        assert_eq!(split_names("Smith, Alice"), vec!["Smith, Alice"]);
+        assert_eq!(split_names("Smith; Jones"), vec!["Smith", "Jones"]);
+        assert_eq!(split_names("Alexandra Anderson"), vec!["Alexandra Anderson"]);
+    }
+
+    // Verify malformed lengths are dropped rather than failing the enclosure
+    #[test]
+    fn test_parse_length() {
+        assert_eq!(parse_length("12216320"), Some(12216320));
+        assert_eq!(parse_length(" 12,216,320 "), Some(12216320));
+        assert_eq!(parse_length(""), None);
+        assert_eq!(parse_length("None"), None);
+        assert_eq!(parse_length("-1"), None);
+        assert_eq!(parse_length("0"), None);
+    }
 }
@@ -0,0 +1,150 @@
+//! Checks the model parsed from a corpus of feeds against snapshots, so changes in parsing are noticed and reviewed
+//!
+//! A corpus is a directory of feeds (with the extension `.xml`, `.rss`, `.atom` or `.json`) and a `snapshots` subdirectory holding the
+//! expected result of parsing each one. The result is recorded as the model serialised to JSON, or the error for broken feeds.
+//!
+//! Snapshots are written, rather than checked, when the `FEED_RS_UPDATE_SNAPSHOTS` environment variable is set. This is how a
+//! snapshot is created for a new feed, or updated after an intended change to the model. Review the changes before committing them.
+//!
+//! Extension authors can check their namespace handlers against their own corpus by passing a parser configured with them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use feed_rs::{parser, test_util};
+//!
+//! let parser = parser::Builder::new().build();
+//! test_util::assert_corpus("tests/corpus", &parser);
+//! ```
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::model::Feed;
+use crate::parser::{ParseFeedResult, Parser};
+
+/// The environment variable which, if set, causes snapshots to be written rather than checked
+pub const UPDATE_SNAPSHOTS: &str = "FEED_RS_UPDATE_SNAPSHOTS";
+
+// The extensions of the files in a corpus that are parsed
+const FEED_EXTENSIONS: &[&str] = &["atom", "json", "rss", "xml"];
+
+/// A feed whose parsed model was not the same as its snapshot, as found by `check_corpus()`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    /// The path of the feed
+    pub feed: PathBuf,
+    /// The snapshot, or `None` if the feed does not have one yet
+    pub expected: Option<String>,
+    /// The snapshot of the result of parsing the feed
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = match &self.expected {
+            Some(expected) => expected,
+            None => return write!(f, "{}: no snapshot (set {} to create it)", self.feed.display(), UPDATE_SNAPSHOTS),
+        };
+
+        // Report the first line that differs, as the snapshots are long
+        let mut expected_lines = expected.lines();
+        let mut actual_lines = self.actual.lines();
+        for line in 1.. {
+            match (expected_lines.next(), actual_lines.next()) {
+                (Some(e), Some(a)) if e == a => continue,
+                (None, None) => break,
+                (e, a) => {
+                    return write!(
+                        f,
+                        "{}: line {} differs\n  expected: {}\n    actual: {}",
+                        self.feed.display(),
+                        line,
+                        e.unwrap_or("<end of snapshot>"),
+                        a.unwrap_or("<end of snapshot>")
+                    )
+                }
+            }
+        }
+        write!(f, "{}: snapshot differs", self.feed.display())
+    }
+}
+
+/// Records the result of parsing a feed as a snapshot: the model serialised to JSON, or the error if it could not be parsed
+///
+/// Objects are written with their keys in order, so the snapshot does not depend on the order of the extension maps.
+pub fn snapshot(result: &ParseFeedResult<Feed>) -> String {
+    match result {
+        Ok(feed) => {
+            let value = serde_json::to_value(feed).expect("the model can be serialised");
+            serde_json::to_string_pretty(&value).expect("the model can be serialised") + "\n"
+        }
+        Err(error) => format!("error: {}\n", error),
+    }
+}
+
+/// Parses each feed in the corpus with the parser, returning those whose model is not the same as their snapshot
+///
+/// If `FEED_RS_UPDATE_SNAPSHOTS` is set, the snapshots are written instead and nothing is returned.
+///
+/// # Panics
+///
+/// If the corpus cannot be read, or a snapshot cannot be written.
+pub fn check_corpus<P: AsRef<Path>>(dir: P, parser: &Parser) -> Vec<Mismatch> {
+    let dir = dir.as_ref();
+    let snapshots = dir.join("snapshots");
+    let update = env::var_os(UPDATE_SNAPSHOTS).is_some();
+
+    let mut mismatches = Vec::new();
+    for feed in feeds(dir) {
+        let source = fs::read(&feed).unwrap_or_else(|e| panic!("could not read {}: {}", feed.display(), e));
+        let actual = snapshot(&parser.parse(source.as_slice()));
+
+        let mut name = feed.file_name().unwrap().to_os_string();
+        name.push(".snap");
+        let path = snapshots.join(name);
+        if update {
+            fs::create_dir_all(&snapshots).unwrap_or_else(|e| panic!("could not create {}: {}", snapshots.display(), e));
+            fs::write(&path, &actual).unwrap_or_else(|e| panic!("could not write {}: {}", path.display(), e));
+            continue;
+        }
+
+        let expected = fs::read_to_string(&path).ok();
+        if expected.as_ref() != Some(&actual) {
+            mismatches.push(Mismatch { feed, expected, actual });
+        }
+    }
+    mismatches
+}
+
+/// Checks each feed in the corpus against its snapshot with `check_corpus()`, panicking with a description of any mismatches
+#[track_caller]
+pub fn assert_corpus<P: AsRef<Path>>(dir: P, parser: &Parser) {
+    let mismatches = check_corpus(dir, parser);
+    if !mismatches.is_empty() {
+        let descriptions = mismatches.iter().map(|mismatch| mismatch.to_string()).collect::<Vec<_>>();
+        panic!(
+            "{} of the feeds do not match their snapshot (set {} to update them):\n{}",
+            mismatches.len(),
+            UPDATE_SNAPSHOTS,
+            descriptions.join("\n")
+        );
+    }
+}
+
+// The feeds in the corpus, in order of their name so the results are stable
+fn feeds(dir: &Path) -> Vec<PathBuf> {
+    let entries = fs::read_dir(dir).unwrap_or_else(|e| panic!("could not read {}: {}", dir.display(), e));
+    let mut feeds = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            matches!(path.extension().and_then(|extension| extension.to_str()),
+                Some(extension) if FEED_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()))
+        })
+        .collect::<Vec<_>>();
+    feeds.sort();
+    feeds
+}
@@ -0,0 +1,291 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use regex::{Captures, Regex};
+
+lazy_static! {
+    // Initialise the set of regular expressions we use to clean up broken dates
+
+    // Feeds may not comply with the specification in various ways (https://tools.ietf.org/html/rfc2822#page-14)
+    static ref RFC2822_FIXES: Vec<(Regex, &'static str)> = {
+        vec!(
+            // Comments (e.g. "+0000 (UTC)") are allowed by the spec, but not by the parser
+            (Regex::new(r"\s*\([^)]*\)\s*$").unwrap(), ""),
+
+            // RFC 2822 mandates a +/- 4 digit offset, or UT/GMT (obsolete) but feeds have "UTC" or "-0000"
+            // Suffixes that are not handled by the parser are trimmed and replaced with the corresponding value timezone.
+            (Regex::new("(UTC|-0000$)").unwrap(), "+0000"),
+
+            // The offset may have a colon, as per RFC 3339
+            (Regex::new(r"([+-]\d{2}):(\d{2})$").unwrap(), "${1}${2}"),
+
+            // The short weekday can be wrong e.g. "Wed, 25 Aug 2012" was actually a Saturday - https://www.timeanddate.com/calendar/monthly.html?year=2012&month=8
+            // or it can be something other than a short weekday name e.g. "Thurs, 13 Jul 2011 07:38:00 GMT"
+            // As its extraneous, we just remove it
+            (Regex::new("(Sun|Mon|Tue|Wed|Thu|Fri|Sat)[a-z]*, ").unwrap(), ""),
+
+            // Long month names are not allowed, so replace them with short
+            (Regex::new("(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*").unwrap(), "$1"),
+
+            // Some timestamps have an hours component adjusted by 24h, while not adjusting the day so we just reset to start of day
+            #[allow(clippy::trivial_regex)]
+            (Regex::new(" 24:").unwrap(), " 00:"),
+
+            // Single digit hours are padded
+            (Regex::new(" ([0-9]):").unwrap(), " 0${1}:"),
+
+            // A missing timezone is assumed to be UTC
+            (Regex::new(r"(\d{2}:\d{2}(:\d{2})?)$").unwrap(), "${1} +0000"),
+        )
+    };
+
+    // Timezone abbreviations not supported by the RFC 2822 parser (which only knows the North American zones) and their offsets
+    static ref RFC2822_TIMEZONE: Regex = Regex::new(r" ([A-Z]{1,5})$").unwrap();
+
+    // Feeds may not comply with the specification (https://tools.ietf.org/html/rfc3339)
+    static ref RFC3339_FIXES: Vec<(Regex, &'static str)> = {
+        vec!(
+            // inserts missing colon in timezone
+            (Regex::new(r#"(\+|-)(\d{2})(\d{2})"#).unwrap(), "${1}${2}:${3}"),
+
+            // The date and time may be separated by a space rather than a "T"
+            (Regex::new(r"^(\d{4}-\d{2}-\d{2})\s+(\d)").unwrap(), "${1}T${2}"),
+
+            // Seconds are required, but are sometimes missing
+            (Regex::new(r"T(\d{2}):(\d{2})([^:\d]|$)").unwrap(), "T${1}:${2}:00${3}"),
+
+            // Zulu time and the separator may be lower case
+            (Regex::new(r"z$").unwrap(), "Z"),
+            (Regex::new(r"^(\d{4}-\d{2}-\d{2})t").unwrap(), "${1}T"),
+        )
+    };
+}
+
+// Formats tried (as UTC) once the timestamp fails to parse to the relevant spec
+const FALLBACK_FORMATS: [&str; 4] = [
+    // RFC 3339 without a timezone
+    "%Y-%m-%dT%H:%M:%S%.f",
+    // asctime() e.g. "Tue Aug  6 10:46:05 2019"
+    "%a %b %e %H:%M:%S %Y",
+    // RFC 2822 without the timezone and a weekday that was not followed by a comma
+    "%a %d %b %Y %H:%M:%S",
+    // Day first, with a two digit year
+    "%d %b %y %H:%M:%S",
+];
+
+// Offsets (in hours) for timezone abbreviations
+fn timezone_offset(abbreviation: &str) -> Option<f32> {
+    let offset = match abbreviation {
+        "Z" | "WET" => 0.0,
+        "BST" | "CET" | "WEST" => 1.0,
+        "CEST" | "EET" | "SAST" => 2.0,
+        "EEST" | "MSK" => 3.0,
+        // India, which is far more common in feeds than Irish Standard Time
+        "IST" => 5.5,
+        "SGT" | "HKT" | "AWST" => 8.0,
+        "JST" | "KST" => 9.0,
+        "ACST" => 9.5,
+        "AEST" => 10.0,
+        "AEDT" => 11.0,
+        "NZST" => 12.0,
+        "NZDT" => 13.0,
+        "AKDT" => -8.0,
+        "AKST" => -9.0,
+        "HST" => -10.0,
+        _ => return None,
+    };
+    Some(offset)
+}
+
+/// Parses a timestamp from an RSS2 feed.
+/// This should be an RFC-2822 formatted timestamp but we need a bunch of fixes / workarounds for the generally broken stuff we find on the internet
+pub(crate) fn timestamp_rfc2822_lenient(text: &str) -> Option<DateTime<Utc>> {
+    // Curiously, we see RFC-3339 dates in RSS 2 feeds so try that first
+    if let Some(ts) = timestamp_rfc3339_lenient(text) {
+        return Some(ts);
+    }
+
+    // Clean the input string by applying each of the regex fixes
+    let mut text = text.trim().to_string();
+    for (regex, replacement) in RFC2822_FIXES.iter() {
+        text = regex.replace(&text, *replacement).to_string();
+    }
+
+    // Replace timezone abbreviations with their offset
+    text = RFC2822_TIMEZONE
+        .replace(&text, |captures: &Captures| match timezone_offset(&captures[1]) {
+            Some(offset) => {
+                let minutes = (offset * 60.0) as i32;
+                format!(" {:+03}{:02}", minutes / 60, minutes.abs() % 60)
+            }
+            None => captures[0].to_string(),
+        })
+        .to_string();
+
+    DateTime::parse_from_rfc2822(&text)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| timestamp_fallback(&text))
+}
+
+/// Parses a timestamp from an Atom or JSON feed.
+/// This should be an RFC-3339 formatted timestamp but we need fixes for feeds that don't comply
+pub(crate) fn timestamp_rfc3339_lenient(text: &str) -> Option<DateTime<Utc>> {
+    // Clean the input string by applying each of the regex fixes
+    let mut text = text.trim().to_string();
+    for (regex, replacement) in RFC3339_FIXES.iter() {
+        text = regex.replace(&text, *replacement).to_string();
+    }
+
+    DateTime::parse_from_rfc3339(text.trim())
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| timestamp_fallback(&text))
+}
+
+/// The format a timestamp is specified to have, which determines how it is parsed
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum TimestampFormat {
+    /// RSS 0.9x and 2.0
+    Rfc2822,
+    /// Atom and JSON Feed
+    Rfc3339,
+    /// Dublin Core, which allows a date on its own as well as an RFC-3339 timestamp
+    W3cDtf,
+}
+
+impl TimestampFormat {
+    /// Parses the timestamp, working around the many ways feeds fail to follow the format
+    pub(crate) fn parse_lenient(self, text: &str) -> Option<DateTime<Utc>> {
+        match self {
+            TimestampFormat::Rfc2822 | TimestampFormat::W3cDtf => timestamp_rfc2822_lenient(text),
+            TimestampFormat::Rfc3339 => timestamp_rfc3339_lenient(text),
+        }
+    }
+
+    /// Parses the timestamp, only accepting values that follow the format
+    pub(crate) fn parse_strict(self, text: &str) -> Option<DateTime<Utc>> {
+        let text = text.trim();
+        let parsed = match self {
+            TimestampFormat::Rfc2822 => DateTime::parse_from_rfc2822(text).ok(),
+            TimestampFormat::Rfc3339 | TimestampFormat::W3cDtf => DateTime::parse_from_rfc3339(text).ok(),
+        };
+        match (parsed, self) {
+            (Some(parsed), _) => Some(parsed.with_timezone(&Utc)),
+            // W3CDTF also allows the seconds to be omitted, or just the date
+            (None, TimestampFormat::W3cDtf) => DateTime::parse_from_str(&text.replace('Z', "+00:00"), "%Y-%m-%dT%H:%M%:z")
+                .map(|parsed| parsed.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                        .map(|naive| Utc.from_utc_datetime(&naive))
+                }),
+            (None, _) => None,
+        }
+    }
+}
+
+// Tries each of the fallback formats, and finally a date on its own (both assumed to be UTC)
+fn timestamp_fallback(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim_end_matches(" +0000");
+    FALLBACK_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)))
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    // Verify we can parse non-spec compliant date strings
+    // Regression tests for https://github.com/feed-rs/feed-rs/issues/7
+    #[test]
+    fn test_timestamp_rss2() {
+        let tests = vec![
+            //
+            ("26 August 2019 10:00:00 +0000", Utc.with_ymd_and_hms(2019, 8, 26, 10, 0, 0).unwrap()),
+            // UTC is not a valid timezone in RFC-2822
+            ("Mon, 01 Jan 0001 00:00:00 UTC", Utc.with_ymd_and_hms(1, 1, 1, 0, 0, 0).unwrap()),
+            // -0000 is not considered a timezone in the parser
+            ("Wed, 22 Jan 2020 10:58:02 -0000", Utc.with_ymd_and_hms(2020, 1, 22, 10, 58, 2).unwrap()),
+            // The 25th of August 2012 was a Saturday, not a Wednesday
+            ("Wed, 25 Aug 2012 03:25:42 GMT", Utc.with_ymd_and_hms(2012, 8, 25, 3, 25, 42).unwrap()),
+            // Long month names are not allowed
+            ("2 September 2019 20:00:00 +0000", Utc.with_ymd_and_hms(2019, 9, 2, 20, 0, 0).unwrap()),
+            // RSS2 should be RFC-2822 but we get Atom/RFC-3339 formats
+            ("2016-10-01T00:00:00+10:00", Utc.with_ymd_and_hms(2016, 9, 30, 14, 0, 0).unwrap()),
+            // Single digit hours should be padded
+            ("24 Sep 2013 1:27 PDT", Utc.with_ymd_and_hms(2013, 9, 24, 8, 27, 0).unwrap()),
+            // Consider an invalid hour specification as start-of-day
+            ("5 Jun 2017 24:05 PDT", Utc.with_ymd_and_hms(2017, 6, 5, 7, 5, 0).unwrap()),
+            // Missing seconds, with a single digit hour
+            ("Wed, 02 Oct 2002 8:00 GMT", Utc.with_ymd_and_hms(2002, 10, 2, 8, 0, 0).unwrap()),
+            // North American timezones are supported by the parser
+            ("Tue, 06 Aug 2019 10:46:05 EDT", Utc.with_ymd_and_hms(2019, 8, 6, 14, 46, 5).unwrap()),
+            // Other timezone abbreviations are not
+            ("Tue, 06 Aug 2019 10:46:05 CEST", Utc.with_ymd_and_hms(2019, 8, 6, 8, 46, 5).unwrap()),
+            ("Tue, 06 Aug 2019 10:46:05 ACST", Utc.with_ymd_and_hms(2019, 8, 6, 1, 16, 5).unwrap()),
+            ("Tue, 06 Aug 2019 10:46:05 IST", Utc.with_ymd_and_hms(2019, 8, 6, 5, 16, 5).unwrap()),
+            ("Tue, 06 Aug 2019 10:46:05 Z", Utc.with_ymd_and_hms(2019, 8, 6, 10, 46, 5).unwrap()),
+            // The offset has a colon
+            ("Tue, 06 Aug 2019 10:46:05 +02:00", Utc.with_ymd_and_hms(2019, 8, 6, 8, 46, 5).unwrap()),
+            // A comment follows the offset
+            ("Tue, 06 Aug 2019 10:46:05 +0000 (UTC)", Utc.with_ymd_and_hms(2019, 8, 6, 10, 46, 5).unwrap()),
+            // The timezone is missing
+            ("Tue, 06 Aug 2019 10:46:05", Utc.with_ymd_and_hms(2019, 8, 6, 10, 46, 5).unwrap()),
+            // asctime() format
+            ("Tue Aug  6 10:46:05 2019", Utc.with_ymd_and_hms(2019, 8, 6, 10, 46, 5).unwrap()),
+            // Two digit year
+            ("06 Aug 19 10:46:05 +0000", Utc.with_ymd_and_hms(2019, 8, 6, 10, 46, 5).unwrap()),
+        ];
+
+        for (source, expected) in tests {
+            let parsed = timestamp_rfc2822_lenient(source).unwrap_or_else(|| panic!("failed to parse {}", source));
+            assert_eq!(parsed, expected, "{}", source);
+        }
+
+        // Some things are beyond repair
+        for source in &["", "yesterday", "Tue, 06 Aug 2019 10:46:05 XYZ"] {
+            assert_eq!(timestamp_rfc2822_lenient(source), None, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_timestamp_atom() {
+        let tests = vec![
+            // properly formated rfc3339 string
+            ("2014-12-29T14:53:35+02:00", Utc.with_ymd_and_hms(2014, 12, 29, 12, 53, 35).unwrap()),
+            // missing colon in timezone
+            ("2014-12-29T14:53:35+0200", Utc.with_ymd_and_hms(2014, 12, 29, 12, 53, 35).unwrap()),
+            // space instead of "T"
+            ("2014-12-29 14:53:35+02:00", Utc.with_ymd_and_hms(2014, 12, 29, 12, 53, 35).unwrap()),
+            // missing seconds (e.g. the RSS 1.0 spec's use of Dublin Core)
+            ("2000-01-01T12:00+00:00", Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap()),
+            ("2014-12-29T14:53Z", Utc.with_ymd_and_hms(2014, 12, 29, 14, 53, 0).unwrap()),
+            // lower case
+            ("2014-12-29t14:53:35z", Utc.with_ymd_and_hms(2014, 12, 29, 14, 53, 35).unwrap()),
+            // missing timezone
+            ("2014-12-29T14:53:35", Utc.with_ymd_and_hms(2014, 12, 29, 14, 53, 35).unwrap()),
+            (
+                "2014-12-29 14:53:35.250",
+                Utc.with_ymd_and_hms(2014, 12, 29, 14, 53, 35).unwrap() + chrono::Duration::milliseconds(250),
+            ),
+            // date only
+            ("2014-12-29", Utc.with_ymd_and_hms(2014, 12, 29, 0, 0, 0).unwrap()),
+        ];
+
+        for (source, expected) in tests {
+            let parsed = timestamp_rfc3339_lenient(source).unwrap_or_else(|| panic!("failed to parse {}", source));
+            assert_eq!(parsed, expected, "{}", source);
+        }
+
+        // Including the invalid offset from the RSS 1.0 example
+        for source in &["", "2014-12-29T", "2017-06-13T03:18:00+00:0"] {
+            assert_eq!(timestamp_rfc3339_lenient(source), None, "{}", source);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use encoding_rs::Encoding;
+use regex::Regex;
+
+lazy_static! {
+    // Matches the encoding declared in the XML prolog e.g. <?xml version="1.0" encoding="ISO-8859-1"?>
+    static ref DECLARED_ENCODING: Regex = Regex::new(r#"^(\s*<\?xml[^>]*?\sencoding\s*=\s*)("[^"]*"|'[^']*')"#).unwrap();
+}
+
+/// Finds the encoding for a label such as "windows-1251", or the charset parameter of a Content-Type such as "text/xml; charset=Shift_JIS"
+pub(crate) fn encoding_for_charset(charset: &str) -> Option<&'static Encoding> {
+    let label = match charset.parse::<mime::Mime>() {
+        Ok(content_type) => content_type.get_param(mime::CHARSET).map(|charset| charset.as_str().to_owned())?,
+        Err(_) => charset.trim().to_owned(),
+    };
+    Encoding::for_label(label.as_bytes())
+}
+
+/// Finds the encoding indicated by a UTF-16 byte order mark
+///
+/// UTF-8 (with or without a BOM) and the ASCII compatible encodings are handled by the XML parser, using the declaration in the prolog.
+pub(crate) fn encoding_for_bom(bytes: &[u8]) -> Option<&'static Encoding> {
+    match Encoding::for_bom(bytes) {
+        Some((encoding, _)) if encoding != encoding_rs::UTF_8 => Some(encoding),
+        _ => None,
+    }
+}
+
+/// Finds where an XML declaration starts, if it follows leading garbage such as a blank line or an error message from the server
+///
+/// Zero is returned if the source has no declaration, or the text before it appears to be part of the document itself (e.g. an XML
+/// example within the content of an entry). A UTF-8 byte order mark before the declaration is skipped along with anything else.
+///
+/// The search stops at the first `{`, so a declaration within the text of a JSON Feed is not mistaken for one of an XML document.
+pub(crate) fn declaration_offset(bytes: &[u8]) -> usize {
+    let end = bytes.iter().position(|b| *b == b'{').unwrap_or(bytes.len());
+    let offset = bytes[..end]
+        .windows(6)
+        .position(|window| window.starts_with(b"<?xml") && window[5].is_ascii_whitespace())
+        .unwrap_or(0);
+    let prefix = &bytes[..offset];
+    let document_markers: [&[u8]; 5] = [b"<rss", b"<feed", b"RDF", b"<entry", b"<!"];
+    if document_markers
+        .iter()
+        .any(|marker| prefix.windows(marker.len()).any(|window| window == *marker))
+    {
+        0
+    } else {
+        offset
+    }
+}
+
+/// Finds where the root element of an XML feed starts, if it follows content that cannot be part of the XML prolog
+///
+/// This locates a feed wrapped in an HTML page (e.g. an error page from a proxy) or following notices printed by the server. None is
+/// returned if there is no root element in the source, or only a declaration, comments, processing instructions or whitespace precede it.
+/// A JSON document (one starting with `{`) is never salvaged, as its text may include the markup of a feed.
+pub(crate) fn root_offset(bytes: &[u8]) -> Option<usize> {
+    let start = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+    if start.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+        return None;
+    }
+
+    let roots: [&[u8]; 3] = [b"<rss", b"<feed", b"<rdf:RDF"];
+    let offset = (0..bytes.len()).find(|i| {
+        roots
+            .iter()
+            .any(|root| bytes[*i..].starts_with(root) && matches!(bytes.get(i + root.len()), Some(b) if b.is_ascii_whitespace() || *b == b'>' || *b == b'/'))
+    })?;
+    if is_prolog(&bytes[..offset]) {
+        None
+    } else {
+        Some(offset)
+    }
+}
+
+// Checks the text is made up of what may precede the root element of an XML document
+fn is_prolog(mut bytes: &[u8]) -> bool {
+    loop {
+        bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+        let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+        bytes = &bytes[start..];
+
+        let end: &[u8] = if bytes.is_empty() {
+            return true;
+        } else if bytes.starts_with(b"<!--") {
+            b"-->"
+        } else if bytes.starts_with(b"<?") {
+            b"?>"
+        } else if bytes.starts_with(b"<!") {
+            b">"
+        } else {
+            return false;
+        };
+        match bytes.windows(end.len()).position(|window| window == end) {
+            Some(i) => bytes = &bytes[i + end.len()..],
+            None => return false,
+        }
+    }
+}
+
+/// Transcodes the source to UTF-8, updating the encoding declared in the XML prolog to match
+///
+/// A byte order mark takes precedence over the requested encoding.
+pub(crate) fn transcode(bytes: &[u8], encoding: &'static Encoding) -> String {
+    let (text, _, _) = encoding.decode(bytes);
+    DECLARED_ENCODING.replace(&text, r#"${1}"utf-8""#).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify the charset is found from labels and content types
+    #[test]
+    fn test_encoding_for_charset() {
+        assert_eq!(encoding_for_charset("windows-1251"), Some(encoding_rs::WINDOWS_1251));
+        assert_eq!(encoding_for_charset(" latin1 "), Some(encoding_rs::WINDOWS_1252));
+        assert_eq!(encoding_for_charset("application/rss+xml; charset=Shift_JIS"), Some(encoding_rs::SHIFT_JIS));
+        assert_eq!(encoding_for_charset("application/rss+xml"), None);
+        assert_eq!(encoding_for_charset("unknown"), None);
+    }
+
+    // Verify garbage before the declaration is found, but not declarations within the document
+    #[test]
+    fn test_declaration_offset() {
+        assert_eq!(declaration_offset(b"<?xml version=\"1.0\"?><rss/>"), 0);
+        assert_eq!(declaration_offset(b"\xef\xbb\xbf<?xml version=\"1.0\"?><rss/>"), 3);
+        assert_eq!(declaration_offset(b"\n\n<?xml version=\"1.0\"?><rss/>"), 2);
+        assert_eq!(
+            declaration_offset(b"<br />\n<b>Warning</b>: headers already sent<br />\n<?xml version=\"1.0\"?><rss/>"),
+            50
+        );
+        assert_eq!(declaration_offset(b"<rss/>"), 0);
+        assert_eq!(declaration_offset(b"<?xml-stylesheet href=\"feed.xsl\"?><rss/>"), 0);
+        assert_eq!(
+            declaration_offset(b"<rss><item><description><![CDATA[<?xml version=\"1.0\"?>]]></description></item></rss>"),
+            0
+        );
+
+        // Nor is a declaration within a JSON Feed
+        assert_eq!(
+            declaration_offset(br#"{"version": "https://jsonfeed.org/version/1", "items": [{"content_text": "<?xml version=\"1.0\"?>"}]}"#),
+            0
+        );
+        assert_eq!(declaration_offset(b"\n{\"title\": \"<?xml \"}"), 0);
+    }
+
+    // Verify the root element is found after content that is not part of the prolog
+    #[test]
+    fn test_root_offset() {
+        assert_eq!(root_offset(b"<html><body><pre><rss version=\"2.0\"></rss></pre></body></html>"), Some(17));
+        assert_eq!(
+            root_offset(b"Notice: Undefined index: page in feed.php on line 3\n<feed xmlns=\"http://www.w3.org/2005/Atom\">"),
+            Some(52)
+        );
+        assert_eq!(root_offset(b"<p>Error</p><rdf:RDF>"), Some(12));
+
+        // A prolog is not skipped, nor is anything without a feed
+        assert_eq!(
+            root_offset(b"\xef\xbb\xbf<?xml version=\"1.0\"?>\n<!-- generated --><!DOCTYPE rss><?xml-stylesheet href=\"a.xsl\"?><rss>"),
+            None
+        );
+        assert_eq!(root_offset(b"<rss version=\"2.0\"></rss>"), None);
+        assert_eq!(root_offset(b"<html><body>Not found</body></html>"), None);
+        assert_eq!(root_offset(b"<html><feedback/><rsstitle/></html>"), None);
+        assert_eq!(root_offset(br#" {"items": [{"content_html": "<feed xmlns='x'>"}]}"#), None);
+    }
+
+    // Verify the declaration is updated once transcoded
+    #[test]
+    fn test_transcode() {
+        let (source, _, _) = encoding_rs::WINDOWS_1251.encode("<?xml version='1.0' encoding='windows-1251'?><title>Новости</title>");
+        assert_eq!(
+            transcode(&source, encoding_rs::WINDOWS_1251),
+            r#"<?xml version='1.0' encoding="utf-8"?><title>Новости</title>"#
+        );
+
+        // Documents without a declaration are left as they are
+        assert_eq!(transcode(b"<title>News</title>", encoding_rs::WINDOWS_1251), "<title>News</title>");
+    }
+}
@@ -0,0 +1,490 @@
+// Elements that are removed along with everything they contain, as they have no text for a reader
+pub(crate) const DROPPED_ELEMENTS: &[&str] = &[
+    "applet", "embed", "frame", "frameset", "iframe", "math", "noscript", "object", "script", "style", "svg", "template",
+];
+
+// Elements that separate the text either side of them, so words are not run together when the tags are removed
+const SEPARATING_ELEMENTS: &[&str] = &[
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dt",
+    "figcaption",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "p",
+    "pre",
+    "td",
+    "th",
+    "tr",
+];
+
+// A start or end tag
+pub(crate) struct Tag {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) closing: bool,
+    pub(crate) self_closing: bool,
+    // Number of bytes in the source, including the angle brackets
+    pub(crate) length: usize,
+}
+
+impl Tag {
+    // Returns the value of the named attribute, if present
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(n, _)| n == name).map(|(_, value)| value.as_str())
+    }
+}
+
+// Parses the tag at the start of the text, which begins with a '<'
+pub(crate) fn parse_tag(text: &str) -> Option<Tag> {
+    let mut chars = text.char_indices().skip(1).peekable();
+
+    let closing = chars.next_if(|(_, c)| *c == '/').is_some();
+    let mut name = String::new();
+    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+        name.push(c.to_ascii_lowercase());
+    }
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut tag = Tag {
+        name,
+        attributes: Vec::new(),
+        closing,
+        self_closing: false,
+        length: 0,
+    };
+    loop {
+        // Skip the whitespace between attributes
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+        match chars.next() {
+            Some((i, '>')) => {
+                tag.length = i + 1;
+                return Some(tag);
+            }
+            Some((_, '/')) => tag.self_closing = true,
+            Some((_, c)) => {
+                tag.self_closing = false;
+
+                // Attribute name, then an optional value
+                let mut name = c.to_ascii_lowercase().to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && !matches!(c, '=' | '>' | '/')) {
+                    name.push(c.to_ascii_lowercase());
+                }
+                while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                let mut value = String::new();
+                if chars.next_if(|(_, c)| *c == '=').is_some() {
+                    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                    match chars.next_if(|(_, c)| *c == '"' || *c == '\'') {
+                        Some((_, quote)) => loop {
+                            match chars.next() {
+                                Some((_, c)) if c == quote => break,
+                                Some((_, c)) => value.push(c),
+                                None => return None,
+                            }
+                        },
+                        None => {
+                            while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && *c != '>') {
+                                value.push(c);
+                            }
+                        }
+                    }
+                }
+                tag.attributes.push((name, value));
+            }
+
+            // The tag was never closed
+            None => return None,
+        }
+    }
+}
+
+// Skips past the end tag for the named element, or to the end of the text if there is none
+pub(crate) fn skip_element<'a>(text: &'a str, name: &str) -> &'a str {
+    let lowercase = text.to_ascii_lowercase();
+    let end_tag = format!("</{}", name);
+    match lowercase.find(&end_tag) {
+        Some(start) => text[start..].find('>').map_or("", |end| &text[start + end + 1..]),
+        None => "",
+    }
+}
+
+// Converts an HTML fragment to plain text, removing the markup, decoding references and collapsing runs of whitespace
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        // A '<' that does not start a tag is just text
+        let tag = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                text.push('<');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag.length..];
+
+        if DROPPED_ELEMENTS.contains(&tag.name.as_str()) && !tag.closing && !tag.self_closing {
+            rest = skip_element(rest, &tag.name);
+        } else if SEPARATING_ELEMENTS.contains(&tag.name.as_str()) {
+            text.push(' ');
+        }
+    }
+    text.push_str(rest);
+
+    decode_entities(&text).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Decodes numeric character references and the named references of HTML 4, along with those that may be used to disguise a scheme
+//
+// As in browsers, the semicolon ending a numeric reference is optional. References that are not recognised are left as they are, as are
+// those to characters that XML does not allow (e.g. "&#0;").
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match decode_entity(&rest[1..]) {
+            Some((c, length)) => {
+                out.push(c);
+                rest = &rest[1 + length..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+// Decodes the reference at the start of the text (which follows an '&'), returning the character and the length of the reference
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    if let Some(number) = text.strip_prefix('#') {
+        let (digits, radix, prefix) = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let length = digits.find(|c: char| !c.is_digit(radix)).unwrap_or(digits.len());
+        let c = u32::from_str_radix(&digits[..length], radix)
+            .ok()
+            .and_then(std::char::from_u32)
+            .filter(|c| matches!(c, '\t' | '\n' | '\r') || (*c >= ' ' && !matches!(c, '\u{fffe}' | '\u{ffff}')))?;
+        let terminated = digits[length..].starts_with(';') as usize;
+        return Some((c, prefix + length + terminated));
+    }
+
+    let end = text.find(|c: char| !c.is_ascii_alphanumeric())?;
+    if !text[end..].starts_with(';') {
+        return None;
+    }
+    let name = &text[..end];
+    let c = match name {
+        "colon" => ':',
+        "tab" => '\t',
+        "newline" => '\n',
+        "apos" => '\'',
+        _ => NAMED_ENTITIES
+            .binary_search_by(|(entity, _)| entity.cmp(&name))
+            .ok()
+            .map(|i| NAMED_ENTITIES[i].1)?,
+    };
+    Some((c, end + 1))
+}
+
+// The named character references of HTML 4 (which include the five predefined by XML), sorted by name
+const NAMED_ENTITIES: &[(&str, char)] = &[
+    ("AElig", '\u{c6}'),
+    ("Aacute", '\u{c1}'),
+    ("Acirc", '\u{c2}'),
+    ("Agrave", '\u{c0}'),
+    ("Alpha", '\u{391}'),
+    ("Aring", '\u{c5}'),
+    ("Atilde", '\u{c3}'),
+    ("Auml", '\u{c4}'),
+    ("Beta", '\u{392}'),
+    ("Ccedil", '\u{c7}'),
+    ("Chi", '\u{3a7}'),
+    ("Dagger", '\u{2021}'),
+    ("Delta", '\u{394}'),
+    ("ETH", '\u{d0}'),
+    ("Eacute", '\u{c9}'),
+    ("Ecirc", '\u{ca}'),
+    ("Egrave", '\u{c8}'),
+    ("Epsilon", '\u{395}'),
+    ("Eta", '\u{397}'),
+    ("Euml", '\u{cb}'),
+    ("Gamma", '\u{393}'),
+    ("Iacute", '\u{cd}'),
+    ("Icirc", '\u{ce}'),
+    ("Igrave", '\u{cc}'),
+    ("Iota", '\u{399}'),
+    ("Iuml", '\u{cf}'),
+    ("Kappa", '\u{39a}'),
+    ("Lambda", '\u{39b}'),
+    ("Mu", '\u{39c}'),
+    ("Ntilde", '\u{d1}'),
+    ("Nu", '\u{39d}'),
+    ("OElig", '\u{152}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocirc", '\u{d4}'),
+    ("Ograve", '\u{d2}'),
+    ("Omega", '\u{3a9}'),
+    ("Omicron", '\u{39f}'),
+    ("Oslash", '\u{d8}'),
+    ("Otilde", '\u{d5}'),
+    ("Ouml", '\u{d6}'),
+    ("Phi", '\u{3a6}'),
+    ("Pi", '\u{3a0}'),
+    ("Prime", '\u{2033}'),
+    ("Psi", '\u{3a8}'),
+    ("Rho", '\u{3a1}'),
+    ("Scaron", '\u{160}'),
+    ("Sigma", '\u{3a3}'),
+    ("THORN", '\u{de}'),
+    ("Tau", '\u{3a4}'),
+    ("Theta", '\u{398}'),
+    ("Uacute", '\u{da}'),
+    ("Ucirc", '\u{db}'),
+    ("Ugrave", '\u{d9}'),
+    ("Upsilon", '\u{3a5}'),
+    ("Uuml", '\u{dc}'),
+    ("Xi", '\u{39e}'),
+    ("Yacute", '\u{dd}'),
+    ("Yuml", '\u{178}'),
+    ("Zeta", '\u{396}'),
+    ("aacute", '\u{e1}'),
+    ("acirc", '\u{e2}'),
+    ("acute", '\u{b4}'),
+    ("aelig", '\u{e6}'),
+    ("agrave", '\u{e0}'),
+    ("alefsym", '\u{2135}'),
+    ("alpha", '\u{3b1}'),
+    ("amp", '\u{26}'),
+    ("and", '\u{2227}'),
+    ("ang", '\u{2220}'),
+    ("aring", '\u{e5}'),
+    ("asymp", '\u{2248}'),
+    ("atilde", '\u{e3}'),
+    ("auml", '\u{e4}'),
+    ("bdquo", '\u{201e}'),
+    ("beta", '\u{3b2}'),
+    ("brvbar", '\u{a6}'),
+    ("bull", '\u{2022}'),
+    ("cap", '\u{2229}'),
+    ("ccedil", '\u{e7}'),
+    ("cedil", '\u{b8}'),
+    ("cent", '\u{a2}'),
+    ("chi", '\u{3c7}'),
+    ("circ", '\u{2c6}'),
+    ("clubs", '\u{2663}'),
+    ("cong", '\u{2245}'),
+    ("copy", '\u{a9}'),
+    ("crarr", '\u{21b5}'),
+    ("cup", '\u{222a}'),
+    ("curren", '\u{a4}'),
+    ("dArr", '\u{21d3}'),
+    ("dagger", '\u{2020}'),
+    ("darr", '\u{2193}'),
+    ("deg", '\u{b0}'),
+    ("delta", '\u{3b4}'),
+    ("diams", '\u{2666}'),
+    ("divide", '\u{f7}'),
+    ("eacute", '\u{e9}'),
+    ("ecirc", '\u{ea}'),
+    ("egrave", '\u{e8}'),
+    ("empty", '\u{2205}'),
+    ("emsp", '\u{2003}'),
+    ("ensp", '\u{2002}'),
+    ("epsilon", '\u{3b5}'),
+    ("equiv", '\u{2261}'),
+    ("eta", '\u{3b7}'),
+    ("eth", '\u{f0}'),
+    ("euml", '\u{eb}'),
+    ("euro", '\u{20ac}'),
+    ("exist", '\u{2203}'),
+    ("fnof", '\u{192}'),
+    ("forall", '\u{2200}'),
+    ("frac12", '\u{bd}'),
+    ("frac14", '\u{bc}'),
+    ("frac34", '\u{be}'),
+    ("frasl", '\u{2044}'),
+    ("gamma", '\u{3b3}'),
+    ("ge", '\u{2265}'),
+    ("gt", '\u{3e}'),
+    ("hArr", '\u{21d4}'),
+    ("harr", '\u{2194}'),
+    ("hearts", '\u{2665}'),
+    ("hellip", '\u{2026}'),
+    ("iacute", '\u{ed}'),
+    ("icirc", '\u{ee}'),
+    ("iexcl", '\u{a1}'),
+    ("igrave", '\u{ec}'),
+    ("image", '\u{2111}'),
+    ("infin", '\u{221e}'),
+    ("int", '\u{222b}'),
+    ("iota", '\u{3b9}'),
+    ("iquest", '\u{bf}'),
+    ("isin", '\u{2208}'),
+    ("iuml", '\u{ef}'),
+    ("kappa", '\u{3ba}'),
+    ("lArr", '\u{21d0}'),
+    ("lambda", '\u{3bb}'),
+    ("lang", '\u{2329}'),
+    ("laquo", '\u{ab}'),
+    ("larr", '\u{2190}'),
+    ("lceil", '\u{2308}'),
+    ("ldquo", '\u{201c}'),
+    ("le", '\u{2264}'),
+    ("lfloor", '\u{230a}'),
+    ("lowast", '\u{2217}'),
+    ("loz", '\u{25ca}'),
+    ("lrm", '\u{200e}'),
+    ("lsaquo", '\u{2039}'),
+    ("lsquo", '\u{2018}'),
+    ("lt", '\u{3c}'),
+    ("macr", '\u{af}'),
+    ("mdash", '\u{2014}'),
+    ("micro", '\u{b5}'),
+    ("middot", '\u{b7}'),
+    ("minus", '\u{2212}'),
+    ("mu", '\u{3bc}'),
+    ("nabla", '\u{2207}'),
+    ("nbsp", '\u{a0}'),
+    ("ndash", '\u{2013}'),
+    ("ne", '\u{2260}'),
+    ("ni", '\u{220b}'),
+    ("not", '\u{ac}'),
+    ("notin", '\u{2209}'),
+    ("nsub", '\u{2284}'),
+    ("ntilde", '\u{f1}'),
+    ("nu", '\u{3bd}'),
+    ("oacute", '\u{f3}'),
+    ("ocirc", '\u{f4}'),
+    ("oelig", '\u{153}'),
+    ("ograve", '\u{f2}'),
+    ("oline", '\u{203e}'),
+    ("omega", '\u{3c9}'),
+    ("omicron", '\u{3bf}'),
+    ("oplus", '\u{2295}'),
+    ("or", '\u{2228}'),
+    ("ordf", '\u{aa}'),
+    ("ordm", '\u{ba}'),
+    ("oslash", '\u{f8}'),
+    ("otilde", '\u{f5}'),
+    ("otimes", '\u{2297}'),
+    ("ouml", '\u{f6}'),
+    ("para", '\u{b6}'),
+    ("part", '\u{2202}'),
+    ("permil", '\u{2030}'),
+    ("perp", '\u{22a5}'),
+    ("phi", '\u{3c6}'),
+    ("pi", '\u{3c0}'),
+    ("piv", '\u{3d6}'),
+    ("plusmn", '\u{b1}'),
+    ("pound", '\u{a3}'),
+    ("prime", '\u{2032}'),
+    ("prod", '\u{220f}'),
+    ("prop", '\u{221d}'),
+    ("psi", '\u{3c8}'),
+    ("quot", '\u{22}'),
+    ("rArr", '\u{21d2}'),
+    ("radic", '\u{221a}'),
+    ("rang", '\u{232a}'),
+    ("raquo", '\u{bb}'),
+    ("rarr", '\u{2192}'),
+    ("rceil", '\u{2309}'),
+    ("rdquo", '\u{201d}'),
+    ("real", '\u{211c}'),
+    ("reg", '\u{ae}'),
+    ("rfloor", '\u{230b}'),
+    ("rho", '\u{3c1}'),
+    ("rlm", '\u{200f}'),
+    ("rsaquo", '\u{203a}'),
+    ("rsquo", '\u{2019}'),
+    ("sbquo", '\u{201a}'),
+    ("scaron", '\u{161}'),
+    ("sdot", '\u{22c5}'),
+    ("sect", '\u{a7}'),
+    ("shy", '\u{ad}'),
+    ("sigma", '\u{3c3}'),
+    ("sigmaf", '\u{3c2}'),
+    ("sim", '\u{223c}'),
+    ("spades", '\u{2660}'),
+    ("sub", '\u{2282}'),
+    ("sube", '\u{2286}'),
+    ("sum", '\u{2211}'),
+    ("sup", '\u{2283}'),
+    ("sup1", '\u{b9}'),
+    ("sup2", '\u{b2}'),
+    ("sup3", '\u{b3}'),
+    ("supe", '\u{2287}'),
+    ("szlig", '\u{df}'),
+    ("tau", '\u{3c4}'),
+    ("there4", '\u{2234}'),
+    ("theta", '\u{3b8}'),
+    ("thetasym", '\u{3d1}'),
+    ("thinsp", '\u{2009}'),
+    ("thorn", '\u{fe}'),
+    ("tilde", '\u{2dc}'),
+    ("times", '\u{d7}'),
+    ("trade", '\u{2122}'),
+    ("uArr", '\u{21d1}'),
+    ("uacute", '\u{fa}'),
+    ("uarr", '\u{2191}'),
+    ("ucirc", '\u{fb}'),
+    ("ugrave", '\u{f9}'),
+    ("uml", '\u{a8}'),
+    ("upsih", '\u{3d2}'),
+    ("upsilon", '\u{3c5}'),
+    ("uuml", '\u{fc}'),
+    ("weierp", '\u{2118}'),
+    ("xi", '\u{3be}'),
+    ("yacute", '\u{fd}'),
+    ("yen", '\u{a5}'),
+    ("yuml", '\u{ff}'),
+    ("zeta", '\u{3b6}'),
+    ("zwj", '\u{200d}'),
+    ("zwnj", '\u{200c}'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify the markup is removed, leaving the text a reader would see
+    #[test]
+    fn test_strip_tags() {
+        assert_eq!(strip_tags("<p>Fish &amp; <b>Chips</b></p><p>Mushy peas</p>"), "Fish & Chips Mushy peas");
+        assert_eq!(strip_tags("Line one<br/>line  two\n<!-- note --> 1 < 2"), "Line one line two 1 < 2");
+        assert_eq!(strip_tags("<script>alert('hi')</script><style>p {}</style>Text"), "Text");
+        assert_eq!(strip_tags("plain text"), "plain text");
+    }
+}
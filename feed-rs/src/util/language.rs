@@ -0,0 +1,107 @@
+// English names of languages, which some feeds use in place of a language code
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("arabic", "ar"),
+    ("chinese", "zh"),
+    ("czech", "cs"),
+    ("danish", "da"),
+    ("dutch", "nl"),
+    ("english", "en"),
+    ("finnish", "fi"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("greek", "el"),
+    ("hebrew", "he"),
+    ("hindi", "hi"),
+    ("hungarian", "hu"),
+    ("indonesian", "id"),
+    ("italian", "it"),
+    ("japanese", "ja"),
+    ("korean", "ko"),
+    ("norwegian", "no"),
+    ("polish", "pl"),
+    ("portuguese", "pt"),
+    ("romanian", "ro"),
+    ("russian", "ru"),
+    ("spanish", "es"),
+    ("swedish", "sv"),
+    ("thai", "th"),
+    ("turkish", "tr"),
+    ("ukrainian", "uk"),
+    ("vietnamese", "vi"),
+];
+
+// Normalises a language (e.g. "EN_us" or "English") into a well-formed BCP 47 tag with the conventional casing (e.g. "en-US")
+//
+// The tag is only checked for its form, not that its subtags are registered, and None is returned if it is not well-formed.
+pub(crate) fn normalise_language_tag(language: &str) -> Option<String> {
+    let language = language.trim().replace('_', "-").to_ascii_lowercase();
+    if let Some((_, code)) = LANGUAGE_NAMES.iter().find(|(name, _)| *name == language) {
+        return Some((*code).to_owned());
+    }
+
+    let subtags: Vec<&str> = language.split('-').collect();
+    if subtags
+        .iter()
+        .any(|subtag| subtag.is_empty() || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        return None;
+    }
+
+    // The tag starts with a language, or is entirely private use ("x-...") or grandfathered ("i-...")
+    let primary = subtags[0];
+    let private = primary == "x" || primary == "i";
+    if !private && (primary.len() < 2 || !primary.chars().all(|c| c.is_ascii_alphabetic())) {
+        return None;
+    }
+
+    // Scripts are title case and regions upper case, but only until a singleton introduces an extension or private use subtags
+    let mut in_extension = private;
+    let mut normalised = Vec::with_capacity(subtags.len());
+    for (i, subtag) in subtags.iter().enumerate() {
+        if subtag.len() == 1 {
+            // Singletons must be followed by at least one subtag
+            if i == subtags.len() - 1 {
+                return None;
+            }
+            in_extension = true;
+        }
+
+        let subtag = if in_extension || i == 0 {
+            subtag.to_string()
+        } else if subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            subtag[..1].to_ascii_uppercase() + &subtag[1..]
+        } else if subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            subtag.to_ascii_uppercase()
+        } else {
+            subtag.to_string()
+        };
+        normalised.push(subtag);
+    }
+
+    Some(normalised.join("-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify tags are given their conventional casing, and names of languages are mapped to their code
+    #[test]
+    fn test_normalise_language_tag() {
+        assert_eq!(normalise_language_tag("en").as_deref(), Some("en"));
+        assert_eq!(normalise_language_tag(" EN-us ").as_deref(), Some("en-US"));
+        assert_eq!(normalise_language_tag("pt_br").as_deref(), Some("pt-BR"));
+        assert_eq!(normalise_language_tag("ZH-hant-tw").as_deref(), Some("zh-Hant-TW"));
+        assert_eq!(normalise_language_tag("es-419").as_deref(), Some("es-419"));
+        assert_eq!(normalise_language_tag("de-CH-x-phonebk").as_deref(), Some("de-CH-x-phonebk"));
+        assert_eq!(normalise_language_tag("x-Klingon").as_deref(), Some("x-klingon"));
+        assert_eq!(normalise_language_tag("English").as_deref(), Some("en"));
+
+        assert_eq!(normalise_language_tag(""), None);
+        assert_eq!(normalise_language_tag("e"), None);
+        assert_eq!(normalise_language_tag("en--us"), None);
+        assert_eq!(normalise_language_tag("en-x"), None);
+        assert_eq!(normalise_language_tag("en us"), None);
+        assert_eq!(normalise_language_tag("12"), None);
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag set by a reader and checked by the parser reading from it, which may be sent to another thread along with them
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Flag(Arc<AtomicBool>);
+
+impl Flag {
+    pub(crate) fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set(&self, value: bool) {
+        self.0.store(value, Ordering::Relaxed)
+    }
+}
+
+/// Reads at most the given number of bytes from the source, recording whether there was more to read
+///
+/// Unlike `Read::take()`, the source is checked for more bytes once the limit is reached, so a source that is exactly the limit
+/// is not treated as exceeding it.
+pub(crate) struct LimitedReader<R: Read> {
+    inner: R,
+    remaining: Option<u64>,
+    exceeded: Flag,
+}
+
+impl<R: Read> LimitedReader<R> {
+    /// Wraps the source, with no limit if `limit` is `None`
+    pub(crate) fn new(inner: R, limit: Option<u64>) -> LimitedReader<R> {
+        LimitedReader {
+            inner,
+            remaining: limit,
+            exceeded: Flag::default(),
+        }
+    }
+
+    /// Set once a read has found the source to be larger than the limit
+    pub(crate) fn exceeded(&self) -> Flag {
+        self.exceeded.clone()
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.remaining {
+            None => self.inner.read(buf),
+            Some(_) if buf.is_empty() => Ok(0),
+            Some(0) => {
+                // The source ends here, but we need to know if it would have carried on
+                if !self.exceeded.get() && self.inner.read(&mut [0u8])? > 0 {
+                    self.exceeded.set(true);
+                }
+                Ok(0)
+            }
+            Some(remaining) => {
+                let max = buf.len().min(remaining as usize);
+                let read = self.inner.read(&mut buf[..max])?;
+                self.remaining = Some(remaining - read as u64);
+                Ok(read)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify the source is cut off at the limit, and only flagged as exceeding it if there was more to read
+    #[test]
+    fn test_limited_reader() {
+        let read = |limit: Option<u64>| {
+            let mut reader = LimitedReader::new(&b"0123456789"[..], limit);
+            let mut text = String::new();
+            reader.read_to_string(&mut text).unwrap();
+            (text, reader.exceeded().get())
+        };
+        assert_eq!(read(None), ("0123456789".to_owned(), false));
+        assert_eq!(read(Some(20)), ("0123456789".to_owned(), false));
+        assert_eq!(read(Some(10)), ("0123456789".to_owned(), false));
+        assert_eq!(read(Some(4)), ("0123".to_owned(), true));
+        assert_eq!(read(Some(0)), ("".to_owned(), true));
+    }
+}
@@ -1,2 +1,10 @@
+pub(crate) mod dates;
+pub(crate) mod encoding;
+pub(crate) mod html;
+pub(crate) mod language;
+pub(crate) mod limit;
+#[cfg(feature = "sanitize")]
+pub(crate) mod sanitize;
+
 #[cfg(test)]
 pub mod test;
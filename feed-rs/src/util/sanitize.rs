@@ -0,0 +1,199 @@
+use crate::util::html::{decode_entities, parse_tag, skip_element, Tag, DROPPED_ELEMENTS};
+
+// Elements that are kept, without any attributes other than those in ALLOWED_ATTRIBUTES
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "audio",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+    "video",
+];
+
+// Attributes that are kept on the allowed elements
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "alt", "cite", "colspan", "controls", "datetime", "dir", "height", "href", "lang", "poster", "rowspan", "src", "title", "type", "width",
+];
+
+// Attributes holding a URL, which must use one of the allowed schemes (or be relative)
+const URL_ATTRIBUTES: &[&str] = &["cite", "href", "poster", "src"];
+const ALLOWED_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Removes anything that is unsafe to render from an HTML fragment
+///
+/// Elements that are not on the allow-list are stripped (keeping their text), apart from those such as `<script>` and `<iframe>`
+/// which are removed along with their content. Comments and any attribute that is not on the allow-list (e.g. event handlers or
+/// styles) are removed, as are URLs using schemes such as `javascript:`.
+pub(crate) fn sanitize_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // Comments, doctypes and processing instructions are removed
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        // Anything that is not a tag is escaped
+        let tag = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                out.push_str("&lt;");
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag.length..];
+
+        if DROPPED_ELEMENTS.contains(&tag.name.as_str()) {
+            if !tag.closing && !tag.self_closing {
+                rest = skip_element(rest, &tag.name);
+            }
+        } else if ALLOWED_ELEMENTS.contains(&tag.name.as_str()) {
+            write_tag(&mut out, &tag);
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+// Writes the tag, keeping only the allowed attributes with safe values
+fn write_tag(out: &mut String, tag: &Tag) {
+    out.push('<');
+    if tag.closing {
+        out.push('/');
+        out.push_str(&tag.name);
+        out.push('>');
+        return;
+    }
+
+    out.push_str(&tag.name);
+    for (name, value) in &tag.attributes {
+        if !ALLOWED_ATTRIBUTES.contains(&name.as_str()) || (URL_ATTRIBUTES.contains(&name.as_str()) && !is_safe_url(value)) {
+            continue;
+        }
+        out.push(' ');
+        out.push_str(name);
+        out.push_str("=\"");
+        out.push_str(&value.replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;"));
+        out.push('"');
+    }
+    if tag.self_closing {
+        out.push_str(" /");
+    }
+    out.push('>');
+}
+
+// Checks the URL is relative, or uses one of the allowed schemes
+fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters in the scheme, and entities may be used to hide it
+    let url: String = decode_entities(url).chars().filter(|c| !c.is_whitespace() && !c.is_control()).collect();
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => ALLOWED_SCHEMES.contains(&url[..i].to_ascii_lowercase().as_str()),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Verify unsafe elements and attributes are removed, leaving the rest of the markup intact
+    #[test]
+    fn test_sanitize_html() {
+        let tests = vec![
+            // Safe markup is untouched
+            ("<p>Fish &amp; <b>chips</b></p>", "<p>Fish &amp; <b>chips</b></p>"),
+            ("<p>Line<br/>break</p>", "<p>Line<br />break</p>"),
+            // Scripts, styles and frames are removed along with their content
+            ("<p>One</p><script>alert('x')</script><p>Two</p>", "<p>One</p><p>Two</p>"),
+            ("<STYLE type=\"text/css\">p { color: red }</STYLE>Text", "Text"),
+            ("<iframe src=\"http://example.com/\"></iframe>After", "After"),
+            ("<script>never closed", ""),
+            // Unknown elements are stripped, but their text is kept
+            ("<font color=\"red\">Red</font>", "Red"),
+            ("<form action=\"/\"><input name=\"q\"/>Search</form>", "Search"),
+            // Comments are removed
+            ("Before<!-- <script>alert('x')</script> -->After", "BeforeAfter"),
+            // Event handlers and styles are removed
+            (
+                "<img src=\"a.png\" onerror=\"alert('x')\" alt='An \"image\"'>",
+                "<img src=\"a.png\" alt=\"An &quot;image&quot;\">",
+            ),
+            ("<p style=\"position: fixed\" class=x>Text</p>", "<p>Text</p>"),
+            // URLs are limited to safe schemes
+            (
+                "<a href=\"https://example.com/?a=1&amp;b=2\">Link</a>",
+                "<a href=\"https://example.com/?a=1&amp;b=2\">Link</a>",
+            ),
+            (
+                "<a href=\"/relative/path:with-colon\">Link</a>",
+                "<a href=\"/relative/path:with-colon\">Link</a>",
+            ),
+            ("<a href=\"javascript:alert('x')\">Link</a>", "<a>Link</a>"),
+            ("<a href=\" JaVaScRiPt:alert('x')\">Link</a>", "<a>Link</a>"),
+            ("<a href=\"java&#x09;script&colon;alert('x')\">Link</a>", "<a>Link</a>"),
+            ("<a href=\"javascript&#58alert('x')\">Link</a>", "<a>Link</a>"),
+            ("<img src=\"data:image/svg+xml;base64,PHN2Zz4=\">", "<img>"),
+            // Stray angle brackets are escaped
+            ("1 < 2 and 3 > 2", "1 &lt; 2 and 3 > 2"),
+            ("<a href=\"unterminated", "&lt;a href=\"unterminated"),
+        ];
+
+        for (source, expected) in tests {
+            assert_eq!(sanitize_html(source), expected, "{}", source);
+        }
+    }
+}
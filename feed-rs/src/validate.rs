@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use url::Url;
+
+use crate::model::{Entry, Feed, FeedType, Link, Person, Text};
+
+/// A requirement of the format of a feed which it does not meet, as found by `validate()`
+///
+/// Fields are named by their path in the model, with entries identified by their position in the feed (e.g. "title" or "entries[2].id").
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// A field the format requires is missing or empty
+    Missing(String),
+    /// More than one entry in the feed has this ID
+    DuplicateId(String),
+    /// The field is not a valid, absolute URI
+    InvalidUri { field: String, uri: String },
+    /// The time in the field (e.g. "entries[0].updated") is before the item was first published
+    UpdatedBeforePublished(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Missing(field) => write!(f, "missing {}", field),
+            Violation::DuplicateId(id) => write!(f, "duplicate entry ID {}", id),
+            Violation::InvalidUri { field, uri } => write!(f, "invalid URI {} in {}", uri, field),
+            Violation::UpdatedBeforePublished(field) => write!(f, "{} is before the time published", field),
+        }
+    }
+}
+
+/// Checks the feed against the requirements of its format (`Feed::feed_type`), so tools that publish feeds with the `writer` can lint them
+///
+/// The checks are those of the specs that the model can express:
+/// * Atom: the feed and each entry have an ID, title and time updated; there are authors for each entry (on it or the feed); and an
+///   entry without content has a link
+/// * RSS 0.9x, 1.0 and 2.0: the channel has a title, link and description, and each item has a title or description (RSS 0.9x and
+///   2.0) or a title and link (RSS 1.0)
+/// * JSON Feed: the feed has a title, and each item an ID
+///
+/// For every format the IDs of the entries which have one must be distinct, links and other URIs must be absolute, and nothing may be
+/// updated before it was published. Note that dates in the future are not reported, as the feed may be written ahead of time.
+///
+/// The result is empty if the feed is valid.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::parser;
+/// use feed_rs::validate::{self, Violation};
+///
+/// let feed = parser::parse(r#"<feed xmlns="http://www.w3.org/2005/Atom">
+///     <id>urn:feed</id>
+///     <title>Example</title>
+///     <entry><id>urn:1</id><content>Untitled</content></entry>
+/// </feed>"#.as_bytes()).unwrap();
+///
+/// let violations = validate::validate(&feed);
+/// assert!(violations.contains(&Violation::Missing("updated".into())));
+/// assert!(violations.contains(&Violation::Missing("entries[0].title".into())));
+/// ```
+pub fn validate(feed: &Feed) -> Vec<Violation> {
+    let mut violations = Violations::default();
+
+    match feed.feed_type {
+        FeedType::Atom => {
+            violations.require(feed.id.is_empty(), "id");
+            violations.require_text(&feed.title, "title");
+            violations.require(feed.updated.is_none(), "updated");
+            if feed.authors.is_empty() && feed.entries.iter().any(|entry| entry.authors.is_empty()) {
+                violations.missing("authors");
+            }
+        }
+        FeedType::JSON => violations.require_text(&feed.title, "title"),
+        FeedType::RSS0 | FeedType::RSS1 | FeedType::RSS2 => {
+            violations.require_text(&feed.title, "title");
+            violations.require(feed.links.is_empty(), "links");
+            violations.require_text(&feed.description, "description");
+        }
+    }
+    if matches!(feed.feed_type, FeedType::Atom) && !feed.id.is_empty() {
+        violations.check_uri("id", &feed.id);
+    }
+    violations.check_dates(feed.updated, feed.published, "updated");
+    violations.check_links(&feed.links, "links");
+    violations.check_people(&feed.authors, "authors");
+    violations.check_people(&feed.contributors, "contributors");
+    if let Some(icon) = &feed.icon {
+        violations.check_uri("icon", &icon.uri);
+    }
+    if let Some(logo) = &feed.logo {
+        violations.check_uri("logo", &logo.uri);
+    }
+
+    let mut ids = HashSet::new();
+    let mut duplicates = HashSet::new();
+    for (index, entry) in feed.entries.iter().enumerate() {
+        validate_entry(feed.feed_type, entry, &format!("entries[{}]", index), &mut violations);
+        if !entry.id.is_empty() && !ids.insert(entry.id.as_str()) && duplicates.insert(entry.id.as_str()) {
+            violations.0.push(Violation::DuplicateId(entry.id.clone()));
+        }
+    }
+
+    violations.0
+}
+
+// Checks an entry, naming its fields with its path in the feed
+fn validate_entry(feed_type: FeedType, entry: &Entry, path: &str, violations: &mut Violations) {
+    let field = |name: &str| format!("{}.{}", path, name);
+    let has_title = matches!(&entry.title, Some(title) if !title.content.trim().is_empty());
+
+    match feed_type {
+        FeedType::Atom => {
+            violations.require(entry.id.is_empty(), &field("id"));
+            violations.require(!has_title, &field("title"));
+            violations.require(entry.updated.is_none(), &field("updated"));
+            violations.require(entry.content.is_none() && entry.links.is_empty(), &field("links"));
+        }
+        FeedType::JSON => violations.require(entry.id.is_empty(), &field("id")),
+        FeedType::RSS0 | FeedType::RSS2 => {
+            let has_summary = matches!(&entry.summary, Some(summary) if !summary.content.trim().is_empty());
+            violations.require(!has_title && !has_summary, &field("title"));
+        }
+        FeedType::RSS1 => {
+            violations.require(!has_title, &field("title"));
+            violations.require(entry.links.is_empty(), &field("links"));
+        }
+    }
+    if matches!(feed_type, FeedType::Atom) && !entry.id.is_empty() {
+        violations.check_uri(&field("id"), &entry.id);
+    }
+    violations.check_dates(entry.updated, entry.published, &field("updated"));
+    violations.check_links(&entry.links, &field("links"));
+    violations.check_people(&entry.authors, &field("authors"));
+    violations.check_people(&entry.contributors, &field("contributors"));
+    if let Some(content) = entry.content.as_ref().and_then(|content| content.src.as_ref()) {
+        violations.check_uri(&field("content.src"), &content.href);
+    }
+}
+
+// The violations found so far, in the order they were found
+#[derive(Default)]
+struct Violations(Vec<Violation>);
+
+impl Violations {
+    fn missing(&mut self, field: &str) {
+        self.0.push(Violation::Missing(field.to_owned()));
+    }
+
+    // Reports the field as missing if the condition holds
+    fn require(&mut self, missing: bool, field: &str) {
+        if missing {
+            self.missing(field);
+        }
+    }
+
+    // Reports the text as missing if there is none, or it is only whitespace
+    fn require_text(&mut self, text: &Option<Text>, field: &str) {
+        self.require(!matches!(text, Some(text) if !text.content.trim().is_empty()), field);
+    }
+
+    fn check_dates(&mut self, updated: Option<DateTime<Utc>>, published: Option<DateTime<Utc>>, field: &str) {
+        if let (Some(updated), Some(published)) = (updated, published) {
+            if updated < published {
+                self.0.push(Violation::UpdatedBeforePublished(field.to_owned()));
+            }
+        }
+    }
+
+    fn check_links(&mut self, links: &[Link], field: &str) {
+        for (index, link) in links.iter().enumerate() {
+            self.check_uri(&format!("{}[{}]", field, index), &link.href);
+        }
+    }
+
+    fn check_people(&mut self, people: &[Person], field: &str) {
+        for (index, person) in people.iter().enumerate() {
+            if let Some(uri) = &person.uri {
+                self.check_uri(&format!("{}[{}].uri", field, index), uri);
+            }
+        }
+    }
+
+    fn check_uri(&mut self, field: &str, uri: &str) {
+        if Url::parse(uri).is_err() {
+            self.0.push(Violation::InvalidUri {
+                field: field.to_owned(),
+                uri: uri.to_owned(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    // Verify a feed which meets the requirements of its format has no violations
+    #[test]
+    fn test_valid() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+                <id>urn:feed</id>
+                <title>Example</title>
+                <updated>2021-03-02T10:00:00Z</updated>
+                <author><name>Jo</name><uri>http://example.com/jo</uri></author>
+                <link href="http://example.com/"/>
+                <entry>
+                    <id>tag:example.com,2021:1</id>
+                    <title>First</title>
+                    <published>2021-03-01T10:00:00Z</published>
+                    <updated>2021-03-02T10:00:00Z</updated>
+                    <content>Some text</content>
+                </entry>
+            </feed>"#;
+        let feed = parser::parse(xml.as_bytes()).unwrap();
+        assert_eq!(validate(&feed), vec![]);
+
+        let xml = r#"<rss version="2.0"><channel>
+                <title>Example</title>
+                <link>http://example.com/</link>
+                <description>An example</description>
+                <item><description>Untitled</description></item>
+            </channel></rss>"#;
+        let feed = parser::parse(xml.as_bytes()).unwrap();
+        assert_eq!(validate(&feed), vec![]);
+    }
+
+    // Verify the missing fields, duplicate IDs, invalid URIs and dates are reported with the path of the field
+    #[test]
+    fn test_violations() {
+        let mut feed = Feed::new(FeedType::Atom);
+        feed.id = "not a uri".into();
+        feed.title = Some(Text::new(" ".into()));
+        feed.links.push(Link::new("/relative", None));
+        let entry = Entry::default().id("urn:1").title(Text::new("First".into()));
+        feed.entries = vec![
+            entry.clone().updated_rfc3339("2021-03-01T10:00:00Z").published_rfc3339("2021-03-02T10:00:00Z"),
+            entry.clone(),
+            entry.updated_rfc3339("2021-03-01T10:00:00Z"),
+        ];
+
+        assert_eq!(
+            validate(&feed),
+            vec![
+                Violation::Missing("title".into()),
+                Violation::Missing("updated".into()),
+                Violation::Missing("authors".into()),
+                Violation::InvalidUri {
+                    field: "id".into(),
+                    uri: "not a uri".into()
+                },
+                Violation::InvalidUri {
+                    field: "links[0]".into(),
+                    uri: "/relative".into()
+                },
+                Violation::Missing("entries[0].links".into()),
+                Violation::UpdatedBeforePublished("entries[0].updated".into()),
+                Violation::Missing("entries[1].updated".into()),
+                Violation::Missing("entries[1].links".into()),
+                Violation::DuplicateId("urn:1".into()),
+                Violation::Missing("entries[2].links".into()),
+            ]
+        );
+
+        // RSS has its own requirements, and does not require IDs
+        feed.feed_type = FeedType::RSS2;
+        feed.id = "".into();
+        feed.entries = vec![Entry::default(), Entry::default()];
+        assert_eq!(
+            validate(&feed),
+            vec![
+                Violation::Missing("title".into()),
+                Violation::Missing("description".into()),
+                Violation::InvalidUri {
+                    field: "links[0]".into(),
+                    uri: "/relative".into()
+                },
+                Violation::Missing("entries[0].title".into()),
+                Violation::Missing("entries[1].title".into()),
+            ]
+        );
+        assert_eq!(Violation::Missing("entries[1].title".into()).to_string(), "missing entries[1].title");
+    }
+}
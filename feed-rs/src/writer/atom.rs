@@ -0,0 +1,248 @@
+use std::io::Write;
+
+use mime::Mime;
+
+use crate::model::{Category, Content, Entry, Feed, FeedSource, Generator, Link, Person, Text};
+use crate::writer::{ElementWriter, WriteFeedResult, Writer};
+
+// The relations of the links that differ between the pages of a paged feed, so are replaced on each page
+const PAGE_RELS: [&str; 6] = ["first", "last", "next", "prev", "previous", "self"];
+
+/// Writes the feed as an Atom 1.0 document
+pub(crate) fn write<W: Write>(feed: &Feed, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    write_document(feed, &feed.entries, &[], out)
+}
+
+/// Writes the entries of the feed as the pages of a paged feed (RFC 5005), each linked to the first, last, previous and next pages
+pub(crate) fn write_pages<F: Fn(usize) -> String>(feed: &Feed, page_size: usize, page_uri: F, options: &Writer) -> WriteFeedResult<Vec<Vec<u8>>> {
+    // A feed without entries is still written, as a single page
+    let pages: Vec<&[Entry]> = if feed.entries.is_empty() {
+        vec![&[]]
+    } else {
+        feed.entries.chunks(page_size.max(1)).collect()
+    };
+
+    let last = pages.len();
+    let link = |rel: &str, number: usize| Link {
+        rel: Some(rel.to_owned()),
+        ..Link::new(page_uri(number), None)
+    };
+    let mut documents = Vec::with_capacity(pages.len());
+    for (index, entries) in pages.into_iter().enumerate() {
+        let number = index + 1;
+        let mut page_links = vec![link("self", number), link("first", 1), link("last", last)];
+        if number > 1 {
+            page_links.push(link("prev", number - 1));
+        }
+        if number < last {
+            page_links.push(link("next", number + 1));
+        }
+
+        let mut document = Vec::new();
+        write_document(feed, entries, &page_links, &mut ElementWriter::new(&mut document, options))?;
+        documents.push(document);
+    }
+
+    Ok(documents)
+}
+
+// Writes the feed with the given entries, replacing the links between pages with those given (if any)
+fn write_document<W: Write>(feed: &Feed, entries: &[Entry], page_links: &[Link], out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.declaration()?;
+    out.stylesheets(&feed.stylesheets)?;
+    let mut attributes = vec![("xmlns", "http://www.w3.org/2005/Atom")];
+    if let Some(language) = &feed.language {
+        attributes.push(("xml:lang", language));
+    }
+    out.start("feed", &attributes)?;
+
+    out.text("id", &[], &feed.id)?;
+    write_text("title", feed.title.as_ref(), out)?;
+    // Atom requires an updated time, so fall back to the newest entry if the source did not provide one
+    out.optional_text("updated", feed.effective_updated().map(|updated| updated.to_rfc3339()).as_deref())?;
+    for author in &feed.authors {
+        write_person("author", author, out)?;
+    }
+    for link in &feed.links {
+        if page_links.is_empty() || !PAGE_RELS.iter().any(|rel| link.has_rel(rel)) {
+            write_link(link, out)?;
+        }
+    }
+    for link in page_links {
+        write_link(link, out)?;
+    }
+    write_license(feed.license.as_ref(), &feed.links, out)?;
+    for category in &feed.categories {
+        write_category(category, out)?;
+    }
+    for contributor in &feed.contributors {
+        write_person("contributor", contributor, out)?;
+    }
+    if let Some(generator) = &feed.generator {
+        write_generator(generator, out)?;
+    }
+    out.optional_text("icon", feed.icon.as_ref().map(|icon| icon.uri.as_str()))?;
+    out.optional_text("logo", feed.logo.as_ref().map(|logo| logo.uri.as_str()))?;
+    write_text("rights", feed.rights.as_ref(), out)?;
+    write_text("subtitle", feed.description.as_ref(), out)?;
+
+    for entry in entries {
+        write_entry(entry, out)?;
+    }
+
+    out.end("feed")
+}
+
+// Writes an <entry>
+fn write_entry<W: Write>(entry: &Entry, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start("entry", &[])?;
+
+    out.text("id", &[], &entry.id)?;
+    write_text("title", entry.title.as_ref(), out)?;
+    out.optional_text("updated", entry.updated.map(|updated| updated.to_rfc3339()).as_deref())?;
+    for author in &entry.authors {
+        write_person("author", author, out)?;
+    }
+    if let Some(content) = &entry.content {
+        write_content(content, out)?;
+    }
+    for link in &entry.links {
+        write_link(link, out)?;
+    }
+    write_license(entry.license.as_ref(), &entry.links, out)?;
+    write_text("summary", entry.summary.as_ref(), out)?;
+    for category in &entry.categories {
+        write_category(category, out)?;
+    }
+    for contributor in &entry.contributors {
+        write_person("contributor", contributor, out)?;
+    }
+    out.optional_text("published", entry.published.map(|published| published.to_rfc3339()).as_deref())?;
+    write_text("rights", entry.rights.as_ref(), out)?;
+    if let Some(source) = &entry.source {
+        write_source(source, out)?;
+    }
+    out.content_hash(entry)?;
+
+    out.end("entry")
+}
+
+// Writes the license as a link, unless it is one of the links already written (as it is when parsed from Atom)
+fn write_license<W: Write>(license: Option<&Link>, links: &[Link], out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    match license {
+        Some(license) if !links.contains(license) => write_link(license, out),
+        _ => Ok(()),
+    }
+}
+
+// Writes <category>
+fn write_category<W: Write>(category: &Category, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let mut attributes = vec![("term", &*category.term)];
+    if let Some(scheme) = &category.scheme {
+        attributes.push(("scheme", scheme));
+    }
+    if let Some(label) = &category.label {
+        attributes.push(("label", label));
+    }
+    out.empty("category", &attributes)
+}
+
+// Writes <content>, either inline or as a link to the source
+fn write_content<W: Write>(content: &Content, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let mut attributes = vec![("type", text_type(&content.content_type))];
+    if let Some(lang) = &content.lang {
+        attributes.push(("xml:lang", lang));
+    }
+    match (&content.body, &content.src) {
+        (Some(body), _) => out.text("content", &attributes, body),
+        (None, Some(src)) => {
+            attributes.push(("src", &src.href));
+            out.empty("content", &attributes)
+        }
+        (None, None) => Ok(()),
+    }
+}
+
+// Writes <generator>
+fn write_generator<W: Write>(generator: &Generator, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let mut attributes = Vec::new();
+    if let Some(uri) = &generator.uri {
+        attributes.push(("uri", uri.as_str()));
+    }
+    if let Some(version) = &generator.version {
+        attributes.push(("version", version));
+    }
+    out.text("generator", &attributes, &generator.content)
+}
+
+// Writes <link>
+fn write_link<W: Write>(link: &Link, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let length = link.length.map(|length| length.to_string());
+    let mut attributes = vec![("href", link.href.as_str())];
+    if let Some(rel) = &link.rel {
+        attributes.push(("rel", rel));
+    }
+    if let Some(media_type) = &link.media_type {
+        attributes.push(("type", media_type.as_ref()));
+    }
+    if let Some(href_lang) = &link.href_lang {
+        attributes.push(("hreflang", href_lang));
+    }
+    if let Some(title) = &link.title {
+        attributes.push(("title", title));
+    }
+    if let Some(length) = &length {
+        attributes.push(("length", length));
+    }
+    out.empty("link", &attributes)
+}
+
+// Writes a person construct such as <author> or <contributor>
+fn write_person<W: Write>(name: &str, person: &Person, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start(name, &[])?;
+    out.text("name", &[], &person.name)?;
+    out.optional_text("uri", person.uri.as_deref())?;
+    out.optional_text("email", person.email.as_deref())?;
+    out.end(name)
+}
+
+// Writes <source>, with the metadata of the feed the entry was copied from
+fn write_source<W: Write>(source: &FeedSource, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start("source", &[])?;
+    out.optional_text("id", source.id.as_deref())?;
+    write_text("title", source.title.as_ref(), out)?;
+    out.optional_text("updated", source.updated.map(|updated| updated.to_rfc3339()).as_deref())?;
+    for author in &source.authors {
+        write_person("author", author, out)?;
+    }
+    for link in &source.links {
+        write_link(link, out)?;
+    }
+    write_text("rights", source.rights.as_ref(), out)?;
+    out.end("source")
+}
+
+// Writes a text construct such as <title> or <rights>, if present
+fn write_text<W: Write>(name: &str, text: Option<&Text>, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    match text {
+        Some(text) => {
+            let mut attributes = vec![("type", text_type(&text.content_type))];
+            if let Some(lang) = &text.lang {
+                attributes.push(("xml:lang", lang));
+            }
+            out.text(name, &attributes, &text.content)
+        }
+        None => Ok(()),
+    }
+}
+
+// Maps the content type to the type attribute of a text construct (HTML is written escaped, so XHTML is also written as HTML)
+fn text_type(mime: &Mime) -> &str {
+    if mime.type_() == mime::TEXT && mime.subtype() == mime::HTML {
+        "html"
+    } else if mime.type_() == mime::TEXT && mime.subtype() == mime::PLAIN {
+        "text"
+    } else {
+        mime.essence_str()
+    }
+}
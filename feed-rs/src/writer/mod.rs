@@ -0,0 +1,357 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, Write};
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+
+use crate::model;
+use crate::parser::{self, ParseFeedError};
+use crate::xml;
+
+mod atom;
+pub(crate) mod opml;
+mod rss2;
+
+#[cfg(test)]
+mod tests;
+
+pub type WriteFeedResult<T> = std::result::Result<T, WriteFeedError>;
+
+/// An error returned when writing a feed fails
+#[derive(Debug)]
+pub enum WriteFeedError {
+    // IO error
+    IoError(std::io::Error),
+    // Underlying issue with XML (encoding etc)
+    XmlWriter(xml::XmlError),
+    // The existing document given to `append()` is not a valid feed
+    Parse(ParseFeedError),
+}
+
+impl From<std::io::Error> for WriteFeedError {
+    fn from(err: std::io::Error) -> Self {
+        WriteFeedError::IoError(err)
+    }
+}
+
+impl From<quick_xml::Error> for WriteFeedError {
+    fn from(err: quick_xml::Error) -> Self {
+        match err {
+            quick_xml::Error::Io(ie) => WriteFeedError::IoError(ie),
+            e => WriteFeedError::XmlWriter(xml::XmlError::from(e)),
+        }
+    }
+}
+
+impl From<ParseFeedError> for WriteFeedError {
+    fn from(err: ParseFeedError) -> Self {
+        WriteFeedError::Parse(err)
+    }
+}
+
+impl fmt::Display for WriteFeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteFeedError::IoError(ie) => write!(f, "unable to write feed: {}", ie),
+            WriteFeedError::XmlWriter(xe) => write!(f, "unable to write XML: {}", xe),
+            WriteFeedError::Parse(pe) => write!(f, "unable to read the existing feed: {}", pe),
+        }
+    }
+}
+
+impl Error for WriteFeedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WriteFeedError::IoError(ie) => Some(ie),
+            WriteFeedError::XmlWriter(xe) => Some(xe),
+            WriteFeedError::Parse(pe) => Some(pe),
+        }
+    }
+}
+
+/// Writes the feed as an Atom 1.0 document
+///
+/// Fields the model populates from other formats are mapped to their Atom equivalent where one exists (e.g. the description is written as the subtitle).
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::{parser, writer};
+/// let feed = parser::parse(r#"<rss version="2.0"><channel><title>Channel</title><item><title>Item</title></item></channel></rss>"#.as_bytes()).unwrap();
+///
+/// let mut atom = Vec::new();
+/// writer::write_atom(&feed, &mut atom).unwrap();
+/// let feed_from_atom = parser::parse(atom.as_slice()).unwrap();
+/// assert_eq!(feed_from_atom.entries.len(), 1);
+/// ```
+pub fn write_atom<W: Write>(feed: &model::Feed, out: W) -> WriteFeedResult<()> {
+    Builder::new().build().write_atom(feed, out)
+}
+
+/// Writes the entries of the feed as the pages of an Atom 1.0 paged feed (RFC 5005), for services that publish long histories
+///
+/// The entries are split into pages of `page_size`, in order, and each page is returned as a document in its own right. Pages are
+/// numbered from 1, and `page_uri` gives the URI each is published at; every page links to itself ("self"), the "first" and "last"
+/// pages, and the "prev" and "next" pages where there are any. Any links of the feed with those relations are left out.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::{parser, writer};
+/// let feed = parser::parse(r#"<rss version="2.0"><channel><title>Channel</title>
+///     <item><guid>1</guid></item><item><guid>2</guid></item><item><guid>3</guid></item>
+/// </channel></rss>"#.as_bytes()).unwrap();
+///
+/// let pages = writer::write_atom_pages(&feed, 2, |number| format!("https://example.com/feed?page={}", number)).unwrap();
+/// assert_eq!(pages.len(), 2);
+/// let first = parser::parse(pages[0].as_slice()).unwrap();
+/// assert_eq!(first.entries.len(), 2);
+/// assert_eq!(first.next_page().unwrap().href, "https://example.com/feed?page=2");
+/// ```
+pub fn write_atom_pages<F: Fn(usize) -> String>(feed: &model::Feed, page_size: usize, page_uri: F) -> WriteFeedResult<Vec<Vec<u8>>> {
+    Builder::new().build().write_atom_pages(feed, page_size, page_uri)
+}
+
+/// Writes the feed as an RSS 2.0 document
+///
+/// Fields the model populates from other formats are mapped to their RSS 2.0 equivalent where one exists (e.g. media content with a URL is written as an enclosure).
+/// Links beyond the main link are written as `<atom:link>`, as is common practice for RSS 2.0.
+/// Other MediaRSS and iTunes data is not written.
+pub fn write_rss2<W: Write>(feed: &model::Feed, out: W) -> WriteFeedResult<()> {
+    Builder::new().build().write_rss2(feed, out)
+}
+
+/// Adds new entries to a feed that has already been written, writing out the updated document
+///
+/// The entries are added ahead of those already in the feed, as feeds list their latest entries first, and replace any earlier versions
+/// of them (matched by ID or else link, as for `Feed::merge()`). The feed is updated as of the newest of them, and only the first
+/// `max_entries` are kept, dropping the oldest. Atom feeds are written as Atom 1.0, and all others as RSS 2.0.
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::{parser, writer};
+/// let existing = r#"<rss version="2.0"><channel><title>Channel</title><item><guid>1</guid></item></channel></rss>"#;
+/// let entries = parser::parse(r#"<rss version="2.0"><channel><item><guid>2</guid></item></channel></rss>"#.as_bytes()).unwrap().entries;
+///
+/// let mut updated = Vec::new();
+/// writer::append(existing.as_bytes(), entries, 100, &mut updated).unwrap();
+/// let feed = parser::parse(updated.as_slice()).unwrap();
+/// assert_eq!(feed.entries.iter().map(|entry| entry.id.as_str()).collect::<Vec<_>>(), vec!["2", "1"]);
+/// ```
+pub fn append<R: Read, W: Write>(source: R, entries: Vec<model::Entry>, max_entries: usize, out: W) -> WriteFeedResult<()> {
+    Builder::new().build().append(source, entries, max_entries, out)
+}
+
+/// Writes feeds with the options set by a `Builder`
+///
+/// # Examples
+///
+/// ```
+/// use feed_rs::{parser, writer};
+/// let feed = parser::parse(r#"<rss version="2.0"><channel><title>Channel</title></channel></rss>"#.as_bytes()).unwrap();
+///
+/// let writer = writer::Builder::new().compact().declaration(false).used_namespaces_only(true).build();
+/// let mut rss = Vec::new();
+/// writer.write_rss2(&feed, &mut rss).unwrap();
+/// assert_eq!(
+///     String::from_utf8(rss).unwrap(),
+///     r#"<rss version="2.0"><channel><title>Channel</title><link></link><description></description></channel></rss>"#
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Writer {
+    pretty: bool,
+    declaration: bool,
+    used_namespaces_only: bool,
+    content_hashes: bool,
+}
+
+impl Writer {
+    /// Writes the feed as an Atom 1.0 document (see `writer::write_atom()`)
+    pub fn write_atom<W: Write>(&self, feed: &model::Feed, out: W) -> WriteFeedResult<()> {
+        atom::write(feed, &mut ElementWriter::new(out, self))
+    }
+
+    /// Writes the entries of the feed as the pages of an Atom 1.0 paged feed (see `writer::write_atom_pages()`)
+    pub fn write_atom_pages<F: Fn(usize) -> String>(&self, feed: &model::Feed, page_size: usize, page_uri: F) -> WriteFeedResult<Vec<Vec<u8>>> {
+        atom::write_pages(feed, page_size, page_uri, self)
+    }
+
+    /// Writes the feed as an RSS 2.0 document (see `writer::write_rss2()`)
+    pub fn write_rss2<W: Write>(&self, feed: &model::Feed, out: W) -> WriteFeedResult<()> {
+        rss2::write(feed, &mut ElementWriter::new(out, self), self.used_namespaces_only)
+    }
+
+    /// Adds new entries to a feed that has already been written, writing out the updated document (see `writer::append()`)
+    pub fn append<R: Read, W: Write>(&self, source: R, entries: Vec<model::Entry>, max_entries: usize, out: W) -> WriteFeedResult<()> {
+        let mut feed = parser::parse(source)?;
+
+        // The new entries replace their earlier versions
+        feed.entries.retain(|existing| !entries.iter().any(|entry| entry.is_same_entry(existing)));
+        feed.updated = feed.updated.max(entries.iter().filter_map(model::Entry::timestamp).max());
+        let mut existing = std::mem::replace(&mut feed.entries, entries);
+        feed.entries.append(&mut existing);
+        feed.entries.truncate(max_entries);
+
+        match feed.feed_type {
+            model::FeedType::Atom => self.write_atom(&feed, out),
+            _ => self.write_rss2(&feed, out),
+        }
+    }
+}
+
+/// Builds a `Writer` with non-default options
+///
+/// By default documents are pretty-printed with an XML declaration, and RSS 2.0 documents declare every namespace the writer may use.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    writer: Writer,
+}
+
+impl Builder {
+    /// Create a new instance of the builder
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Create a new writer with the options accumulated in this builder
+    pub fn build(self) -> Writer {
+        self.writer
+    }
+
+    /// Writes each element on its own line, indented by its depth (the default), which suits debugging and diffs
+    pub fn pretty(mut self) -> Self {
+        self.writer.pretty = true;
+        self
+    }
+
+    /// Writes the document without whitespace between elements, for the smallest output when serving a feed
+    pub fn compact(mut self) -> Self {
+        self.writer.pretty = false;
+        self
+    }
+
+    /// Whether the document starts with an XML declaration (enabled by default), which may be left out when it is embedded in another
+    pub fn declaration(mut self, enabled: bool) -> Self {
+        self.writer.declaration = enabled;
+        self
+    }
+
+    /// Declares only the namespaces whose elements are written (false by default)
+    ///
+    /// RSS 2.0 documents otherwise declare the Atom, content, Dublin Core and syndication namespaces, whether or not the feed has
+    /// data for them. Atom documents only use the Atom namespace, so are unaffected.
+    pub fn used_namespaces_only(mut self, enabled: bool) -> Self {
+        self.writer.used_namespaces_only = enabled;
+        self
+    }
+
+    /// Writes a hash of the content of each entry (false by default), for cheap change detection and integrity checks downstream
+    ///
+    /// The hash is the `Entry::content_hash()`, written as a `hash` element in the `model::CONTENT_HASH_NAMESPACE`. Once the feed is
+    /// parsed again, `Entry::verify_content_hash()` checks it against the content.
+    pub fn content_hashes(mut self, enabled: bool) -> Self {
+        self.writer.content_hashes = enabled;
+        self
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            writer: Writer {
+                pretty: true,
+                declaration: true,
+                used_namespaces_only: false,
+                content_hashes: false,
+            },
+        }
+    }
+}
+
+// Wraps the XML writer with the shapes of element used by the feed formats
+pub(crate) struct ElementWriter<W: Write> {
+    writer: quick_xml::Writer<W>,
+    declaration: bool,
+    content_hashes: bool,
+}
+
+impl<W: Write> ElementWriter<W> {
+    fn new(out: W, options: &Writer) -> ElementWriter<W> {
+        let writer = if options.pretty {
+            quick_xml::Writer::new_with_indent(out, b' ', 2)
+        } else {
+            quick_xml::Writer::new(out)
+        };
+        ElementWriter {
+            writer,
+            declaration: options.declaration,
+            content_hashes: options.content_hashes,
+        }
+    }
+
+    // Writes the XML declaration that starts the document, unless disabled
+    fn declaration(&mut self) -> WriteFeedResult<()> {
+        if self.declaration {
+            self.writer.write_event(Event::Decl(BytesDecl::new(b"1.0", Some(b"utf-8"), None)))?;
+        }
+        Ok(())
+    }
+
+    // Writes the content hash of the entry, if enabled, declaring its namespace on the element so it does not depend on the document
+    fn content_hash(&mut self, entry: &model::Entry) -> WriteFeedResult<()> {
+        if self.content_hashes {
+            self.text("hash", &[("xmlns", model::CONTENT_HASH_NAMESPACE)], &entry.content_hash())?;
+        }
+        Ok(())
+    }
+
+    // Writes an <?xml-stylesheet?> processing instruction for each of the stylesheets
+    fn stylesheets(&mut self, stylesheets: &[model::Stylesheet]) -> WriteFeedResult<()> {
+        for stylesheet in stylesheets {
+            let mut pi = format!(r#"xml-stylesheet href="{}""#, String::from_utf8_lossy(&escape(stylesheet.href.as_bytes())));
+            if let Some(media_type) = &stylesheet.media_type {
+                pi.push_str(&format!(r#" type="{}""#, String::from_utf8_lossy(&escape(media_type.as_bytes()))));
+            }
+            self.writer.write_event(Event::PI(BytesText::from_escaped_str(pi)))?;
+        }
+        Ok(())
+    }
+
+    // Opens an element, which must be closed by a matching call to end()
+    fn start(&mut self, name: &str, attributes: &[(&str, &str)]) -> WriteFeedResult<()> {
+        let element = BytesStart::borrowed_name(name.as_bytes()).with_attributes(attributes.iter().copied());
+        self.writer.write_event(Event::Start(element))?;
+        Ok(())
+    }
+
+    // Closes an element opened with start()
+    fn end(&mut self, name: &str) -> WriteFeedResult<()> {
+        self.writer.write_event(Event::End(BytesEnd::borrowed(name.as_bytes())))?;
+        Ok(())
+    }
+
+    // Writes an element with attributes but no content e.g. <link href="..."/>
+    fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) -> WriteFeedResult<()> {
+        let element = BytesStart::borrowed_name(name.as_bytes()).with_attributes(attributes.iter().copied());
+        self.writer.write_event(Event::Empty(element))?;
+        Ok(())
+    }
+
+    // Writes an element containing (escaped) text e.g. <title>Feed &amp; more</title>
+    fn text(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) -> WriteFeedResult<()> {
+        self.start(name, attributes)?;
+        self.writer.write_event(Event::Text(BytesText::from_plain_str(text)))?;
+        self.end(name)
+    }
+
+    // Writes a text element if the value is present
+    fn optional_text(&mut self, name: &str, text: Option<&str>) -> WriteFeedResult<()> {
+        match text {
+            Some(text) => self.text(name, &[], text),
+            None => Ok(()),
+        }
+    }
+}
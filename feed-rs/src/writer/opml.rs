@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use crate::opml::{Opml, Outline};
+use crate::writer::rss2::format_timestamp;
+use crate::writer::{Builder, ElementWriter, WriteFeedResult};
+
+/// Writes the document as OPML 2.0
+pub(crate) fn write<W: Write>(opml: &Opml, out: W) -> WriteFeedResult<()> {
+    let mut out = ElementWriter::new(out, &Builder::new().build());
+    out.declaration()?;
+    out.start("opml", &[("version", "2.0")])?;
+
+    out.start("head", &[])?;
+    out.optional_text("title", opml.title.as_deref())?;
+    out.optional_text("dateCreated", opml.date_created.map(format_timestamp).as_deref())?;
+    out.optional_text("ownerName", opml.owner_name.as_deref())?;
+    out.optional_text("ownerEmail", opml.owner_email.as_deref())?;
+    out.end("head")?;
+
+    out.start("body", &[])?;
+    for outline in &opml.outlines {
+        write_outline(outline, &mut out)?;
+    }
+    out.end("body")?;
+
+    out.end("opml")
+}
+
+// Writes an <outline>, along with those nested within it
+fn write_outline<W: Write>(outline: &Outline, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let mut attributes = vec![("text", outline.text.as_str())];
+    let optional = [
+        ("title", &outline.title),
+        ("type", &outline.outline_type),
+        ("xmlUrl", &outline.xml_url),
+        ("htmlUrl", &outline.html_url),
+    ];
+    for (name, value) in optional.iter() {
+        if let Some(value) = value {
+            attributes.push((name, value.as_str()));
+        }
+    }
+
+    if outline.outlines.is_empty() {
+        return out.empty("outline", &attributes);
+    }
+    out.start("outline", &attributes)?;
+    for child in &outline.outlines {
+        write_outline(child, out)?;
+    }
+    out.end("outline")
+}
@@ -0,0 +1,307 @@
+use std::io::Write;
+
+use chrono::{DateTime, Utc, Weekday};
+
+use crate::model::{Cloud, Entry, Feed, FeedSource, Image, Link, MediaContent, Person, TextInput, UpdateHints, UpdatePeriod};
+use crate::writer::{ElementWriter, WriteFeedResult};
+
+// The namespaces of the elements written beyond those of RSS 2.0, by prefix
+const ATOM: (&str, &str) = ("xmlns:atom", "http://www.w3.org/2005/Atom");
+const CONTENT: (&str, &str) = ("xmlns:content", "http://purl.org/rss/1.0/modules/content/");
+const CREATIVE_COMMONS: (&str, &str) = ("xmlns:creativeCommons", "http://backend.userland.com/creativeCommonsRssModule");
+const DUBLIN_CORE: (&str, &str) = ("xmlns:dc", "http://purl.org/dc/elements/1.1/");
+const SYNDICATION: (&str, &str) = ("xmlns:sy", "http://purl.org/rss/1.0/modules/syndication/");
+
+/// Writes the feed as an RSS 2.0 document
+pub(crate) fn write<W: Write>(feed: &Feed, out: &mut ElementWriter<W>, used_namespaces_only: bool) -> WriteFeedResult<()> {
+    out.declaration()?;
+    out.stylesheets(&feed.stylesheets)?;
+    let mut attributes = vec![("version", "2.0")];
+    if used_namespaces_only {
+        attributes.extend(used_namespaces(feed));
+    } else {
+        attributes.extend(&[ATOM, CONTENT, CREATIVE_COMMONS, DUBLIN_CORE, SYNDICATION]);
+    }
+    out.start("rss", &attributes)?;
+    out.start("channel", &[])?;
+
+    // Title, link and description are required
+    out.text("title", &[], feed.title.as_ref().map_or("", |title| title.content.as_str()))?;
+    if feed.links.is_empty() {
+        out.text("link", &[], "")?;
+    }
+    write_links(&feed.links, out)?;
+    out.text(
+        "description",
+        &[],
+        feed.description.as_ref().map_or("", |description| description.content.as_str()),
+    )?;
+
+    out.optional_text("language", feed.language.as_deref())?;
+    out.optional_text("copyright", feed.rights.as_ref().map(|rights| rights.content.as_str()))?;
+    write_license(feed.license.as_ref(), &feed.links, out)?;
+    for contributor in &feed.contributors {
+        let role = match contributor.role.as_deref() {
+            Some(role) if role == "managingEditor" || role == "webMaster" => role,
+            _ => continue,
+        };
+        if let Some(contact) = contact(contributor) {
+            out.text(role, &[], &contact)?;
+        }
+    }
+    out.optional_text("pubDate", feed.published.map(format_timestamp).as_deref())?;
+    out.optional_text("lastBuildDate", feed.updated.map(format_timestamp).as_deref())?;
+    for category in &feed.categories {
+        write_category(&category.term, category.scheme.as_deref(), out)?;
+    }
+    if let Some(generator) = &feed.generator {
+        out.text("generator", &[], &generator.content)?;
+    }
+    out.optional_text("docs", feed.docs.as_deref())?;
+    if let Some(cloud) = &feed.cloud {
+        write_cloud(cloud, out)?;
+    }
+    out.optional_text("ttl", feed.ttl.map(|ttl| ttl.to_string()).as_deref())?;
+    if let Some(logo) = &feed.logo {
+        write_image(logo, out)?;
+    }
+    if let Some(text_input) = &feed.text_input {
+        write_text_input(text_input, out)?;
+    }
+    if let Some(hints) = &feed.update_hints {
+        write_update_hints(hints, out)?;
+    }
+
+    for entry in &feed.entries {
+        write_item(entry, out)?;
+    }
+
+    out.end("channel")?;
+    out.end("rss")
+}
+
+// Finds the namespaces of the elements that will be written for the feed
+fn used_namespaces(feed: &Feed) -> Vec<(&'static str, &'static str)> {
+    let mut namespaces = Vec::new();
+    // Links beyond the main link are written as <atom:link>
+    if feed.links.len() > 1 || feed.entries.iter().any(|entry| entry.links.len() > 1) {
+        namespaces.push(ATOM);
+    }
+    if feed
+        .entries
+        .iter()
+        .any(|entry| matches!(&entry.content, Some(content) if content.body.is_some()))
+    {
+        namespaces.push(CONTENT);
+    }
+    if written_license(feed.license.as_ref(), &feed.links) || feed.entries.iter().any(|entry| written_license(entry.license.as_ref(), &entry.links)) {
+        namespaces.push(CREATIVE_COMMONS);
+    }
+    // Authors without an email are written as <dc:creator>
+    if feed.entries.iter().any(|entry| entry.authors.iter().any(|author| author.email.is_none())) {
+        namespaces.push(DUBLIN_CORE);
+    }
+    if let Some(hints) = &feed.update_hints {
+        if hints.update_period.is_some() || hints.update_frequency.is_some() || hints.update_base.is_some() {
+            namespaces.push(SYNDICATION);
+        }
+    }
+    namespaces
+}
+
+// Formats the email address of the person, followed by their name unless they are only known by their email (and named after their role)
+fn contact(person: &Person) -> Option<String> {
+    let email = person.email.as_ref()?;
+    if person.name.is_empty() || person.role.as_deref() == Some(&*person.name) {
+        Some(email.clone())
+    } else {
+        Some(format!("{} ({})", email, person.name))
+    }
+}
+
+// Writes a <cloud>, which has every attribute bar the port (used by "http-post" subscriptions) even if empty
+fn write_cloud<W: Write>(cloud: &Cloud, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let port = cloud.port.map(|port| port.to_string());
+    let mut attributes = vec![("domain", cloud.domain.as_str())];
+    if let Some(port) = &port {
+        attributes.push(("port", port.as_str()));
+    }
+    attributes.push(("path", cloud.path.as_str()));
+    attributes.push(("registerProcedure", cloud.register_procedure.as_deref().unwrap_or("")));
+    attributes.push(("protocol", cloud.protocol.as_str()));
+    out.empty("cloud", &attributes)
+}
+
+// Writes a <textInput>, where the title and description are required even if unknown
+fn write_text_input<W: Write>(text_input: &TextInput, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start("textInput", &[])?;
+    out.text("title", &[], text_input.title.as_deref().unwrap_or(""))?;
+    out.text("description", &[], text_input.description.as_deref().unwrap_or(""))?;
+    out.text("name", &[], &text_input.name)?;
+    out.text("link", &[], &text_input.link)?;
+    out.end("textInput")
+}
+
+// Writes an <item>
+fn write_item<W: Write>(entry: &Entry, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start("item", &[])?;
+
+    out.optional_text("title", entry.title.as_ref().map(|title| title.content.as_str()))?;
+    write_links(&entry.links, out)?;
+    out.optional_text("description", entry.summary.as_ref().map(|summary| summary.content.as_str()))?;
+    // RSS 2.0 authors are email addresses (with the name in brackets), so authors known only by name are written with Dublin Core
+    for author in &entry.authors {
+        match contact(author) {
+            Some(contact) => out.text("author", &[], &contact)?,
+            None => out.text("dc:creator", &[], &author.name)?,
+        }
+    }
+    for category in &entry.categories {
+        write_category(&category.term, category.scheme.as_deref(), out)?;
+    }
+    write_license(entry.license.as_ref(), &entry.links, out)?;
+    let is_permalink = if entry.is_permalink == Some(true) { "true" } else { "false" };
+    out.text("guid", &[("isPermaLink", is_permalink)], &entry.id)?;
+    for content in entry.media.iter().flat_map(|media| media.content.iter()) {
+        write_enclosure(content, out)?;
+    }
+    out.optional_text("pubDate", entry.published.map(format_timestamp).as_deref())?;
+    if let Some(source) = &entry.source {
+        write_source(source, out)?;
+    }
+    out.optional_text("content:encoded", entry.content.as_ref().and_then(|content| content.body.as_deref()))?;
+    out.content_hash(entry)?;
+
+    out.end("item")
+}
+
+// A license is written as <creativeCommons:license>, unless it is one of the links (as it is when parsed from Atom)
+fn written_license(license: Option<&Link>, links: &[Link]) -> bool {
+    matches!(license, Some(license) if !links.contains(license))
+}
+
+// Writes a <creativeCommons:license>, if the license is not written with the links
+fn write_license<W: Write>(license: Option<&Link>, links: &[Link], out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    match license {
+        Some(license) if !links.contains(license) => out.text("creativeCommons:license", &[], &license.href),
+        _ => Ok(()),
+    }
+}
+
+// Writes a <source>, which requires the URL of the channel so is left out without one
+fn write_source<W: Write>(source: &FeedSource, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let link = source.links.iter().find(|link| link.has_rel("self")).or_else(|| source.links.first());
+    match link {
+        Some(link) => out.text(
+            "source",
+            &[("url", &link.href)],
+            source.title.as_ref().map_or("", |title| title.content.as_str()),
+        ),
+        None => Ok(()),
+    }
+}
+
+// Writes the main link (the first without a relationship, or an alternate) as <link>, and any others as <atom:link>
+fn write_links<W: Write>(links: &[Link], out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    let main = links
+        .iter()
+        .position(|link| link.rel.is_none() || link.rel.as_deref() == Some("alternate"))
+        .unwrap_or(0);
+    for (i, link) in links.iter().enumerate() {
+        if i == main {
+            out.text("link", &[], link.href.as_str())?;
+            continue;
+        }
+
+        let length = link.length.map(|length| length.to_string());
+        let attributes: Vec<(&str, &str)> = vec![
+            Some(("href", link.href.as_str())),
+            link.rel.as_deref().map(|rel| ("rel", rel)),
+            link.media_type.as_ref().map(|media_type| ("type", media_type.as_ref())),
+            link.href_lang.as_deref().map(|href_lang| ("hreflang", href_lang)),
+            link.title.as_deref().map(|title| ("title", title)),
+            length.as_deref().map(|length| ("length", length)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        out.empty("atom:link", &attributes)?;
+    }
+
+    Ok(())
+}
+
+// Writes <category>
+fn write_category<W: Write>(term: &str, domain: Option<&str>, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    match domain {
+        Some(domain) => out.text("category", &[("domain", domain)], term),
+        None => out.text("category", &[], term),
+    }
+}
+
+// Writes an <enclosure> for media content with a URL
+fn write_enclosure<W: Write>(content: &MediaContent, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    if let Some(url) = &content.url {
+        // RSS 2.0 requires all three attributes
+        let length = content.size.unwrap_or(0).to_string();
+        let content_type = content.content_type.as_ref().map_or("application/octet-stream", |mime| mime.essence_str());
+        out.empty("enclosure", &[("url", url.as_str()), ("length", &length), ("type", content_type)])?;
+    }
+
+    Ok(())
+}
+
+// Writes <image>
+fn write_image<W: Write>(image: &Image, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    out.start("image", &[])?;
+    out.text("url", &[], &image.uri)?;
+    out.optional_text("title", image.title.as_deref())?;
+    out.optional_text("link", image.link.as_ref().map(|link| link.href.as_str()))?;
+    out.optional_text("width", image.width.map(|width| width.to_string()).as_deref())?;
+    out.optional_text("height", image.height.map(|height| height.to_string()).as_deref())?;
+    out.optional_text("description", image.description.as_deref())?;
+    out.end("image")
+}
+
+// Formats the timestamp per RFC 2822, with a two digit day as is conventional in RSS
+pub(super) fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%a, %d %b %Y %H:%M:%S %z").to_string()
+}
+
+// Writes <skipHours> and <skipDays>, along with the schedule from the syndication module
+fn write_update_hints<W: Write>(hints: &UpdateHints, out: &mut ElementWriter<W>) -> WriteFeedResult<()> {
+    if !hints.skip_hours.is_empty() {
+        out.start("skipHours", &[])?;
+        for hour in &hints.skip_hours {
+            out.text("hour", &[], &hour.to_string())?;
+        }
+        out.end("skipHours")?;
+    }
+    if !hints.skip_days.is_empty() {
+        out.start("skipDays", &[])?;
+        for day in &hints.skip_days {
+            let day = match day {
+                Weekday::Mon => "Monday",
+                Weekday::Tue => "Tuesday",
+                Weekday::Wed => "Wednesday",
+                Weekday::Thu => "Thursday",
+                Weekday::Fri => "Friday",
+                Weekday::Sat => "Saturday",
+                Weekday::Sun => "Sunday",
+            };
+            out.text("day", &[], day)?;
+        }
+        out.end("skipDays")?;
+    }
+
+    let period = hints.update_period.map(|period| match period {
+        UpdatePeriod::Hourly => "hourly",
+        UpdatePeriod::Daily => "daily",
+        UpdatePeriod::Weekly => "weekly",
+        UpdatePeriod::Monthly => "monthly",
+        UpdatePeriod::Yearly => "yearly",
+    });
+    out.optional_text("sy:updatePeriod", period)?;
+    out.optional_text("sy:updateFrequency", hints.update_frequency.map(|frequency| frequency.to_string()).as_deref())?;
+    out.optional_text("sy:updateBase", hints.update_base.map(|base| base.to_rfc3339()).as_deref())
+}
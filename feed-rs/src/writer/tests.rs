@@ -0,0 +1,373 @@
+use chrono::{TimeZone, Utc};
+
+use crate::model::{Entry, Feed, FeedType, Text};
+use crate::opml::{Opml, Outline};
+use crate::parser;
+use crate::util::test;
+use crate::writer;
+
+// Verify feeds survive a round trip through the writer and parser unchanged
+#[test]
+fn test_round_trip() {
+    for fixture in &[
+        "atom_example_1.xml",
+        "atom_example_2.xml",
+        "atom_example_3.xml",
+        "atom_example_4.xml",
+        "atom_example_5.xml",
+        "atom_example_6.xml",
+        "atom_spec_1.xml",
+        "atom_source.xml",
+    ] {
+        let feed = parser::parse(test::fixture_as_raw(fixture).as_slice()).unwrap();
+        let mut out = Vec::new();
+        writer::write_atom(&feed, &mut out).unwrap();
+        let actual = parser::parse(out.as_slice()).unwrap();
+        assert_eq!(
+            without_extensions(actual).semantic_differences(&without_extensions(feed)),
+            Vec::<String>::new(),
+            "{}",
+            fixture
+        );
+    }
+    for fixture in &[
+        "rss_2.0_cloud.xml",
+        "rss_2.0_example_1.xml",
+        "rss_2.0_example_2.xml",
+        "rss_2.0_example_4.xml",
+        "rss_2.0_example_5.xml",
+        "rss_2.0_example_6.xml",
+        "rss_2.0_spec_1.xml",
+        "rss_2.0_text_input.xml",
+    ] {
+        let feed = parser::parse(test::fixture_as_raw(fixture).as_slice()).unwrap();
+        let mut out = Vec::new();
+        writer::write_rss2(&feed, &mut out).unwrap();
+        let actual = parser::parse(out.as_slice()).unwrap();
+        assert_eq!(
+            without_extensions(actual).semantic_differences(&without_extensions(feed)),
+            Vec::<String>::new(),
+            "{}",
+            fixture
+        );
+    }
+}
+
+// The writer only writes the core model, so the raw values of extensions are not expected to survive
+fn without_extensions(mut feed: Feed) -> Feed {
+    feed.itunes = None;
+    feed.podcast = None;
+    feed.dublin_core = None;
+    feed.extensions.clear();
+    for entry in &mut feed.entries {
+        entry.itunes = None;
+        entry.podcast = None;
+        entry.dublin_core = None;
+        entry.geo = None;
+        entry.threading = None;
+        entry.extensions.clear();
+    }
+    feed
+}
+
+// Verify a license survives being written in the other format, where it is a link in Atom and a Creative Commons license in RSS
+#[test]
+fn test_write_license() {
+    let rss = r#"<rss version="2.0" xmlns:creativeCommons="http://backend.userland.com/creativeCommonsRssModule"><channel>
+            <title>Feed</title>
+            <link>http://example.com/</link>
+            <creativeCommons:license>http://creativecommons.org/licenses/by/4.0/</creativeCommons:license>
+            <item><guid>urn:1</guid><link>http://example.com/1</link><creativeCommons:license>http://creativecommons.org/licenses/by-nc/4.0/</creativeCommons:license></item>
+        </channel></rss>"#;
+    let feed = parser::parse(rss.as_bytes()).unwrap();
+
+    let mut atom = Vec::new();
+    writer::write_atom(&feed, &mut atom).unwrap();
+    let from_atom = parser::parse(atom.as_slice()).unwrap();
+    assert_eq!(from_atom.license, feed.license);
+    assert_eq!(from_atom.entries[0].license, feed.entries[0].license);
+
+    let mut rss = Vec::new();
+    writer::Builder::new()
+        .used_namespaces_only(true)
+        .build()
+        .write_rss2(&from_atom, &mut rss)
+        .unwrap();
+    let rss = String::from_utf8(rss).unwrap();
+    assert!(!rss.contains("creativeCommons"), "{}", rss);
+    let from_rss = parser::parse(rss.as_bytes()).unwrap();
+    assert_eq!(from_rss.license, feed.license);
+    assert_eq!(from_rss.entries[0].license, feed.entries[0].license);
+}
+
+// Verify the structure of the written document, including escaping
+#[test]
+fn test_write_rss2() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>Fish &amp; Chips</title>
+            <link href="http://example.com/"/>
+            <updated>2021-03-01T10:00:00Z</updated>
+            <entry>
+                <id>urn:entry:1</id>
+                <title type="html">&lt;b&gt;Battered&lt;/b&gt;</title>
+                <author><name>Chef</name></author>
+                <author><name>Fishmonger</name><email>fish@example.com</email></author>
+                <published>2021-03-01T09:00:00Z</published>
+            </entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let mut out = Vec::new();
+    writer::write_rss2(&feed, &mut out).unwrap();
+
+    let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:creativeCommons="http://backend.userland.com/creativeCommonsRssModule" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+  <channel>
+    <title>Fish &amp; Chips</title>
+    <link>http://example.com/</link>
+    <description></description>
+    <lastBuildDate>Mon, 01 Mar 2021 10:00:00 +0000</lastBuildDate>
+    <item>
+      <title>&lt;b&gt;Battered&lt;/b&gt;</title>
+      <dc:creator>Chef</dc:creator>
+      <author>fish@example.com (Fishmonger)</author>
+      <guid isPermaLink="false">urn:entry:1</guid>
+      <pubDate>Mon, 01 Mar 2021 09:00:00 +0000</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+// Verify RSS contacts are written for their role, whether or not they have a name
+#[test]
+fn test_write_rss2_contacts() {
+    let xml = r#"<rss version="2.0"><channel>
+            <title>Scripting News</title>
+            <managingEditor>dave@userland.com (Dave Winer)</managingEditor>
+            <webMaster>ops@userland.com</webMaster>
+            <item><title>First</title><author>dave@userland.com (Dave Winer)</author><author>ops@userland.com</author></item>
+        </channel></rss>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let mut out = Vec::new();
+    writer::write_rss2(&feed, &mut out).unwrap();
+
+    let written = String::from_utf8(out).unwrap();
+    for element in &[
+        "<managingEditor>dave@userland.com (Dave Winer)</managingEditor>",
+        "<webMaster>ops@userland.com</webMaster>",
+        "<author>dave@userland.com (Dave Winer)</author>",
+        "<author>ops@userland.com</author>",
+    ] {
+        assert!(written.contains(element), "{}", element);
+    }
+    let actual = parser::parse(written.as_bytes()).unwrap();
+    assert_eq!(actual.contributors, feed.contributors);
+    assert_eq!(actual.entries[0].authors, feed.entries[0].authors);
+}
+
+// Verify compact output, and that the declaration and unused namespaces can be left out
+#[test]
+fn test_writer_options() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>Feed</title>
+            <entry><id>urn:entry:1</id><author><name>Chef</name></author></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+
+    let writer = writer::Builder::new().compact().declaration(false).used_namespaces_only(true).build();
+    let mut out = Vec::new();
+    writer.write_rss2(&feed, &mut out).unwrap();
+    let expected = concat!(
+        r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel><title>Feed</title><link></link><description></description>"#,
+        r#"<item><dc:creator>Chef</dc:creator><guid isPermaLink="false">urn:entry:1</guid></item></channel></rss>"#
+    );
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    let writer = writer::Builder::new().compact().build();
+    let mut out = Vec::new();
+    writer.write_atom(&feed, &mut out).unwrap();
+    let expected = concat!(
+        r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><title type="text">Feed</title>"#,
+        r#"<entry><id>urn:entry:1</id><author><name>Chef</name></author></entry></feed>"#
+    );
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+// Verify the entries are split into pages linked to each other, replacing the links of the feed between pages
+#[test]
+fn test_write_atom_pages() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>Archive</title>
+            <link href="http://example.com/"/>
+            <link rel="self" href="http://example.com/feed.xml"/>
+            <link rel="next" href="http://example.com/old.xml"/>
+            <entry><id>urn:1</id></entry>
+            <entry><id>urn:2</id></entry>
+            <entry><id>urn:3</id></entry>
+            <entry><id>urn:4</id></entry>
+            <entry><id>urn:5</id></entry>
+        </feed>"#;
+    let feed = parser::parse(xml.as_bytes()).unwrap();
+    let pages = writer::write_atom_pages(&feed, 2, |number| format!("http://example.com/feed.xml?page={}", number)).unwrap();
+    let pages = pages.iter().map(|page| parser::parse(page.as_slice()).unwrap()).collect::<Vec<_>>();
+
+    let ids = pages
+        .iter()
+        .map(|page| page.entries.iter().map(|entry| entry.id.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![vec!["urn:1", "urn:2"], vec!["urn:3", "urn:4"], vec!["urn:5"]]);
+
+    let links = |page: &Feed| {
+        page.links
+            .iter()
+            .map(|link| format!("{} {}", link.rel.as_deref().unwrap_or("-"), link.href))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        links(&pages[1]),
+        vec![
+            "alternate http://example.com/",
+            "self http://example.com/feed.xml?page=2",
+            "first http://example.com/feed.xml?page=1",
+            "last http://example.com/feed.xml?page=3",
+            "prev http://example.com/feed.xml?page=1",
+            "next http://example.com/feed.xml?page=3",
+        ]
+    );
+    assert!(pages[0].links.iter().all(|link| !link.has_rel("prev")));
+    assert!(pages[2].next_page().is_none());
+    for page in &pages {
+        assert_eq!(page.id, "urn:feed");
+        assert_eq!(page.title, feed.title);
+    }
+
+    // A feed without entries is a single page
+    let feed = Feed::new(FeedType::Atom);
+    let pages = writer::write_atom_pages(&feed, 2, |number| number.to_string()).unwrap();
+    assert_eq!(pages.len(), 1);
+}
+
+// Verify new entries are added ahead of the others, replacing their earlier versions, and the oldest are dropped beyond the limit
+#[test]
+fn test_append() {
+    let xml = r#"<rss version="2.0"><channel>
+            <title>Feed</title>
+            <link>http://example.com/</link>
+            <lastBuildDate>Mon, 01 Mar 2021 10:00:00 +0000</lastBuildDate>
+            <item><guid>urn:3</guid><title>Third</title></item>
+            <item><guid>urn:2</guid><title>Second</title></item>
+            <item><guid>urn:1</guid><title>First</title></item>
+        </channel></rss>"#;
+    let entries = vec![
+        Entry::default()
+            .id("urn:4")
+            .title(Text::new("Fourth".into()))
+            .updated_rfc3339("2021-03-02T10:00:00Z"),
+        Entry::default().id("urn:3").title(Text::new("Third, corrected".into())),
+    ];
+    let mut out = Vec::new();
+    writer::append(xml.as_bytes(), entries, 3, &mut out).unwrap();
+
+    let feed = parser::parse(out.as_slice()).unwrap();
+    assert_eq!(feed.feed_type, FeedType::RSS2);
+    assert_eq!(feed.title.unwrap().content, "Feed");
+    assert_eq!(feed.updated, Some(Utc.with_ymd_and_hms(2021, 3, 2, 10, 0, 0).unwrap()));
+    let titles = feed
+        .entries
+        .iter()
+        .map(|entry| entry.title.as_ref().unwrap().content.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec!["Fourth", "Third, corrected", "Second"]);
+
+    // Atom feeds stay Atom, and stay as updated as they were if the entries are older
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><updated>2021-03-01T10:00:00Z</updated></feed>"#;
+    let mut out = Vec::new();
+    writer::append(
+        xml.as_bytes(),
+        vec![Entry::default().id("urn:1").updated_rfc3339("2021-02-01T10:00:00Z")],
+        10,
+        &mut out,
+    )
+    .unwrap();
+    let feed = parser::parse(out.as_slice()).unwrap();
+    assert_eq!(feed.feed_type, FeedType::Atom);
+    assert_eq!(feed.updated, Some(Utc.with_ymd_and_hms(2021, 3, 1, 10, 0, 0).unwrap()));
+    assert_eq!(feed.entries.len(), 1);
+
+    assert!(matches!(
+        writer::append("not a feed".as_bytes(), vec![], 10, Vec::new()),
+        Err(writer::WriteFeedError::Parse(_))
+    ));
+}
+
+// Verify the content hashes written with each entry match once parsed, in either format, and fail to match once the content changes
+#[test]
+fn test_content_hashes() {
+    let writer = writer::Builder::new().content_hashes(true).build();
+    for fixture in &[
+        "atom_example_1.xml",
+        "atom_example_2.xml",
+        "atom_source.xml",
+        "rss_2.0_example_1.xml",
+        "rss_2.0_spec_1.xml",
+    ] {
+        let feed = parser::parse(test::fixture_as_raw(fixture).as_slice()).unwrap();
+        for atom in &[true, false] {
+            let mut out = Vec::new();
+            if *atom {
+                writer.write_atom(&feed, &mut out).unwrap();
+            } else {
+                writer.write_rss2(&feed, &mut out).unwrap();
+            }
+            let actual = parser::parse(out.as_slice()).unwrap();
+            assert!(!actual.entries.is_empty(), "{}", fixture);
+            for entry in &actual.entries {
+                assert_eq!(entry.verify_content_hash(), Some(true), "{} {}", fixture, entry.id);
+            }
+        }
+    }
+
+    let feed = parser::parse(test::fixture_as_raw("rss_2.0_example_1.xml").as_slice()).unwrap();
+    let mut out = Vec::new();
+    writer.write_rss2(&feed, &mut out).unwrap();
+    let mut actual = parser::parse(out.as_slice()).unwrap();
+    actual.entries[0].title = Some(Text::new("Changed".into()));
+    assert_eq!(actual.entries[0].verify_content_hash(), Some(false));
+
+    // Hashes are only written when enabled
+    let mut out = Vec::new();
+    writer::write_rss2(&feed, &mut out).unwrap();
+    assert_eq!(parser::parse(out.as_slice()).unwrap().entries[0].verify_content_hash(), None);
+}
+
+// Verify OPML documents survive a round trip, and the structure of the written document
+#[test]
+fn test_write_opml() {
+    let opml = Opml::parse(test::fixture_as_raw("opml_example_1.xml").as_slice()).unwrap();
+    assert_eq!(Opml::parse(opml.to_xml().as_bytes()).unwrap(), opml);
+
+    let opml = Opml {
+        version: "1.0".into(),
+        title: Some("Subscriptions".into()),
+        outlines: vec![Outline::folder(
+            "News & Views",
+            vec![Outline::feed("Example", "http://example.com/feed?a=1&b=2")],
+        )],
+        ..Default::default()
+    };
+    let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+<opml version="2.0">
+  <head>
+    <title>Subscriptions</title>
+  </head>
+  <body>
+    <outline text="News &amp; Views">
+      <outline text="Example" type="rss" xmlUrl="http://example.com/feed?a=1&amp;b=2"/>
+    </outline>
+  </body>
+</opml>"#;
+    assert_eq!(opml.to_xml(), expected);
+}
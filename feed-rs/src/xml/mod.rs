@@ -2,13 +2,19 @@ use core::fmt;
 use std::cell::RefCell;
 use std::error::Error;
 use std::fmt::Debug;
-use std::io::BufRead;
+use std::io::{self, BufRead, Read};
 use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Reader;
+use regex::Regex;
 use url::Url;
 
+use crate::util::html::decode_entities;
+use crate::util::limit::Flag;
+
 #[cfg(test)]
 mod tests;
 
@@ -28,22 +34,36 @@ impl<R: BufRead> ElementSource<R> {
     ///
     /// * `xml_data` - the data you wish to parse
     /// * `xml_base_uri` - the base URI if known (e.g. Content-Location, feed URI etc)
-    pub(crate) fn new(xml_data: R, xml_base_uri: Option<&str>) -> XmlResult<ElementSource<R>> {
+    /// * `config` - settings for the underlying reader (buffer sizes etc)
+    pub(crate) fn new(xml_data: R, xml_base_uri: Option<&str>, config: &XmlConfig) -> XmlResult<ElementSource<R>> {
         // Create the XML parser
-        let mut reader = quick_xml::Reader::from_reader(xml_data);
+        let position = Arc::new(SharedPosition::default());
+        let raw = if config.record_raw { Some(Arc::new(Mutex::new(Vec::new()))) } else { None };
+        let mut reader = quick_xml::Reader::from_reader(PositionReader {
+            inner: xml_data,
+            position: position.clone(),
+            raw: raw.clone(),
+        });
         reader.expand_empty_elements(true).trim_markup_names_in_closing_tags(true).trim_text(false);
+        // Mismatched end tags are resolved by the state when recovering from malformed XML
+        reader.check_end_names(!config.recover);
 
-        let state = RefCell::new(SourceState::new(reader, xml_base_uri)?);
+        let state = RefCell::new(SourceState::new(reader, position, raw, xml_base_uri, config)?);
         Ok(ElementSource { state })
     }
 
     /// Returns the first element in the source
-    pub(crate) fn root(&self) -> XmlResult<Option<Element<R>>> {
+    pub(crate) fn root(&self) -> XmlResult<Option<Element<'_, R>>> {
         self.next_element_at_depth(1)
     }
 
-    // Return the raw XML of all children at or below the nominated depth
-    fn children_as_string(&self, depth: u32, buffer: &mut String) -> XmlResult<()> {
+    /// Returns the pseudo-attributes (e.g. `href="style.xsl" type="text/xsl"`) of each `<?xml-stylesheet?>` processing instruction read so far
+    pub(crate) fn stylesheets(&self) -> Vec<String> {
+        self.state.borrow().stylesheets.clone()
+    }
+
+    // Passes the events for all children at or below the nominated depth to the visitor
+    fn visit_children<F: FnMut(&XmlEvent)>(&self, depth: u32, mut visitor: F) -> XmlResult<()> {
         // Read nodes at the current depth or greater
         let mut state = self.state.borrow_mut();
         let mut current_depth = depth;
@@ -58,30 +78,22 @@ impl<R: BufRead> ElementSource<R> {
             // Fetch the next event
             if let Some(event) = peeked.as_ref().unwrap() {
                 match event {
-                    XmlEvent::Start { name, attributes, .. } => {
+                    XmlEvent::Start { .. } => {
                         // Note that we have descended into an element
                         current_depth += 1;
-
-                        // Append element start to the buffer
-                        append_element_start(buffer, name, attributes);
                     }
 
-                    XmlEvent::Text(text) => {
-                        // Append text to the buffer
-                        append_element_text(buffer, text);
-                    }
+                    XmlEvent::Text(_) => {}
 
-                    XmlEvent::End { name, .. } => {
+                    XmlEvent::End { .. } => {
                         // Break out of the iteration if we would move above our iteration depth
                         current_depth -= 1;
                         if current_depth < depth {
                             break;
                         }
-
-                        // Append this terminating element
-                        append_element_end(buffer, name);
                     }
                 }
+                visitor(event);
 
                 // Consume this node
                 state.next()?;
@@ -94,28 +106,73 @@ impl<R: BufRead> ElementSource<R> {
         Ok(())
     }
 
-    // Returns the next element at the nominated depth
-    fn next_element_at_depth(&self, iter_depth: u32) -> XmlResult<Option<Element<R>>> {
+    /// Returns the number of bytes consumed from the underlying source so far
+    pub(crate) fn bytes_consumed(&self) -> u64 {
+        self.state.borrow().reader.buffer_position() as u64
+    }
+
+    /// Returns true if the source has been cut off by a limit on its size, so the elements being read may be incomplete
+    pub(crate) fn is_truncated(&self) -> bool {
+        self.state.borrow().is_truncated()
+    }
+
+    // Returns the source of the element with the start tag ending at the offset, up to the event just consumed (its end tag once its
+    // children have been read)
+    //
+    // The recorded source before the end is discarded, as elements are only extracted once.
+    fn raw_since(&self, start_tag_end: u64) -> Option<String> {
+        let mut state = self.state.borrow_mut();
+        let (raw_base, current_end) = (state.raw_base, state.current_end);
+        let raw = state.raw.as_ref()?;
+        let mut raw = raw.lock().unwrap();
+        if start_tag_end < raw_base || current_end < start_tag_end {
+            return None;
+        }
+
+        // The start tag is the last '<' before its end, as it cannot appear unescaped within the tag
+        let (tag_end, end) = ((start_tag_end - raw_base) as usize, (current_end - raw_base) as usize);
+        let start = raw[..tag_end].iter().rposition(|b| *b == b'<')?;
+        let extracted = String::from_utf8_lossy(&raw[start..end]).into_owned();
+        raw.drain(..end);
+        drop(raw);
+        state.raw_base = current_end;
+        Some(extracted)
+    }
+
+    /// Returns the next element at the nominated depth (the root is at depth 1), skipping any deeper elements
+    pub(crate) fn next_element_at_depth(&self, iter_depth: u32) -> XmlResult<Option<Element<'_, R>>> {
         // Read nodes until we arrive at the correct depth
         let mut state = self.state.borrow_mut();
         while let Some(node) = state.next()? {
             match node {
                 // The start of an element may be interesting to the iterator
-                XmlEvent::Start { name, attributes, namespace } => {
+                XmlEvent::Start {
+                    name,
+                    attributes,
+                    namespace,
+                    namespace_uri,
+                } => {
                     // Starting an element increases our depth
                     state.current_depth += 1;
+                    if state.record_path {
+                        state.path.push(name.clone());
+                    }
 
-                    // Update the xml-base if required
+                    // Update the xml-base and xml:lang if required
                     ElementSource::xml_base_push(&mut state, &attributes)?;
+                    ElementSource::xml_lang_push(&mut state, &attributes);
 
                     // If we are at the correct depth we found a node of interest
                     if state.current_depth == iter_depth {
                         let element = Element {
+                            start_tag_end: state.current_end,
                             namespace,
+                            namespace_uri,
                             name,
                             attributes,
                             xml_base: ElementSource::xml_base_fetch(&state),
-                            source: &self,
+                            xml_lang: ElementSource::xml_lang_fetch(&state),
+                            source: self,
                             depth: state.current_depth,
                         };
                         return Ok(Some(element));
@@ -125,9 +182,13 @@ impl<R: BufRead> ElementSource<R> {
                 // The end of an element moves back up the hierarchy
                 XmlEvent::End { .. } => {
                     state.current_depth -= 1;
+                    if state.record_path {
+                        state.path.pop();
+                    }
 
-                    // Update the xml-base if required
+                    // Update the xml-base and xml:lang if required
                     ElementSource::xml_base_pop(&mut state);
+                    ElementSource::xml_lang_pop(&mut state);
                 }
 
                 // Not interested in other events when looking for elements
@@ -141,10 +202,13 @@ impl<R: BufRead> ElementSource<R> {
         }
 
         // Hit the end of the document
-        if state.current_depth > 0 {
+        if state.current_depth > 0 && !state.is_truncated() {
             let msg = format!("documented terminated at depth {}", state.current_depth);
             let e = quick_xml::Error::UnexpectedEof(msg);
-            Err(XmlError::Parser { e })
+            Err(XmlError::Parser {
+                e,
+                position: Some(state.position.get()),
+            })
         } else {
             Ok(None)
         }
@@ -154,16 +218,26 @@ impl<R: BufRead> ElementSource<R> {
     fn text_node(&self) -> Option<String> {
         let mut state = self.state.borrow_mut();
 
-        // If the next event is characters, we have found our text
-        if let Ok(Some(XmlEvent::Text(_text))) = state.peek() {
+        // The text may be split across several events (e.g. text followed by a CDATA section), which are joined
+        let mut segments = Vec::new();
+        while let Ok(Some(XmlEvent::Text(_text))) = state.peek() {
             // Grab the next event - we know its a Text event from the above
             match state.next() {
-                Ok(Some(XmlEvent::Text(text))) => return Some(text),
+                Ok(Some(XmlEvent::Text(text))) => segments.push(text),
                 _ => unreachable!("state.next() did not return expected XmlEvent::Text"),
             }
         }
 
-        None
+        // Whitespace used to lay out a CDATA section is not part of the text, but the text between sections is kept as found
+        match segments.len() {
+            0 => None,
+            1 => segments.pop(),
+            _ => {
+                let start = segments.iter().position(|segment| !segment.trim().is_empty()).unwrap_or(segments.len());
+                let end = segments.iter().rposition(|segment| !segment.trim().is_empty()).map_or(start, |end| end + 1);
+                Some(segments[start..end].concat())
+            }
+        }
     }
 
     // Fetches the currently active xml-base
@@ -211,21 +285,123 @@ impl<R: BufRead> ElementSource<R> {
         // No xml-base found
         Ok(())
     }
+
+    // Fetches the currently active xml:lang, where an empty value means the language is unknown
+    fn xml_lang_fetch(state: &SourceState<R>) -> Option<String> {
+        state.langs.last().map(|(_, lang)| lang.clone()).filter(|lang| !lang.is_empty())
+    }
+
+    // Pops xml:lang entries off the stack if they are deeper than our new depth
+    fn xml_lang_pop(state: &mut SourceState<R>) {
+        while let Some((depth, _)) = state.langs.last() {
+            if *depth > state.current_depth {
+                state.langs.pop();
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Pushes the xml:lang of the element on to the stack if it has one
+    fn xml_lang_push(state: &mut SourceState<R>, attributes: &[NameValue]) {
+        if let Some(lang) = attributes.iter().find(|nv| nv.name == "xml:lang") {
+            state.langs.push((state.current_depth, lang.value.trim().to_owned()));
+        }
+    }
+}
+
+// Real feeds are nowhere near this deep, even with XHTML content, so anything deeper is likely to be an attempt to exhaust the parser
+const DEFAULT_MAX_DEPTH: usize = 256;
+
+lazy_static! {
+    // Matches the declaration of an external entity (general or parameter) in a document type declaration
+    static ref EXTERNAL_ENTITY: Regex = Regex::new(r#"<!ENTITY\s+(?:%\s+)?[^\s>]+\s+(?:SYSTEM|PUBLIC)\b"#).unwrap();
+}
+
+/// Settings for the underlying XML reader
+#[derive(Clone, Debug)]
+pub(crate) struct XmlConfig {
+    /// Initial capacity of the buffer each XML event (element start tag, text etc) is read into
+    pub event_buffer_capacity: usize,
+    /// Track the path from the root to the current element (see `Element::path()`)
+    pub record_path: bool,
+    /// The deepest that elements may be nested, if limited
+    pub max_depth: Option<usize>,
+    /// The most elements the document may contain, if limited
+    pub max_elements: Option<usize>,
+    /// The longest the text of an element may be in bytes (all of its text and CDATA sections together), if limited
+    pub max_text_length: Option<usize>,
+    /// Text longer than `max_text_length` is truncated if set, rather than failing with `XmlError::LimitExceeded`
+    pub truncate_text: bool,
+    /// Set if the source has been cut off by a limit on its size, in which case the end of the source is not an error
+    pub truncated: Option<Flag>,
+    /// Record the source as it is read, so the source of elements is available (see `Element::raw()`)
+    pub record_raw: bool,
+    /// Make a best effort to read malformed XML, rather than failing (see `SourceState::fetch_next()`)
+    pub recover: bool,
+}
+
+impl Default for XmlConfig {
+    fn default() -> Self {
+        XmlConfig {
+            event_buffer_capacity: 512,
+            record_path: false,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            max_elements: None,
+            max_text_length: None,
+            truncate_text: true,
+            truncated: None,
+            record_raw: false,
+            recover: false,
+        }
+    }
 }
 
 // Wraps the XML source and current depth of iteration
 struct SourceState<R: BufRead> {
-    reader: Reader<R>,
+    reader: Reader<PositionReader<R>>,
+    position: Arc<SharedPosition>,
     buf_event: Vec<u8>,
     buf_ns: Vec<u8>,
     next: XmlResult<Option<XmlEvent>>,
     current_depth: u32,
     base_uris: Vec<(u32, Url)>,
+    langs: Vec<(u32, String)>,
+    record_path: bool,
+    path: Vec<String>,
+    stylesheets: Vec<String>,
+    open_elements: usize,
+    elements: usize,
+    max_depth: Option<usize>,
+    max_elements: Option<usize>,
+    max_text_length: Option<usize>,
+    truncate_text: bool,
+    // The length of the text read so far for each open element, counted against `max_text_length`
+    text_lengths: Vec<usize>,
+    truncated: Option<Flag>,
+    // The source read so far (if recording), starting at the offset `raw_base`
+    raw: Option<Arc<Mutex<Vec<u8>>>>,
+    raw_base: u64,
+    // The offsets in the source at which the event just consumed, and the one after it, end
+    current_end: u64,
+    next_end: u64,
+    // When recovering from malformed XML, the qualified names of the open elements, the number of them to leave open once those
+    // left unclosed by an end tag have been closed, and whether the source ended within an element
+    recover: bool,
+    open_names: Vec<String>,
+    closing_to: Option<usize>,
+    ended_early: bool,
 }
 
 impl<R: BufRead> SourceState<R> {
     // Wrap the reader in additional state (buffers, tree depth etc)
-    fn new(reader: Reader<R>, xml_base_uri: Option<&str>) -> XmlResult<SourceState<R>> {
+    fn new(
+        reader: Reader<PositionReader<R>>,
+        position: Arc<SharedPosition>,
+        raw: Option<Arc<Mutex<Vec<u8>>>>,
+        xml_base_uri: Option<&str>,
+        config: &XmlConfig,
+    ) -> XmlResult<SourceState<R>> {
         // If we have a base URI, parse it and init at the root
         let mut base_uris = Vec::new();
         if let Some(xml_base_uri) = xml_base_uri {
@@ -233,51 +409,156 @@ impl<R: BufRead> SourceState<R> {
             base_uris.push((0, uri));
         }
 
-        let buf_event = Vec::with_capacity(512);
+        let buf_event = Vec::with_capacity(config.event_buffer_capacity);
         let buf_ns = Vec::with_capacity(128);
         let mut state = SourceState {
             reader,
+            position,
             buf_event,
             buf_ns,
             next: Ok(None),
             current_depth: 0,
             base_uris,
+            langs: Vec::new(),
+            record_path: config.record_path,
+            path: Vec::new(),
+            stylesheets: Vec::new(),
+            open_elements: 0,
+            elements: 0,
+            max_depth: config.max_depth,
+            max_elements: config.max_elements,
+            max_text_length: config.max_text_length,
+            text_lengths: Vec::new(),
+            truncate_text: config.truncate_text,
+            truncated: config.truncated.clone(),
+            raw,
+            raw_base: 0,
+            current_end: 0,
+            next_end: 0,
+            recover: config.recover,
+            open_names: Vec::new(),
+            closing_to: None,
+            ended_early: false,
         };
         state.next = state.fetch_next();
+        state.next_end = state.reader.buffer_position() as u64;
         Ok(state)
     }
 
+    // Checks if the source has been cut off, by a limit on its size or (when recovering) ending within an element
+    fn is_truncated(&self) -> bool {
+        self.ended_early || is_truncated(&self.truncated)
+    }
+
     // Returns the next event
+    //
+    // When recovering from malformed XML:
+    // * an end tag closes any elements left open within the element it matches, and is ignored if it matches none
+    // * the end of the source within an element is treated as the source being truncated
+    // * characters that are not allowed in XML (e.g. control characters) are removed from text and attribute values
     fn fetch_next(&mut self) -> XmlResult<Option<XmlEvent>> {
+        if let Some(depth) = self.closing_to {
+            if self.open_names.len() > depth {
+                return Ok(Some(self.close_element()));
+            }
+            self.closing_to = None;
+        }
+
         let reader = &mut self.reader;
         loop {
-            let (ns, event) = reader.read_namespaced_event(&mut self.buf_event, &mut self.buf_ns)?;
+            // Events are converted into owned values before the next read, so the buffer is reused rather than growing with the document
+            self.buf_event.clear();
+            let (ns, event) = match reader.read_namespaced_event(&mut self.buf_event, &mut self.buf_ns) {
+                Ok(read) => read,
+                // A source cut off by a limit ends wherever the limit falls
+                Err(quick_xml::Error::UnexpectedEof(_)) if is_truncated(&self.truncated) => return Ok(None),
+                Err(quick_xml::Error::UnexpectedEof(_)) if self.recover => {
+                    self.ended_early = true;
+                    return Ok(None);
+                }
+                Err(e) => {
+                    let position = Some(self.position.get());
+                    return Err(XmlError::Parser { e, position });
+                }
+            };
             match event {
                 // Start of an element
                 Event::Start(ref e) => {
-                    return Ok(Some(XmlEvent::start(ns, e, &reader)));
+                    self.open_elements += 1;
+                    self.text_lengths.push(0);
+                    self.elements += 1;
+                    if exceeds(self.open_elements, self.max_depth) {
+                        return Err(XmlError::LimitExceeded {
+                            limit: "depth",
+                            position: Some(self.position.get()),
+                        });
+                    }
+                    if exceeds(self.elements, self.max_elements) {
+                        return Err(XmlError::LimitExceeded {
+                            limit: "elements",
+                            position: Some(self.position.get()),
+                        });
+                    }
+                    if !self.recover {
+                        return Ok(Some(XmlEvent::start(ns, e, reader)));
+                    }
+                    self.open_names.push(reader.decode(e.name()).into_owned());
+                    let mut start = XmlEvent::start(ns, e, reader);
+                    if let XmlEvent::Start { attributes, .. } = &mut start {
+                        for attribute in attributes {
+                            remove_invalid_chars(&mut attribute.value);
+                        }
+                    }
+                    return Ok(Some(start));
                 }
 
                 // End of an element
+                Event::End(ref e) if self.recover => {
+                    let name = reader.decode(e.name());
+                    if let Some(depth) = self.open_names.iter().rposition(|open| *open == name) {
+                        self.closing_to = Some(depth);
+                        return Ok(Some(self.close_element()));
+                    }
+                }
                 Event::End(ref e) => {
-                    return Ok(Some(XmlEvent::end(e, &reader)));
+                    self.open_elements = self.open_elements.saturating_sub(1);
+                    self.text_lengths.pop();
+                    return Ok(Some(XmlEvent::end(e, reader)));
                 }
 
                 // Text
                 Event::Text(ref t) => {
-                    let event = XmlEvent::text(t, &reader);
-                    if let Ok(Some(ref _t)) = event {
-                        return event;
+                    if let Some(XmlEvent::Text(text)) = XmlEvent::text(t, reader)? {
+                        return self.limit_text(text).map(|text| Some(XmlEvent::Text(text)));
                     }
                 }
 
                 // CData
                 Event::CData(ref t) => {
-                    return Ok(Some(XmlEvent::text_from_cdata(t, &reader)));
+                    if let XmlEvent::Text(text) = XmlEvent::text_from_cdata(t, reader) {
+                        return self.limit_text(text).map(|text| Some(XmlEvent::Text(text)));
+                    }
+                }
+
+                // Processing instructions are skipped, but stylesheets are kept for the caller
+                Event::PI(ref pi) => {
+                    let pi = reader.decode(pi);
+                    if let Some(pseudo_attributes) = pi.strip_prefix("xml-stylesheet") {
+                        self.stylesheets.push(pseudo_attributes.trim().to_owned());
+                    }
+                }
+
+                // Entities declared in the document type are never expanded, but a document declaring an external entity is
+                // rejected outright as it can only be an attempt to read a local file or make a request (XXE)
+                Event::DocType(ref d) if EXTERNAL_ENTITY.is_match(&reader.decode(d)) => {
+                    return Err(XmlError::ExternalEntity {
+                        position: Some(self.position.get()),
+                    });
                 }
 
                 // The end of the document
                 Event::Eof => {
+                    self.ended_early = self.recover && !self.open_names.is_empty();
                     return Ok(None);
                 }
 
@@ -287,10 +568,48 @@ impl<R: BufRead> SourceState<R> {
         }
     }
 
+    // Closes the innermost open element when recovering from malformed XML
+    fn close_element(&mut self) -> XmlEvent {
+        self.open_elements = self.open_elements.saturating_sub(1);
+        self.text_lengths.pop();
+        let name = self.open_names.pop().unwrap_or_default();
+        XmlEvent::End {
+            name: name.split(':').next_back().unwrap_or("").into(),
+        }
+    }
+
+    // Truncates text that takes the element over the limit, or rejects it if truncation is not allowed, and removes invalid characters
+    // when recovering
+    fn limit_text(&mut self, mut text: String) -> XmlResult<String> {
+        if self.recover {
+            remove_invalid_chars(&mut text);
+        }
+        if let Some(max_text_length) = self.max_text_length {
+            // Text outside of the root element is not part of any element, so is limited on its own
+            let length = self.text_lengths.last().copied().unwrap_or(0);
+            let remaining = max_text_length.saturating_sub(length);
+            if text.len() > remaining {
+                if !self.truncate_text {
+                    return Err(XmlError::LimitExceeded {
+                        limit: "text",
+                        position: Some(self.position.get()),
+                    });
+                }
+                truncate_text(&mut text, remaining);
+            }
+            if let Some(length) = self.text_lengths.last_mut() {
+                *length += text.len();
+            }
+        }
+        Ok(text)
+    }
+
     // Returns the next interesting event or None if no more events are found
     fn next(&mut self) -> XmlResult<Option<XmlEvent>> {
         let next = mem::replace(&mut self.next, Ok(None));
+        self.current_end = self.next_end;
         self.next = self.fetch_next();
+        self.next_end = self.reader.buffer_position() as u64;
         next
     }
 
@@ -306,18 +625,27 @@ pub(crate) struct Element<'a, R: BufRead> {
     /// Qualified name of the element.
     pub name: String,
 
-    /// The namespace mapping at this point of the document.
+    /// The namespace of the element, or None if it is not in one.
     pub namespace: Option<NS>,
 
+    /// The URI of the element's namespace, whether or not it is automatically recognised
+    pub namespace_uri: Option<String>,
+
     /// A list of attributes associated with the element.
     pub attributes: Vec<NameValue>,
 
     /// The base URL for this element per the xml:base specification (https://www.w3.org/TR/xmlbase/)
     pub xml_base: Option<Url>,
 
+    /// The language of this element per xml:lang, which is inherited from its ancestors (https://www.w3.org/TR/xml/#sec-lang-tag)
+    pub xml_lang: Option<String>,
+
     // Depth of this element
     depth: u32,
 
+    // The offset in the source at which the start tag ends
+    start_tag_end: u64,
+
     // The underlying source of XML events
     source: &'a ElementSource<R>,
 }
@@ -328,15 +656,32 @@ impl<'a, R: BufRead> Element<'a, R> {
         self.attributes.iter().find(|a| a.name == name).map(|a| a.value.clone())
     }
 
+    /// Returns the number of bytes consumed from the underlying source so far
+    pub(crate) fn bytes_consumed(&self) -> u64 {
+        self.source.bytes_consumed()
+    }
+
+    /// Returns true if the source has been cut off by a limit on its size, so this element may be incomplete
+    pub(crate) fn is_truncated(&self) -> bool {
+        self.source.is_truncated()
+    }
+
+    /// Returns the source of this element, from its start tag to its end tag, if the source is recorded (`XmlConfig::record_raw`)
+    ///
+    /// The children must have been consumed first, and the source of elements before this one is no longer available.
+    pub(crate) fn raw(&self) -> Option<String> {
+        self.source.raw_since(self.start_tag_end)
+    }
+
     /// If the first child of the current node is XML characters, then it is returned as a `String` otherwise `None`.
     pub(crate) fn child_as_text(&self) -> Option<String> {
         self.source.text_node()
     }
 
     /// Returns an iterator over children of this element (i.e. descends a level in the hierarchy)
-    pub(crate) fn children(&self) -> ElementIter<R> {
+    pub(crate) fn children(&self) -> ElementIter<'_, R> {
         ElementIter {
-            source: &self.source,
+            source: self.source,
             depth: self.depth + 1,
         }
     }
@@ -347,15 +692,89 @@ impl<'a, R: BufRead> Element<'a, R> {
     pub(crate) fn children_as_string(&self) -> XmlResult<Option<String>> {
         // Fill the buffer with the XML content below this element
         let mut buffer = String::new();
-        self.source.children_as_string(self.depth + 1, &mut buffer)?;
+        self.source.visit_children(self.depth + 1, |event| match event {
+            XmlEvent::Start { name, attributes, .. } => append_element_start(&mut buffer, name, attributes),
+            XmlEvent::Text(text) => append_element_text(&mut buffer, text),
+            XmlEvent::End { name } => append_element_end(&mut buffer, name),
+        })?;
+
+        Ok(Some(buffer))
+    }
+
+    /// Returns the XHTML below this element as a string, without the `<div>` that Atom wraps around it
+    ///
+    /// Unlike `children_as_string()`, text is escaped again so the markup is reproduced as it appeared in the feed.
+    pub(crate) fn children_as_xhtml(&self) -> XmlResult<Option<String>> {
+        let mut buffer = String::new();
 
+        // The wrapper is only dropped if it is the sole child (ignoring whitespace), in which case its content is at "inner"
+        let mut depth = 0;
+        let mut top_level = 0;
+        let mut wrapped = true;
+        let mut inner = 0..0;
+        self.source.visit_children(self.depth + 1, |event| match event {
+            XmlEvent::Start { name, attributes, .. } => {
+                if depth == 0 {
+                    top_level += 1;
+                    wrapped &= name == "div";
+                }
+                append_element_start(&mut buffer, name, attributes);
+                if depth == 0 {
+                    inner.start = buffer.len();
+                }
+                depth += 1;
+            }
+            XmlEvent::Text(text) => {
+                if depth == 0 && !text.trim().is_empty() {
+                    wrapped = false;
+                }
+                append_escaped_text(&mut buffer, text);
+            }
+            XmlEvent::End { name } => {
+                depth -= 1;
+                if depth == 0 {
+                    inner.end = buffer.len();
+                }
+                append_element_end(&mut buffer, name);
+            }
+        })?;
+
+        if wrapped && top_level == 1 {
+            buffer.truncate(inner.end);
+            buffer.drain(..inner.start);
+        }
         Ok(Some(buffer))
     }
 
+    /// Passes each XML event below this element to the visitor, consuming the children in the process
+    pub(crate) fn visit_children<F: FnMut(&XmlEvent)>(&self, visitor: F) -> XmlResult<()> {
+        self.source.visit_children(self.depth + 1, visitor)
+    }
+
+    /// Returns the names of the elements from the root of the document down to (and including) this one
+    ///
+    /// NOTE: this is only available if `XmlConfig::record_path` is set, otherwise it is empty
+    pub(crate) fn path(&self) -> Vec<String> {
+        self.source.state.borrow().path.clone()
+    }
+
     /// Returns the namespace + tag name for this element
     pub(crate) fn ns_and_tag(&self) -> (&Option<NS>, &str) {
         (&self.namespace, &self.name)
     }
+
+    /// Returns the namespace + tag name for this element, treating the namespaces of the format being parsed (e.g. `ATOM_NAMESPACES`)
+    /// as no namespace
+    ///
+    /// This allows a format's own elements to be matched whether or not the document declares its namespace, while those of the same
+    /// name in other namespaces (including unknown ones) are not mistaken for them.
+    pub(crate) fn ns_and_tag_in(&self, namespaces: &[NS]) -> (&Option<NS>, &str) {
+        const NO_NAMESPACE: &Option<NS> = &None;
+        match &self.namespace {
+            Some(namespace) if namespaces.contains(namespace) => (NO_NAMESPACE, &self.name),
+            namespace => (namespace, &self.name),
+        }
+    }
 }
 
 impl<'a, R: BufRead> Debug for Element<'a, R> {
@@ -380,27 +799,85 @@ impl<'a, R: BufRead> Iterator for ElementIter<'a, R> {
     }
 }
 
+/// The namespaces of Atom 1.0 and the legacy Atom 0.3, whose elements make up an Atom feed
+pub(crate) const ATOM_NAMESPACES: &[NS] = &[NS::Atom, NS::Atom03];
+
+/// The namespaces of the RSS family, whose elements make up an RSS feed (RSS 0.90 and 1.0 are RDF documents, and some RSS 2.0 feeds
+/// declare a namespace)
+pub(crate) const RSS_NAMESPACES: &[NS] = &[NS::Rdf, NS::Rss090, NS::Rss1, NS::Rss2];
+
 /// Set of automatically recognised namespaces
 #[derive(Debug, PartialEq)]
 pub(crate) enum NS {
+    // http://www.w3.org/2005/Atom
+    Atom,
+    // http://purl.org/atom/ns#
+    Atom03,
     // http://purl.org/rss/1.0/modules/content/
     Content,
+    // http://backend.userland.com/creativeCommonsRssModule
+    CreativeCommons,
     // http://purl.org/dc/elements/1.1/
     DublinCore,
+    // http://purl.org/dc/terms/
+    DublinCoreTerms,
+    // http://purl.oclc.org/net/rss_2.0/enc#
+    Enclosure,
+    // http://www.georss.org/georss
+    GeoRSS,
     // http://search.yahoo.com/mrss/
     MediaRSS,
     // http://www.itunes.com/dtds/podcast-1.0.dtd
     Itunes,
+    // https://podcastindex.org/namespace/1.0
+    Podcast,
+    // http://purl.org/rss/1.0/modules/slash/
+    Slash,
+    // http://purl.org/rss/1.0/modules/syndication/
+    Syndication,
+    // http://purl.org/syndication/thread/1.0
+    Threading,
+    // http://www.w3.org/2003/01/geo/wgs84_pos#
+    W3CGeo,
+    // http://wellformedweb.org/CommentAPI/
+    WellFormedWeb,
+    // http://www.w3.org/1999/02/22-rdf-syntax-ns#
+    Rdf,
+    // http://my.netscape.com/rdf/simple/0.9/
+    Rss090,
+    // http://purl.org/rss/1.0/
+    Rss1,
+    // http://backend.userland.com/rss2
+    Rss2,
+    // Any other namespace, whose URI is kept in `Element::namespace_uri`
+    Unknown,
 }
 
 impl NS {
-    fn parse(s: &str) -> Option<NS> {
+    fn parse(s: &str) -> NS {
         match s {
-            "http://purl.org/rss/1.0/modules/content/" => Some(NS::Content),
-            "http://purl.org/dc/elements/1.1/" => Some(NS::DublinCore),
-            "http://search.yahoo.com/mrss/" => Some(NS::MediaRSS),
-            "http://www.itunes.com/dtds/podcast-1.0.dtd" => Some(NS::Itunes),
-            _ => None,
+            "http://www.w3.org/2005/Atom" => NS::Atom,
+            "http://purl.org/atom/ns#" => NS::Atom03,
+            "http://purl.org/rss/1.0/modules/content/" => NS::Content,
+            "http://backend.userland.com/creativeCommonsRssModule" => NS::CreativeCommons,
+            "http://purl.org/dc/elements/1.1/" => NS::DublinCore,
+            "http://purl.org/dc/terms/" => NS::DublinCoreTerms,
+            "http://purl.oclc.org/net/rss_2.0/enc#" => NS::Enclosure,
+            "http://www.georss.org/georss" => NS::GeoRSS,
+            "http://search.yahoo.com/mrss/" => NS::MediaRSS,
+            "http://www.itunes.com/dtds/podcast-1.0.dtd" => NS::Itunes,
+            // Early adopters used the URL of the spec on GitHub
+            "https://podcastindex.org/namespace/1.0" | "https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/1.0.md" => NS::Podcast,
+            "http://purl.org/rss/1.0/modules/slash/" => NS::Slash,
+            "http://purl.org/rss/1.0/modules/syndication/" => NS::Syndication,
+            "http://purl.org/syndication/thread/1.0" => NS::Threading,
+            "http://www.w3.org/2003/01/geo/wgs84_pos#" => NS::W3CGeo,
+            "http://wellformedweb.org/CommentAPI/" => NS::WellFormedWeb,
+            "http://www.w3.org/1999/02/22-rdf-syntax-ns#" => NS::Rdf,
+            "http://my.netscape.com/rdf/simple/0.9/" => NS::Rss090,
+            "http://purl.org/rss/1.0/" => NS::Rss1,
+            "http://backend.userland.com/rss2" => NS::Rss2,
+            _ => NS::Unknown,
         }
     }
 }
@@ -414,15 +891,38 @@ pub(crate) struct NameValue {
 /// Errors for the underlying parser
 #[derive(Debug)]
 pub enum XmlError {
-    Parser { e: quick_xml::Error },
-    Url { e: url::ParseError },
+    /// Malformed XML, with the position in the source it was found if known
+    Parser {
+        e: quick_xml::Error,
+        position: Option<Position>,
+    },
+    Url {
+        e: url::ParseError,
+    },
+    /// The document declares an external entity, which is not allowed
+    ExternalEntity {
+        position: Option<Position>,
+    },
+    /// The document exceeds one of the limits on its size (e.g. "depth" for the nesting of elements)
+    LimitExceeded {
+        limit: &'static str,
+        position: Option<Position>,
+    },
 }
 
 impl fmt::Display for XmlError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            XmlError::Parser { e } => write!(f, "Parser error: {}", e),
+            XmlError::Parser { e, position: Some(position) } => write!(f, "Parser error at {}: {}", position, e),
+            XmlError::Parser { e, position: None } => write!(f, "Parser error: {}", e),
             XmlError::Url { e } => write!(f, "Url error: {}", e),
+            XmlError::ExternalEntity { position: Some(position) } => write!(f, "External entity declared at {}", position),
+            XmlError::ExternalEntity { position: None } => f.write_str("External entity declared"),
+            XmlError::LimitExceeded {
+                limit,
+                position: Some(position),
+            } => write!(f, "Limit on {} exceeded at {}", limit, position),
+            XmlError::LimitExceeded { limit, position: None } => write!(f, "Limit on {} exceeded", limit),
         }
     }
 }
@@ -431,7 +931,135 @@ impl Error for XmlError {}
 
 impl From<quick_xml::Error> for XmlError {
     fn from(e: quick_xml::Error) -> Self {
-        XmlError::Parser { e }
+        XmlError::Parser { e, position: None }
+    }
+}
+
+/// A location within the source document, counting from 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub line: u64,
+    /// Counted in characters rather than bytes
+    pub column: u64,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+// The position of the XML reader, shared by the PositionReader that advances it and the SourceState that reports it
+//
+// Atomics are used (rather than a Cell) so the source of elements can be sent to another thread.
+pub(crate) struct SharedPosition {
+    line: AtomicU64,
+    column: AtomicU64,
+}
+
+impl Default for SharedPosition {
+    fn default() -> Self {
+        SharedPosition {
+            line: AtomicU64::new(1),
+            column: AtomicU64::new(1),
+        }
+    }
+}
+
+impl SharedPosition {
+    fn get(&self) -> Position {
+        Position {
+            line: self.line.load(Ordering::Relaxed),
+            column: self.column.load(Ordering::Relaxed),
+        }
+    }
+
+    fn set(&self, position: Position) {
+        self.line.store(position.line, Ordering::Relaxed);
+        self.column.store(position.column, Ordering::Relaxed);
+    }
+}
+
+// Tracks the position of the XML reader through the source (shared with SourceState), so errors can report where they were found
+pub(crate) struct PositionReader<R: BufRead> {
+    inner: R,
+    position: Arc<SharedPosition>,
+    raw: Option<Arc<Mutex<Vec<u8>>>>,
+}
+
+// Checks if the source has been cut off, if it is limited
+fn is_truncated(truncated: &Option<Flag>) -> bool {
+    truncated.as_ref().map(|truncated| truncated.get()).unwrap_or(false)
+}
+
+// Removes the characters that are not allowed in an XML document (https://www.w3.org/TR/xml/#charsets)
+fn remove_invalid_chars(text: &mut String) {
+    let invalid = |c: char| matches!(c, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}');
+    if text.contains(invalid) {
+        text.retain(|c| !invalid(c));
+    }
+}
+
+/// Truncates the text to at most `max_length` bytes, on a character boundary
+pub(crate) fn truncate_text(text: &mut String, max_length: usize) {
+    if text.len() > max_length {
+        let mut end = max_length;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+}
+
+// Checks if the count is over the limit, if there is one
+fn exceeds(count: usize, limit: Option<usize>) -> bool {
+    match limit {
+        Some(limit) => count > limit,
+        None => false,
+    }
+}
+
+// Moves the position past the consumed bytes
+fn advance(position: &SharedPosition, consumed: &[u8]) {
+    let mut next = position.get();
+    for b in consumed {
+        if *b == b'\n' {
+            next.line += 1;
+            next.column = 1;
+        } else if b & 0xC0 != 0x80 {
+            // Continuation bytes of a UTF-8 sequence are part of the preceding character
+            next.column += 1;
+        }
+    }
+    position.set(next);
+}
+
+impl<R: BufRead> Read for PositionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        advance(&self.position, &buf[..read]);
+        if let Some(raw) = &self.raw {
+            raw.lock().unwrap().extend_from_slice(&buf[..read]);
+        }
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for PositionReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The reader only consumes what it has been given by fill_buf(), so this returns the same buffer without further reads
+        if let Ok(buf) = self.inner.fill_buf() {
+            let consumed = &buf[..amt.min(buf.len())];
+            advance(&self.position, consumed);
+            if let Some(raw) = &self.raw {
+                raw.lock().unwrap().extend_from_slice(consumed);
+            }
+        }
+        self.inner.consume(amt);
     }
 }
 
@@ -441,11 +1069,12 @@ impl From<url::ParseError> for XmlError {
     }
 }
 
-// Abstraction over the underlying XML reader event model
-enum XmlEvent {
+/// Abstraction over the underlying XML reader event model
+pub(crate) enum XmlEvent {
     // An XML start tag
     Start {
         namespace: Option<NS>,
+        namespace_uri: Option<String>,
         name: String,
         attributes: Vec<NameValue>,
     },
@@ -468,13 +1097,14 @@ impl XmlEvent {
 
     // Extracts the element name, dropping the namespace prefix if present
     fn parse_name<R: BufRead>(bytes: &[u8], reader: &Reader<R>) -> String {
-        reader.decode(bytes).split(':').rev().next().unwrap_or("").into()
+        reader.decode(bytes).split(':').next_back().unwrap_or("").into()
     }
 
     // Creates a new event corresponding to an XML start-tag
     fn start<R: BufRead>(ns: Option<&[u8]>, event: &BytesStart, reader: &Reader<R>) -> XmlEvent {
         // Parse the namespace
-        let namespace = ns.map(|bytes| reader.decode(bytes)).and_then(|s| NS::parse(s.as_ref()));
+        let namespace_uri = ns.map(|bytes| reader.decode(bytes).into_owned());
+        let namespace = namespace_uri.as_deref().map(NS::parse);
 
         // Parse the name
         let name = XmlEvent::parse_name(event.name(), reader);
@@ -497,12 +1127,20 @@ impl XmlEvent {
             })
             .collect::<Vec<NameValue>>();
 
-        XmlEvent::Start { namespace, name, attributes }
+        XmlEvent::Start {
+            namespace,
+            namespace_uri,
+            name,
+            attributes,
+        }
     }
 
     // Creates a new event corresponding to an XML text node
+    //
+    // References are decoded leniently, so the HTML entities that commonly appear in feeds (e.g. &nbsp;) do not fail the parse
     fn text<R: BufRead>(text: &BytesText, reader: &Reader<R>) -> XmlResult<Option<XmlEvent>> {
-        let text = text.unescape_and_decode(reader)?;
+        let text = reader.decode(text);
+        let text = if text.contains('&') { decode_entities(&text) } else { text.into_owned() };
         if text.is_empty() {
             Ok(None)
         } else {
@@ -533,7 +1171,8 @@ fn append_element_start(buffer: &mut String, name: &str, attributes: &[NameValue
         buffer.push(' ');
         buffer.push_str(attr.name.as_str());
         buffer.push_str("=\"");
-        buffer.push_str(attr.value.as_str());
+        // Values are still escaped as they were in the source, apart from quotes if they were delimited with apostrophes
+        buffer.push_str(&attr.value.replace('"', "&quot;"));
         buffer.push('"');
     }
     buffer.push('>');
@@ -543,3 +1182,15 @@ fn append_element_start(buffer: &mut String, name: &str, attributes: &[NameValue
 fn append_element_text(buffer: &mut String, text: &str) {
     buffer.push_str(text);
 }
+
+// Appends a text element, escaping the characters that would otherwise be read as markup
+fn append_escaped_text(buffer: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            c => buffer.push(c),
+        }
+    }
+}
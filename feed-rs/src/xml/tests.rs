@@ -83,7 +83,7 @@ fn test_iterate_stream() -> TestResult {
     let test_data = test::fixture_as_string("xml_sample_1.xml");
 
     // Root element should be "catalog"
-    let source = ElementSource::new(test_data.as_bytes(), None)?;
+    let source = ElementSource::new(test_data.as_bytes(), None, &XmlConfig::default())?;
     let catalog = source.root()?.unwrap();
     assert_eq!(catalog.name, "catalog");
     handle_catalog(catalog)?;
@@ -97,7 +97,7 @@ fn test_children_as_string() -> TestResult {
     let test_data = test::fixture_as_string("xml_sample_2.xml");
 
     // Root element should be "catalog"
-    let source = ElementSource::new(test_data.as_bytes(), None)?;
+    let source = ElementSource::new(test_data.as_bytes(), None, &XmlConfig::default())?;
     let catalog = source.root()?.unwrap();
     assert_eq!(catalog.name, "catalog");
 
@@ -131,7 +131,7 @@ fn test_rss_decoding() -> TestResult {
         ("<title>Nice &#x3C;gorilla&#x3E;, what's he weigh?</title>", "Nice <gorilla>, what's he weigh?"),
     ];
     for (xml, expected) in tests {
-        let source = ElementSource::new(xml.as_bytes(), None)?;
+        let source = ElementSource::new(xml.as_bytes(), None, &XmlConfig::default())?;
         let title = source.root()?.unwrap();
         let parsed = title.children_as_string()?.unwrap();
         assert_eq!(expected, parsed);
@@ -175,7 +175,7 @@ fn test_xml_base() -> TestResult {
         </feed>
     "#;
 
-    let source = ElementSource::new(xml.as_bytes(), None)?;
+    let source = ElementSource::new(xml.as_bytes(), None, &XmlConfig::default())?;
     let feed = source.root()?.unwrap();
     assert_eq!(&Url::parse("http://1.example.com/")?, feed.xml_base.as_ref().unwrap());
 
@@ -201,7 +201,7 @@ fn test_xml_base_header() -> TestResult {
         </feed>
     "#;
 
-    let source = ElementSource::new(xml.as_bytes(), Some("http://example.com"))?;
+    let source = ElementSource::new(xml.as_bytes(), Some("http://example.com"), &XmlConfig::default())?;
     let feed = source.root()?.unwrap();
     assert_eq!(&Url::parse("http://example.com/feed/base/")?, feed.xml_base.as_ref().unwrap());
 
@@ -209,3 +209,25 @@ fn test_xml_base_header() -> TestResult {
 
     Ok(())
 }
+
+// Verify a source of elements can be moved to another thread part way through, still reporting where errors are found
+#[test]
+fn test_send() -> TestResult {
+    let config = XmlConfig {
+        record_raw: true,
+        truncated: Some(Flag::default()),
+        ..XmlConfig::default()
+    };
+    let source = ElementSource::new("<rss>\n  <channel></chanel>".as_bytes(), None, &config)?;
+    let root = source.root()?.unwrap().name;
+    let error = std::thread::spawn(move || source.next_element_at_depth(2).and_then(|_| source.next_element_at_depth(2)).err())
+        .join()
+        .unwrap();
+
+    assert_eq!(root, "rss");
+    match error {
+        Some(XmlError::Parser { position: Some(position), .. }) => assert_eq!(position.line, 2),
+        other => panic!("unexpected result {:?}", other),
+    }
+    Ok(())
+}
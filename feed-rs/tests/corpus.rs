@@ -0,0 +1,13 @@
+// Checks the model parsed from each of the fixtures against its snapshot in fixture/snapshots
+//
+// After an intended change to the model, update the snapshots with:
+//   FEED_RS_UPDATE_SNAPSHOTS=1 cargo test --features test-util --test corpus
+// and review the differences before committing them.
+
+use feed_rs::{parser, test_util};
+
+#[test]
+fn test_corpus() {
+    let parser = parser::Builder::new().build();
+    test_util::assert_corpus(concat!(env!("CARGO_MANIFEST_DIR"), "/fixture"), &parser);
+}
@@ -0,0 +1,37 @@
+// An example of parsing feeds in the browser, where the crate is built for wasm32-unknown-unknown
+//
+// A web app would export parse_feed() with #[wasm_bindgen], passing in the bytes it fetched and handing the JSON on to
+// JavaScript. Nothing here needs a filesystem, a network or a clock, so it runs the same natively and in the browser.
+
+use feed_rs::parser;
+
+// Parses a feed fetched from the URL, returning the model as JSON or the reason it could not be parsed
+fn parse_feed(url: &str, bytes: &[u8]) -> Result<String, String> {
+    let parser = parser::Builder::new().base_uri(url).build();
+    let feed = parser.parse(bytes).map_err(|e| e.to_string())?;
+    serde_json::to_string(&feed).map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_parse_feed() {
+    let xml = r#"<rss version="2.0">
+            <channel>
+                <title>Example</title>
+                <item>
+                    <guid isPermaLink="false">urn:1</guid>
+                    <title>First</title>
+                    <link>/first</link>
+                    <pubDate>Tue, 02 Mar 2021 10:00:00 GMT</pubDate>
+                </item>
+            </channel>
+        </rss>"#;
+    let json = parse_feed("https://example.com/feed.xml", xml.as_bytes()).unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let entry = &value["entries"][0];
+    assert_eq!(entry["title"]["content"], "First");
+    assert_eq!(entry["links"][0]["href"], "https://example.com/first");
+    assert_eq!(entry["published"], "2021-03-02T10:00:00Z");
+
+    assert!(parse_feed("https://example.com/feed.xml", b"<html></html>").is_err());
+}